@@ -6,11 +6,13 @@ case "$GIMLI_JOB" in
     "build")
         cargo build $GIMLI_PROFILE
         cargo build --release $GIMLI_PROFILE
+        cargo build --features rayon $GIMLI_PROFILE
         ;;
 
     "test")
         cargo build $GIMLI_PROFILE
         cargo test $GIMLI_PROFILE
+        cargo test --features rayon $GIMLI_PROFILE
         cargo build --release $GIMLI_PROFILE
         cargo test --release $GIMLI_PROFILE
         case "$TRAVIS_OS_NAME" in
@@ -33,11 +35,15 @@ case "$GIMLI_JOB" in
         ;;
 
     "alloc")
-        test "$TRAVIS_RUST_VERSION" == "nightly"
         cargo build           --no-default-features --features read,alloc $GIMLI_PROFILE
         cargo build --release --no-default-features --features read,alloc $GIMLI_PROFILE
         ;;
 
+    "no_std")
+        cargo build           --no-default-features --features read $GIMLI_PROFILE
+        cargo build --release --no-default-features --features read $GIMLI_PROFILE
+        ;;
+
     "bench")
         cargo bench
         ;;
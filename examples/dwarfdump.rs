@@ -566,16 +566,13 @@ where
         dump_line(w, &dwarf)?;
     }
     if flags.pubnames {
-        let debug_pubnames = &gimli::Section::load(&mut load_section).unwrap();
-        dump_pubnames(w, debug_pubnames, &dwarf.debug_info)?;
+        dump_pubnames(w, &dwarf.debug_pubnames, &dwarf.debug_info)?;
     }
     if flags.aranges {
-        let debug_aranges = &gimli::Section::load(&mut load_section).unwrap();
-        dump_aranges(w, debug_aranges, &dwarf.debug_info)?;
+        dump_aranges(w, &dwarf.debug_aranges, &dwarf.debug_info)?;
     }
     if flags.pubtypes {
-        let debug_pubtypes = &gimli::Section::load(&mut load_section).unwrap();
-        dump_pubtypes(w, debug_pubtypes, &dwarf.debug_info)?;
+        dump_pubtypes(w, &dwarf.debug_pubtypes, &dwarf.debug_info)?;
     }
     Ok(())
 }
@@ -1,3 +1,5 @@
+use crate::constants;
+
 /// Whether the format of a compilation unit is 32- or 64-bit.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Format {
@@ -85,81 +87,90 @@ impl Default for LineEncoding {
 pub struct Register(pub u16);
 
 /// An offset into the `.debug_abbrev` section.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DebugAbbrevOffset<T = usize>(pub T);
 
 /// An offset to a set of entries in the `.debug_addr` section.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DebugAddrBase<T = usize>(pub T);
 
 /// An index into a set of addresses in the `.debug_addr` section.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DebugAddrIndex<T = usize>(pub T);
 
 /// An offset into the `.debug_info` section.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Ord, PartialOrd, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DebugInfoOffset<T = usize>(pub T);
 
 /// An offset into the `.debug_line` section.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DebugLineOffset<T = usize>(pub T);
 
 /// An offset into the `.debug_line_str` section.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DebugLineStrOffset<T = usize>(pub T);
 
 /// An offset into either the `.debug_loc` section or the `.debug_loclists` section,
 /// depending on the version of the unit the offset was contained in.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct LocationListsOffset<T = usize>(pub T);
 
 /// An offset to a set of location list offsets in the `.debug_loclists` section.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DebugLocListsBase<T = usize>(pub T);
 
 /// An index into a set of location list offsets in the `.debug_loclists` section.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DebugLocListsIndex<T = usize>(pub T);
 
 /// An offset into the `.debug_macinfo` section.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DebugMacinfoOffset<T = usize>(pub T);
 
+/// An offset into the `.debug_macro` section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DebugMacroOffset<T = usize>(pub T);
+
 /// An offset into either the `.debug_ranges` section or the `.debug_rnglists` section,
 /// depending on the version of the unit the offset was contained in.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct RangeListsOffset<T = usize>(pub T);
 
 /// An offset to a set of range list offsets in the `.debug_rnglists` section.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DebugRngListsBase<T = usize>(pub T);
 
 /// An index into a set of range list offsets in the `.debug_rnglists` section.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DebugRngListsIndex<T = usize>(pub T);
 
 /// An offset into the `.debug_str` section.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DebugStrOffset<T = usize>(pub T);
 
 /// An offset to a set of entries in the `.debug_str_offsets` section.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DebugStrOffsetsBase<T = usize>(pub T);
 
 /// An index into a set of entries in the `.debug_str_offsets` section.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DebugStrOffsetsIndex<T = usize>(pub T);
 
 /// An offset into the `.debug_types` section.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Ord, PartialOrd, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DebugTypesOffset<T = usize>(pub T);
 
 /// A type signature as used in the `.debug_types` section.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DebugTypeSignature(pub u64);
 
+/// An identifier for a split DWARF compilation unit, as used in `DW_AT_dwo_id`
+/// and the `.debug_cu_index` section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DwoId(pub u64);
+
 /// An offset into the `.debug_frame` section.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DebugFrameOffset<T = usize>(pub T);
 
 impl<T> From<T> for DebugFrameOffset<T> {
@@ -170,7 +181,7 @@ impl<T> From<T> for DebugFrameOffset<T> {
 }
 
 /// An offset into the `.eh_frame` section.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct EhFrameOffset<T = usize>(pub T);
 
 impl<T> From<T> for EhFrameOffset<T> {
@@ -198,6 +209,8 @@ pub enum SectionId {
     DebugAddr,
     /// The `.debug_aranges` section.
     DebugAranges,
+    /// The `.debug_cu_index` section.
+    DebugCuIndex,
     /// The `.debug_frame` section.
     DebugFrame,
     /// The `.eh_frame` section.
@@ -216,6 +229,10 @@ pub enum SectionId {
     DebugLocLists,
     /// The `.debug_macinfo` section.
     DebugMacinfo,
+    /// The `.debug_macro` section.
+    DebugMacro,
+    /// The `.debug_names` section.
+    DebugNames,
     /// The `.debug_pubnames` section.
     DebugPubNames,
     /// The `.debug_pubtypes` section.
@@ -228,6 +245,10 @@ pub enum SectionId {
     DebugStr,
     /// The `.debug_str_offsets` section.
     DebugStrOffsets,
+    /// The `.debug_sup` section.
+    DebugSup,
+    /// The `.debug_tu_index` section.
+    DebugTuIndex,
     /// The `.debug_types` section.
     DebugTypes,
 }
@@ -239,6 +260,7 @@ impl SectionId {
             SectionId::DebugAbbrev => ".debug_abbrev",
             SectionId::DebugAddr => ".debug_addr",
             SectionId::DebugAranges => ".debug_aranges",
+            SectionId::DebugCuIndex => ".debug_cu_index",
             SectionId::DebugFrame => ".debug_frame",
             SectionId::EhFrame => ".eh_frame",
             SectionId::EhFrameHdr => ".eh_frame_hdr",
@@ -248,13 +270,197 @@ impl SectionId {
             SectionId::DebugLoc => ".debug_loc",
             SectionId::DebugLocLists => ".debug_loclists",
             SectionId::DebugMacinfo => ".debug_macinfo",
+            SectionId::DebugMacro => ".debug_macro",
+            SectionId::DebugNames => ".debug_names",
             SectionId::DebugPubNames => ".debug_pubnames",
             SectionId::DebugPubTypes => ".debug_pubtypes",
             SectionId::DebugRanges => ".debug_ranges",
             SectionId::DebugRngLists => ".debug_rnglists",
             SectionId::DebugStr => ".debug_str",
             SectionId::DebugStrOffsets => ".debug_str_offsets",
+            SectionId::DebugSup => ".debug_sup",
+            SectionId::DebugTuIndex => ".debug_tu_index",
             SectionId::DebugTypes => ".debug_types",
         }
     }
+
+    /// Returns the ELF section name for this kind, when found in a .dwo or .dwp file.
+    ///
+    /// Returns `None` for sections that are not found in these files.
+    pub fn dwo_name(self) -> Option<&'static str> {
+        Some(match self {
+            SectionId::DebugAbbrev => ".debug_abbrev.dwo",
+            SectionId::DebugCuIndex => ".debug_cu_index",
+            SectionId::DebugInfo => ".debug_info.dwo",
+            SectionId::DebugLine => ".debug_line.dwo",
+            // The GNU split-dwarf extension to DWARF 4 uses `.debug_loc.dwo`.
+            SectionId::DebugLoc => ".debug_loc.dwo",
+            SectionId::DebugLocLists => ".debug_loclists.dwo",
+            SectionId::DebugMacinfo => ".debug_macinfo.dwo",
+            SectionId::DebugMacro => ".debug_macro.dwo",
+            SectionId::DebugRngLists => ".debug_rnglists.dwo",
+            SectionId::DebugStr => ".debug_str.dwo",
+            SectionId::DebugStrOffsets => ".debug_str_offsets.dwo",
+            SectionId::DebugTuIndex => ".debug_tu_index",
+            SectionId::DebugTypes => ".debug_types.dwo",
+            _ => return None,
+        })
+    }
+
+    /// Returns the `DW_SECT_*` value for this section in a version 5 `.dwp`
+    /// unit index.
+    ///
+    /// Returns `None` for sections that are not found in the index.
+    pub fn dw_sect(self) -> Option<constants::DwSect> {
+        Some(match self {
+            SectionId::DebugInfo => constants::DW_SECT_INFO,
+            SectionId::DebugAbbrev => constants::DW_SECT_ABBREV,
+            SectionId::DebugLine => constants::DW_SECT_LINE,
+            SectionId::DebugLocLists => constants::DW_SECT_LOCLISTS,
+            SectionId::DebugStrOffsets => constants::DW_SECT_STR_OFFSETS,
+            SectionId::DebugMacro => constants::DW_SECT_MACRO,
+            SectionId::DebugRngLists => constants::DW_SECT_RNGLISTS,
+            _ => return None,
+        })
+    }
+
+    /// Returns the `DW_SECT_V2_*` value for this section in a version 2 `.dwp`
+    /// unit index.
+    ///
+    /// Returns `None` for sections that are not found in the index.
+    pub fn dw_sect_v2(self) -> Option<constants::DwSectV2> {
+        Some(match self {
+            SectionId::DebugInfo => constants::DW_SECT_V2_INFO,
+            SectionId::DebugTypes => constants::DW_SECT_V2_TYPES,
+            SectionId::DebugAbbrev => constants::DW_SECT_V2_ABBREV,
+            SectionId::DebugLine => constants::DW_SECT_V2_LINE,
+            SectionId::DebugLoc => constants::DW_SECT_V2_LOC,
+            SectionId::DebugStrOffsets => constants::DW_SECT_V2_STR_OFFSETS,
+            SectionId::DebugMacinfo => constants::DW_SECT_V2_MACINFO,
+            SectionId::DebugMacro => constants::DW_SECT_V2_MACRO,
+            _ => return None,
+        })
+    }
+}
+
+impl constants::DwSect {
+    /// Returns the section identified by this value in a version 5 `.dwp`
+    /// unit index.
+    ///
+    /// Returns `None` for unknown values.
+    pub fn section_id(self) -> Option<SectionId> {
+        Some(match self {
+            constants::DW_SECT_INFO => SectionId::DebugInfo,
+            constants::DW_SECT_ABBREV => SectionId::DebugAbbrev,
+            constants::DW_SECT_LINE => SectionId::DebugLine,
+            constants::DW_SECT_LOCLISTS => SectionId::DebugLocLists,
+            constants::DW_SECT_STR_OFFSETS => SectionId::DebugStrOffsets,
+            constants::DW_SECT_MACRO => SectionId::DebugMacro,
+            constants::DW_SECT_RNGLISTS => SectionId::DebugRngLists,
+            _ => return None,
+        })
+    }
+}
+
+impl constants::DwSectV2 {
+    /// Returns the section identified by this value in a version 2 `.dwp`
+    /// unit index.
+    ///
+    /// Returns `None` for unknown values.
+    pub fn section_id(self) -> Option<SectionId> {
+        Some(match self {
+            constants::DW_SECT_V2_INFO => SectionId::DebugInfo,
+            constants::DW_SECT_V2_TYPES => SectionId::DebugTypes,
+            constants::DW_SECT_V2_ABBREV => SectionId::DebugAbbrev,
+            constants::DW_SECT_V2_LINE => SectionId::DebugLine,
+            constants::DW_SECT_V2_LOC => SectionId::DebugLoc,
+            constants::DW_SECT_V2_STR_OFFSETS => SectionId::DebugStrOffsets,
+            constants::DW_SECT_V2_MACINFO => SectionId::DebugMacinfo,
+            constants::DW_SECT_V2_MACRO => SectionId::DebugMacro,
+            _ => return None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::fmt::Debug;
+    use std::hash::Hash;
+
+    fn check_key<T: Copy + Debug + Eq + Ord + Hash>(low: T, high: T) {
+        assert!(low < high);
+        let mut map = HashMap::new();
+        map.insert(low, 1);
+        map.insert(high, 2);
+        map.insert(low, 3);
+        assert_eq!(map.len(), 2);
+        assert_eq!(map[&low], 3);
+        assert_eq!(map[&high], 2);
+    }
+
+    #[test]
+    fn test_offset_keys() {
+        check_key(DebugAbbrevOffset(1), DebugAbbrevOffset(2));
+        check_key(DebugAddrBase(1), DebugAddrBase(2));
+        check_key(DebugAddrIndex(1), DebugAddrIndex(2));
+        check_key(DebugInfoOffset(1), DebugInfoOffset(2));
+        check_key(DebugLineOffset(1), DebugLineOffset(2));
+        check_key(DebugLineStrOffset(1), DebugLineStrOffset(2));
+        check_key(LocationListsOffset(1), LocationListsOffset(2));
+        check_key(DebugLocListsBase(1), DebugLocListsBase(2));
+        check_key(DebugLocListsIndex(1), DebugLocListsIndex(2));
+        check_key(DebugMacinfoOffset(1), DebugMacinfoOffset(2));
+        check_key(RangeListsOffset(1), RangeListsOffset(2));
+        check_key(DebugRngListsBase(1), DebugRngListsBase(2));
+        check_key(DebugRngListsIndex(1), DebugRngListsIndex(2));
+        check_key(DebugStrOffset(1), DebugStrOffset(2));
+        check_key(DebugStrOffsetsBase(1), DebugStrOffsetsBase(2));
+        check_key(DebugStrOffsetsIndex(1), DebugStrOffsetsIndex(2));
+        check_key(DebugTypesOffset(1), DebugTypesOffset(2));
+        check_key(DebugTypeSignature(1), DebugTypeSignature(2));
+        check_key(DwoId(1), DwoId(2));
+        check_key(DebugFrameOffset(1), DebugFrameOffset(2));
+        check_key(EhFrameOffset(1), EhFrameOffset(2));
+    }
+
+    #[test]
+    fn test_dw_sect() {
+        let sections = [
+            SectionId::DebugInfo,
+            SectionId::DebugTypes,
+            SectionId::DebugAbbrev,
+            SectionId::DebugLine,
+            SectionId::DebugLoc,
+            SectionId::DebugLocLists,
+            SectionId::DebugStrOffsets,
+            SectionId::DebugMacinfo,
+            SectionId::DebugMacro,
+            SectionId::DebugRngLists,
+            SectionId::DebugStr,
+        ];
+        for &section in &sections {
+            if let Some(sect) = section.dw_sect() {
+                assert_eq!(sect.section_id(), Some(section));
+            }
+            if let Some(sect) = section.dw_sect_v2() {
+                assert_eq!(sect.section_id(), Some(section));
+            }
+        }
+
+        // The same values identify different sections in each version.
+        assert_eq!(
+            constants::DwSect(5).section_id(),
+            Some(SectionId::DebugLocLists)
+        );
+        assert_eq!(
+            constants::DwSectV2(5).section_id(),
+            Some(SectionId::DebugLoc)
+        );
+        assert_eq!(constants::DwSect(2).section_id(), None);
+        assert_eq!(SectionId::DebugTypes.dw_sect(), None);
+        assert_eq!(SectionId::DebugRngLists.dw_sect_v2(), None);
+        assert_eq!(SectionId::DebugStr.dw_sect(), None);
+    }
 }
@@ -29,7 +29,7 @@ use std::fmt;
 
 // The `dw!` macro turns this:
 //
-//     dw!(DwFoo(u32) {
+//     dw!(DwFoo(u32) "DW_FOO" {
 //         DW_FOO_bar = 0,
 //         DW_FOO_baz = 1,
 //         DW_FOO_bang = 2,
@@ -37,7 +37,7 @@ use std::fmt;
 //
 // into this:
 //
-//     #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+//     #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 //     pub struct DwFoo(pub u32);
 //
 //     pub const DW_FOO_bar: DwFoo = DwFoo(0);
@@ -48,6 +48,10 @@ use std::fmt;
 //         pub fn static_string(&self) -> Option<&'static str> {
 //             ...
 //         }
+//
+//         pub fn from_static_str(name: &str) -> Option<DwFoo> {
+//             ...
+//         }
 //     }
 //
 //     impl fmt::Display for DwFoo {
@@ -55,10 +59,19 @@ use std::fmt;
 //             ...
 //         }
 //     }
+//
+//     impl fmt::Debug for DwFoo {
+//         fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+//             ...
+//         }
+//     }
+//
+// Unknown values use the prefix for their `Display` and `Debug` output, such
+// as `DW_FOO_unknown(0x3)`.
 macro_rules! dw {
-    ($(#[$meta:meta])* $struct_name:ident($struct_type:ty) { $($name:ident = $val:expr),+ $(,)? }) => {
+    ($(#[$meta:meta])* $struct_name:ident($struct_type:ty) $prefix:tt { $($name:ident = $val:expr),+ $(,)? }) => {
         $(#[$meta])*
-        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
         pub struct $struct_name(pub $struct_type);
 
         $(
@@ -74,6 +87,15 @@ macro_rules! dw {
                     _ => return None,
                 })
             }
+
+            pub fn from_static_str(name: &str) -> Option<$struct_name> {
+                Some(match name {
+                    $(
+                        stringify!($name) => $name,
+                    )+
+                    _ => return None,
+                })
+            }
         }
 
         impl fmt::Display for $struct_name {
@@ -81,20 +103,64 @@ macro_rules! dw {
                 if let Some(s) = self.static_string() {
                     f.pad(s)
                 } else {
-                    f.pad(&format!("Unknown {}: {}",
-                                   stringify!($struct_name),
-                                   self.0))
+                    // Padding requires formatting into a temporary string.
+                    #[cfg(feature = "alloc")]
+                    return f.pad(&format!("{}_unknown({:#x})", $prefix, self.0));
+                    #[cfg(not(feature = "alloc"))]
+                    return write!(f, "{}_unknown({:#x})", $prefix, self.0);
+                }
+            }
+        }
+
+        impl fmt::Debug for $struct_name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+                if let Some(s) = self.static_string() {
+                    f.write_str(s)
+                } else {
+                    write!(f, "{}_unknown({:#x})", $prefix, self.0)
                 }
             }
         }
     };
 }
 
+dw!(
+/// The section type field in a `.dwp` unit index.
+///
+/// This is used for version 5.
+/// See Section 7.3.5, Table 7.1.
+DwSect(u32) "DW_SECT" {
+    DW_SECT_INFO = 1,
+    DW_SECT_ABBREV = 3,
+    DW_SECT_LINE = 4,
+    DW_SECT_LOCLISTS = 5,
+    DW_SECT_STR_OFFSETS = 6,
+    DW_SECT_MACRO = 7,
+    DW_SECT_RNGLISTS = 8,
+});
+
+dw!(
+/// The section type field in a `.dwp` unit index with version 2.
+///
+/// This is used by the GNU split DWARF extension to DWARF 4. Some of the
+/// values are the same as for `DwSect`, but values 5, 7 and 8 identify
+/// different sections, and value 2 is only used here.
+DwSectV2(u32) "DW_SECT_V2" {
+    DW_SECT_V2_INFO = 1,
+    DW_SECT_V2_TYPES = 2,
+    DW_SECT_V2_ABBREV = 3,
+    DW_SECT_V2_LINE = 4,
+    DW_SECT_V2_LOC = 5,
+    DW_SECT_V2_STR_OFFSETS = 6,
+    DW_SECT_V2_MACINFO = 7,
+    DW_SECT_V2_MACRO = 8,
+});
+
 dw!(
 /// The unit type field in a unit header.
 ///
 /// See Section 7.5.1, Table 7.2.
-DwUt(u8) {
+DwUt(u8) "DW_UT" {
     DW_UT_compile = 0x01,
     DW_UT_type = 0x02,
     DW_UT_partial = 0x03,
@@ -113,7 +179,7 @@ dw!(
 /// > opcode is encoded in the high order two bits of the first byte (that is,
 /// > opcode = byte >> 6). An operand or extended opcode may be encoded in the
 /// > low order 6 bits. Additional operands are encoded in subsequent bytes.
-DwCfa(u8) {
+DwCfa(u8) "DW_CFA" {
     DW_CFA_advance_loc = 0x01 << 6,
     DW_CFA_offset = 0x02 << 6,
     DW_CFA_restore = 0x03 << 6,
@@ -154,7 +220,7 @@ dw!(
 /// The child determination encodings for DIE attributes.
 ///
 /// See Section 7.5.3, Table 7.4.
-DwChildren(u8) {
+DwChildren(u8) "DW_CHILDREN" {
     DW_CHILDREN_no = 0,
     DW_CHILDREN_yes = 1,
 });
@@ -163,7 +229,7 @@ dw!(
 /// The tag encodings for DIE attributes.
 ///
 /// See Section 7.5.3, Table 7.3.
-DwTag(u64) {
+DwTag(u64) "DW_TAG" {
     DW_TAG_null = 0x00,
 
     DW_TAG_array_type = 0x01,
@@ -308,7 +374,7 @@ dw!(
 /// The attribute encodings for DIE attributes.
 ///
 /// See Section 7.5.4, Table 7.5.
-DwAt(u64) {
+DwAt(u64) "DW_AT" {
     DW_AT_null = 0x00,
 
     DW_AT_sibling = 0x01,
@@ -617,7 +683,7 @@ dw!(
 /// The attribute form encodings for DIE attributes.
 ///
 /// See Section 7.5.6, Table 7.6.
-DwForm(u64) {
+DwForm(u64) "DW_FORM" {
     DW_FORM_null = 0x00,
 
     DW_FORM_addr = 0x01,
@@ -681,7 +747,7 @@ dw!(
 /// The encodings of the constants used in the `DW_AT_encoding` attribute.
 ///
 /// See Section 7.8, Table 7.11.
-DwAte(u8) {
+DwAte(u8) "DW_ATE" {
     DW_ATE_address = 0x01,
     DW_ATE_boolean = 0x02,
     DW_ATE_complex_float = 0x03,
@@ -713,7 +779,7 @@ dw!(
 /// The encodings of the constants used in location list entries.
 ///
 /// See Section 7.7.3, Table 7.10.
-DwLle(u8) {
+DwLle(u8) "DW_LLE" {
     DW_LLE_end_of_list = 0x00,
     DW_LLE_base_addressx = 0x01,
     DW_LLE_startx_endx = 0x02,
@@ -730,7 +796,7 @@ dw!(
 /// The encodings of the constants used in the `DW_AT_decimal_sign` attribute.
 ///
 /// See Section 7.8, Table 7.12.
-DwDs(u8) {
+DwDs(u8) "DW_DS" {
     DW_DS_unsigned = 0x01,
     DW_DS_leading_overpunch = 0x02,
     DW_DS_trailing_overpunch = 0x03,
@@ -742,7 +808,7 @@ dw!(
 /// The encodings of the constants used in the `DW_AT_endianity` attribute.
 ///
 /// See Section 7.8, Table 7.13.
-DwEnd(u8) {
+DwEnd(u8) "DW_END" {
     DW_END_default = 0x00,
     DW_END_big = 0x01,
     DW_END_little = 0x02,
@@ -754,7 +820,7 @@ dw!(
 /// The encodings of the constants used in the `DW_AT_accessibility` attribute.
 ///
 /// See Section 7.9, Table 7.14.
-DwAccess(u8) {
+DwAccess(u8) "DW_ACCESS" {
     DW_ACCESS_public = 0x01,
     DW_ACCESS_protected = 0x02,
     DW_ACCESS_private = 0x03,
@@ -764,7 +830,7 @@ dw!(
 /// The encodings of the constants used in the `DW_AT_visibility` attribute.
 ///
 /// See Section 7.10, Table 7.15.
-DwVis(u8) {
+DwVis(u8) "DW_VIS" {
     DW_VIS_local = 0x01,
     DW_VIS_exported = 0x02,
     DW_VIS_qualified = 0x03,
@@ -774,7 +840,7 @@ dw!(
 /// The encodings of the constants used in the `DW_AT_virtuality` attribute.
 ///
 /// See Section 7.11, Table 7.16.
-DwVirtuality(u8) {
+DwVirtuality(u8) "DW_VIRTUALITY" {
     DW_VIRTUALITY_none = 0x00,
     DW_VIRTUALITY_virtual = 0x01,
     DW_VIRTUALITY_pure_virtual = 0x02,
@@ -784,7 +850,7 @@ dw!(
 /// The encodings of the constants used in the `DW_AT_language` attribute.
 ///
 /// See Section 7.12, Table 7.17.
-DwLang(u16) {
+DwLang(u16) "DW_LANG" {
     DW_LANG_C89 = 0x0001,
     DW_LANG_C = 0x0002,
     DW_LANG_Ada83 = 0x0003,
@@ -874,7 +940,7 @@ dw!(
 ///
 /// There is only one value that is common to all target architectures.
 /// See Section 7.13.
-DwAddr(u64) {
+DwAddr(u64) "DW_ADDR" {
     DW_ADDR_none = 0x00,
 });
 
@@ -882,7 +948,7 @@ dw!(
 /// The encodings of the constants used in the `DW_AT_identifier_case` attribute.
 ///
 /// See Section 7.14, Table 7.18.
-DwId(u8) {
+DwId(u8) "DW_ID" {
     DW_ID_case_sensitive = 0x00,
     DW_ID_up_case = 0x01,
     DW_ID_down_case = 0x02,
@@ -893,7 +959,7 @@ dw!(
 /// The encodings of the constants used in the `DW_AT_calling_convention` attribute.
 ///
 /// See Section 7.15, Table 7.19.
-DwCc(u8) {
+DwCc(u8) "DW_CC" {
     DW_CC_normal = 0x01,
     DW_CC_program = 0x02,
     DW_CC_nocall = 0x03,
@@ -907,7 +973,7 @@ dw!(
 /// The encodings of the constants used in the `DW_AT_inline` attribute.
 ///
 /// See Section 7.16, Table 7.20.
-DwInl(u8) {
+DwInl(u8) "DW_INL" {
     DW_INL_not_inlined = 0x00,
     DW_INL_inlined = 0x01,
     DW_INL_declared_not_inlined = 0x02,
@@ -918,7 +984,7 @@ dw!(
 /// The encodings of the constants used in the `DW_AT_ordering` attribute.
 ///
 /// See Section 7.17, Table 7.17.
-DwOrd(u8) {
+DwOrd(u8) "DW_ORD" {
     DW_ORD_row_major = 0x00,
     DW_ORD_col_major = 0x01,
 });
@@ -927,7 +993,7 @@ dw!(
 /// The encodings of the constants used in the `DW_AT_discr_list` attribute.
 ///
 /// See Section 7.18, Table 7.22.
-DwDsc(u8) {
+DwDsc(u8) "DW_DSC" {
     DW_DSC_label = 0x00,
     DW_DSC_range = 0x01,
 });
@@ -936,7 +1002,7 @@ dw!(
 /// Name index attribute encodings.
 ///
 /// See Section 7.19, Table 7.23.
-DwIdx(u16) {
+DwIdx(u16) "DW_IDX" {
     DW_IDX_compile_unit = 1,
     DW_IDX_type_unit = 2,
     DW_IDX_die_offset = 3,
@@ -946,11 +1012,25 @@ DwIdx(u16) {
     DW_IDX_hi_user = 0x3fff,
 });
 
+dw!(
+/// The atom types used in the header data of Apple accelerator tables.
+///
+/// These are an LLVM extension, and are not part of the DWARF standard.
+DwAtom(u16) "DW_ATOM" {
+    DW_ATOM_null = 0,
+    DW_ATOM_die_offset = 1,
+    DW_ATOM_cu_offset = 2,
+    DW_ATOM_die_tag = 3,
+    DW_ATOM_type_flags = 4,
+    DW_ATOM_type_type_flags = 5,
+    DW_ATOM_qual_name_hash = 6,
+});
+
 dw!(
 /// The encodings of the constants used in the `DW_AT_defaulted` attribute.
 ///
 /// See Section 7.20, Table 7.24.
-DwDefaulted(u8) {
+DwDefaulted(u8) "DW_DEFAULTED" {
     DW_DEFAULTED_no = 0x00,
     DW_DEFAULTED_in_class = 0x01,
     DW_DEFAULTED_out_of_class = 0x02,
@@ -960,7 +1040,7 @@ dw!(
 /// The encodings for the standard opcodes for line number information.
 ///
 /// See Section 7.22, Table 7.25.
-DwLns(u8) {
+DwLns(u8) "DW_LNS" {
     DW_LNS_copy = 0x01,
     DW_LNS_advance_pc = 0x02,
     DW_LNS_advance_line = 0x03,
@@ -979,7 +1059,7 @@ dw!(
 /// The encodings for the extended opcodes for line number information.
 ///
 /// See Section 7.22, Table 7.26.
-DwLne(u8) {
+DwLne(u8) "DW_LNE" {
     DW_LNE_end_sequence = 0x01,
     DW_LNE_set_address = 0x02,
     DW_LNE_define_file = 0x03,
@@ -993,7 +1073,7 @@ dw!(
 /// The encodings for the line number header entry formats.
 ///
 /// See Section 7.22, Table 7.27.
-DwLnct(u16) {
+DwLnct(u16) "DW_LNCT" {
     DW_LNCT_path = 0x1,
     DW_LNCT_directory_index = 0x2,
     DW_LNCT_timestamp = 0x3,
@@ -1003,11 +1083,24 @@ DwLnct(u16) {
     DW_LNCT_hi_user = 0x3fff,
 });
 
+dw!(
+/// The encodings for macro information entry types in the `.debug_macinfo`
+/// section.
+///
+/// See Section 7.22, Table 7.24 of DWARF version 4.
+DwMacinfo(u8) "DW_MACINFO" {
+    DW_MACINFO_define = 0x01,
+    DW_MACINFO_undef = 0x02,
+    DW_MACINFO_start_file = 0x03,
+    DW_MACINFO_end_file = 0x04,
+    DW_MACINFO_vendor_ext = 0xff,
+});
+
 dw!(
 /// The encodings for macro information entry types.
 ///
 /// See Section 7.23, Table 7.28.
-DwMacro(u8) {
+DwMacro(u8) "DW_MACRO" {
     DW_MACRO_define = 0x01,
     DW_MACRO_undef = 0x02,
     DW_MACRO_start_file = 0x03,
@@ -1028,7 +1121,7 @@ dw!(
 /// Range list entry encoding values.
 ///
 /// See Section 7.25, Table 7.30.
-DwRle(u8) {
+DwRle(u8) "DW_RLE" {
     DW_RLE_end_of_list = 0x00,
     DW_RLE_base_addressx = 0x01,
     DW_RLE_startx_endx = 0x02,
@@ -1043,7 +1136,7 @@ dw!(
 /// The encodings for DWARF expression operations.
 ///
 /// See Section 7.7.1, Table 7.9.
-DwOp(u8) {
+DwOp(u8) "DW_OP" {
     DW_OP_addr = 0x03,
     DW_OP_deref = 0x06,
     DW_OP_const1u = 0x08,
@@ -1229,7 +1322,7 @@ dw!(
 /// be applied.
 ///
 /// Defined in http://refspecs.linux-foundation.org/LSB_4.0.0/LSB-Core-generic/LSB-Core-generic/dwarfext.html
-DwEhPe(u8) {
+DwEhPe(u8) "DW_EH_PE" {
 // Format of pointer encoding.
 
 // "Unsigned value is encoded using the Little Endian Base 128"
@@ -1337,6 +1430,44 @@ impl DwEhPe {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_debug() {
+        assert_eq!(format!("{:?}", DW_TAG_subprogram), "DW_TAG_subprogram");
+        assert_eq!(format!("{:?}", DW_AT_name), "DW_AT_name");
+        assert_eq!(format!("{:?}", DW_FORM_strp), "DW_FORM_strp");
+        assert_eq!(format!("{:?}", DW_OP_addr), "DW_OP_addr");
+        assert_eq!(format!("{:?}", DwTag(0x1234)), "DW_TAG_unknown(0x1234)");
+        assert_eq!(format!("{:?}", Some(DW_LANG_Rust)), "Some(DW_LANG_Rust)");
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", DW_TAG_subprogram), "DW_TAG_subprogram");
+        assert_eq!(format!("{}", DwTag(0x1234)), "DW_TAG_unknown(0x1234)");
+        assert_eq!(format!("{}", DwOp(0xff)), "DW_OP_unknown(0xff)");
+        assert_eq!(format!("{:>24}", DwTag(0x1234)), "  DW_TAG_unknown(0x1234)");
+    }
+
+    #[test]
+    fn test_from_static_str() {
+        assert_eq!(DwAt::from_static_str("DW_AT_name"), Some(DW_AT_name));
+        assert_eq!(
+            DwTag::from_static_str("DW_TAG_subprogram"),
+            Some(DW_TAG_subprogram)
+        );
+        assert_eq!(
+            DwForm::from_static_str("DW_FORM_strx1"),
+            Some(DW_FORM_strx1)
+        );
+        assert_eq!(DwAt::from_static_str("DW_TAG_subprogram"), None);
+        assert_eq!(DwAt::from_static_str("DW_AT_unknown"), None);
+
+        for at in &[DW_AT_sibling, DW_AT_name, DW_AT_MIPS_linkage_name] {
+            let name = at.static_string().unwrap();
+            assert_eq!(DwAt::from_static_str(name), Some(*at));
+        }
+    }
+
     #[test]
     fn test_dw_eh_pe_format() {
         let encoding = DwEhPe(DW_EH_PE_pcrel.0 | DW_EH_PE_uleb128.0);
@@ -57,6 +57,30 @@ pub trait Endianity: Debug + Default + Clone + Copy + PartialEq + Eq {
         }
     }
 
+    /// Reads an unsigned 128 bit integer from `buf`.
+    ///
+    /// ```
+    /// use gimli::{BigEndian, Endianity, LittleEndian};
+    ///
+    /// let buf = [1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2];
+    /// assert_eq!(LittleEndian.read_u128(&buf), (2 << 120) | 1);
+    /// assert_eq!(BigEndian.read_u128(&buf), (1 << 120) | 2);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when `buf.len() < 16`.
+    #[inline]
+    fn read_u128(self, buf: &[u8]) -> u128 {
+        let first = u128::from(self.read_u64(&buf[..8]));
+        let second = u128::from(self.read_u64(&buf[8..16]));
+        if self.is_big_endian() {
+            (first << 64) | second
+        } else {
+            (second << 64) | first
+        }
+    }
+
     /// Read an unsigned n-bytes integer u64.
     ///
     /// # Panics
@@ -71,6 +95,16 @@ pub trait Endianity: Debug + Default + Clone + Copy + PartialEq + Eq {
         }
     }
 
+    /// Reads a signed 8 bit integer from `buf`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `buf.len() < 1`.
+    #[inline]
+    fn read_i8(self, buf: &[u8]) -> i8 {
+        buf[0] as i8
+    }
+
     /// Reads a signed 16 bit integer from `buf`.
     ///
     /// # Panics
@@ -101,6 +135,16 @@ pub trait Endianity: Debug + Default + Clone + Copy + PartialEq + Eq {
         self.read_u64(buf) as i64
     }
 
+    /// Reads a signed 128 bit integer from `buf`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `buf.len() < 16`.
+    #[inline]
+    fn read_i128(self, buf: &[u8]) -> i128 {
+        self.read_u128(buf) as i128
+    }
+
     /// Reads a 32 bit floating point number from `buf`.
     ///
     /// # Panics
@@ -187,6 +231,27 @@ impl Default for RunTimeEndian {
     }
 }
 
+impl RunTimeEndian {
+    /// Return the byte order for a target that is little endian if
+    /// `little_endian` is true, and big endian otherwise.
+    ///
+    /// ```
+    /// use gimli::{Endianity, RunTimeEndian};
+    ///
+    /// let endian = RunTimeEndian::from_little_endian(false);
+    /// assert_eq!(endian, RunTimeEndian::Big);
+    /// assert_eq!(endian.read_u16(&[0x12, 0x34]), 0x1234);
+    /// ```
+    #[inline]
+    pub fn from_little_endian(little_endian: bool) -> RunTimeEndian {
+        if little_endian {
+            RunTimeEndian::Little
+        } else {
+            RunTimeEndian::Big
+        }
+    }
+}
+
 impl Endianity for RunTimeEndian {
     #[inline]
     fn is_big_endian(self) -> bool {
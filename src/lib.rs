@@ -8,21 +8,25 @@
 //!
 //! Cargo features that can be enabled with `gimli`:
 //!
-//! * `std`: Enabled by default. Use the `std` library. Disabling this feature
-//! allows using `gimli` in embedded environments that do not have access to
-//! `std`. Note that even when `std` is disabled, `gimli` still requires an
-//! implementation of the `alloc` crate, and you must enable the `nightly`
-//! feature.
+//! * `std`: Enabled by default. Use the `std` library. Automatically enables
+//!   `alloc` too. Disabling this feature allows using `gimli` in embedded
+//!   environments that do not have access to `std`.
 //!
-//! * `alloc`: Nightly only. Enables usage of the unstable, nightly-only
-//! `#![feature(alloc)]` Rust feature that allows `gimli` to use boxes and
-//! collection types in a `#[no_std]` environment.
+//! * `alloc`: Use the `alloc` crate. This enables the APIs that need to
+//!   allocate, such as parsing abbreviations and debugging information entries,
+//!   line number programs, and `EndianRcSlice`. This requires Rust 1.36 or later
+//!   if `std` is not enabled.
 //!
-//! * `read`: Enabled by default. Enables the `read` module. Requires
-//! either `alloc` or `std` to also be enabled.
+//! * `read`: Enabled by default. Enables the `read` module. Without `alloc`,
+//!   only the APIs that borrow from the input are available, such as
+//!   `EndianSlice`, the unit headers, call frame information, and the
+//!   string, address, range list and location list sections.
 //!
 //! * `write`: Enabled by default. Enables the `write` module. Automatically
-//! enables `std` too.
+//!   enables `std` too.
+//!
+//! * `rayon`: Enables `Dwarf::par_units` for constructing units in parallel
+//!   using the `rayon` crate.
 #![deny(missing_docs)]
 #![deny(missing_debug_implementations)]
 // Selectively enable rust 2018 warnings
@@ -40,13 +44,12 @@
 // False positives when block expressions are used inside an assertion.
 #![allow(clippy::panic_params)]
 #![no_std]
-#![cfg_attr(feature = "alloc", feature(alloc))]
 
 #[cfg(feature = "std")]
 #[macro_use]
 extern crate std;
 
-#[cfg(not(feature = "std"))]
+#[cfg(all(feature = "alloc", not(feature = "std")))]
 #[macro_use]
 extern crate alloc;
 #[cfg(not(feature = "std"))]
@@ -64,7 +67,7 @@ mod imports {
     pub use std::vec;
 }
 
-#[cfg(not(feature = "std"))]
+#[cfg(all(feature = "alloc", not(feature = "std")))]
 mod imports {
     pub use alloc::borrow;
     pub use alloc::boxed;
@@ -75,6 +78,7 @@ mod imports {
     pub use alloc::vec;
 }
 
+#[cfg(feature = "alloc")]
 use crate::imports::*;
 
 pub use stable_deref_trait::{CloneStableDeref, StableDeref};
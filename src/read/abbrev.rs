@@ -1,12 +1,18 @@
 //! Functions for parsing DWARF debugging abbreviations.
 
+#[cfg(feature = "alloc")]
 use crate::collections::btree_map;
+#[cfg(feature = "alloc")]
 use crate::vec::Vec;
 
-use crate::common::{DebugAbbrevOffset, SectionId};
+#[cfg(feature = "alloc")]
+use crate::common::DebugAbbrevOffset;
+use crate::common::{Encoding, SectionId};
 use crate::constants;
 use crate::endianity::Endianity;
-use crate::read::{EndianSlice, Error, Reader, Result, Section, UnitHeader};
+use crate::read::{EndianSlice, Reader, Section, UnitHeader};
+#[cfg(feature = "alloc")]
+use crate::read::{Error, Result};
 
 /// The `DebugAbbrev` struct represents the abbreviations describing
 /// `DebuggingInformationEntry`s' attribute names and forms found in the
@@ -44,6 +50,7 @@ impl<R: Reader> DebugAbbrev<R> {
     /// `.debug_abbrev` section.
     ///
     /// The `offset` should generally be retrieved from a unit header.
+    #[cfg(feature = "alloc")]
     pub fn abbreviations(
         &self,
         debug_abbrev_offset: DebugAbbrevOffset<R::Offset>,
@@ -79,9 +86,7 @@ impl<T> DebugAbbrev<T> {
 }
 
 impl<R> Section<R> for DebugAbbrev<R> {
-    fn id() -> SectionId {
-        SectionId::DebugAbbrev
-    }
+    const ID: SectionId = SectionId::DebugAbbrev;
 
     fn reader(&self) -> &R {
         &self.debug_abbrev_section
@@ -101,12 +106,14 @@ impl<R> From<R> for DebugAbbrev<R> {
 /// Construct an `Abbreviations` instance with the
 /// [`abbreviations()`](struct.UnitHeader.html#method.abbreviations)
 /// method.
+#[cfg(feature = "alloc")]
 #[derive(Debug, Default, Clone)]
 pub struct Abbreviations {
     vec: Vec<Abbreviation>,
     map: btree_map::BTreeMap<u64, Abbreviation>,
 }
 
+#[cfg(feature = "alloc")]
 impl Abbreviations {
     /// Construct a new, empty set of abbreviations.
     fn empty() -> Abbreviations {
@@ -128,9 +135,12 @@ impl Abbreviations {
             // in a Vec, as long as the map doesn't already contain them.
             // A potential further optimization would be to allow some
             // holes in the Vec, but there's no need for that yet.
-            if code_usize - 1 < self.vec.len() {
+            // Code 0 is reserved for null entries, and wraps to an index
+            // that is never in the Vec.
+            let index = code_usize.wrapping_sub(1);
+            if index < self.vec.len() {
                 return Err(());
-            } else if code_usize - 1 == self.vec.len() {
+            } else if index == self.vec.len() {
                 if !self.map.is_empty() && self.map.contains_key(&abbrev.code) {
                     return Err(());
                 } else {
@@ -149,11 +159,16 @@ impl Abbreviations {
     }
 
     /// Get the abbreviation associated with the given code.
+    ///
+    /// Sequential codes starting at 1 are looked up by indexing, and other
+    /// codes are looked up in a map. Returns `None` for code 0, which is
+    /// reserved for null entries.
     #[inline]
     pub fn get(&self, code: u64) -> Option<&Abbreviation> {
         let code_usize = code as usize;
-        if code_usize as u64 == code && code_usize - 1 < self.vec.len() {
-            Some(&self.vec[code_usize - 1])
+        let index = code_usize.wrapping_sub(1);
+        if code_usize as u64 == code && index < self.vec.len() {
+            Some(&self.vec[index])
         } else {
             self.map.get(&code)
         }
@@ -175,6 +190,7 @@ impl Abbreviations {
 
 /// An abbreviation describes the shape of a `DebuggingInformationEntry`'s type:
 /// its code, tag type, whether it has children, and its set of attributes.
+#[cfg(feature = "alloc")]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Abbreviation {
     code: u64,
@@ -183,6 +199,7 @@ pub struct Abbreviation {
     attributes: Vec<AttributeSpecification>,
 }
 
+#[cfg(feature = "alloc")]
 impl Abbreviation {
     /// Construct a new `Abbreviation`.
     ///
@@ -330,48 +347,11 @@ impl AttributeSpecification {
     /// Note that because some attributes are variably sized, the size cannot
     /// always be known without parsing, in which case we return `None`.
     pub fn size<R: Reader>(&self, header: &UnitHeader<R>) -> Option<usize> {
-        match self.form {
-            constants::DW_FORM_addr => Some(header.address_size() as usize),
-
-            constants::DW_FORM_implicit_const => Some(0),
-
-            constants::DW_FORM_flag |
-            constants::DW_FORM_flag_present |
-            constants::DW_FORM_data1 |
-            constants::DW_FORM_ref1 => Some(1),
-
-            constants::DW_FORM_data2 |
-            constants::DW_FORM_ref2 => Some(2),
-
-            constants::DW_FORM_data4 |
-            constants::DW_FORM_ref4 => Some(4),
-
-            constants::DW_FORM_data8 |
-            constants::DW_FORM_ref8 => Some(8),
-
-            constants::DW_FORM_sec_offset |
-            constants::DW_FORM_ref_addr |
-            constants::DW_FORM_ref_sig8 |
-            constants::DW_FORM_strp => Some(header.format().word_size() as usize),
-
-            // Variably sized forms.
-            constants::DW_FORM_block |
-            constants::DW_FORM_block1 |
-            constants::DW_FORM_block2 |
-            constants::DW_FORM_block4 |
-            constants::DW_FORM_exprloc |
-            constants::DW_FORM_ref_udata |
-            constants::DW_FORM_string |
-            constants::DW_FORM_sdata |
-            constants::DW_FORM_udata |
-            constants::DW_FORM_indirect |
-
-            // We don't know the size of unknown forms.
-            _ => None,
-        }
+        get_attribute_size(self.form, header.encoding()).map(usize::from)
     }
 
     /// Parse an attribute's form.
+    #[cfg(feature = "alloc")]
     fn parse_form<R: Reader>(input: &mut R) -> Result<constants::DwForm> {
         let val = input.read_uleb128()?;
         if val == 0 {
@@ -383,6 +363,7 @@ impl AttributeSpecification {
 
     /// Parse an attribute specification. Returns `None` for the null attribute
     /// specification, `Some` for an actual attribute specification.
+    #[cfg(feature = "alloc")]
     fn parse<R: Reader>(input: &mut R) -> Result<Option<AttributeSpecification>> {
         let name = input.read_uleb128()?;
         if name == 0 {
@@ -407,9 +388,80 @@ impl AttributeSpecification {
     }
 }
 
+/// Return the size of an attribute value with the given form, in bytes.
+///
+/// Returns `None` if the size depends on the value, or if the form is unknown.
+pub(crate) fn get_attribute_size(form: constants::DwForm, encoding: Encoding) -> Option<u8> {
+    match form {
+        constants::DW_FORM_addr => Some(encoding.address_size),
+
+        constants::DW_FORM_implicit_const | constants::DW_FORM_flag_present => Some(0),
+
+        constants::DW_FORM_data1
+        | constants::DW_FORM_flag
+        | constants::DW_FORM_strx1
+        | constants::DW_FORM_ref1
+        | constants::DW_FORM_addrx1 => Some(1),
+
+        constants::DW_FORM_data2
+        | constants::DW_FORM_ref2
+        | constants::DW_FORM_addrx2
+        | constants::DW_FORM_strx2 => Some(2),
+
+        constants::DW_FORM_addrx3 | constants::DW_FORM_strx3 => Some(3),
+
+        constants::DW_FORM_data4
+        | constants::DW_FORM_ref_sup4
+        | constants::DW_FORM_ref4
+        | constants::DW_FORM_strx4
+        | constants::DW_FORM_addrx4 => Some(4),
+
+        constants::DW_FORM_data8
+        | constants::DW_FORM_ref8
+        | constants::DW_FORM_ref_sig8
+        | constants::DW_FORM_ref_sup8 => Some(8),
+
+        constants::DW_FORM_data16 => Some(16),
+
+        constants::DW_FORM_sec_offset
+        | constants::DW_FORM_GNU_ref_alt
+        | constants::DW_FORM_strp
+        | constants::DW_FORM_strp_sup
+        | constants::DW_FORM_GNU_strp_alt
+        | constants::DW_FORM_line_strp => Some(encoding.format.word_size()),
+
+        constants::DW_FORM_ref_addr => {
+            // This is an offset, but DWARF version 2 specifies that DW_FORM_ref_addr
+            // has the same size as an address on the target system.  This was changed
+            // in DWARF version 3.
+            Some(if encoding.version == 2 {
+                encoding.address_size
+            } else {
+                encoding.format.word_size()
+            })
+        }
+
+        // Variably sized forms.
+        constants::DW_FORM_block
+        | constants::DW_FORM_block1
+        | constants::DW_FORM_block2
+        | constants::DW_FORM_block4
+        | constants::DW_FORM_exprloc
+        | constants::DW_FORM_ref_udata
+        | constants::DW_FORM_string
+        | constants::DW_FORM_sdata
+        | constants::DW_FORM_udata
+        | constants::DW_FORM_indirect => None,
+
+        // We don't know the size of unknown forms.
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
+    use crate::common::Format;
     use crate::constants;
     use crate::endianity::LittleEndian;
     use crate::read::{EndianSlice, Error};
@@ -571,6 +623,32 @@ pub mod tests {
         // 32-bit usize conversions.
         let mut abbrevs = Abbreviations::empty();
         abbrevs.insert(abbrev(2)).unwrap();
+
+        // Sparse codes are stored in the map.
+        let mut abbrevs = Abbreviations::empty();
+        abbrevs.insert(abbrev(1)).unwrap();
+        abbrevs.insert(abbrev(u64::max_value())).unwrap();
+        assert_eq!(abbrevs.vec.len(), 1);
+        assert_eq!(abbrevs.map.len(), 1);
+        assert_abbrev(&abbrevs, 1);
+        assert_abbrev(&abbrevs, u64::max_value());
+    }
+
+    #[test]
+    fn test_abbreviations_get_zero() {
+        let mut abbrevs = Abbreviations::empty();
+        assert!(abbrevs.get(0).is_none());
+        abbrevs
+            .insert(Abbreviation::new(
+                1,
+                constants::DW_TAG_base_type,
+                constants::DW_CHILDREN_no,
+                vec![],
+            ))
+            .unwrap();
+        assert!(abbrevs.get(0).is_none());
+        assert!(abbrevs.get(1).is_some());
+        assert!(abbrevs.get(2).is_none());
     }
 
     #[test]
@@ -846,4 +924,43 @@ pub mod tests {
             otherwise => panic!("Unexpected result: {:?}", otherwise),
         };
     }
+
+    #[test]
+    fn test_get_attribute_size() {
+        let encoding = Encoding {
+            format: Format::Dwarf32,
+            version: 4,
+            address_size: 8,
+        };
+        for &(form, size) in &[
+            (constants::DW_FORM_addr, Some(8)),
+            (constants::DW_FORM_flag_present, Some(0)),
+            (constants::DW_FORM_implicit_const, Some(0)),
+            (constants::DW_FORM_flag, Some(1)),
+            (constants::DW_FORM_strx3, Some(3)),
+            (constants::DW_FORM_ref_sup4, Some(4)),
+            (constants::DW_FORM_ref_sig8, Some(8)),
+            (constants::DW_FORM_data16, Some(16)),
+            (constants::DW_FORM_strp, Some(4)),
+            (constants::DW_FORM_ref_addr, Some(4)),
+            (constants::DW_FORM_block1, None),
+            (constants::DW_FORM_udata, None),
+            (constants::DW_FORM_string, None),
+            (constants::DW_FORM_indirect, None),
+            (constants::DwForm(0x7fff), None),
+        ] {
+            assert_eq!(get_attribute_size(form, encoding), size, "{}", form);
+        }
+
+        let encoding = Encoding {
+            format: Format::Dwarf64,
+            version: 2,
+            address_size: 4,
+        };
+        assert_eq!(get_attribute_size(constants::DW_FORM_strp, encoding), Some(8));
+        assert_eq!(
+            get_attribute_size(constants::DW_FORM_ref_addr, encoding),
+            Some(4)
+        );
+    }
 }
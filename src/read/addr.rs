@@ -1,5 +1,7 @@
-use crate::common::{DebugAddrBase, DebugAddrIndex, SectionId};
-use crate::read::{Reader, ReaderOffset, Result, Section};
+use fallible_iterator::FallibleIterator;
+
+use crate::common::{DebugAddrBase, DebugAddrIndex, Encoding, SectionId};
+use crate::read::{Error, Reader, ReaderOffset, Result, Section};
 
 /// The raw contents of the `.debug_addr` section.
 #[derive(Debug, Default, Clone, Copy)]
@@ -11,6 +13,59 @@ impl<R: Reader> DebugAddr<R> {
     // TODO: add an iterator over the sets of addresses in the section.
     // This is not needed for common usage of the section though.
 
+    /// Iterate over the addresses in the set of addresses at the given `base`.
+    ///
+    /// The `base` must be the `DW_AT_addr_base` value from the compilation unit DIE.
+    /// This is an offset that points to the first address following the header.
+    ///
+    /// The `unit_encoding` must match the compilation unit that the base was
+    /// contained in.
+    ///
+    /// For DWARF version 5, the header before `base` is parsed to find the end
+    /// of the set of addresses, and the address size in the header must match
+    /// the unit. The GNU split DWARF extension to DWARF 4 does not emit a header,
+    /// so for earlier versions the iteration continues to the end of the section.
+    ///
+    /// Can be [used with
+    /// `FallibleIterator`](./index.html#using-with-fallibleiterator).
+    pub fn addresses(
+        &self,
+        unit_encoding: Encoding,
+        base: DebugAddrBase<R::Offset>,
+    ) -> Result<AddrIter<R>> {
+        let mut input = self.section.clone();
+        if unit_encoding.version >= 5 {
+            // initial_length + version + address_size + segment_selector_size
+            let header_size =
+                R::Offset::from_u8(unit_encoding.format.initial_length_size() + 2 + 1 + 1);
+            if base.0 < header_size {
+                return Err(Error::OffsetOutOfBounds);
+            }
+            input.skip(base.0 - header_size)?;
+
+            let (length, _format) = input.read_initial_length()?;
+            input.truncate(length)?;
+            let version = input.read_u16()?;
+            if version != 5 {
+                return Err(Error::UnknownVersion(u64::from(version)));
+            }
+            let address_size = input.read_address_size()?;
+            if address_size != unit_encoding.address_size {
+                return Err(Error::UnsupportedAddressSize(address_size));
+            }
+            let segment_selector_size = input.read_u8()?;
+            if segment_selector_size != 0 {
+                return Err(Error::UnsupportedSegmentSize);
+            }
+        } else {
+            input.skip(base.0)?;
+        }
+        Ok(AddrIter {
+            input,
+            address_size: unit_encoding.address_size,
+        })
+    }
+
     /// Returns the address at the given `base` and `index`.
     ///
     /// A set of addresses in the `.debug_addr` section consists of a header
@@ -40,6 +95,41 @@ impl<R: Reader> DebugAddr<R> {
     }
 }
 
+/// An iterator over the addresses in a set of addresses in the `.debug_addr` section.
+///
+/// This is returned by `DebugAddr::addresses`.
+#[derive(Debug, Clone)]
+pub struct AddrIter<R: Reader> {
+    input: R,
+    address_size: u8,
+}
+
+impl<R: Reader> AddrIter<R> {
+    /// Advance the iterator to the next address.
+    pub fn next(&mut self) -> Result<Option<u64>> {
+        if self.input.is_empty() {
+            return Ok(None);
+        }
+
+        match self.input.read_address(self.address_size) {
+            Ok(address) => Ok(Some(address)),
+            Err(e) => {
+                self.input.empty();
+                Err(e)
+            }
+        }
+    }
+}
+
+impl<R: Reader> FallibleIterator for AddrIter<R> {
+    type Item = u64;
+    type Error = Error;
+
+    fn next(&mut self) -> ::std::result::Result<Option<Self::Item>, Self::Error> {
+        AddrIter::next(self)
+    }
+}
+
 impl<T> DebugAddr<T> {
     /// Create a `DebugAddr` section that references the data in `self`.
     ///
@@ -65,9 +155,7 @@ impl<T> DebugAddr<T> {
 }
 
 impl<R> Section<R> for DebugAddr<R> {
-    fn id() -> SectionId {
-        SectionId::DebugAddr
-    }
+    const ID: SectionId = SectionId::DebugAddr;
 
     fn reader(&self) -> &R {
         &self.section
@@ -85,6 +173,7 @@ mod tests {
     use super::*;
     use crate::read::EndianSlice;
     use crate::test_util::GimliSectionMethods;
+    use crate::vec::Vec;
     use crate::{Format, LittleEndian};
     use test_assembler::{Endian, Label, LabelMaker, Section};
 
@@ -125,4 +214,85 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_addresses() {
+        for format in vec![Format::Dwarf32, Format::Dwarf64] {
+            let encoding = Encoding {
+                format,
+                version: 5,
+                address_size: 4,
+            };
+            let zero = Label::new();
+            let length1 = Label::new();
+            let start1 = Label::new();
+            let first1 = Label::new();
+            let end1 = Label::new();
+            let length2 = Label::new();
+            let start2 = Label::new();
+            let first2 = Label::new();
+            let end2 = Label::new();
+            let section = Section::with_endian(Endian::Little)
+                .mark(&zero)
+                .initial_length(format, &length1, &start1)
+                .D16(5)
+                .D8(4)
+                .D8(0)
+                .mark(&first1)
+                .L32(0x1000)
+                .L32(0x1001)
+                .mark(&end1)
+                .initial_length(format, &length2, &start2)
+                .D16(5)
+                .D8(4)
+                .D8(0)
+                .mark(&first2)
+                .L32(0x2000)
+                .mark(&end2);
+            length1.set_const((&end1 - &start1) as u64);
+            length2.set_const((&end2 - &start2) as u64);
+            let section = section.get_contents().unwrap();
+            let debug_addr = DebugAddr::from(EndianSlice::new(&section, LittleEndian));
+
+            let base1 = DebugAddrBase((&first1 - &zero) as usize);
+            let addresses = debug_addr.addresses(encoding, base1).unwrap();
+            assert_eq!(addresses.collect::<Vec<_>>(), Ok(vec![0x1000, 0x1001]));
+
+            let base2 = DebugAddrBase((&first2 - &zero) as usize);
+            let addresses = debug_addr.addresses(encoding, base2).unwrap();
+            assert_eq!(addresses.collect::<Vec<_>>(), Ok(vec![0x2000]));
+
+            // The address size must match the header.
+            let encoding8 = Encoding {
+                address_size: 8,
+                ..encoding
+            };
+            assert_eq!(
+                debug_addr.addresses(encoding8, base1).err(),
+                Some(Error::UnsupportedAddressSize(4))
+            );
+            assert_eq!(
+                debug_addr.addresses(encoding, DebugAddrBase(0)).err(),
+                Some(Error::OffsetOutOfBounds)
+            );
+        }
+    }
+
+    #[test]
+    fn test_addresses_gnu() {
+        let encoding = Encoding {
+            format: Format::Dwarf32,
+            version: 4,
+            address_size: 8,
+        };
+        let section = Section::with_endian(Endian::Little)
+            .L64(0x1000)
+            .L64(0x2000)
+            .L64(0x3000)
+            .get_contents()
+            .unwrap();
+        let debug_addr = DebugAddr::from(EndianSlice::new(&section, LittleEndian));
+        let addresses = debug_addr.addresses(encoding, DebugAddrBase(8)).unwrap();
+        assert_eq!(addresses.collect::<Vec<_>>(), Ok(vec![0x2000, 0x3000]));
+    }
 }
@@ -0,0 +1,653 @@
+//! Functions for parsing the Apple accelerator tables.
+//!
+//! These tables are found in the `.apple_names`, `.apple_types`,
+//! `.apple_namespaces` and `.apple_objc` sections produced by LLVM.
+
+use crate::vec::Vec;
+use fallible_iterator::FallibleIterator;
+
+use crate::common::{DebugInfoOffset, DebugStrOffset, Format};
+use crate::constants;
+use crate::endianity::Endianity;
+use crate::read::names::parse_name_attribute_value;
+use crate::read::{DebugStr, EndianSlice, Error, Reader, ReaderOffset, Result};
+
+/// The magic number at the start of an Apple accelerator table: `"HASH"`.
+const APPLE_ACCEL_MAGIC: u32 = 0x4841_5348;
+
+/// The DJB hash function.
+const APPLE_ACCEL_HASH_DJB: u16 = 0;
+
+/// A bucket value indicating that the bucket is empty.
+const APPLE_ACCEL_EMPTY_BUCKET: u32 = 0xffff_ffff;
+
+/// A parsed Apple accelerator table.
+///
+/// This may be used for any of the `.apple_names`, `.apple_types`,
+/// `.apple_namespaces` and `.apple_objc` sections.
+#[derive(Debug, Clone)]
+pub struct AppleAccelTable<R: Reader> {
+    version: u16,
+    hash_function: u16,
+    bucket_count: u32,
+    hashes_count: u32,
+    die_offset_base: u32,
+    atoms: Vec<AppleAtomSpecification>,
+    buckets: R,
+    hashes: R,
+    offsets: R,
+    section: R,
+}
+
+impl<'input, Endian> AppleAccelTable<EndianSlice<'input, Endian>>
+where
+    Endian: Endianity,
+{
+    /// Parse an `AppleAccelTable` from the data in an Apple accelerator table
+    /// section, such as `.apple_names`.
+    ///
+    /// It is the caller's responsibility to read the section and present it
+    /// as a `&[u8]` slice. That means using some ELF loader on Linux,
+    /// a Mach-O loader on OSX, etc.
+    ///
+    /// ```
+    /// use gimli::{AppleAccelTable, DebugStr, LittleEndian};
+    ///
+    /// # fn example() -> gimli::Result<()> {
+    /// # let buf = [];
+    /// # let read_apple_names_section_somehow = || &buf;
+    /// # let read_debug_str_section_somehow = || &buf;
+    /// let apple_names = AppleAccelTable::new(read_apple_names_section_somehow(), LittleEndian)?;
+    /// let debug_str = DebugStr::new(read_debug_str_section_somehow(), LittleEndian);
+    ///
+    /// let mut entries = apple_names.lookup(b"main", &debug_str)?;
+    /// while let Some(entry) = entries.next()? {
+    ///     println!("main is at {:?}", entry.die_offset());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(section: &'input [u8], endian: Endian) -> Result<Self> {
+        Self::parse(EndianSlice::new(section, endian))
+    }
+}
+
+impl<R: Reader> AppleAccelTable<R> {
+    /// Parse an `AppleAccelTable` from the data in an Apple accelerator table section.
+    pub fn parse(section: R) -> Result<Self> {
+        let mut input = section.clone();
+        let magic = input.read_u32()?;
+        if magic != APPLE_ACCEL_MAGIC {
+            return Err(Error::InvalidAppleAccelMagic(magic));
+        }
+        let version = input.read_u16()?;
+        if version != 1 {
+            return Err(Error::UnknownVersion(u64::from(version)));
+        }
+        let hash_function = input.read_u16()?;
+        if hash_function != APPLE_ACCEL_HASH_DJB {
+            return Err(Error::UnknownHashFunction(hash_function));
+        }
+        let bucket_count = input.read_u32()?;
+        let hashes_count = input.read_u32()?;
+        let header_data_len = input.read_u32()?;
+
+        let mut header_data = input.split(R::Offset::from_u32(header_data_len))?;
+        let die_offset_base = header_data.read_u32()?;
+        let atom_count = header_data.read_u32()?;
+        let mut atoms = Vec::new();
+        for _ in 0..atom_count {
+            let atom_type = constants::DwAtom(header_data.read_u16()?);
+            let form = constants::DwForm(u64::from(header_data.read_u16()?));
+            atoms.push(AppleAtomSpecification { atom_type, form });
+        }
+
+        let buckets = input.split(R::Offset::from_u64(u64::from(bucket_count) * 4)?)?;
+        let hashes = input.split(R::Offset::from_u64(u64::from(hashes_count) * 4)?)?;
+        let offsets = input.split(R::Offset::from_u64(u64::from(hashes_count) * 4)?)?;
+
+        Ok(AppleAccelTable {
+            version,
+            hash_function,
+            bucket_count,
+            hashes_count,
+            die_offset_base,
+            atoms,
+            buckets,
+            hashes,
+            offsets,
+            section,
+        })
+    }
+
+    /// Return the version of this table.
+    #[inline]
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    /// Return the hash function used by this table.
+    ///
+    /// Currently only the DJB hash function (0) is supported.
+    #[inline]
+    pub fn hash_function(&self) -> u16 {
+        self.hash_function
+    }
+
+    /// Return the number of buckets in the hash table.
+    #[inline]
+    pub fn bucket_count(&self) -> u32 {
+        self.bucket_count
+    }
+
+    /// Return the number of hashes in the hash table.
+    #[inline]
+    pub fn hashes_count(&self) -> u32 {
+        self.hashes_count
+    }
+
+    /// Return the base offset that is added to `DW_ATOM_die_offset` values
+    /// that use a `DW_FORM_ref*` form.
+    #[inline]
+    pub fn die_offset_base(&self) -> u32 {
+        self.die_offset_base
+    }
+
+    /// Return the descriptions of the atoms in each entry.
+    #[inline]
+    pub fn atoms(&self) -> &[AppleAtomSpecification] {
+        &self.atoms
+    }
+
+    /// Find the entries for the given name.
+    ///
+    /// The names in the table are offsets into `debug_str`.
+    ///
+    /// Can be [used with
+    /// `FallibleIterator`](./index.html#using-with-fallibleiterator).
+    pub fn lookup(
+        &self,
+        name: &[u8],
+        debug_str: &DebugStr<R>,
+    ) -> Result<AppleAccelEntryIter<'_, R>> {
+        let mut iter = AppleAccelEntryIter {
+            table: self,
+            input: self.section.clone(),
+            remaining: 0,
+        };
+        iter.input.empty();
+        if self.bucket_count == 0 {
+            return Ok(iter);
+        }
+
+        let hash = hash(name);
+        let bucket = hash % self.bucket_count;
+        let mut buckets = self.buckets.clone();
+        buckets.skip(R::Offset::from_u32(bucket * 4))?;
+        let start = buckets.read_u32()?;
+        if start == APPLE_ACCEL_EMPTY_BUCKET {
+            return Ok(iter);
+        }
+
+        let mut hashes = self.hashes.clone();
+        hashes.skip(R::Offset::from_u64(u64::from(start) * 4)?)?;
+        let mut offsets = self.offsets.clone();
+        offsets.skip(R::Offset::from_u64(u64::from(start) * 4)?)?;
+        for _ in start..self.hashes_count {
+            let entry_hash = hashes.read_u32()?;
+            let offset = offsets.read_u32()?;
+            if entry_hash % self.bucket_count != bucket {
+                break;
+            }
+            if entry_hash != hash {
+                continue;
+            }
+
+            // The hash data is a list of names with this hash, terminated by a zero offset.
+            let mut input = self.section.clone();
+            input.skip(R::Offset::from_u32(offset))?;
+            loop {
+                let string_offset = input.read_u32()?;
+                if string_offset == 0 {
+                    break;
+                }
+                let count = input.read_u32()?;
+                let string =
+                    debug_str.get_str(DebugStrOffset(R::Offset::from_u32(string_offset)))?;
+                if string.to_slice()? == name {
+                    iter.input = input;
+                    iter.remaining = count;
+                    return Ok(iter);
+                }
+                for _ in 0..count {
+                    for atom in &self.atoms {
+                        parse_name_attribute_value(&mut input, atom.form, Format::Dwarf32)?;
+                    }
+                }
+            }
+        }
+        Ok(iter)
+    }
+}
+
+/// The hash function used by Apple accelerator tables.
+///
+/// This is the DJB hash of the name.
+fn hash(name: &[u8]) -> u32 {
+    name.iter().fold(5381u32, |hash, c| {
+        hash.wrapping_mul(33).wrapping_add(u32::from(*c))
+    })
+}
+
+/// An iterator over the entries for a name in an Apple accelerator table.
+///
+/// Can be [used with
+/// `FallibleIterator`](./index.html#using-with-fallibleiterator).
+#[derive(Clone, Debug)]
+pub struct AppleAccelEntryIter<'table, R: Reader> {
+    table: &'table AppleAccelTable<R>,
+    input: R,
+    remaining: u32,
+}
+
+impl<'table, R: Reader> AppleAccelEntryIter<'table, R> {
+    /// Advance the iterator to the next entry.
+    pub fn next(&mut self) -> Result<Option<AppleAccelEntry<R::Offset>>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        match self.parse_entry() {
+            Ok(entry) => {
+                self.remaining -= 1;
+                Ok(Some(entry))
+            }
+            Err(e) => {
+                self.remaining = 0;
+                Err(e)
+            }
+        }
+    }
+
+    fn parse_entry(&mut self) -> Result<AppleAccelEntry<R::Offset>> {
+        let mut entry = AppleAccelEntry {
+            atoms: Vec::with_capacity(self.table.atoms.len()),
+            die_offset: None,
+            cu_offset: None,
+            tag: None,
+            type_flags: None,
+            qual_name_hash: None,
+        };
+        for spec in &self.table.atoms {
+            let value = parse_name_attribute_value(&mut self.input, spec.form, Format::Dwarf32)?;
+            match spec.atom_type {
+                constants::DW_ATOM_die_offset => {
+                    // Only references are relative to the base.
+                    let offset = match spec.form {
+                        constants::DW_FORM_ref1
+                        | constants::DW_FORM_ref2
+                        | constants::DW_FORM_ref4
+                        | constants::DW_FORM_ref8
+                        | constants::DW_FORM_ref_udata => value
+                            .checked_add(u64::from(self.table.die_offset_base))
+                            .ok_or(Error::UnsupportedOffset)?,
+                        _ => value,
+                    };
+                    entry.die_offset = Some(DebugInfoOffset(R::Offset::from_u64(offset)?));
+                }
+                constants::DW_ATOM_cu_offset => {
+                    entry.cu_offset = Some(DebugInfoOffset(R::Offset::from_u64(value)?));
+                }
+                constants::DW_ATOM_die_tag => entry.tag = Some(constants::DwTag(value)),
+                constants::DW_ATOM_type_flags => entry.type_flags = Some(value),
+                constants::DW_ATOM_qual_name_hash => entry.qual_name_hash = Some(value),
+                _ => {}
+            }
+            entry.atoms.push(AppleAtom {
+                atom_type: spec.atom_type,
+                form: spec.form,
+                value,
+            });
+        }
+        Ok(entry)
+    }
+}
+
+impl<'table, R: Reader> FallibleIterator for AppleAccelEntryIter<'table, R> {
+    type Item = AppleAccelEntry<R::Offset>;
+    type Error = Error;
+
+    fn next(&mut self) -> ::std::result::Result<Option<Self::Item>, Self::Error> {
+        AppleAccelEntryIter::next(self)
+    }
+}
+
+/// An entry in an Apple accelerator table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppleAccelEntry<T = usize> {
+    atoms: Vec<AppleAtom>,
+    die_offset: Option<DebugInfoOffset<T>>,
+    cu_offset: Option<DebugInfoOffset<T>>,
+    tag: Option<constants::DwTag>,
+    type_flags: Option<u64>,
+    qual_name_hash: Option<u64>,
+}
+
+impl<T: Copy> AppleAccelEntry<T> {
+    /// Return all of the atoms of this entry.
+    #[inline]
+    pub fn atoms(&self) -> &[AppleAtom] {
+        &self.atoms
+    }
+
+    /// Return the `DW_ATOM_die_offset` atom.
+    ///
+    /// This is the offset of the debugging information entry in the `.debug_info` section.
+    /// If the atom uses a `DW_FORM_ref*` form, then the table's `die_offset_base`
+    /// has been added to it.
+    #[inline]
+    pub fn die_offset(&self) -> Option<DebugInfoOffset<T>> {
+        self.die_offset
+    }
+
+    /// Return the `DW_ATOM_cu_offset` atom.
+    #[inline]
+    pub fn cu_offset(&self) -> Option<DebugInfoOffset<T>> {
+        self.cu_offset
+    }
+
+    /// Return the `DW_ATOM_die_tag` atom.
+    #[inline]
+    pub fn tag(&self) -> Option<constants::DwTag> {
+        self.tag
+    }
+
+    /// Return the `DW_ATOM_type_flags` atom.
+    #[inline]
+    pub fn type_flags(&self) -> Option<u64> {
+        self.type_flags
+    }
+
+    /// Return the `DW_ATOM_qual_name_hash` atom.
+    #[inline]
+    pub fn qual_name_hash(&self) -> Option<u64> {
+        self.qual_name_hash
+    }
+}
+
+/// An atom in an entry of an Apple accelerator table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AppleAtom {
+    atom_type: constants::DwAtom,
+    form: constants::DwForm,
+    value: u64,
+}
+
+impl AppleAtom {
+    /// Return the type of this atom.
+    #[inline]
+    pub fn atom_type(&self) -> constants::DwAtom {
+        self.atom_type
+    }
+
+    /// Return the form of this atom.
+    #[inline]
+    pub fn form(&self) -> constants::DwForm {
+        self.form
+    }
+
+    /// Return the value of this atom.
+    #[inline]
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+}
+
+/// The description of an atom in the header data of an Apple accelerator table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AppleAtomSpecification {
+    atom_type: constants::DwAtom,
+    form: constants::DwForm,
+}
+
+impl AppleAtomSpecification {
+    /// Get the atom's type.
+    #[inline]
+    pub fn atom_type(&self) -> constants::DwAtom {
+        self.atom_type
+    }
+
+    /// Get the atom's form.
+    #[inline]
+    pub fn form(&self) -> constants::DwForm {
+        self.form
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::endianity::LittleEndian;
+    use test_assembler::{Endian, Label, LabelMaker, Section};
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(hash(b""), 5381);
+        assert_eq!(hash(b"main"), 0x7c9a_7f6a);
+        assert_ne!(hash(b"MAIN"), hash(b"main"));
+    }
+
+    #[test]
+    fn test_apple_accel_table() {
+        let debug_str_buf = b"\0main\0foo\0bar\0";
+        let debug_str = DebugStr::new(debug_str_buf, LittleEndian);
+        let (main, foo, bar) = (1, 6, 10);
+
+        // Put all names in one bucket, so that they are in a single chain of hashes.
+        // The hash data for "main" also contains "bar", to check that the name is compared.
+        let mut hashes = vec![(hash(b"main"), Label::new()), (hash(b"foo"), Label::new())];
+        hashes.sort_by_key(|&(hash, _)| hash);
+
+        let section = Section::with_endian(Endian::Little);
+        let section_start = section.start();
+        let header_data_start = Label::new();
+        let header_data_end = Label::new();
+        let header_data_len = Label::new();
+        let offsets: Vec<_> = hashes.iter().map(|_| Label::new()).collect();
+        let mut section = section
+            .L32(APPLE_ACCEL_MAGIC)
+            .L16(1)
+            .L16(APPLE_ACCEL_HASH_DJB)
+            // Bucket count.
+            .L32(1)
+            // Hashes count.
+            .L32(hashes.len() as u32)
+            .L32(&header_data_len)
+            .mark(&header_data_start)
+            // Die offset base.
+            .L32(0x100)
+            // Atoms.
+            .L32(2)
+            .L16(constants::DW_ATOM_die_offset.0)
+            .L16(constants::DW_FORM_data4.0 as u16)
+            .L16(constants::DW_ATOM_die_tag.0)
+            .L16(constants::DW_FORM_data2.0 as u16)
+            .mark(&header_data_end)
+            // Buckets.
+            .L32(0);
+        for &(hash, _) in &hashes {
+            section = section.L32(hash);
+        }
+        for offset in &offsets {
+            section = section.L32(offset);
+        }
+        for (&(hash, ref data), offset) in hashes.iter().zip(&offsets) {
+            section = section.mark(data);
+            offset.set_const((data - &section_start) as u64);
+            if hash == self::hash(b"main") {
+                section = section
+                    .L32(bar)
+                    .L32(1)
+                    .L32(0x30)
+                    .L16(constants::DW_TAG_base_type.0 as u16)
+                    .L32(main)
+                    .L32(2)
+                    .L32(0x10)
+                    .L16(constants::DW_TAG_subprogram.0 as u16)
+                    .L32(0x20)
+                    .L16(constants::DW_TAG_subprogram.0 as u16);
+            } else {
+                section = section
+                    .L32(foo)
+                    .L32(1)
+                    .L32(0x40)
+                    .L16(constants::DW_TAG_variable.0 as u16);
+            }
+            section = section.L32(0);
+        }
+        header_data_len.set_const((&header_data_end - &header_data_start) as u64);
+        let buf = section.get_contents().unwrap();
+
+        let table = AppleAccelTable::new(&buf, LittleEndian).unwrap();
+        assert_eq!(table.version(), 1);
+        assert_eq!(table.hash_function(), APPLE_ACCEL_HASH_DJB);
+        assert_eq!(table.bucket_count(), 1);
+        assert_eq!(table.hashes_count(), 2);
+        assert_eq!(table.die_offset_base(), 0x100);
+        assert_eq!(
+            table.atoms(),
+            &[
+                AppleAtomSpecification {
+                    atom_type: constants::DW_ATOM_die_offset,
+                    form: constants::DW_FORM_data4,
+                },
+                AppleAtomSpecification {
+                    atom_type: constants::DW_ATOM_die_tag,
+                    form: constants::DW_FORM_data2,
+                },
+            ]
+        );
+
+        let entries: Vec<_> = table
+            .lookup(b"main", &debug_str)
+            .unwrap()
+            .collect()
+            .unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].die_offset(), Some(DebugInfoOffset(0x10)));
+        assert_eq!(entries[0].tag(), Some(constants::DW_TAG_subprogram));
+        assert_eq!(entries[0].cu_offset(), None);
+        assert_eq!(
+            entries[0].atoms()[1],
+            AppleAtom {
+                atom_type: constants::DW_ATOM_die_tag,
+                form: constants::DW_FORM_data2,
+                value: constants::DW_TAG_subprogram.0,
+            }
+        );
+        assert_eq!(entries[1].die_offset(), Some(DebugInfoOffset(0x20)));
+
+        let entries: Vec<_> = table.lookup(b"foo", &debug_str).unwrap().collect().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].die_offset(), Some(DebugInfoOffset(0x40)));
+        assert_eq!(entries[0].tag(), Some(constants::DW_TAG_variable));
+
+        // "bar" is in the table, but under the wrong hash.
+        assert_eq!(table.lookup(b"bar", &debug_str).unwrap().next(), Ok(None));
+        assert_eq!(table.lookup(b"baz", &debug_str).unwrap().next(), Ok(None));
+    }
+
+    #[test]
+    fn test_apple_accel_table_die_offset_base() {
+        let debug_str = DebugStr::new(b"\0main\0", LittleEndian);
+        let die_offset = |form: constants::DwForm, value: u64| {
+            let section = Section::with_endian(Endian::Little);
+            let section_start = section.start();
+            let data = Label::new();
+            let offset = Label::new();
+            let section = section
+                .L32(APPLE_ACCEL_MAGIC)
+                .L16(1)
+                .L16(APPLE_ACCEL_HASH_DJB)
+                .L32(1)
+                .L32(1)
+                .L32(12)
+                // Die offset base.
+                .L32(0x100)
+                .L32(1)
+                .L16(constants::DW_ATOM_die_offset.0)
+                .L16(form.0 as u16)
+                .L32(0)
+                .L32(hash(b"main"))
+                .L32(&offset)
+                .mark(&data)
+                .L32(1)
+                .L32(1);
+            let section = if form == constants::DW_FORM_ref8 {
+                section.L64(value)
+            } else {
+                section.L32(value as u32)
+            };
+            offset.set_const((&data - &section_start) as u64);
+            let buf = section.L32(0).get_contents().unwrap();
+            let table = AppleAccelTable::new(&buf, LittleEndian).unwrap();
+            let mut entries = table.lookup(b"main", &debug_str).unwrap();
+            entries.next().map(|entry| entry.unwrap().die_offset().unwrap())
+        };
+
+        // The base is only added to references.
+        assert_eq!(
+            die_offset(constants::DW_FORM_data4, 0x10),
+            Ok(DebugInfoOffset(0x10))
+        );
+        assert_eq!(
+            die_offset(constants::DW_FORM_ref4, 0x10),
+            Ok(DebugInfoOffset(0x110))
+        );
+        assert_eq!(
+            die_offset(constants::DW_FORM_ref8, !0),
+            Err(Error::UnsupportedOffset)
+        );
+    }
+
+    #[test]
+    fn test_apple_accel_table_empty_bucket() {
+        let section = Section::with_endian(Endian::Little)
+            .L32(APPLE_ACCEL_MAGIC)
+            .L16(1)
+            .L16(APPLE_ACCEL_HASH_DJB)
+            .L32(1)
+            .L32(0)
+            .L32(8)
+            .L32(0)
+            .L32(0)
+            .L32(APPLE_ACCEL_EMPTY_BUCKET);
+        let buf = section.get_contents().unwrap();
+        let debug_str = DebugStr::new(b"\0main\0", LittleEndian);
+        let table = AppleAccelTable::new(&buf, LittleEndian).unwrap();
+        assert_eq!(table.lookup(b"main", &debug_str).unwrap().next(), Ok(None));
+    }
+
+    #[test]
+    fn test_apple_accel_table_bad_header() {
+        let buf = Section::with_endian(Endian::Little)
+            .L32(0x1234_5678)
+            .get_contents()
+            .unwrap();
+        assert_eq!(
+            AppleAccelTable::new(&buf, LittleEndian).err(),
+            Some(Error::InvalidAppleAccelMagic(0x1234_5678))
+        );
+
+        let buf = Section::with_endian(Endian::Little)
+            .L32(APPLE_ACCEL_MAGIC)
+            .L16(1)
+            .L16(1)
+            .get_contents()
+            .unwrap();
+        assert_eq!(
+            AppleAccelTable::new(&buf, LittleEndian).err(),
+            Some(Error::UnknownHashFunction(1))
+        );
+    }
+}
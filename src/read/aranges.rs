@@ -4,17 +4,70 @@ use std::marker::PhantomData;
 
 use crate::common::{DebugInfoOffset, Encoding, SectionId};
 use crate::endianity::Endianity;
-use crate::read::lookup::{DebugLookup, LookupEntryIter, LookupParser};
+use crate::read::lookup::{LookupEntryIter, LookupParser};
 use crate::read::{
     parse_debug_info_offset, EndianSlice, Error, Reader, ReaderOffset, Result, Section,
 };
 
+/// A header for a set of entries in the `.debug_aranges` section.
+///
+/// These entries all belong to a single unit.
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct ArangeHeader<T = usize> {
+pub struct ArangeHeader<R, Offset = <R as Reader>::Offset>
+where
+    R: Reader<Offset = Offset>,
+    Offset: ReaderOffset,
+{
     encoding: Encoding,
-    length: T,
-    offset: DebugInfoOffset<T>,
+    length: Offset,
+    offset: DebugInfoOffset<Offset>,
     segment_size: u8,
+    entries: R,
+}
+
+impl<R, Offset> ArangeHeader<R, Offset>
+where
+    R: Reader<Offset = Offset>,
+    Offset: ReaderOffset,
+{
+    /// Return the length of this set of entries, including the header.
+    #[inline]
+    pub fn length(&self) -> Offset {
+        self.length
+    }
+
+    /// Return the encoding parameters for this set of entries.
+    #[inline]
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// Return the size in bytes of a segment selector.
+    #[inline]
+    pub fn segment_size(&self) -> u8 {
+        self.segment_size
+    }
+
+    /// Return the size in bytes of an address.
+    #[inline]
+    pub fn address_size(&self) -> u8 {
+        self.encoding.address_size
+    }
+
+    /// Return the offset into the .debug_info section for this set of arange entries.
+    #[inline]
+    pub fn debug_info_offset(&self) -> DebugInfoOffset<Offset> {
+        self.offset
+    }
+
+    /// Return the arange entries in this set.
+    ///
+    /// Can be [used with
+    /// `FallibleIterator`](./index.html#using-with-fallibleiterator).
+    #[inline]
+    pub fn entries(&self) -> ArangeEntryIter<R> {
+        ArangeEntryIter(LookupEntryIter::new_set(self.entries.clone(), self.clone()))
+    }
 }
 
 /// A single parsed arange.
@@ -61,7 +114,8 @@ impl<T: Copy + Ord> PartialOrd for ArangeEntry<T> {
 impl<T: Copy + Ord> Ord for ArangeEntry<T> {
     fn cmp(&self, other: &ArangeEntry<T>) -> Ordering {
         // The expected comparison, but ignore header.
-        self.segment.cmp(&other.segment)
+        self.segment
+            .cmp(&other.segment)
             .then(self.address.cmp(&other.address))
             .then(self.length.cmp(&other.length))
     }
@@ -74,7 +128,7 @@ struct ArangeParser<R: Reader> {
 }
 
 impl<R: Reader> LookupParser<R> for ArangeParser<R> {
-    type Header = ArangeHeader<R::Offset>;
+    type Header = ArangeHeader<R>;
     type Entry = ArangeEntry<R::Offset>;
 
     /// Parse an arange set header. Returns a tuple of the aranges to be
@@ -89,8 +143,13 @@ impl<R: Reader> LookupParser<R> for ArangeParser<R> {
         }
 
         let offset = parse_debug_info_offset(&mut rest, format)?;
-        let address_size = rest.read_u8()?;
+        let address_size = rest.read_address_size()?;
         let segment_size = rest.read_u8()?;
+        // Segment selectors are read in the same way as addresses.
+        match segment_size {
+            0 | 1 | 2 | 4 | 8 => {}
+            _ => return Err(Error::UnsupportedSegmentSize),
+        }
 
         // unit_length + version + offset + address_size + segment_size
         let header_length = format.initial_length_size() + 2 + format.word_size() + 1 + 1;
@@ -113,12 +172,13 @@ impl<R: Reader> LookupParser<R> for ArangeParser<R> {
             // TODO: segment_size
         };
         Ok((
-            rest,
+            rest.clone(),
             ArangeHeader {
                 encoding,
                 length,
                 offset,
                 segment_size,
+                entries: rest,
             },
         ))
     }
@@ -163,8 +223,10 @@ impl<R: Reader> LookupParser<R> for ArangeParser<R> {
 
 /// The `DebugAranges` struct represents the DWARF address range information
 /// found in the `.debug_aranges` section.
-#[derive(Debug, Clone)]
-pub struct DebugAranges<R: Reader>(DebugLookup<R, ArangeParser<R>>);
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DebugAranges<R> {
+    section: R,
+}
 
 impl<'input, Endian> DebugAranges<EndianSlice<'input, Endian>>
 where
@@ -206,28 +268,117 @@ impl<R: Reader> DebugAranges<R> {
     /// }
     /// ```
     pub fn items(&self) -> ArangeEntryIter<R> {
-        ArangeEntryIter(self.0.items())
+        ArangeEntryIter(LookupEntryIter::new(self.section.clone()))
+    }
+
+    /// Iterate the headers in the `.debug_aranges` section.
+    ///
+    /// Each header describes a set of aranges for a single unit.
+    ///
+    /// ```
+    /// use gimli::{DebugAranges, EndianSlice, LittleEndian};
+    ///
+    /// # let buf = [];
+    /// # let read_debug_aranges_section = || &buf;
+    /// let debug_aranges = DebugAranges::new(read_debug_aranges_section(), LittleEndian);
+    ///
+    /// let mut headers = debug_aranges.headers();
+    /// while let Some(header) = headers.next().unwrap() {
+    ///     println!("aranges for unit at {:?}", header.debug_info_offset());
+    ///     let mut entries = header.entries();
+    ///     while let Some(arange) = entries.next().unwrap() {
+    ///         println!("arange starts at {}, has length {}", arange.address(), arange.length());
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Can be [used with
+    /// `FallibleIterator`](./index.html#using-with-fallibleiterator).
+    pub fn headers(&self) -> ArangeHeaderIter<R> {
+        ArangeHeaderIter {
+            input: self.section.clone(),
+        }
     }
 }
 
-impl<R: Reader> Section<R> for DebugAranges<R> {
-    fn id() -> SectionId {
-        SectionId::DebugAranges
+impl<T> DebugAranges<T> {
+    /// Create a `DebugAranges` section that references the data in `self`.
+    ///
+    /// This is useful when `R` implements `Reader` but `T` does not.
+    ///
+    /// ## Example Usage
+    ///
+    /// ```rust,no_run
+    /// # let load_section = || unimplemented!();
+    /// // Read the DWARF section into a `Vec` with whatever object loader you're using.
+    /// let owned_section: gimli::DebugAranges<Vec<u8>> = load_section();
+    /// // Create a reference to the DWARF section.
+    /// let section = owned_section.borrow(|section| {
+    ///     gimli::EndianSlice::new(&section, gimli::LittleEndian)
+    /// });
+    /// ```
+    pub fn borrow<'a, F, R>(&'a self, mut borrow: F) -> DebugAranges<R>
+    where
+        F: FnMut(&'a T) -> R,
+    {
+        borrow(&self.section).into()
     }
+}
+
+impl<R> Section<R> for DebugAranges<R> {
+    const ID: SectionId = SectionId::DebugAranges;
 
     fn reader(&self) -> &R {
-        self.0.reader()
+        &self.section
+    }
+}
+
+impl<R> From<R> for DebugAranges<R> {
+    fn from(section: R) -> Self {
+        DebugAranges { section }
+    }
+}
+
+/// An iterator over the headers of a `.debug_aranges` section.
+///
+/// Can be [used with
+/// `FallibleIterator`](./index.html#using-with-fallibleiterator).
+#[derive(Clone, Debug)]
+pub struct ArangeHeaderIter<R: Reader> {
+    input: R,
+}
+
+impl<R: Reader> ArangeHeaderIter<R> {
+    /// Advance the iterator to the next header.
+    pub fn next(&mut self) -> Result<Option<ArangeHeader<R>>> {
+        if self.input.is_empty() {
+            return Ok(None);
+        }
+
+        match ArangeParser::parse_header(&mut self.input) {
+            Ok((_, header)) => Ok(Some(header)),
+            Err(e) => {
+                self.input.empty();
+                Err(e)
+            }
+        }
     }
 }
 
-impl<R: Reader> From<R> for DebugAranges<R> {
-    fn from(debug_aranges_section: R) -> Self {
-        DebugAranges(DebugLookup::from(debug_aranges_section))
+impl<R: Reader> FallibleIterator for ArangeHeaderIter<R> {
+    type Item = ArangeHeader<R>;
+    type Error = Error;
+
+    fn next(&mut self) -> ::std::result::Result<Option<Self::Item>, Self::Error> {
+        ArangeHeaderIter::next(self)
     }
 }
 
 /// An iterator over the aranges from a `.debug_aranges` section.
 ///
+/// Returned by `DebugAranges::items` for all aranges in the section,
+/// or by `ArangeHeader::entries` for the aranges of a single unit.
+///
 /// Can be [used with
 /// `FallibleIterator`](./index.html#using-with-fallibleiterator).
 #[derive(Debug, Clone)]
@@ -261,6 +412,7 @@ mod tests {
     use crate::endianity::LittleEndian;
     use crate::read::lookup::LookupParser;
     use crate::read::EndianSlice;
+    use crate::vec::Vec;
 
     #[test]
     fn test_parse_header_ok() {
@@ -317,10 +469,38 @@ mod tests {
                 length: 0x20,
                 offset: DebugInfoOffset(0x0403_0201),
                 segment_size: 4,
+                entries: EndianSlice::new(&buf[buf.len() - 32..buf.len() - 16], LittleEndian),
             }
         );
     }
 
+    #[test]
+    fn test_parse_header_address_size() {
+        for &address_size in &[0, 3, 16, 0xff] {
+            #[rustfmt::skip]
+            let buf = [
+                // 32-bit length = 10.
+                0x0a, 0x00, 0x00, 0x00,
+                // Version.
+                0x02, 0x00,
+                // Offset.
+                0x01, 0x02, 0x03, 0x04,
+                // Address size.
+                address_size,
+                // Segment size.
+                0x00,
+                // Dummy data.
+                0x00, 0x00,
+            ];
+
+            let rest = &mut EndianSlice::new(&buf, LittleEndian);
+            match ArangeParser::parse_header(rest) {
+                Err(Error::UnsupportedAddressSize(size)) => assert_eq!(size, address_size),
+                otherwise => panic!("Unexpected result: {:?}", otherwise),
+            }
+        }
+    }
+
     #[test]
     fn test_parse_entry_ok() {
         let header = ArangeHeader {
@@ -332,6 +512,7 @@ mod tests {
             length: 0,
             offset: DebugInfoOffset(0),
             segment_size: 0,
+            entries: EndianSlice::new(&[], LittleEndian),
         };
         let buf = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09];
         let rest = &mut EndianSlice::new(&buf, LittleEndian);
@@ -359,6 +540,7 @@ mod tests {
             length: 0,
             offset: DebugInfoOffset(0),
             segment_size: 8,
+            entries: EndianSlice::new(&[], LittleEndian),
         };
         #[rustfmt::skip]
         let buf = [
@@ -396,6 +578,7 @@ mod tests {
             length: 0,
             offset: DebugInfoOffset(0),
             segment_size: 0,
+            entries: EndianSlice::new(&[], LittleEndian),
         };
         #[rustfmt::skip]
         let buf = [
@@ -421,4 +604,204 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn test_parse_header_segment_size() {
+        #[rustfmt::skip]
+        let buf = [
+            // 32-bit length = 12.
+            0x0c, 0x00, 0x00, 0x00,
+            // Version.
+            0x02, 0x00,
+            // Offset.
+            0x00, 0x00, 0x00, 0x00,
+            // Address size.
+            0x04,
+            // Segment size.
+            0x03,
+            // Padding.
+            0x00, 0x00, 0x00, 0x00,
+        ];
+        let rest = &mut EndianSlice::new(&buf, LittleEndian);
+        match ArangeParser::parse_header(rest) {
+            Err(Error::UnsupportedSegmentSize) => {}
+            otherwise => panic!("Unexpected result: {:?}", otherwise),
+        }
+    }
+
+    #[test]
+    fn test_headers_segment() {
+        #[rustfmt::skip]
+        let buf = [
+            // 32-bit length = 46.
+            0x2e, 0x00, 0x00, 0x00,
+            // Version.
+            0x02, 0x00,
+            // Offset.
+            0x01, 0x02, 0x03, 0x04,
+            // Address size.
+            0x04,
+            // Segment size.
+            0x02,
+            // Length to here = 12, tuple length = 10.
+            // Padding to tuple length multiple = 8.
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            // Aranges.
+            0x01, 0x00,
+            0x00, 0x10, 0x00, 0x00,
+            0x20, 0x00, 0x00, 0x00,
+            0x02, 0x00,
+            0x00, 0x10, 0x00, 0x00,
+            0x30, 0x00, 0x00, 0x00,
+            // Terminating zero tuple.
+            0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00,
+        ];
+
+        let debug_aranges = DebugAranges::new(&buf, LittleEndian);
+        let mut headers = debug_aranges.headers();
+        let header = headers
+            .next()
+            .expect("should parse header ok")
+            .expect("should have a header");
+        assert_eq!(header.segment_size(), 2);
+        let entries: Vec<_> = header.entries().collect().expect("should parse entries ok");
+        assert_eq!(
+            entries,
+            [
+                ArangeEntry {
+                    segment: Some(1),
+                    address: 0x1000,
+                    length: 0x20,
+                    unit_header_offset: DebugInfoOffset(0x0403_0201),
+                },
+                ArangeEntry {
+                    segment: Some(2),
+                    address: 0x1000,
+                    length: 0x30,
+                    unit_header_offset: DebugInfoOffset(0x0403_0201),
+                },
+            ]
+        );
+        assert_eq!(headers.next(), Ok(None));
+    }
+
+    #[test]
+    fn test_headers() {
+        #[rustfmt::skip]
+        let buf = [
+            // First set.
+            // 32-bit length = 28.
+            0x1c, 0x00, 0x00, 0x00,
+            // Version.
+            0x02, 0x00,
+            // Offset.
+            0x01, 0x02, 0x03, 0x04,
+            // Address size.
+            0x04,
+            // Segment size.
+            0x00,
+            // Length to here = 12, tuple length = 8.
+            // Padding to tuple length multiple = 4.
+            0x00, 0x00, 0x00, 0x00,
+            // Arange.
+            0x00, 0x10, 0x00, 0x00,
+            0x20, 0x00, 0x00, 0x00,
+            // Terminating zero tuple.
+            0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00,
+
+            // Second set.
+            // 32-bit length = 36.
+            0x24, 0x00, 0x00, 0x00,
+            // Version.
+            0x02, 0x00,
+            // Offset.
+            0x11, 0x12, 0x13, 0x14,
+            // Address size.
+            0x04,
+            // Segment size.
+            0x00,
+            // Padding.
+            0x00, 0x00, 0x00, 0x00,
+            // Aranges.
+            0x00, 0x20, 0x00, 0x00,
+            0x30, 0x00, 0x00, 0x00,
+            0x00, 0x30, 0x00, 0x00,
+            0x40, 0x00, 0x00, 0x00,
+            // Terminating zero tuple.
+            0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00,
+        ];
+
+        let debug_aranges = DebugAranges::new(&buf, LittleEndian);
+        let mut headers = debug_aranges.headers();
+
+        let header = headers
+            .next()
+            .expect("should parse header ok")
+            .expect("should have a header");
+        assert_eq!(header.length(), 0x1c);
+        assert_eq!(
+            header.encoding(),
+            Encoding {
+                format: Format::Dwarf32,
+                version: 2,
+                address_size: 4,
+            }
+        );
+        assert_eq!(header.address_size(), 4);
+        assert_eq!(header.segment_size(), 0);
+        assert_eq!(header.debug_info_offset(), DebugInfoOffset(0x0403_0201));
+        let entries: Vec<_> = header.entries().collect().expect("should parse entries ok");
+        assert_eq!(
+            entries,
+            [ArangeEntry {
+                segment: None,
+                address: 0x1000,
+                length: 0x20,
+                unit_header_offset: DebugInfoOffset(0x0403_0201),
+            }]
+        );
+
+        let header = headers
+            .next()
+            .expect("should parse header ok")
+            .expect("should have a header");
+        assert_eq!(header.debug_info_offset(), DebugInfoOffset(0x1413_1211));
+        let entries: Vec<_> = header
+            .entries()
+            .map(|arange| Ok((arange.address(), arange.length())))
+            .collect()
+            .expect("should parse entries ok");
+        assert_eq!(entries, [(0x2000, 0x30), (0x3000, 0x40)]);
+
+        assert_eq!(headers.next(), Ok(None));
+
+        // `items` iterates the entries of all sets.
+        let items: Vec<_> = debug_aranges
+            .items()
+            .map(|arange| Ok(arange.address()))
+            .collect()
+            .expect("should parse entries ok");
+        assert_eq!(items, [0x1000, 0x2000, 0x3000]);
+    }
+
+    #[test]
+    fn test_headers_error() {
+        #[rustfmt::skip]
+        let buf = [
+            // 32-bit length = 8.
+            0x08, 0x00, 0x00, 0x00,
+            // Version.
+            0x03, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+
+        let debug_aranges = DebugAranges::new(&buf, LittleEndian);
+        let mut headers = debug_aranges.headers();
+        assert_eq!(headers.next(), Err(Error::UnknownVersion(3)));
+        assert_eq!(headers.next(), Ok(None));
+    }
 }
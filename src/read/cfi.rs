@@ -1,3 +1,4 @@
+#[cfg(feature = "alloc")]
 use crate::boxed::Box;
 use arrayvec::ArrayVec;
 use fallible_iterator::FallibleIterator;
@@ -75,9 +76,7 @@ where
 }
 
 impl<R: Reader> Section<R> for DebugFrame<R> {
-    fn id() -> SectionId {
-        SectionId::DebugFrame
-    }
+    const ID: SectionId = SectionId::DebugFrame;
 
     fn reader(&self) -> &R {
         &self.section
@@ -174,9 +173,7 @@ impl<R: Reader> EhFrameHdr<R> {
 }
 
 impl<R: Reader> Section<R> for EhFrameHdr<R> {
-    fn id() -> SectionId {
-        SectionId::EhFrameHdr
-    }
+    const ID: SectionId = SectionId::EhFrameHdr;
 
     fn reader(&self) -> &R {
         &self.0
@@ -439,9 +436,7 @@ where
 }
 
 impl<R: Reader> Section<R> for EhFrame<R> {
-    fn id() -> SectionId {
-        SectionId::EhFrame
-    }
+    const ID: SectionId = SectionId::EhFrame;
 
     fn reader(&self) -> &R {
         &self.section
@@ -1277,7 +1272,7 @@ impl<R: Reader> CommonInformationEntry<R> {
         let mut augmentation_string = rest.read_null_terminated_slice()?;
 
         let (address_size, segment_size) = if Section::has_address_and_segment_sizes(version) {
-            let address_size = rest.read_u8()?;
+            let address_size = rest.read_address_size()?;
             let segment_size = rest.read_u8()?;
             (address_size, segment_size)
         } else {
@@ -1789,13 +1784,23 @@ impl<R: Reader> FrameDescriptionEntry<R> {
 /// # unreachable!()
 /// # }
 /// ```
+///
+/// When the `alloc` feature is enabled, the context is stored on the heap.
+/// Otherwise it is stored inline, and is large enough that it should not be
+/// moved frequently.
 #[derive(Clone, Debug)]
-pub struct UninitializedUnwindContext<R: Reader>(Box<UnwindContext<R>>);
+pub struct UninitializedUnwindContext<R: Reader>(UnwindContextStorage<R>);
+
+#[cfg(feature = "alloc")]
+type UnwindContextStorage<R> = Box<UnwindContext<R>>;
+
+#[cfg(not(feature = "alloc"))]
+type UnwindContextStorage<R> = UnwindContext<R>;
 
 impl<R: Reader> UninitializedUnwindContext<R> {
     /// Construct a new call frame unwinding context.
     pub fn new() -> UninitializedUnwindContext<R> {
-        UninitializedUnwindContext(Box::new(UnwindContext::new()))
+        UninitializedUnwindContext(UnwindContext::new().into())
     }
 }
 
@@ -0,0 +1,176 @@
+use crate::endianity::{BigEndian, Endianity};
+use crate::read::{EndianSlice, Error, Reader, ReaderOffset, Result};
+use crate::vec::Vec;
+
+/// The magic number at the start of a GNU `.zdebug_*` section.
+const ZDEBUG_MAGIC: &[u8] = b"ZLIB";
+
+/// The `ch_type` value in an ELF compression header for zlib compression.
+const ELFCOMPRESS_ZLIB: u32 = 1;
+
+/// Decompress the contents of a zlib compressed debugging information section.
+///
+/// Two container formats are recognized by their header bytes:
+///
+/// * GNU `.zdebug_*` sections, which start with the magic bytes `ZLIB`, followed
+///   by the uncompressed size as a big endian 64-bit integer.
+///
+/// * ELF sections with the `SHF_COMPRESSED` flag, which start with an `Elf32_Chdr`
+///   or `Elf64_Chdr` compression header. The header is read using the given
+///   `endian`, and `address_size` (4 or 8) selects between the 32-bit and
+///   64-bit header layouts.
+///
+/// The header is followed by a zlib stream, which must decompress to exactly
+/// the uncompressed size given in the header. The returned data can then be
+/// wrapped in a reader such as `EndianSlice` or `EndianRcSlice`.
+///
+/// ```
+/// # fn foo(compressed: &[u8]) -> gimli::Result<()> {
+/// use gimli::{DebugInfo, LittleEndian};
+///
+/// let data = gimli::decompress_zlib(compressed, LittleEndian, 8)?;
+/// let debug_info = DebugInfo::new(&data, LittleEndian);
+/// # let _ = debug_info;
+/// # Ok(())
+/// # }
+/// ```
+pub fn decompress_zlib<Endian>(data: &[u8], endian: Endian, address_size: u8) -> Result<Vec<u8>>
+where
+    Endian: Endianity,
+{
+    let (size, compressed) = if data.starts_with(ZDEBUG_MAGIC) {
+        let mut input = EndianSlice::new(&data[ZDEBUG_MAGIC.len()..], BigEndian);
+        let size = input.read_u64()?;
+        (size, input.slice())
+    } else {
+        let mut input = EndianSlice::new(data, endian);
+        let compression_type = input.read_u32()?;
+        let size = match address_size {
+            4 => {
+                let size = input.read_u32()?;
+                // ch_addralign
+                input.read_u32()?;
+                u64::from(size)
+            }
+            8 => {
+                // ch_reserved
+                input.read_u32()?;
+                let size = input.read_u64()?;
+                // ch_addralign
+                input.read_u64()?;
+                size
+            }
+            otherwise => return Err(Error::UnsupportedAddressSize(otherwise)),
+        };
+        if compression_type != ELFCOMPRESS_ZLIB {
+            return Err(Error::UnsupportedCompressionType(compression_type));
+        }
+        (size, input.slice())
+    };
+
+    let size = usize::from_u64(size)?;
+    let decompressed = miniz_oxide::inflate::decompress_to_vec_zlib_with_limit(compressed, size)
+        .map_err(|_| Error::InvalidCompressedData)?;
+    if decompressed.len() != size {
+        return Err(Error::InvalidCompressedData);
+    }
+    Ok(decompressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::endianity::LittleEndian;
+    use test_assembler::{Endian, Section};
+
+    const DATA: &[u8] = b"some uncompressed debugging information";
+
+    fn compressed() -> Vec<u8> {
+        miniz_oxide::deflate::compress_to_vec_zlib(DATA, 6)
+    }
+
+    #[test]
+    fn test_decompress_zdebug() {
+        let section = Section::with_endian(Endian::Big)
+            .append_bytes(b"ZLIB")
+            .B64(DATA.len() as u64)
+            .append_bytes(&compressed());
+        let buf = section.get_contents().unwrap();
+        // The endianity and address size are ignored for `.zdebug_*` sections.
+        assert_eq!(decompress_zlib(&buf, LittleEndian, 4), Ok(DATA.to_vec()));
+        assert_eq!(decompress_zlib(&buf, LittleEndian, 8), Ok(DATA.to_vec()));
+    }
+
+    #[test]
+    fn test_decompress_elf32() {
+        let section = Section::with_endian(Endian::Little)
+            .L32(ELFCOMPRESS_ZLIB)
+            .L32(DATA.len() as u32)
+            .L32(1)
+            .append_bytes(&compressed());
+        let buf = section.get_contents().unwrap();
+        assert_eq!(decompress_zlib(&buf, LittleEndian, 4), Ok(DATA.to_vec()));
+    }
+
+    #[test]
+    fn test_decompress_elf64() {
+        let section = Section::with_endian(Endian::Big)
+            .B32(ELFCOMPRESS_ZLIB)
+            .B32(0)
+            .B64(DATA.len() as u64)
+            .B64(1)
+            .append_bytes(&compressed());
+        let buf = section.get_contents().unwrap();
+        assert_eq!(decompress_zlib(&buf, BigEndian, 8), Ok(DATA.to_vec()));
+    }
+
+    #[test]
+    fn test_decompress_unknown_type() {
+        let section = Section::with_endian(Endian::Little)
+            .L32(2)
+            .L32(0)
+            .L64(DATA.len() as u64)
+            .L64(1)
+            .append_bytes(&compressed());
+        let buf = section.get_contents().unwrap();
+        assert_eq!(
+            decompress_zlib(&buf, LittleEndian, 8),
+            Err(Error::UnsupportedCompressionType(2))
+        );
+    }
+
+    #[test]
+    fn test_decompress_truncated() {
+        // Truncated header.
+        let buf = b"ZLIB\0\0\0";
+        match decompress_zlib(&buf[..], LittleEndian, 8) {
+            Err(Error::UnexpectedEof(_)) => {}
+            otherwise => panic!("Unexpected result: {:?}", otherwise),
+        }
+
+        // Truncated compressed data.
+        let compressed = compressed();
+        let section = Section::with_endian(Endian::Big)
+            .append_bytes(b"ZLIB")
+            .B64(DATA.len() as u64)
+            .append_bytes(&compressed[..compressed.len() / 2]);
+        let buf = section.get_contents().unwrap();
+        assert_eq!(
+            decompress_zlib(&buf, LittleEndian, 8),
+            Err(Error::InvalidCompressedData)
+        );
+    }
+
+    #[test]
+    fn test_decompress_wrong_size() {
+        let section = Section::with_endian(Endian::Big)
+            .append_bytes(b"ZLIB")
+            .B64(DATA.len() as u64 + 1)
+            .append_bytes(&compressed());
+        let buf = section.get_contents().unwrap();
+        assert_eq!(
+            decompress_zlib(&buf, LittleEndian, 8),
+            Err(Error::InvalidCompressedData)
+        );
+    }
+}
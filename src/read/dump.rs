@@ -0,0 +1,198 @@
+use std::io;
+
+use crate::read::{AttributeValue, Dwarf, Error, Reader, ReaderOffset, Unit};
+
+/// Write the tree of debugging information entries of a unit as text.
+///
+/// Each entry is written with its offset within the unit and its tag, and is
+/// followed by its attributes. Children are indented by their depth in the
+/// tree. Strings and indexed addresses are resolved using `dwarf`, and
+/// constants are written using their symbolic names.
+///
+/// This is intended for debugging producers and consumers of DWARF, and the
+/// format of the output may change.
+///
+/// Errors from parsing the DWARF are returned as `io::ErrorKind::InvalidData`.
+///
+/// ```
+/// # fn foo<R: gimli::Reader>(dwarf: &gimli::Dwarf<R>) -> Result<(), Box<dyn std::error::Error>> {
+/// let stdout = std::io::stdout();
+/// let mut units = dwarf.units();
+/// while let Some(header) = units.next()? {
+///     let unit = dwarf.unit(header)?;
+///     gimli::dump_unit(&mut stdout.lock(), dwarf, &unit)?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn dump_unit<W, R>(w: &mut W, dwarf: &Dwarf<R>, unit: &Unit<R>) -> io::Result<()>
+where
+    W: io::Write,
+    R: Reader,
+{
+    let mut depth = 0;
+    let mut entries = unit.entries();
+    while let Some((delta_depth, entry)) = entries.next_dfs().map_err(dump_error)? {
+        depth += delta_depth;
+        let indent = depth.max(0) as usize * 2;
+        writeln!(
+            w,
+            "{:indent$}<0x{:08x}> {}",
+            "",
+            entry.offset().0.into_u64(),
+            entry.tag(),
+            indent = indent
+        )?;
+
+        let mut attrs = entry.attrs();
+        while let Some(attr) = attrs.next().map_err(dump_error)? {
+            write!(w, "{:indent$}  {}: ", "", attr.name(), indent = indent)?;
+            dump_attr_value(w, dwarf, unit, attr.value())?;
+            writeln!(w)?;
+        }
+    }
+    Ok(())
+}
+
+fn dump_attr_value<W, R>(
+    w: &mut W,
+    dwarf: &Dwarf<R>,
+    unit: &Unit<R>,
+    value: AttributeValue<R>,
+) -> io::Result<()>
+where
+    W: io::Write,
+    R: Reader,
+{
+    match value {
+        AttributeValue::Addr(address) => write!(w, "0x{:x}", address),
+        AttributeValue::DebugAddrIndex(index) => {
+            let address = dwarf.address(unit, index).map_err(dump_error)?;
+            write!(w, "0x{:x}", address)
+        }
+        AttributeValue::Data1(data) => write!(w, "{}", data),
+        AttributeValue::Data2(data) => write!(w, "{}", data),
+        AttributeValue::Data4(data) => write!(w, "{}", data),
+        AttributeValue::Data8(data) => write!(w, "{}", data),
+        AttributeValue::Sdata(data) => write!(w, "{}", data),
+        AttributeValue::Udata(data) => write!(w, "{}", data),
+        AttributeValue::Flag(flag) => write!(w, "{}", flag),
+        AttributeValue::FileIndex(index) => match dwarf.file_name(unit, index) {
+            Ok(Some(name)) => write!(
+                w,
+                "{} ({:?})",
+                index,
+                name.to_string_lossy().map_err(dump_error)?
+            ),
+            _ => write!(w, "{}", index),
+        },
+        AttributeValue::UnitRef(offset) => write!(w, "<0x{:08x}>", offset.0.into_u64()),
+        AttributeValue::DebugInfoRef(offset) => {
+            write!(w, "<.debug_info+0x{:08x}>", offset.0.into_u64())
+        }
+        AttributeValue::String(_)
+        | AttributeValue::DebugStrRef(_)
+        | AttributeValue::DebugStrRefSup(_)
+        | AttributeValue::DebugLineStrRef(_)
+        | AttributeValue::DebugStrOffsetsIndex(_) => {
+            let string = dwarf.attr_string(unit, value).map_err(dump_error)?;
+            write!(w, "{:?}", string.to_string_lossy().map_err(dump_error)?)
+        }
+        AttributeValue::Encoding(value) => write!(w, "{}", value),
+        AttributeValue::DecimalSign(value) => write!(w, "{}", value),
+        AttributeValue::Endianity(value) => write!(w, "{}", value),
+        AttributeValue::Accessibility(value) => write!(w, "{}", value),
+        AttributeValue::Visibility(value) => write!(w, "{}", value),
+        AttributeValue::Virtuality(value) => write!(w, "{}", value),
+        AttributeValue::Language(value) => write!(w, "{}", value),
+        AttributeValue::AddressClass(value) => write!(w, "{}", value),
+        AttributeValue::IdentifierCase(value) => write!(w, "{}", value),
+        AttributeValue::CallingConvention(value) => write!(w, "{}", value),
+        AttributeValue::Inline(value) => write!(w, "{}", value),
+        AttributeValue::Ordering(value) => write!(w, "{}", value),
+        value => write!(w, "{:?}", value),
+    }
+}
+
+fn dump_error(err: Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants;
+    use crate::endianity::LittleEndian;
+    use crate::read::abbrev::tests::AbbrevSectionMethods;
+    use crate::read::{DebugAbbrev, DebugInfo, DebugStr};
+    use crate::string::String;
+    use crate::test_util::GimliSectionMethods;
+    use crate::vec::Vec;
+    use test_assembler::{Endian, Label, LabelMaker, Section};
+
+    #[test]
+    fn test_dump_unit() {
+        let abbrev_buf = Section::with_endian(Endian::Little)
+            .abbrev(
+                1,
+                constants::DW_TAG_compile_unit,
+                constants::DW_CHILDREN_yes,
+            )
+            .abbrev_attr(constants::DW_AT_name, constants::DW_FORM_strp)
+            .abbrev_attr(constants::DW_AT_language, constants::DW_FORM_data1)
+            .abbrev_attr(constants::DW_AT_low_pc, constants::DW_FORM_addr)
+            .abbrev_attr_null()
+            .abbrev(2, constants::DW_TAG_subprogram, constants::DW_CHILDREN_no)
+            .abbrev_attr(constants::DW_AT_name, constants::DW_FORM_string)
+            .abbrev_attr(constants::DW_AT_external, constants::DW_FORM_flag_present)
+            .abbrev_attr(constants::DW_AT_type, constants::DW_FORM_ref4)
+            .abbrev_attr_null()
+            .abbrev_null()
+            .get_contents()
+            .unwrap();
+
+        let length = Label::new();
+        let start = Label::new();
+        let end = Label::new();
+        let info_buf = Section::with_endian(Endian::Little)
+            .L32(&length)
+            .mark(&start)
+            .L16(4)
+            .L32(0)
+            .D8(8)
+            .uleb(1)
+            .L32(0)
+            .D8(constants::DW_LANG_C99.0 as u8)
+            .L64(0x1000)
+            .uleb(2)
+            .append_bytes(b"main\0")
+            .L32(0x0b)
+            .uleb(0)
+            .mark(&end);
+        length.set_const((&end - &start) as u64);
+        let info_buf = info_buf.get_contents().unwrap();
+
+        let dwarf = Dwarf {
+            debug_abbrev: DebugAbbrev::new(&abbrev_buf, LittleEndian),
+            debug_info: DebugInfo::new(&info_buf, LittleEndian),
+            debug_str: DebugStr::new(b"a.c\0", LittleEndian),
+            ..Default::default()
+        };
+        let header = dwarf.units().next().unwrap().unwrap();
+        let unit = dwarf.unit(header).unwrap();
+
+        let mut buf = Vec::new();
+        dump_unit(&mut buf, &dwarf, &unit).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "<0x0000000b> DW_TAG_compile_unit\n\
+             \x20 DW_AT_name: \"a.c\"\n\
+             \x20 DW_AT_language: DW_LANG_C99\n\
+             \x20 DW_AT_low_pc: 0x1000\n\
+             \x20 <0x00000019> DW_TAG_subprogram\n\
+             \x20   DW_AT_name: \"main\"\n\
+             \x20   DW_AT_external: true\n\
+             \x20   DW_AT_type: <0x0000000b>\n"
+        );
+    }
+}
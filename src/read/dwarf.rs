@@ -1,23 +1,33 @@
 use fallible_iterator::FallibleIterator;
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
+use crate::borrow::Cow;
 use crate::common::{
-    DebugAddrBase, DebugAddrIndex, DebugInfoOffset, DebugLineStrOffset, DebugLocListsBase,
-    DebugLocListsIndex, DebugRngListsBase, DebugRngListsIndex, DebugStrOffset, DebugStrOffsetsBase,
-    DebugStrOffsetsIndex, DebugTypesOffset, Encoding, LocationListsOffset, RangeListsOffset,
-    SectionId, UnitSectionOffset,
+    DebugAddrBase, DebugAddrIndex, DebugInfoOffset, DebugLineOffset, DebugLineStrOffset,
+    DebugLocListsBase, DebugLocListsIndex, DebugRngListsBase, DebugRngListsIndex, DebugStrOffset,
+    DebugStrOffsetsBase, DebugStrOffsetsIndex, DebugTypeSignature, DebugTypesOffset, DwoId,
+    Encoding, LocationListsOffset, RangeListsOffset, SectionId, UnitSectionOffset,
 };
 use crate::constants;
+use crate::endianity::Endianity;
 use crate::read::{
-    Abbreviations, AttributeValue, CompilationUnitHeader, CompilationUnitHeadersIter, DebugAbbrev,
-    DebugAddr, DebugInfo, DebugLine, DebugLineStr, DebugStr, DebugStrOffsets, DebugTypes,
-    DebuggingInformationEntry, EntriesCursor, EntriesTree, Error, IncompleteLineProgram,
-    LocListIter, LocationLists, Range, RangeLists, Reader, ReaderOffset, ReaderOffsetId, Result,
-    RngListIter, Section, TypeUnitHeader, TypeUnitHeadersIter, UnitHeader, UnitOffset,
+    Abbreviations, AttributeValue, ColumnType, CompilationUnitHeader, CompilationUnitHeadersIter,
+    DebugAbbrev, DebugAddr, DebugAranges, DebugCuIndex, DebugInfo, DebugLine, DebugLineStr,
+    DebugLoc, DebugLocLists, DebugNames, DebugPubNames, DebugPubTypes, DebugRngLists, DebugStr,
+    DebugStrOffsets, DebugSup, DebugTuIndex, DebugTypes, DebuggingInformationEntry, EndianSlice,
+    EntriesCursor, EntriesRaw, EntriesTree, EntriesTreeNode, Error, Expression,
+    IncompleteLineProgram, LineProgramHeader, LineRows, LocListIter, LocationLists, NameIndexIter,
+    Operation, PubNamesEntryIter, Range, RangeLists, RawLocListIter, RawRngListIter, Reader,
+    ReaderOffset, ReaderOffsetId, Result, RngListIter, Section, TypeUnitHeader,
+    TypeUnitHeadersIter, UnitHeader, UnitIndex, UnitIndexSection, UnitOffset,
 };
 use crate::string::String;
+use crate::vec::Vec;
+use crate::Arc;
 
 /// All of the commonly used DWARF sections, and other common information.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct Dwarf<R> {
     /// The `.debug_abbrev` section.
     pub debug_abbrev: DebugAbbrev<R>,
@@ -25,6 +35,9 @@ pub struct Dwarf<R> {
     /// The `.debug_addr` section.
     pub debug_addr: DebugAddr<R>,
 
+    /// The `.debug_aranges` section.
+    pub debug_aranges: DebugAranges<R>,
+
     /// The `.debug_info` section.
     pub debug_info: DebugInfo<R>,
 
@@ -34,6 +47,15 @@ pub struct Dwarf<R> {
     /// The `.debug_line_str` section.
     pub debug_line_str: DebugLineStr<R>,
 
+    /// The `.debug_names` section.
+    pub debug_names: DebugNames<R>,
+
+    /// The `.debug_pubnames` section.
+    pub debug_pubnames: DebugPubNames<R>,
+
+    /// The `.debug_pubtypes` section.
+    pub debug_pubtypes: DebugPubTypes<R>,
+
     /// The `.debug_str` section.
     pub debug_str: DebugStr<R>,
 
@@ -41,8 +63,13 @@ pub struct Dwarf<R> {
     pub debug_str_offsets: DebugStrOffsets<R>,
 
     /// The `.debug_str` section for a supplementary object file.
+    ///
+    /// This is only used if `sup` is `None`.
     pub debug_str_sup: DebugStr<R>,
 
+    /// The `.debug_sup` section.
+    pub debug_sup: DebugSup<R>,
+
     /// The `.debug_types` section.
     pub debug_types: DebugTypes<R>,
 
@@ -51,6 +78,48 @@ pub struct Dwarf<R> {
 
     /// The range lists in the `.debug_ranges` and `.debug_rnglists` sections.
     pub ranges: RangeLists<R>,
+
+    /// The type of this file.
+    pub file_type: DwarfFileType,
+
+    /// The DWARF sections for a supplementary object file.
+    ///
+    /// `DW_FORM_strp_sup` strings are read from this file if it is present,
+    /// and `DW_FORM_ref_sup*` references can be found using
+    /// `Dwarf::sup_die_from_offset`.
+    pub sup: Option<Arc<Dwarf<R>>>,
+}
+
+/// The type of a file containing DWARF sections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DwarfFileType {
+    /// A normal executable or object file.
+    Main,
+    /// A `.dwo` split DWARF file, or the sections for a unit in a `.dwp` file.
+    ///
+    /// Split units do not have the attributes that specify the bases for
+    /// indexed strings, location lists and range lists, so the bases are
+    /// implied instead:
+    ///
+    /// - For DWARF 5 split units, the bases are the offset just after the
+    ///   header of the unit's contribution to `.debug_str_offsets.dwo`,
+    ///   `.debug_loclists.dwo` and `.debug_rnglists.dwo`. That is, 8 bytes
+    ///   for `.debug_str_offsets` and 12 bytes for the lists in the 32-bit
+    ///   DWARF format, and 16 and 20 bytes in the 64-bit format.
+    ///
+    /// - For the GNU split DWARF extension to DWARF 4, these sections do not
+    ///   have headers, so the bases are 0. Range list offsets are instead
+    ///   relative to the `DW_AT_GNU_ranges_base` of the skeleton unit, which
+    ///   is set by `Unit::copy_relocated_attributes`.
+    Dwo,
+}
+
+// `#[default]` on enum variants requires a newer Rust than we support.
+#[allow(clippy::derivable_impls)]
+impl Default for DwarfFileType {
+    fn default() -> Self {
+        DwarfFileType::Main
+    }
 }
 
 impl<T> Dwarf<T> {
@@ -60,6 +129,10 @@ impl<T> Dwarf<T> {
     /// `sup` loads a DWARF sections from the supplementary object file.
     /// These functions should return an empty section if the section does not exist.
     ///
+    /// Only the `.debug_str` section is loaded from the supplementary object
+    /// file. To also follow references to entries in the supplementary object
+    /// file, load it separately and store it in `Dwarf::sup`.
+    ///
     /// The provided callback functions may either directly return a `Reader` instance
     /// (such as `EndianSlice`), or they may return some other type and then convert
     /// that type into a `Reader` using `Dwarf::borrow`.
@@ -76,15 +149,22 @@ impl<T> Dwarf<T> {
         Ok(Dwarf {
             debug_abbrev: Section::load(&mut section)?,
             debug_addr: Section::load(&mut section)?,
+            debug_aranges: Section::load(&mut section)?,
             debug_info: Section::load(&mut section)?,
             debug_line: Section::load(&mut section)?,
             debug_line_str: Section::load(&mut section)?,
+            debug_names: Section::load(&mut section)?,
+            debug_pubnames: Section::load(&mut section)?,
+            debug_pubtypes: Section::load(&mut section)?,
             debug_str: Section::load(&mut section)?,
             debug_str_offsets: Section::load(&mut section)?,
             debug_str_sup: Section::load(&mut sup)?,
+            debug_sup: Section::load(&mut section)?,
             debug_types: Section::load(&mut section)?,
             locations: LocationLists::new(debug_loc, debug_loclists),
             ranges: RangeLists::new(debug_ranges, debug_rnglists),
+            file_type: DwarfFileType::Main,
+            sup: None,
         })
     }
 
@@ -115,26 +195,91 @@ impl<T> Dwarf<T> {
     /// # }
     /// ```
     pub fn borrow<'a, F, R>(&'a self, mut borrow: F) -> Dwarf<R>
+    where
+        F: FnMut(&'a T) -> R,
+    {
+        let mut dwarf = self.borrow_sections(&mut borrow);
+        dwarf.sup = self
+            .sup
+            .as_ref()
+            .map(|sup| Arc::new(sup.borrow_sections(&mut borrow)));
+        dwarf
+    }
+
+    /// Borrow the sections, but not the supplementary object file.
+    fn borrow_sections<'a, F, R>(&'a self, mut borrow: F) -> Dwarf<R>
     where
         F: FnMut(&'a T) -> R,
     {
         Dwarf {
             debug_abbrev: self.debug_abbrev.borrow(&mut borrow),
             debug_addr: self.debug_addr.borrow(&mut borrow),
+            debug_aranges: self.debug_aranges.borrow(&mut borrow),
             debug_info: self.debug_info.borrow(&mut borrow),
             debug_line: self.debug_line.borrow(&mut borrow),
             debug_line_str: self.debug_line_str.borrow(&mut borrow),
+            debug_names: self.debug_names.borrow(&mut borrow),
+            debug_pubnames: self.debug_pubnames.borrow(&mut borrow),
+            debug_pubtypes: self.debug_pubtypes.borrow(&mut borrow),
             debug_str: self.debug_str.borrow(&mut borrow),
             debug_str_offsets: self.debug_str_offsets.borrow(&mut borrow),
             debug_str_sup: self.debug_str_sup.borrow(&mut borrow),
+            debug_sup: self.debug_sup.borrow(&mut borrow),
             debug_types: self.debug_types.borrow(&mut borrow),
             locations: self.locations.borrow(&mut borrow),
             ranges: self.ranges.borrow(&mut borrow),
+            file_type: self.file_type,
+            sup: None,
         }
     }
+
+    /// Return a reference to the DWARF sections for the supplementary object file.
+    #[inline]
+    pub fn sup(&self) -> Option<&Dwarf<T>> {
+        self.sup.as_ref().map(Arc::as_ref)
+    }
 }
 
 impl<R: Reader> Dwarf<R> {
+    /// Mark this as a `.dwo` file, and use the given parent file for the
+    /// sections that split units take from the executable.
+    ///
+    /// This should be used for a `.dwo` file that was loaded separately.
+    /// The split units in it must also be associated with their skeleton
+    /// units using `Unit::copy_relocated_attributes`.
+    pub fn make_dwo(&mut self, parent: &Dwarf<R>) {
+        self.file_type = DwarfFileType::Dwo;
+        // These sections are always taken from the parent file and not the dwo.
+        self.debug_addr = parent.debug_addr.clone();
+        self.debug_str_sup = parent.debug_str_sup.clone();
+        self.sup = parent.sup.clone();
+        // .debug_rnglists comes from the dwo, .debug_ranges comes from the parent file.
+        self.ranges
+            .set_debug_ranges(parent.ranges.debug_ranges().clone());
+    }
+
+    /// Try to load the sections of a standalone `.dwo` file using the given
+    /// loader function, and use the given parent file for the sections that
+    /// split units take from the executable.
+    ///
+    /// `section` loads a DWARF section from the `.dwo` file. Note that the
+    /// sections in a `.dwo` file have a `.dwo` suffix, so for example
+    /// `SectionId::DebugInfo` should load the `.debug_info.dwo` section.
+    /// `SectionId::dwo_name` returns these section names.
+    /// This function should return an empty section if the section does not exist.
+    ///
+    /// This is equivalent to `Dwarf::load` followed by `Dwarf::make_dwo`.
+    /// Sections that are taken from the parent file may still be requested
+    /// from `section`, but are not used.
+    pub fn load_dwo<F, E>(section: F, parent: &Dwarf<R>) -> std::result::Result<Self, E>
+    where
+        F: FnMut(SectionId) -> std::result::Result<R, E>,
+    {
+        let mut dwarf = Dwarf::load(section, |_| Ok(parent.debug_str_sup.reader().clone()))?;
+        dwarf.make_dwo(parent);
+        Ok(dwarf)
+    }
+
     /// Iterate the compilation- and partial-unit headers in the
     /// `.debug_info` section.
     ///
@@ -166,6 +311,251 @@ impl<R: Reader> Dwarf<R> {
         Unit::new_type_unit(self, header)
     }
 
+    /// Find the type unit with the given type signature.
+    ///
+    /// This searches the type units in the `.debug_types` section, and the
+    /// DWARF 5 type units in the `.debug_info` section. Returns the unit and
+    /// the offset of the entry that describes the type within that unit.
+    /// Returns `Ok(None)` if there is no type unit with the signature.
+    ///
+    /// This is used to follow `DW_FORM_ref_sig8` references. It parses the
+    /// unit headers each time that it is called, so callers that follow many
+    /// references should build their own map from the headers returned by
+    /// `type_units` and `units`.
+    #[allow(clippy::type_complexity)]
+    pub fn type_unit_for_signature(
+        &self,
+        signature: DebugTypeSignature,
+    ) -> Result<Option<(Unit<R>, UnitOffset<R::Offset>)>> {
+        let mut type_units = self.type_units();
+        while let Some(header) = type_units.next()? {
+            if header.type_signature() == signature {
+                let offset = header.type_offset();
+                return self.type_unit(header).map(|unit| Some((unit, offset)));
+            }
+        }
+
+        let mut units = self.units();
+        while let Some(header) = units.next()? {
+            if header.type_signature() == Some(signature) {
+                if let Some(offset) = header.type_offset() {
+                    return self.unit(header).map(|unit| Some((unit, offset)));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Find the compilation unit containing the entry at the given offset
+    /// in the `.debug_info` section.
+    ///
+    /// Returns the unit and the offset of the entry within that unit.
+    /// This can be used to follow references such as `DW_FORM_ref_addr`,
+    /// which may refer to entries in other units.
+    ///
+    /// The unit header is found with `DebugInfo::header_containing_offset`,
+    /// which scans the headers from the start of the section, and then the
+    /// abbreviations and root entry of the unit are parsed. Callers that follow
+    /// many references should cache the units that they find. The line program
+    /// header of the unit is not parsed, so `Unit::line_program` is always
+    /// `None`; use `Dwarf::unit` instead if it is needed.
+    ///
+    /// Returns `Error::NoEntryAtGivenOffset` if no unit contains the offset.
+    pub fn die_from_offset(
+        &self,
+        offset: DebugInfoOffset<R::Offset>,
+    ) -> Result<(Unit<R>, UnitOffset<R::Offset>)> {
+        self.die_from_offset_internal(offset, false)
+    }
+
+    fn die_from_offset_internal(
+        &self,
+        offset: DebugInfoOffset<R::Offset>,
+        line_program: bool,
+    ) -> Result<(Unit<R>, UnitOffset<R::Offset>)> {
+        let header = match self.debug_info.header_containing_offset(offset) {
+            Ok(header) => header,
+            Err(Error::OffsetOutOfBounds) => return Err(Error::NoEntryAtGivenOffset),
+            Err(e) => return Err(e),
+        };
+        let unit_offset = offset
+            .to_unit_offset(&header)
+            .ok_or(Error::NoEntryAtGivenOffset)?;
+        let unit = Unit::new_internal(
+            self,
+            UnitSectionOffset::DebugInfoOffset(header.offset()),
+            header.dwo_id(),
+            header.header(),
+            line_program,
+        )?;
+        Ok((unit, unit_offset))
+    }
+
+    /// Find the compilation unit containing the entry at the given offset
+    /// in the `.debug_info` section of the supplementary object file.
+    ///
+    /// This can be used to follow `DW_FORM_ref_sup4` and `DW_FORM_ref_sup8`
+    /// references, which are parsed as `AttributeValue::DebugInfoRefSup`.
+    ///
+    /// Returns `Error::MissingSupplementaryFile` if `sup` is `None`.
+    pub fn sup_die_from_offset(
+        &self,
+        offset: DebugInfoOffset<R::Offset>,
+    ) -> Result<(Unit<R>, UnitOffset<R::Offset>)> {
+        match self.sup() {
+            Some(sup) => sup.die_from_offset(offset),
+            None => Err(Error::MissingSupplementaryFile),
+        }
+    }
+
+    /// Find the unit and entry that are imported by a `DW_AT_import`
+    /// attribute value.
+    ///
+    /// This is used for `DW_TAG_imported_unit` entries, which import the
+    /// entries of a partial unit (or other unit) into the current unit. The
+    /// attribute value is usually a `DW_FORM_ref_addr` reference to the root
+    /// entry of the imported unit, and may be a reference into the
+    /// supplementary object file.
+    ///
+    /// Returns the imported unit and the offset of the entry within that unit,
+    /// or `Ok(None)` if the attribute value is not a reference to another unit.
+    /// Unlike `Dwarf::die_from_offset`, the line program header of the
+    /// imported unit is parsed, since the imported entries may refer to it.
+    ///
+    /// Imported units may import other units, and may contain cycles. Consumers
+    /// that recursively follow imports must track the units they have already
+    /// visited.
+    #[allow(clippy::type_complexity)]
+    pub fn imported_unit(
+        &self,
+        attr: AttributeValue<R>,
+    ) -> Result<Option<(Unit<R>, UnitOffset<R::Offset>)>> {
+        match attr {
+            AttributeValue::DebugInfoRef(offset) => {
+                self.die_from_offset_internal(offset, true).map(Some)
+            }
+            AttributeValue::DebugInfoRefSup(offset) => match self.sup() {
+                Some(sup) => sup.die_from_offset_internal(offset, true).map(Some),
+                None => Err(Error::MissingSupplementaryFile),
+            },
+            _ => Ok(None),
+        }
+    }
+
+    /// Return the value of an attribute of an entry, following
+    /// `DW_AT_specification` and `DW_AT_abstract_origin` references if the
+    /// entry does not have the attribute.
+    ///
+    /// Entries for out-of-line definitions and inlined subroutines often only
+    /// contain the attributes that differ from the declaration or abstract
+    /// instance that they refer to, so this is needed to find attributes
+    /// such as `DW_AT_name` and `DW_AT_decl_line` for these entries.
+    ///
+    /// References may be to entries in the same unit, or `DW_FORM_ref_addr`
+    /// references to entries in other units. If the value is found in another
+    /// unit, then that unit is also returned, and any unit-relative value, such
+    /// as `AttributeValue::UnitRef` or `AttributeValue::DebugStrOffsetsIndex`,
+    /// must be resolved using it instead of `unit`. The returned unit is `None`
+    /// if the value was found in `unit`. It is found using
+    /// `Dwarf::die_from_offset`, so its line program is not parsed.
+    ///
+    /// Returns `Ok(None)` if the attribute is not found, or if the references
+    /// contain a cycle.
+    #[allow(clippy::type_complexity)]
+    pub fn resolve_attribute(
+        &self,
+        unit: &Unit<R>,
+        entry: &DebuggingInformationEntry<R>,
+        name: constants::DwAt,
+    ) -> Result<Option<(Option<Unit<R>>, AttributeValue<R>)>> {
+        fn origin<R: Reader>(
+            entry: &DebuggingInformationEntry<R>,
+        ) -> Result<Option<AttributeValue<R>>> {
+            match entry.attr_value(constants::DW_AT_specification)? {
+                Some(value) => Ok(Some(value)),
+                None => entry.attr_value(constants::DW_AT_abstract_origin),
+            }
+        }
+
+        if let Some(value) = entry.attr_value(name)? {
+            return Ok(Some((None, value)));
+        }
+
+        let mut visited = Vec::new();
+        let mut other_unit = None;
+        let mut reference = origin(entry)?;
+        while let Some(value) = reference {
+            let offset = match value {
+                AttributeValue::UnitRef(offset) => offset,
+                AttributeValue::DebugInfoRef(offset) => {
+                    // Avoid looking up a unit that we already have.
+                    let section_offset = UnitSectionOffset::DebugInfoOffset(offset);
+                    if let Some(offset) = section_offset.to_unit_offset(unit) {
+                        other_unit = None;
+                        offset
+                    } else if let Some(offset) = other_unit
+                        .as_ref()
+                        .and_then(|other| section_offset.to_unit_offset(other))
+                    {
+                        offset
+                    } else {
+                        let (unit, offset) = self.die_from_offset(offset)?;
+                        other_unit = Some(unit);
+                        offset
+                    }
+                }
+                _ => return Ok(None),
+            };
+            let current = other_unit.as_ref().unwrap_or(unit);
+            if visited.contains(&(current.offset, offset)) {
+                return Ok(None);
+            }
+            visited.push((current.offset, offset));
+
+            let entry = current.entry(offset)?;
+            if let Some(value) = entry.attr_value(name)? {
+                return Ok(Some((other_unit, value)));
+            }
+            reference = origin(&entry)?;
+        }
+        Ok(None)
+    }
+
+    /// Find the type unit containing the entry at the given offset
+    /// in the `.debug_types` section.
+    ///
+    /// Returns the unit and the offset of the entry within that unit.
+    ///
+    /// This scans the unit headers from the start of the section, and then
+    /// parses the abbreviations and root entry of the unit. Callers that
+    /// follow many references should cache the units that they find. The line
+    /// program header of the unit is not parsed, so `Unit::line_program` is
+    /// always `None`; use `Dwarf::type_unit` instead if it is needed.
+    ///
+    /// Returns `Error::NoEntryAtGivenOffset` if no unit contains the offset.
+    pub fn type_die_from_offset(
+        &self,
+        offset: DebugTypesOffset<R::Offset>,
+    ) -> Result<(Unit<R>, UnitOffset<R::Offset>)> {
+        let mut units = self.type_units();
+        while let Some(header) = units.next()? {
+            if header.offset().0 > offset.0 {
+                break;
+            }
+            if let Some(unit_offset) = offset.to_unit_offset(&header) {
+                let unit = Unit::new_internal(
+                    self,
+                    UnitSectionOffset::DebugTypesOffset(header.offset()),
+                    None,
+                    header.header(),
+                    false,
+                )?;
+                return Ok((unit, unit_offset));
+            }
+        }
+        Err(Error::NoEntryAtGivenOffset)
+    }
+
     /// Parse the abbreviations for a compilation unit.
     // TODO: provide caching of abbreviations
     #[inline]
@@ -181,14 +571,32 @@ impl<R: Reader> Dwarf<R> {
     }
 
     /// Return the string offset at the given index.
-    #[inline]
+    ///
+    /// For DWARF version 5 units, the index is checked against the count
+    /// given by the `.debug_str_offsets` header at `Unit::str_offsets_base`,
+    /// and `Error::OffsetOutOfBounds` is returned if it is too large. If the
+    /// header could not be parsed, then the error from parsing it is returned
+    /// instead.
     pub fn string_offset(
         &self,
         unit: &Unit<R>,
         index: DebugStrOffsetsIndex<R::Offset>,
     ) -> Result<DebugStrOffset<R::Offset>> {
+        let format = unit.header.format();
+        if unit.header.version() >= 5 {
+            let count = match unit.str_offsets_count {
+                Some((base, count)) if base == unit.str_offsets_base => count?,
+                _ => self
+                    .debug_str_offsets
+                    .header(format, unit.str_offsets_base)?
+                    .count(),
+            };
+            if index.0 >= count {
+                return Err(Error::OffsetOutOfBounds);
+            }
+        }
         self.debug_str_offsets
-            .get_str_offset(unit.header.format(), unit.str_offsets_base, index)
+            .get_str_offset(format, unit.str_offsets_base, index)
     }
 
     /// Return the string at the given offset in `.debug_str`.
@@ -209,28 +617,35 @@ impl<R: Reader> Dwarf<R> {
     ///
     /// - an inline `DW_FORM_string` string
     /// - a `DW_FORM_strp` reference to an offset into the `.debug_str` section
-    /// - a `DW_FORM_strp_sup` reference to an offset into a supplementary
-    /// object file
+    /// - a `DW_FORM_strp_sup` reference to an offset into the `.debug_str`
+    ///   section of the supplementary object file
     /// - a `DW_FORM_line_strp` reference to an offset into the `.debug_line_str`
-    /// section
+    ///   section
     /// - a `DW_FORM_strx` index into the `.debug_str_offsets` entries for the unit
     ///
     /// then return the attribute's string value. Returns an error if the attribute
     /// value does not have a string form, or if a string form has an invalid value.
     pub fn attr_string(&self, unit: &Unit<R>, attr: AttributeValue<R>) -> Result<R> {
         match attr {
-            AttributeValue::String(string) => Ok(string),
-            AttributeValue::DebugStrRef(offset) => self.debug_str.get_str(offset),
-            AttributeValue::DebugStrRefSup(offset) => self.debug_str_sup.get_str(offset),
-            AttributeValue::DebugLineStrRef(offset) => self.debug_line_str.get_str(offset),
             AttributeValue::DebugStrOffsetsIndex(index) => {
-                let offset = self.debug_str_offsets.get_str_offset(
-                    unit.header.format(),
-                    unit.str_offsets_base,
-                    index,
-                )?;
+                let offset = self.string_offset(unit, index)?;
                 self.debug_str.get_str(offset)
             }
+            attr => self.attr_section_string(attr),
+        }
+    }
+
+    /// Return an attribute value as a string slice, for the string forms that
+    /// do not depend on the unit.
+    fn attr_section_string(&self, attr: AttributeValue<R>) -> Result<R> {
+        match attr {
+            AttributeValue::String(string) => Ok(string),
+            AttributeValue::DebugStrRef(offset) => self.debug_str.get_str(offset),
+            AttributeValue::DebugStrRefSup(offset) => match self.sup() {
+                Some(sup) => sup.debug_str.get_str(offset),
+                None => self.debug_str_sup.get_str(offset),
+            },
+            AttributeValue::DebugLineStrRef(offset) => self.debug_line_str.get_str(offset),
             _ => Err(Error::ExpectedStringAttributeValue),
         }
     }
@@ -241,6 +656,50 @@ impl<R: Reader> Dwarf<R> {
             .get_address(unit.encoding().address_size, unit.addr_base, index)
     }
 
+    /// Try to return an attribute value as an address.
+    ///
+    /// An address index is looked up in `.debug_addr` using the unit's
+    /// `DW_AT_addr_base`. Returns `Ok(None)` for values that are not addresses.
+    pub fn attr_address(&self, unit: &Unit<R>, attr: AttributeValue<R>) -> Result<Option<u64>> {
+        match attr {
+            AttributeValue::Addr(addr) => Ok(Some(addr)),
+            AttributeValue::DebugAddrIndex(index) => self.address(unit, index).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Resolve an operation that refers to an entry in `.debug_addr`.
+    ///
+    /// `Operation::AddressIndex` is converted to `Operation::Address`, and
+    /// `Operation::ConstantIndex` is converted to `Operation::Literal`,
+    /// using the unit's `DW_AT_addr_base`. Other operations are returned unchanged.
+    ///
+    /// Returns `Error::InvalidAddressIndex` with the index and the unit's
+    /// `DW_AT_addr_base` if the index is past the end of `.debug_addr`.
+    pub fn resolve_operation(
+        &self,
+        unit: &Unit<R>,
+        operation: Operation<R>,
+    ) -> Result<Operation<R>> {
+        let address = |index: DebugAddrIndex<R::Offset>| {
+            self.address(unit, index).map_err(|err| match err {
+                Error::UnexpectedEof(_) => {
+                    Error::InvalidAddressIndex(index.0.into_u64(), unit.addr_base.0.into_u64())
+                }
+                err => err,
+            })
+        };
+        Ok(match operation {
+            Operation::AddressIndex { index } => Operation::Address {
+                address: address(index)?,
+            },
+            Operation::ConstantIndex { index } => Operation::Literal {
+                value: address(index)?,
+            },
+            operation => operation,
+        })
+    }
+
     /// Return the range list offset at the given index.
     pub fn ranges_offset(
         &self,
@@ -252,20 +711,47 @@ impl<R: Reader> Dwarf<R> {
     }
 
     /// Iterate over the `RangeListEntry`s starting at the given offset.
+    ///
+    /// The unit's `DW_AT_low_pc` is used as the initial base address.
     pub fn ranges(
         &self,
         unit: &Unit<R>,
         offset: RangeListsOffset<R::Offset>,
+    ) -> Result<RngListIter<R>> {
+        self.ranges_with_base(unit, offset, unit.low_pc)
+    }
+
+    /// Iterate over the `RangeListEntry`s starting at the given offset,
+    /// using the given initial base address.
+    ///
+    /// Base address entries in the range list will still override this base address.
+    pub fn ranges_with_base(
+        &self,
+        unit: &Unit<R>,
+        offset: RangeListsOffset<R::Offset>,
+        base_address: u64,
     ) -> Result<RngListIter<R>> {
         self.ranges.ranges(
             offset,
             unit.encoding(),
-            unit.low_pc,
+            base_address,
             &self.debug_addr,
             unit.addr_base,
         )
     }
 
+    /// Iterate over the `RawRngListEntry`ies starting at the given offset.
+    ///
+    /// This iterator does not perform any processing of the range entries,
+    /// such as handling base addresses or reading addresses from `.debug_addr`.
+    pub fn raw_ranges(
+        &self,
+        unit: &Unit<R>,
+        offset: RangeListsOffset<R::Offset>,
+    ) -> Result<RawRngListIter<R>> {
+        self.ranges.raw_ranges(offset, unit.encoding())
+    }
+
     /// Try to return an attribute value as a range list offset.
     ///
     /// If the attribute value is one of:
@@ -281,7 +767,18 @@ impl<R: Reader> Dwarf<R> {
         attr: AttributeValue<R>,
     ) -> Result<Option<RangeListsOffset<R::Offset>>> {
         match attr {
-            AttributeValue::RangeListsRef(offset) => Ok(Some(offset)),
+            AttributeValue::RangeListsRef(offset) => {
+                if self.file_type == DwarfFileType::Dwo && unit.header.version() < 5 {
+                    // For the GNU split-dwarf extension to DWARF 4, range list offsets
+                    // in split units are relative to the `DW_AT_GNU_ranges_base`
+                    // of the skeleton unit.
+                    Ok(Some(RangeListsOffset(
+                        offset.0.wrapping_add(unit.rnglists_base.0),
+                    )))
+                } else {
+                    Ok(Some(offset))
+                }
+            }
             AttributeValue::DebugRngListsIndex(index) => self.ranges_offset(unit, index).map(Some),
             _ => Ok(None),
         }
@@ -310,52 +807,61 @@ impl<R: Reader> Dwarf<R> {
     /// Return an iterator for the address ranges of a `DebuggingInformationEntry`.
     ///
     /// This uses `DW_AT_low_pc`, `DW_AT_high_pc` and `DW_AT_ranges`.
+    ///
+    /// `DW_AT_high_pc` may be either an address, or an offset from `DW_AT_low_pc`.
+    /// Addresses may be given directly or as an index into `.debug_addr`.
+    /// If the entry has neither `DW_AT_ranges` nor a `DW_AT_low_pc` and
+    /// `DW_AT_high_pc` pair, then the iterator is empty.
     pub fn die_ranges(
         &self,
         unit: &Unit<R>,
         entry: &DebuggingInformationEntry<R>,
     ) -> Result<RangeIter<R>> {
-        let mut low_pc = None;
-        let mut high_pc = None;
-        let mut size = None;
-        let mut attrs = entry.attrs();
-        while let Some(attr) = attrs.next()? {
-            match attr.name() {
-                constants::DW_AT_low_pc => {
-                    if let AttributeValue::Addr(val) = attr.value() {
-                        low_pc = Some(val);
-                    }
-                }
-                constants::DW_AT_high_pc => match attr.value() {
-                    AttributeValue::Addr(val) => high_pc = Some(val),
-                    AttributeValue::Udata(val) => size = Some(val),
-                    _ => return Err(Error::UnsupportedAttributeForm),
-                },
-                constants::DW_AT_ranges => {
-                    if let Some(list) = self.attr_ranges(unit, attr.value())? {
-                        return Ok(RangeIter(RangeIterInner::List(list)));
-                    }
-                }
-                _ => {}
+        if let Some(value) = entry.attr_value(constants::DW_AT_ranges)? {
+            if let Some(list) = self.attr_ranges(unit, value)? {
+                return Ok(RangeIter(RangeIterInner::List(list)));
             }
         }
-        let range = low_pc.and_then(|begin| {
-            let end = size.map(|size| begin + size).or(high_pc);
-            // TODO: perhaps return an error if `end` is `None`
-            end.map(|end| Range { begin, end })
-        });
+        let range = entry
+            .pc_range(&self.debug_addr, unit.addr_base)?
+            .map(|range| Range {
+                begin: range.start,
+                end: range.end,
+            });
         Ok(RangeIter(RangeIterInner::Single(range)))
     }
 
+    /// Return the location given by the `DW_AT_location` attribute of a
+    /// `DebuggingInformationEntry`.
+    ///
+    /// The attribute is either a single location expression, which is returned
+    /// as `DieLocation::Expression`, or a reference to a location list, which
+    /// is returned as `DieLocation::List` with an iterator for the list entries.
+    ///
+    /// Returns `None` if the entry does not have a `DW_AT_location` attribute,
+    /// or if the attribute has a form that is neither of these.
+    pub fn die_location(
+        &self,
+        unit: &Unit<R>,
+        entry: &DebuggingInformationEntry<R>,
+    ) -> Result<Option<DieLocation<R>>> {
+        let value = match entry.attr_value(constants::DW_AT_location)? {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        if let Some(expression) = value.exprloc_value() {
+            return Ok(Some(DieLocation::Expression(expression)));
+        }
+        Ok(self.attr_locations(unit, value)?.map(DieLocation::List))
+    }
+
     /// Return an iterator for the address ranges of a `Unit`.
     ///
     /// This uses `DW_AT_low_pc`, `DW_AT_high_pc` and `DW_AT_ranges` of the
     /// root `DebuggingInformationEntry`.
     pub fn unit_ranges(&self, unit: &Unit<R>) -> Result<RangeIter<R>> {
-        let mut cursor = unit.header.entries(&unit.abbreviations);
-        cursor.next_dfs()?;
-        let root = cursor.current().ok_or(Error::MissingUnitDie)?;
-        self.die_ranges(unit, root)
+        let root = unit.root()?;
+        self.die_ranges(unit, &root)
     }
 
     /// Return the location list offset at the given index.
@@ -369,20 +875,72 @@ impl<R: Reader> Dwarf<R> {
     }
 
     /// Iterate over the `LocationListEntry`s starting at the given offset.
+    ///
+    /// The unit's `DW_AT_low_pc` is used as the initial base address.
     pub fn locations(
         &self,
         unit: &Unit<R>,
         offset: LocationListsOffset<R::Offset>,
+    ) -> Result<LocListIter<R>> {
+        self.locations_with_base(unit, offset, unit.low_pc)
+    }
+
+    /// Iterate over the `LocationListEntry`s starting at the given offset,
+    /// using the given initial base address.
+    ///
+    /// Base address entries in the location list will still override this base address.
+    pub fn locations_with_base(
+        &self,
+        unit: &Unit<R>,
+        offset: LocationListsOffset<R::Offset>,
+        base_address: u64,
     ) -> Result<LocListIter<R>> {
         self.locations.locations(
             offset,
             unit.encoding(),
-            unit.low_pc,
+            base_address,
             &self.debug_addr,
             unit.addr_base,
         )
     }
 
+    /// Iterate over the `RawLocListEntry`s starting at the given offset.
+    ///
+    /// This iterator does not perform any processing of the location entries,
+    /// such as handling base addresses or reading addresses from `.debug_addr`.
+    pub fn raw_locations(
+        &self,
+        unit: &Unit<R>,
+        offset: LocationListsOffset<R::Offset>,
+    ) -> Result<RawLocListIter<R>> {
+        self.locations.raw_locations(offset, unit.encoding())
+    }
+
+    /// Return the location expression that is valid at the given address,
+    /// using the location list starting at the given offset.
+    ///
+    /// If no location list entry contains the address, then the expression
+    /// from the `DW_LLE_default_location` entry is returned, if any.
+    pub fn location_at(
+        &self,
+        unit: &Unit<R>,
+        offset: LocationListsOffset<R::Offset>,
+        address: u64,
+    ) -> Result<Option<Expression<R>>> {
+        let mut locations = self.locations(unit, offset)?;
+        let mut default = None;
+        while let Some((location, is_default)) = locations.next_entry()? {
+            if is_default {
+                if default.is_none() {
+                    default = Some(location.data);
+                }
+            } else if location.range.begin <= address && address < location.range.end {
+                return Ok(Some(location.data));
+            }
+        }
+        Ok(default)
+    }
+
     /// Try to return an attribute value as a location list offset.
     ///
     /// If the attribute value is one of:
@@ -426,368 +984,4166 @@ impl<R: Reader> Dwarf<R> {
         }
     }
 
-    /// Call `Reader::lookup_offset_id` for each section, and return the first match.
+    /// Return the path name of the file with the given index in the unit's
+    /// line program header.
     ///
-    /// The first element of the tuple is `true` for supplementary sections.
-    pub fn lookup_offset_id(&self, id: ReaderOffsetId) -> Option<(bool, SectionId, R::Offset)> {
-        None.or_else(|| self.debug_abbrev.lookup_offset_id(id))
-            .or_else(|| self.debug_addr.lookup_offset_id(id))
-            .or_else(|| self.debug_info.lookup_offset_id(id))
-            .or_else(|| self.debug_line.lookup_offset_id(id))
-            .or_else(|| self.debug_line_str.lookup_offset_id(id))
-            .or_else(|| self.debug_str.lookup_offset_id(id))
-            .or_else(|| self.debug_str_offsets.lookup_offset_id(id))
-            .or_else(|| self.debug_types.lookup_offset_id(id))
-            .or_else(|| self.locations.lookup_offset_id(id))
-            .or_else(|| self.ranges.lookup_offset_id(id))
-            .map(|(id, offset)| (false, id, offset))
-            .or_else(|| {
-                self.debug_str_sup
-                    .lookup_offset_id(id)
-                    .map(|(id, offset)| (true, id, offset))
-            })
+    /// This is the name as recorded in the file entry, which may be relative to
+    /// the directory returned by `Dwarf::file_directory`.
+    ///
+    /// File indices are 1-based for DWARF version <= 4, and 0-based for DWARF version 5.
+    /// Returns `None` if the unit does not have a line program, or if there is
+    /// no file with the given index.
+    pub fn file_name(&self, unit: &Unit<R>, file_index: u64) -> Result<Option<R>> {
+        match unit.line_program {
+            Some(ref program) => self.file_name_string(unit, program.header(), file_index),
+            None => Ok(None),
+        }
     }
 
-    /// Returns a string representation of the given error.
+    /// Return the path name of the file with the given index in a line
+    /// program header for the unit.
     ///
-    /// This uses information from the DWARF sections to provide more information in some cases.
-    pub fn format_error(&self, err: Error) -> String {
-        #[allow(clippy::single_match)]
-        match err {
-            Error::UnexpectedEof(id) => match self.lookup_offset_id(id) {
-                Some((sup, section, offset)) => {
-                    return format!(
-                        "{} at {}{}+0x{:x}",
-                        err.description(),
-                        section.name(),
-                        if sup { "(sup)" } else { "" },
-                        offset.into_u64(),
-                    );
-                }
-                None => {}
-            },
-            _ => {}
+    /// The path name is resolved using `Dwarf::attr_string`, so it may use
+    /// any of the string forms that are allowed for `DW_LNCT_path`.
+    ///
+    /// Returns `None` if there is no file with the given index.
+    pub fn file_name_string(
+        &self,
+        unit: &Unit<R>,
+        header: &LineProgramHeader<R>,
+        file_index: u64,
+    ) -> Result<Option<R>> {
+        match header.file(file_index) {
+            Some(file) => self.attr_string(unit, file.path_name()).map(Some),
+            None => Ok(None),
         }
-        err.description().into()
     }
-}
-
-/// All of the commonly used information for a unit in the `.debug_info` or `.debug_types`
-/// sections.
-#[derive(Debug)]
-pub struct Unit<R, Offset = <R as Reader>::Offset>
-where
-    R: Reader<Offset = Offset>,
-    Offset: ReaderOffset,
-{
-    /// The section offset of the unit.
-    pub offset: UnitSectionOffset<Offset>,
 
-    /// The header of the unit.
-    pub header: UnitHeader<R, Offset>,
-
-    /// The parsed abbreviations for the unit.
-    pub abbreviations: Abbreviations,
+    /// Return the name of the include directory with the given index in a
+    /// line program header for the unit.
+    ///
+    /// The name is resolved in the same way as `Dwarf::file_name_string`.
+    ///
+    /// Returns `None` if there is no directory with the given index.
+    pub fn directory_string(
+        &self,
+        unit: &Unit<R>,
+        header: &LineProgramHeader<R>,
+        directory_index: u64,
+    ) -> Result<Option<R>> {
+        match header.directory(directory_index) {
+            Some(directory) => self.attr_string(unit, directory).map(Some),
+            None => Ok(None),
+        }
+    }
 
-    /// The `DW_AT_name` attribute of the unit.
+    /// Return the directory of the file with the given index in the unit's
+    /// line program header.
+    ///
+    /// For DWARF version <= 4, a directory index of 0 corresponds to the
+    /// unit's `DW_AT_comp_dir`.
+    ///
+    /// Returns `None` if the unit does not have a line program, if there is
+    /// no file with the given index, or if the file's directory is unknown.
+    pub fn file_directory(&self, unit: &Unit<R>, file_index: u64) -> Result<Option<R>> {
+        let header = match unit.line_program {
+            Some(ref program) => program.header(),
+            None => return Ok(None),
+        };
+        match header
+            .file(file_index)
+            .and_then(|file| file.directory(header))
+        {
+            Some(directory) => self.attr_string(unit, directory).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Return the declaration location given by the `DW_AT_decl_file`,
+    /// `DW_AT_decl_line` and `DW_AT_decl_column` attributes of an entry.
+    ///
+    /// The file index is resolved using `Dwarf::file_name`, so it is 1-based
+    /// for DWARF version <= 4, and 0-based for DWARF version 5. For DWARF
+    /// version <= 4, a file index of 0 means that there is no file, and the
+    /// returned `DeclLocation::file` is `None`.
+    ///
+    /// Returns `Ok(None)` if none of the attributes are present.
+    pub fn decl_location(
+        &self,
+        unit: &Unit<R>,
+        entry: &DebuggingInformationEntry<R>,
+    ) -> Result<Option<DeclLocation<R>>> {
+        let mut found = false;
+        let mut file = None;
+        let mut line = None;
+        let mut column = None;
+        let mut attrs = entry.attrs();
+        while let Some(attr) = attrs.next()? {
+            match attr.name() {
+                constants::DW_AT_decl_file => {
+                    let index = match attr.value() {
+                        AttributeValue::FileIndex(index) => index,
+                        value => value.udata_value().ok_or(Error::UnsupportedAttributeForm)?,
+                    };
+                    // Before DWARF 5, file 0 means that there is no source file.
+                    file = if index == 0 && unit.header.version() < 5 {
+                        None
+                    } else {
+                        self.file_name(unit, index)?
+                    };
+                    found = true;
+                }
+                constants::DW_AT_decl_line => {
+                    line = Some(attr.udata_value().ok_or(Error::UnsupportedAttributeForm)?);
+                    found = true;
+                }
+                constants::DW_AT_decl_column => {
+                    column = Some(attr.udata_value().ok_or(Error::UnsupportedAttributeForm)?);
+                    found = true;
+                }
+                _ => {}
+            }
+        }
+        if !found {
+            return Ok(None);
+        }
+        Ok(Some(DeclLocation { file, line, column }))
+    }
+
+    /// Find the source location of the given address in the unit's line program.
+    ///
+    /// The line program is run to completion using
+    /// `IncompleteLineProgram::complete`, and the row that covers `probe` is
+    /// found using `CompleteLineProgram::find_row`. Callers that perform many
+    /// lookups should complete the program once and use `find_row` instead.
+    ///
+    /// Returns `None` if the unit does not have a line program, or if no row
+    /// contains the address.
+    pub fn find_location(&self, unit: &Unit<R>, probe: u64) -> Result<Option<SourceLocation<R>>> {
+        let program = match unit.line_program {
+            Some(ref program) => program.clone(),
+            None => return Ok(None),
+        };
+        let (program, _) = program.complete()?;
+        let row = match program.find_row(probe) {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+
+        let file = self.file_name_string(unit, program.header(), row.file_index())?;
+        let column = match row.column() {
+            ColumnType::LeftEdge => None,
+            ColumnType::Column(column) => Some(column),
+        };
+        Ok(Some(SourceLocation {
+            file,
+            line: row.line(),
+            column,
+        }))
+    }
+
+    /// Find the inlined subroutines in the unit that contain the given address.
+    ///
+    /// The frames are ordered from outermost to innermost. The first frame is
+    /// for a subroutine that was inlined into the `DW_TAG_subprogram` that
+    /// contains the address, and each following frame was inlined into the
+    /// previous frame. Returns an empty `Vec` if the address is not within an
+    /// inlined subroutine.
+    ///
+    /// Only the `DW_TAG_subprogram`, `DW_TAG_inlined_subroutine` and
+    /// `DW_TAG_lexical_block` entries are searched, starting at the children of
+    /// the root entry, and their subtrees are skipped if their address ranges
+    /// do not contain the address. The address ranges of entries are given by
+    /// `Dwarf::die_ranges`. The name of each frame is found using
+    /// `Dwarf::resolve_attribute`, which only looks up another unit if the
+    /// abstract origin is not in `unit`.
+    pub fn inlined_frames(&self, unit: &Unit<R>, probe: u64) -> Result<Vec<InlineFrame<R>>> {
+        let mut frames = Vec::new();
+        let mut tree = unit.entries_tree(None)?;
+        self.find_inlined_frames(unit, tree.root()?, probe, &mut frames)?;
+        Ok(frames)
+    }
+
+    /// Search the children of `node` for the entries that contain `probe`.
+    ///
+    /// Returns true if a function containing `probe` was found.
+    fn find_inlined_frames(
+        &self,
+        unit: &Unit<R>,
+        node: EntriesTreeNode<R>,
+        probe: u64,
+        frames: &mut Vec<InlineFrame<R>>,
+    ) -> Result<bool> {
+        let mut children = node.children();
+        while let Some(child) = children.next()? {
+            let entry = child.entry();
+            let is_function = match entry.tag() {
+                constants::DW_TAG_subprogram | constants::DW_TAG_inlined_subroutine => true,
+                constants::DW_TAG_lexical_block => false,
+                _ => continue,
+            };
+
+            let mut contains = false;
+            let mut ranges = self.die_ranges(unit, entry)?;
+            while let Some(range) = ranges.next()? {
+                if range.begin <= probe && probe < range.end {
+                    contains = true;
+                    break;
+                }
+            }
+            if !contains {
+                continue;
+            }
+
+            if entry.tag() == constants::DW_TAG_inlined_subroutine {
+                frames.push(self.inline_frame(unit, entry)?);
+            }
+            let found = self.find_inlined_frames(unit, child, probe, frames)?;
+            if is_function || found {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn inline_frame(
+        &self,
+        unit: &Unit<R>,
+        entry: &DebuggingInformationEntry<R>,
+    ) -> Result<InlineFrame<R>> {
+        let name = match self.resolve_attribute(unit, entry, constants::DW_AT_name)? {
+            Some((other_unit, value)) => {
+                let unit = other_unit.as_ref().unwrap_or(unit);
+                Some(self.attr_string(unit, value)?)
+            }
+            None => None,
+        };
+        let mut frame = InlineFrame {
+            offset: entry.offset(),
+            name,
+            call_file: None,
+            call_line: None,
+            call_column: None,
+        };
+        let mut attrs = entry.attrs();
+        while let Some(attr) = attrs.next()? {
+            match attr.name() {
+                constants::DW_AT_call_file => {
+                    let index = match attr.value() {
+                        AttributeValue::FileIndex(index) => index,
+                        value => value.udata_value().ok_or(Error::UnsupportedAttributeForm)?,
+                    };
+                    frame.call_file = self.file_name(unit, index)?;
+                }
+                constants::DW_AT_call_line => {
+                    frame.call_line = attr.udata_value();
+                }
+                constants::DW_AT_call_column => {
+                    frame.call_column = attr.udata_value();
+                }
+                _ => {}
+            }
+        }
+        Ok(frame)
+    }
+
+    /// Find the debugging information entries with the given name.
+    ///
+    /// The entries are found using the first of these that is available:
+    ///
+    /// - the name indexes in `.debug_names`,
+    /// - the entries in `.debug_pubnames`,
+    /// - a scan of the `DW_AT_name` attributes of all entries in `.debug_info`.
+    ///
+    /// A section is only used if it is not empty, and the other sources are not
+    /// searched even if it contains no entries for the name. The scan of
+    /// `.debug_info` only parses the abbreviations and entries of each unit,
+    /// and returns an error for a `DW_AT_name` that cannot be read as a string.
+    ///
+    /// The name must match exactly. Although the `.debug_names` hash table uses
+    /// a case folded hash, the matching names in the string table are still
+    /// compared case sensitively.
+    ///
+    /// The search is performed lazily, one name index or unit at a time, so
+    /// callers that only need the first match can stop early.
+    ///
+    /// Can be [used with
+    /// `FallibleIterator`](./index.html#using-with-fallibleiterator).
+    pub fn find_symbol<'a>(&'a self, name: &'a str) -> Result<SymbolIter<'a, R>> {
+        let source = if !self.debug_names.reader().is_empty() {
+            SymbolSource::Names(self.debug_names.indexes())
+        } else if !self.debug_pubnames.reader().is_empty() {
+            SymbolSource::PubNames(self.debug_pubnames.items())
+        } else {
+            SymbolSource::Units(self.units())
+        };
+        Ok(SymbolIter {
+            dwarf: self,
+            name: name.as_bytes(),
+            source,
+            pending: Vec::new(),
+        })
+    }
+
+    /// Call `Reader::lookup_offset_id` for each section, and return the first match.
+    ///
+    /// The first element of the tuple is `true` for supplementary sections.
+    pub fn lookup_offset_id(&self, id: ReaderOffsetId) -> Option<(bool, SectionId, R::Offset)> {
+        None.or_else(|| self.debug_abbrev.lookup_offset_id(id))
+            .or_else(|| self.debug_addr.lookup_offset_id(id))
+            .or_else(|| self.debug_aranges.lookup_offset_id(id))
+            .or_else(|| self.debug_info.lookup_offset_id(id))
+            .or_else(|| self.debug_line.lookup_offset_id(id))
+            .or_else(|| self.debug_line_str.lookup_offset_id(id))
+            .or_else(|| self.debug_names.lookup_offset_id(id))
+            .or_else(|| self.debug_pubnames.lookup_offset_id(id))
+            .or_else(|| self.debug_pubtypes.lookup_offset_id(id))
+            .or_else(|| self.debug_str.lookup_offset_id(id))
+            .or_else(|| self.debug_str_offsets.lookup_offset_id(id))
+            .or_else(|| self.debug_sup.lookup_offset_id(id))
+            .or_else(|| self.debug_types.lookup_offset_id(id))
+            .or_else(|| self.locations.lookup_offset_id(id))
+            .or_else(|| self.ranges.lookup_offset_id(id))
+            .map(|(id, offset)| (false, id, offset))
+            .or_else(|| {
+                self.debug_str_sup
+                    .lookup_offset_id(id)
+                    .map(|(id, offset)| (true, id, offset))
+            })
+            .or_else(|| {
+                self.sup()
+                    .and_then(|sup| sup.lookup_offset_id(id))
+                    .map(|(_, id, offset)| (true, id, offset))
+            })
+    }
+
+    /// Returns a string representation of the given error.
+    ///
+    /// This uses information from the DWARF sections to provide more information in some cases.
+    pub fn format_error(&self, err: Error) -> String {
+        if let Some((sup, section, offset)) =
+            err.offset_id().and_then(|id| self.lookup_offset_id(id))
+        {
+            return format!(
+                "{} at {}{}+0x{:x}",
+                err.description(),
+                section.name(),
+                if sup { "(sup)" } else { "" },
+                offset.into_u64(),
+            );
+        }
+        err.description().into()
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<R> Dwarf<R>
+where
+    R: Reader + Send + Sync,
+    R::Offset: Send + Sync,
+{
+    /// Construct the `Unit`s for all of the compilation unit headers in the
+    /// `.debug_info` section, in parallel.
+    ///
+    /// The unit headers are read before the units are constructed.
+    /// If an error occurs while reading the headers, then it is returned
+    /// as the last item.
+    ///
+    /// This requires the `rayon` feature.
+    ///
+    /// ```rust,no_run
+    /// use rayon::prelude::*;
+    ///
+    /// # fn example<R>(dwarf: &gimli::Dwarf<R>) -> gimli::Result<()>
+    /// # where R: gimli::Reader + Send + Sync, R::Offset: Send + Sync
+    /// # {
+    /// // Collect the names of all functions.
+    /// let names = dwarf
+    ///     .par_units()
+    ///     .map(|unit| {
+    ///         let unit = unit?;
+    ///         let mut names = Vec::new();
+    ///         let mut entries = unit.entries();
+    ///         while let Some((_, entry)) = entries.next_dfs()? {
+    ///             if entry.tag() != gimli::DW_TAG_subprogram {
+    ///                 continue;
+    ///             }
+    ///             if let Some(name) = entry.attr_value(gimli::DW_AT_name)? {
+    ///                 let name = dwarf.attr_string(&unit, name)?;
+    ///                 names.push(name.to_string_lossy()?.into_owned());
+    ///             }
+    ///         }
+    ///         Ok(names)
+    ///     })
+    ///     .collect::<gimli::Result<Vec<_>>>()?;
+    /// # let _ = names;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn par_units(&self) -> impl ParallelIterator<Item = Result<Unit<R>>> + '_ {
+        let mut headers = Vec::new();
+        let mut units = self.units();
+        loop {
+            match units.next() {
+                Ok(Some(header)) => headers.push(Ok(header)),
+                Ok(None) => break,
+                Err(e) => {
+                    headers.push(Err(e));
+                    break;
+                }
+            }
+        }
+        headers
+            .into_par_iter()
+            .map(move |header| header.and_then(|header| self.unit(header)))
+    }
+}
+
+impl<'input, Endian> Dwarf<EndianSlice<'input, Endian>>
+where
+    Endian: Endianity,
+{
+    /// Return the string value of an attribute, converted to a `&str`.
+    ///
+    /// This is the same as `attr_string`, except that it returns an error
+    /// if the string is not valid UTF-8.
+    pub fn attr_str(
+        &self,
+        unit: &Unit<EndianSlice<'input, Endian>>,
+        attr: AttributeValue<EndianSlice<'input, Endian>>,
+    ) -> Result<&'input str> {
+        self.attr_string(unit, attr)?.to_string()
+    }
+
+    /// Return the string value of an attribute, converted to a `Cow<str>`.
+    ///
+    /// This is the same as `attr_string`, except that invalid UTF-8 sequences
+    /// are replaced with `U+FFFD REPLACEMENT CHARACTER`.
+    pub fn attr_string_lossy(
+        &self,
+        unit: &Unit<EndianSlice<'input, Endian>>,
+        attr: AttributeValue<EndianSlice<'input, Endian>>,
+    ) -> Result<Cow<'input, str>> {
+        Ok(self.attr_string(unit, attr)?.to_string_lossy())
+    }
+}
+
+/// The sections from a `.dwp` file.
+///
+/// A `.dwp` file is a package of the sections from many `.dwo` files.
+/// The `.debug_cu_index` and `.debug_tu_index` sections give the
+/// contributions of each unit to the other sections.
+#[derive(Debug)]
+pub struct DwarfPackage<R: Reader> {
+    /// The compilation unit index in the `.debug_cu_index` section.
+    pub cu_index: UnitIndex<R>,
+
+    /// The type unit index in the `.debug_tu_index` section.
+    pub tu_index: UnitIndex<R>,
+
+    /// The `.debug_abbrev.dwo` section.
+    pub debug_abbrev: DebugAbbrev<R>,
+
+    /// The `.debug_info.dwo` section.
+    pub debug_info: DebugInfo<R>,
+
+    /// The `.debug_line.dwo` section.
+    pub debug_line: DebugLine<R>,
+
+    /// The `.debug_str.dwo` section.
+    pub debug_str: DebugStr<R>,
+
+    /// The `.debug_str_offsets.dwo` section.
+    pub debug_str_offsets: DebugStrOffsets<R>,
+
+    /// The `.debug_loc.dwo` section.
+    ///
+    /// Only present when using GNU split-dwarf extension to DWARF 4.
+    pub debug_loc: DebugLoc<R>,
+
+    /// The `.debug_loclists.dwo` section.
+    pub debug_loclists: DebugLocLists<R>,
+
+    /// The `.debug_rnglists.dwo` section.
+    pub debug_rnglists: DebugRngLists<R>,
+
+    /// The `.debug_types.dwo` section.
+    ///
+    /// Only present when using GNU split-dwarf extension to DWARF 4.
+    pub debug_types: DebugTypes<R>,
+
+    /// An empty section.
+    ///
+    /// Used when creating a `Dwarf<R>` for sections that are not in the package.
+    pub empty: R,
+}
+
+impl<R: Reader> DwarfPackage<R> {
+    /// Try to load the `.dwp` sections using the given loader function.
+    ///
+    /// `section` loads a DWARF section from the package file. Note that
+    /// the sections in a package file have a `.dwo` suffix, so for example
+    /// `SectionId::DebugInfo` should load the `.debug_info.dwo` section.
+    /// `SectionId::dwo_name` returns these section names.
+    /// This function should return an empty section if the section does not exist.
+    ///
+    /// `empty` is used to create sections that are not present in the package.
+    pub fn load<F, E>(mut section: F, empty: R) -> std::result::Result<Self, E>
+    where
+        F: FnMut(SectionId) -> std::result::Result<R, E>,
+        E: From<Error>,
+    {
+        let cu_index: DebugCuIndex<R> = Section::load(&mut section)?;
+        let tu_index: DebugTuIndex<R> = Section::load(&mut section)?;
+        Ok(DwarfPackage {
+            cu_index: cu_index.index()?,
+            tu_index: tu_index.index()?,
+            debug_abbrev: Section::load(&mut section)?,
+            debug_info: Section::load(&mut section)?,
+            debug_line: Section::load(&mut section)?,
+            debug_str: Section::load(&mut section)?,
+            debug_str_offsets: Section::load(&mut section)?,
+            debug_loc: Section::load(&mut section)?,
+            debug_loclists: Section::load(&mut section)?,
+            debug_rnglists: Section::load(&mut section)?,
+            debug_types: Section::load(&mut section)?,
+            empty,
+        })
+    }
+
+    /// Find the compilation unit with the given DWO identifier and return its section
+    /// contributions.
+    ///
+    /// The DWO identifier is given by the `DW_AT_dwo_id` or `DW_AT_GNU_dwo_id`
+    /// attribute of the skeleton unit in the parent file.
+    ///
+    /// Returns `None` if the package does not contain the unit.
+    pub fn find_cu(&self, id: DwoId, parent: &Dwarf<R>) -> Result<Option<Dwarf<R>>> {
+        match self.cu_index.find(id.0) {
+            Some(row) => self.cu_sections(row, parent).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Find the type unit with the given type signature and return its section
+    /// contributions.
+    ///
+    /// Returns `None` if the package does not contain the unit.
+    pub fn find_tu(
+        &self,
+        signature: DebugTypeSignature,
+        parent: &Dwarf<R>,
+    ) -> Result<Option<Dwarf<R>>> {
+        match self.tu_index.find(signature.0) {
+            Some(row) => self.tu_sections(row, parent).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Return the section contributions of the compilation unit at the given index.
+    ///
+    /// Row indices are 1-based, and must be at most `cu_index.unit_count()`.
+    ///
+    /// This function should only be needed by low level parsers.
+    pub fn cu_sections(&self, index: u32, parent: &Dwarf<R>) -> Result<Dwarf<R>> {
+        self.sections(&self.cu_index, index, parent)
+    }
+
+    /// Return the section contributions of the type unit at the given index.
+    ///
+    /// Row indices are 1-based, and must be at most `tu_index.unit_count()`.
+    ///
+    /// This function should only be needed by low level parsers.
+    pub fn tu_sections(&self, index: u32, parent: &Dwarf<R>) -> Result<Dwarf<R>> {
+        self.sections(&self.tu_index, index, parent)
+    }
+
+    fn sections(
+        &self,
+        unit_index: &UnitIndex<R>,
+        index: u32,
+        parent: &Dwarf<R>,
+    ) -> Result<Dwarf<R>> {
+        let mut debug_abbrev = DebugAbbrev::from(self.empty.clone());
+        let mut debug_info = DebugInfo::from(self.empty.clone());
+        let mut debug_line = DebugLine::from(self.empty.clone());
+        let mut debug_str_offsets = DebugStrOffsets::from(self.empty.clone());
+        let mut debug_loc = DebugLoc::from(self.empty.clone());
+        let mut debug_loclists = DebugLocLists::from(self.empty.clone());
+        let mut debug_rnglists = DebugRngLists::from(self.empty.clone());
+        let mut debug_types = DebugTypes::from(self.empty.clone());
+        for section in unit_index.sections(index)? {
+            match section.section {
+                SectionId::DebugAbbrev => {
+                    debug_abbrev = contribution(&self.debug_abbrev, section)?;
+                }
+                SectionId::DebugInfo => {
+                    debug_info = contribution(&self.debug_info, section)?;
+                }
+                SectionId::DebugLine => {
+                    debug_line = contribution(&self.debug_line, section)?;
+                }
+                SectionId::DebugStrOffsets => {
+                    debug_str_offsets = contribution(&self.debug_str_offsets, section)?;
+                }
+                SectionId::DebugLoc => {
+                    debug_loc = contribution(&self.debug_loc, section)?;
+                }
+                SectionId::DebugLocLists => {
+                    debug_loclists = contribution(&self.debug_loclists, section)?;
+                }
+                SectionId::DebugRngLists => {
+                    debug_rnglists = contribution(&self.debug_rnglists, section)?;
+                }
+                SectionId::DebugTypes => {
+                    debug_types = contribution(&self.debug_types, section)?;
+                }
+                // Macro sections are not used by `Dwarf`.
+                _ => {}
+            }
+        }
+
+        Ok(Dwarf {
+            debug_abbrev,
+            debug_addr: parent.debug_addr.clone(),
+            debug_aranges: self.empty.clone().into(),
+            debug_info,
+            debug_line,
+            debug_line_str: parent.debug_line_str.clone(),
+            debug_names: self.empty.clone().into(),
+            debug_pubnames: self.empty.clone().into(),
+            debug_pubtypes: self.empty.clone().into(),
+            debug_str: self.debug_str.clone(),
+            debug_str_offsets,
+            debug_str_sup: parent.debug_str_sup.clone(),
+            debug_sup: self.empty.clone().into(),
+            debug_types,
+            locations: LocationLists::new(debug_loc, debug_loclists),
+            ranges: RangeLists::new(parent.ranges.debug_ranges().clone(), debug_rnglists),
+            file_type: DwarfFileType::Dwo,
+            sup: parent.sup.clone(),
+        })
+    }
+}
+
+/// Return the part of `section` that is given by a unit's contribution.
+fn contribution<R, S>(section: &S, contribution: UnitIndexSection) -> Result<S>
+where
+    R: Reader,
+    S: Section<R>,
+{
+    let mut data = section.reader().clone();
+    data.skip(R::Offset::from_u32(contribution.offset))?;
+    data.truncate(R::Offset::from_u32(contribution.size))?;
+    Ok(S::from(data))
+}
+
+/// The source location of an address, as returned by `Dwarf::find_location`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation<R> {
+    /// The path name of the source file.
+    ///
+    /// This may be relative to the file's directory in the line program header.
+    pub file: Option<R>,
+
+    /// The line number. Lines are numbered beginning at 1.
+    pub line: Option<u64>,
+
+    /// The column number. Columns are numbered beginning at 1.
+    ///
+    /// This is `None` if the location is at the left edge of the line.
+    pub column: Option<u64>,
+}
+
+/// An inlined subroutine that contains an address, as returned by
+/// `Dwarf::inlined_frames`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InlineFrame<R, Offset = <R as Reader>::Offset>
+where
+    R: Reader<Offset = Offset>,
+    Offset: ReaderOffset,
+{
+    /// The offset of the `DW_TAG_inlined_subroutine` entry within its unit.
+    pub offset: UnitOffset<Offset>,
+
+    /// The name of the inlined function.
+    ///
+    /// This is usually found by following the `DW_AT_abstract_origin`
+    /// reference of the entry.
     pub name: Option<R>,
 
-    /// The `DW_AT_comp_dir` attribute of the unit.
-    pub comp_dir: Option<R>,
+    /// The path name of the source file containing the call, from
+    /// `DW_AT_call_file`.
+    ///
+    /// This may be relative to the file's directory in the line program header.
+    pub call_file: Option<R>,
+
+    /// The line number of the call, from `DW_AT_call_line`.
+    pub call_line: Option<u64>,
+
+    /// The column number of the call, from `DW_AT_call_column`.
+    pub call_column: Option<u64>,
+}
+
+/// The declaration location of an entry, as returned by
+/// `Dwarf::decl_location`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeclLocation<R> {
+    /// The path name of the source file, from `DW_AT_decl_file`.
+    ///
+    /// This may be relative to the file's directory in the line program header.
+    pub file: Option<R>,
+
+    /// The line number, from `DW_AT_decl_line`.
+    pub line: Option<u64>,
+
+    /// The column number, from `DW_AT_decl_column`.
+    pub column: Option<u64>,
+}
+
+/// All of the commonly used information for a unit in the `.debug_info` or `.debug_types`
+/// sections.
+///
+/// The attributes of the root entry that are stored in the fields of this
+/// struct are extracted when the unit is constructed. Other root attributes
+/// can be read from the entry returned by `Unit::root`.
+#[derive(Debug)]
+pub struct Unit<R, Offset = <R as Reader>::Offset>
+where
+    R: Reader<Offset = Offset>,
+    Offset: ReaderOffset,
+{
+    /// The section offset of the unit.
+    pub offset: UnitSectionOffset<Offset>,
+
+    /// The header of the unit.
+    pub header: UnitHeader<R, Offset>,
+
+    /// The parsed abbreviations for the unit.
+    pub abbreviations: Abbreviations,
+
+    /// The `DW_AT_name` attribute of the unit.
+    pub name: Option<R>,
+
+    /// The `DW_AT_comp_dir` attribute of the unit.
+    pub comp_dir: Option<R>,
+
+    /// The `DW_AT_language` attribute of the unit.
+    pub language: Option<constants::DwLang>,
+
+    /// The `DW_AT_low_pc` attribute of the unit. Defaults to 0.
+    pub low_pc: u64,
+
+    /// The end address given by the `DW_AT_high_pc` attribute of the unit.
+    ///
+    /// This is always an absolute address, even if the attribute is encoded
+    /// as an offset from `DW_AT_low_pc`.
+    pub high_pc: Option<u64>,
+
+    /// The `DW_AT_str_offsets_base` attribute of the unit.
+    ///
+    /// For DWARF version 5 units without this attribute, this defaults to the
+    /// size of the `.debug_str_offsets` header, which is the base of the first
+    /// set of entries in the section. Otherwise it defaults to 0.
+    pub str_offsets_base: DebugStrOffsetsBase<Offset>,
+
+    /// The result of parsing the count from the `.debug_str_offsets` header,
+    /// and the `str_offsets_base` it was parsed for.
+    ///
+    /// This is `None` for units before DWARF version 5, which have no header.
+    /// It is ignored if `str_offsets_base` has since been changed.
+    pub(crate) str_offsets_count: Option<(DebugStrOffsetsBase<Offset>, Result<Offset>)>,
+
+    /// The `DW_AT_addr_base` attribute of the unit. Defaults to 0.
+    pub addr_base: DebugAddrBase<Offset>,
+
+    /// The `DW_AT_loclists_base` attribute of the unit. Defaults to 0.
+    pub loclists_base: DebugLocListsBase<Offset>,
+
+    /// The `DW_AT_rnglists_base` attribute of the unit. Defaults to 0.
+    pub rnglists_base: DebugRngListsBase<Offset>,
+
+    /// The `DW_AT_dwo_name` or `DW_AT_GNU_dwo_name` attribute of a skeleton
+    /// unit, which gives the name of the split DWARF object file.
+    ///
+    /// This may be a string reference; use `Dwarf::attr_string` to read it.
+    pub dwo_name: Option<AttributeValue<R, Offset>>,
+
+    /// The `DW_AT_producer` attribute of the unit, which identifies the
+    /// compiler that produced the unit.
+    ///
+    /// This may be a string reference; use `Unit::producer_string` to read it.
+    pub producer: Option<AttributeValue<R, Offset>>,
+
+    /// The identifier of the split compilation unit for a skeleton unit, or
+    /// of this unit if it is a split compilation unit.
+    ///
+    /// This is read from the unit header for DWARF 5 units, and otherwise
+    /// from the `DW_AT_GNU_dwo_id` attribute.
+    pub dwo_id: Option<DwoId>,
+
+    /// The line number program of the unit.
+    pub line_program: Option<IncompleteLineProgram<R, Offset>>,
+}
+
+/// The attributes of a unit's root entry that are used to construct a `Unit`.
+///
+/// Values are left unresolved, so that they can be interpreted once the rest
+/// of the unit is known.
+#[derive(Debug)]
+struct UnitAttrs<R: Reader> {
+    name: Option<AttributeValue<R>>,
+    comp_dir: Option<AttributeValue<R>>,
+    language: Option<constants::DwLang>,
+    low_pc: Option<AttributeValue<R>>,
+    high_pc: Option<AttributeValue<R>>,
+    size: Option<u64>,
+    line_program_offset: Option<DebugLineOffset<R::Offset>>,
+    str_offsets_base: Option<DebugStrOffsetsBase<R::Offset>>,
+    addr_base: Option<DebugAddrBase<R::Offset>>,
+    loclists_base: Option<DebugLocListsBase<R::Offset>>,
+    rnglists_base: Option<DebugRngListsBase<R::Offset>>,
+    dwo_name: Option<AttributeValue<R>>,
+    dwo_id: Option<DwoId>,
+    producer: Option<AttributeValue<R>>,
+}
+
+/// Extract the attributes of a unit's root entry that are stored in `Unit`.
+fn extract_unit_attrs<R: Reader>(root: &DebuggingInformationEntry<R>) -> Result<UnitAttrs<R>> {
+    let mut unit = UnitAttrs {
+        name: None,
+        comp_dir: None,
+        language: None,
+        low_pc: None,
+        high_pc: None,
+        size: None,
+        line_program_offset: None,
+        str_offsets_base: None,
+        addr_base: None,
+        loclists_base: None,
+        rnglists_base: None,
+        dwo_name: None,
+        dwo_id: None,
+        producer: None,
+    };
+    let mut attrs = root.attrs();
+    while let Some(attr) = attrs.next()? {
+        match attr.name() {
+            constants::DW_AT_name => {
+                unit.name = Some(attr.value());
+            }
+            constants::DW_AT_comp_dir => {
+                unit.comp_dir = Some(attr.value());
+            }
+            constants::DW_AT_language => {
+                if let AttributeValue::Language(language) = attr.value() {
+                    unit.language = Some(language);
+                }
+            }
+            constants::DW_AT_low_pc => {
+                unit.low_pc = Some(attr.value());
+            }
+            constants::DW_AT_high_pc => match attr.value() {
+                value @ AttributeValue::Addr(_) | value @ AttributeValue::DebugAddrIndex(_) => {
+                    unit.high_pc = Some(value)
+                }
+                value => unit.size = value.udata_value(),
+            },
+            constants::DW_AT_stmt_list => {
+                if let AttributeValue::DebugLineRef(offset) = attr.value() {
+                    unit.line_program_offset = Some(offset);
+                }
+            }
+            constants::DW_AT_str_offsets_base => {
+                if let AttributeValue::DebugStrOffsetsBase(base) = attr.value() {
+                    unit.str_offsets_base = Some(base);
+                }
+            }
+            constants::DW_AT_addr_base | constants::DW_AT_GNU_addr_base => {
+                if let AttributeValue::DebugAddrBase(base) = attr.value() {
+                    unit.addr_base = Some(base);
+                }
+            }
+            constants::DW_AT_loclists_base => {
+                if let AttributeValue::DebugLocListsBase(base) = attr.value() {
+                    unit.loclists_base = Some(base);
+                }
+            }
+            constants::DW_AT_rnglists_base | constants::DW_AT_GNU_ranges_base => {
+                if let AttributeValue::DebugRngListsBase(base) = attr.value() {
+                    unit.rnglists_base = Some(base);
+                }
+            }
+            constants::DW_AT_dwo_name | constants::DW_AT_GNU_dwo_name => {
+                unit.dwo_name = Some(attr.value());
+            }
+            constants::DW_AT_GNU_dwo_id => {
+                unit.dwo_id = attr.udata_value().map(DwoId);
+            }
+            constants::DW_AT_producer => {
+                unit.producer = Some(attr.value());
+            }
+            _ => {}
+        }
+    }
+    Ok(unit)
+}
+
+/// Return the `str_offsets_base` for a unit without a `DW_AT_str_offsets_base`.
+///
+/// DWARF 5 units use the first set of entries in `.debug_str_offsets`, which
+/// follow its header. This also applies to split units in `.dwo` files, which
+/// do not have base attributes. GNU extensions use a base of 0.
+fn default_str_offsets_base<T: ReaderOffset>(encoding: Encoding) -> DebugStrOffsetsBase<T> {
+    if encoding.version >= 5 {
+        DebugStrOffsetsBase(T::from_u8(encoding.format.initial_length_size() + 4))
+    } else {
+        DebugStrOffsetsBase(T::from_u8(0))
+    }
+}
+
+impl<R: Reader> Unit<R> {
+    /// Construct a new `Unit` from the given compilation unit header.
+    #[inline]
+    pub fn new(dwarf: &Dwarf<R>, header: CompilationUnitHeader<R>) -> Result<Self> {
+        Self::new_internal(
+            dwarf,
+            UnitSectionOffset::DebugInfoOffset(header.offset()),
+            header.dwo_id(),
+            header.header(),
+            true,
+        )
+    }
+
+    /// Construct a new `Unit` from the given type unit header.
+    #[inline]
+    pub fn new_type_unit(dwarf: &Dwarf<R>, header: TypeUnitHeader<R>) -> Result<Self> {
+        Self::new_internal(
+            dwarf,
+            UnitSectionOffset::DebugTypesOffset(header.offset()),
+            None,
+            header.header(),
+            true,
+        )
+    }
+
+    /// Construct a new `Unit`, and parse its line program header if
+    /// `line_program` is true.
+    fn new_internal(
+        dwarf: &Dwarf<R>,
+        offset: UnitSectionOffset<R::Offset>,
+        dwo_id: Option<DwoId>,
+        header: UnitHeader<R>,
+        line_program: bool,
+    ) -> Result<Self> {
+        let abbreviations = header.abbreviations(&dwarf.debug_abbrev)?;
+        // Split units in DWARF 5 `.dwo` files do not have base attributes,
+        // and instead the bases skip the header of their section.
+        // The bases default to 0 for GNU extensions.
+        let initial_length_size = header.format().initial_length_size();
+        let str_offsets_base = default_str_offsets_base(header.encoding());
+        let lists_base = if dwarf.file_type == DwarfFileType::Dwo && header.version() >= 5 {
+            initial_length_size + 8
+        } else {
+            0
+        };
+        let mut unit = Unit {
+            offset,
+            header,
+            abbreviations,
+            name: None,
+            comp_dir: None,
+            language: None,
+            low_pc: 0,
+            high_pc: None,
+            str_offsets_base,
+            str_offsets_count: None,
+            addr_base: DebugAddrBase(R::Offset::from_u8(0)),
+            loclists_base: DebugLocListsBase(R::Offset::from_u8(lists_base)),
+            rnglists_base: DebugRngListsBase(R::Offset::from_u8(lists_base)),
+            dwo_name: None,
+            producer: None,
+            dwo_id,
+            line_program: None,
+        };
+        let attrs = {
+            let mut cursor = unit.header.entries(&unit.abbreviations);
+            cursor.next_dfs()?;
+            let root = cursor.current().ok_or(Error::MissingUnitDie)?;
+            extract_unit_attrs(root)?
+        };
+
+        unit.language = attrs.language;
+        if let Some(base) = attrs.str_offsets_base {
+            unit.str_offsets_base = base;
+        }
+        if unit.header.version() >= 5 {
+            // Parse the header once here so that `Dwarf::string_offset` can
+            // check indices without parsing it again.
+            let count = dwarf
+                .debug_str_offsets
+                .header(unit.header.format(), unit.str_offsets_base)
+                .map(|header| header.count());
+            unit.str_offsets_count = Some((unit.str_offsets_base, count));
+        }
+        if let Some(base) = attrs.addr_base {
+            unit.addr_base = base;
+        }
+        if let Some(base) = attrs.loclists_base {
+            unit.loclists_base = base;
+        }
+        if let Some(base) = attrs.rnglists_base {
+            unit.rnglists_base = base;
+        }
+        unit.dwo_name = attrs.dwo_name;
+        unit.producer = attrs.producer;
+        // Prefer the identifier in the DWARF 5 unit header.
+        if unit.dwo_id.is_none() {
+            unit.dwo_id = attrs.dwo_id;
+        }
+        // Address indices can only be resolved once `addr_base` is known.
+        // Split units use the `addr_base` of their skeleton unit, which
+        // isn't known yet, so they must use `copy_relocated_attributes`.
+        let address = |value| match value {
+            Some(AttributeValue::DebugAddrIndex(_)) if dwarf.file_type == DwarfFileType::Dwo => {
+                Ok(None)
+            }
+            Some(value) => dwarf.attr_address(&unit, value),
+            None => Ok(None),
+        };
+        let low_pc = address(attrs.low_pc)?;
+        let high_pc = address(attrs.high_pc)?;
+        if let Some(low_pc) = low_pc {
+            unit.low_pc = low_pc;
+            unit.high_pc = attrs
+                .size
+                .map(|size| low_pc.wrapping_add(size))
+                .or(high_pc);
+        }
+        unit.name = match attrs.name {
+            Some(val) => Some(dwarf.attr_string(&unit, val)?),
+            None => None,
+        };
+        unit.comp_dir = match attrs.comp_dir {
+            Some(val) => Some(dwarf.attr_string(&unit, val)?),
+            None => None,
+        };
+        unit.line_program = match attrs.line_program_offset {
+            Some(offset) if line_program => Some(dwarf.debug_line.program(
+                offset,
+                unit.header.address_size(),
+                unit.comp_dir.clone(),
+                unit.name.clone(),
+            )?),
+            _ => None,
+        };
+        Ok(unit)
+    }
+
+    /// Return the encoding parameters for this unit.
+    #[inline]
+    pub fn encoding(&self) -> Encoding {
+        self.header.encoding()
+    }
+
+    /// Navigate this unit's `DebuggingInformationEntry`s.
+    #[inline]
+    pub fn entries(&self) -> EntriesCursor<R> {
+        self.header.entries(&self.abbreviations)
+    }
+
+    /// Navigate this unit's `DebuggingInformationEntry`s
+    /// starting at the given offset.
+    #[inline]
+    pub fn entries_at_offset(&self, offset: UnitOffset<R::Offset>) -> Result<EntriesCursor<R>> {
+        self.header.entries_at_offset(&self.abbreviations, offset)
+    }
+
+    /// Navigate this unit's `DebuggingInformationEntry`s as a tree
+    /// starting at the given offset.
+    #[inline]
+    pub fn entries_tree(&self, offset: Option<UnitOffset<R::Offset>>) -> Result<EntriesTree<R>> {
+        self.header.entries_tree(&self.abbreviations, offset)
+    }
+
+    /// Read the raw data that defines the Debugging Information Entries.
+    #[inline]
+    pub fn entries_raw(
+        &self,
+        offset: Option<UnitOffset<R::Offset>>,
+    ) -> Result<EntriesRaw<'_, '_, R>> {
+        self.header.entries_raw(&self.abbreviations, offset)
+    }
+
+    /// Read the `DebuggingInformationEntry` at the given offset.
+    ///
+    /// This can be used to follow unit-local references such as `DW_FORM_ref4`.
+    #[inline]
+    pub fn entry(
+        &self,
+        offset: UnitOffset<R::Offset>,
+    ) -> Result<DebuggingInformationEntry<'_, '_, R>> {
+        self.header.entry(&self.abbreviations, offset)
+    }
+
+    /// Return the offset of the root `DebuggingInformationEntry` of this unit.
+    ///
+    /// The root entry is always the first entry after the unit header.
+    #[inline]
+    pub fn root_offset(&self) -> UnitOffset<R::Offset> {
+        UnitOffset(self.header.header_size())
+    }
+
+    /// Read the root `DebuggingInformationEntry` of this unit.
+    ///
+    /// This does not need to create a cursor and step to the first entry.
+    pub fn root(&self) -> Result<DebuggingInformationEntry<'_, '_, R>> {
+        match self.entry(self.root_offset()) {
+            Err(Error::NoEntryAtGivenOffset) => Err(Error::MissingUnitDie),
+            otherwise => otherwise,
+        }
+    }
+
+    /// Convert an offset relative to the start of this unit to an offset
+    /// relative to the start of the section containing this unit.
+    ///
+    /// This is a `DebugInfoOffset` for units in the `.debug_info` section,
+    /// and a `DebugTypesOffset` for units in the `.debug_types` section.
+    ///
+    /// This is equivalent to `UnitOffset::to_unit_section_offset`.
+    #[inline]
+    pub fn entry_global_offset(
+        &self,
+        offset: UnitOffset<R::Offset>,
+    ) -> UnitSectionOffset<R::Offset> {
+        offset.to_unit_section_offset(self)
+    }
+
+    /// Return the address range given by the `DW_AT_low_pc` and
+    /// `DW_AT_high_pc` attributes of the unit.
+    ///
+    /// Returns `None` if the unit does not have both attributes. Use
+    /// `Dwarf::unit_ranges` to also handle `DW_AT_ranges`.
+    #[inline]
+    pub fn pc_range(&self) -> Option<std::ops::Range<u64>> {
+        self.high_pc.map(|high_pc| self.low_pc..high_pc)
+    }
+
+    /// Return the `DW_AT_producer` attribute of the unit as a string.
+    ///
+    /// Returns `None` if the unit does not have a `DW_AT_producer` attribute.
+    pub fn producer_string(&self, dwarf: &Dwarf<R>) -> Result<Option<R>> {
+        match self.producer {
+            Some(ref producer) => dwarf.attr_string(self, producer.clone()).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Return an iterator over the rows of the unit's line program.
+    ///
+    /// This clones the `IncompleteLineProgram` in `line_program`, so it may be
+    /// called multiple times, and each call returns a new iterator starting at
+    /// the first row. Use `IncompleteLineProgram::sequences` or
+    /// `IncompleteLineProgram::complete` instead if the rows will be
+    /// searched repeatedly.
+    ///
+    /// Returns `None` if the unit does not have a `DW_AT_stmt_list` attribute.
+    pub fn line_rows(&self) -> Option<LineRows<R, IncompleteLineProgram<R>, R::Offset>> {
+        self.line_program
+            .as_ref()
+            .map(|program| program.clone().rows())
+    }
+
+    /// Copy attributes that are subject to relocation from another unit.
+    ///
+    /// This is intended to be used to copy attributes from a skeleton
+    /// compilation unit to the corresponding split compilation unit, since
+    /// the split unit relies on the skeleton unit for its addresses and bases.
+    pub fn copy_relocated_attributes(&mut self, other: &Unit<R>) {
+        self.low_pc = other.low_pc;
+        self.high_pc = other.high_pc;
+        self.addr_base = other.addr_base;
+        if self.header.version() < 5 {
+            // For the GNU split-dwarf extension, this is `DW_AT_GNU_ranges_base`.
+            self.rnglists_base = other.rnglists_base;
+        }
+    }
+}
+
+impl<T: ReaderOffset> UnitSectionOffset<T> {
+    /// Convert an offset to be relative to the start of the given unit,
+    /// instead of relative to the start of the section.
+    /// Returns `None` if the offset is not within the unit entries.
+    pub fn to_unit_offset<R>(&self, unit: &Unit<R>) -> Option<UnitOffset<T>>
+    where
+        R: Reader<Offset = T>,
+    {
+        let (offset, unit_offset) = match (self, unit.offset) {
+            (
+                UnitSectionOffset::DebugInfoOffset(offset),
+                UnitSectionOffset::DebugInfoOffset(unit_offset),
+            ) => (offset.0, unit_offset.0),
+            (
+                UnitSectionOffset::DebugTypesOffset(offset),
+                UnitSectionOffset::DebugTypesOffset(unit_offset),
+            ) => (offset.0, unit_offset.0),
+            _ => return None,
+        };
+        let offset = match offset.checked_sub(unit_offset) {
+            Some(offset) => UnitOffset(offset),
+            None => return None,
+        };
+        if !unit.header.is_valid_offset(offset) {
+            return None;
+        }
+        Some(offset)
+    }
+}
+
+impl<T: ReaderOffset> UnitOffset<T> {
+    /// Convert an offset to be relative to the start of the .debug_info section,
+    /// instead of relative to the start of the given compilation unit.
+    pub fn to_unit_section_offset<R>(&self, unit: &Unit<R>) -> UnitSectionOffset<T>
+    where
+        R: Reader<Offset = T>,
+    {
+        match unit.offset {
+            UnitSectionOffset::DebugInfoOffset(unit_offset) => {
+                UnitSectionOffset::DebugInfoOffset(DebugInfoOffset(unit_offset.0 + self.0))
+            }
+            UnitSectionOffset::DebugTypesOffset(unit_offset) => {
+                UnitSectionOffset::DebugTypesOffset(DebugTypesOffset(unit_offset.0 + self.0))
+            }
+        }
+    }
+}
+
+/// The location of a `DebuggingInformationEntry`.
+///
+/// Returned by `Dwarf::die_location`.
+#[derive(Debug)]
+pub enum DieLocation<R: Reader> {
+    /// A single location expression, which is valid for the whole lifetime
+    /// of the entry.
+    Expression(Expression<R>),
+    /// A location list, which gives a location expression for each range of
+    /// addresses.
+    List(LocListIter<R>),
+}
+
+/// An iterator for the address ranges of a `DebuggingInformationEntry`.
+///
+/// Returned by `Dwarf::die_ranges` and `Dwarf::unit_ranges`.
+#[derive(Debug)]
+pub struct RangeIter<R: Reader>(RangeIterInner<R>);
+
+#[derive(Debug)]
+enum RangeIterInner<R: Reader> {
+    Single(Option<Range>),
+    List(RngListIter<R>),
+}
+
+impl<R: Reader> Default for RangeIter<R> {
+    fn default() -> Self {
+        RangeIter(RangeIterInner::Single(None))
+    }
+}
+
+impl<R: Reader> RangeIter<R> {
+    /// Advance the iterator to the next range.
+    pub fn next(&mut self) -> Result<Option<Range>> {
+        match self.0 {
+            RangeIterInner::Single(ref mut range) => Ok(range.take()),
+            RangeIterInner::List(ref mut list) => list.next(),
+        }
+    }
+}
+
+impl<R: Reader> FallibleIterator for RangeIter<R> {
+    type Item = Range;
+    type Error = Error;
+
+    #[inline]
+    fn next(&mut self) -> ::std::result::Result<Option<Self::Item>, Self::Error> {
+        RangeIter::next(self)
+    }
+}
+
+/// An iterator over the debugging information entries with a given name.
+///
+/// Each item is the offset of the unit containing the entry, and the offset
+/// of the entry within that unit.
+///
+/// Returned by `Dwarf::find_symbol`.
+#[derive(Debug)]
+pub struct SymbolIter<'a, R: Reader> {
+    dwarf: &'a Dwarf<R>,
+    name: &'a [u8],
+    source: SymbolSource<R>,
+    // Matches that have been found but not yet returned, in reverse order.
+    #[allow(clippy::type_complexity)]
+    pending: Vec<(DebugInfoOffset<R::Offset>, UnitOffset<R::Offset>)>,
+}
+
+#[derive(Debug)]
+enum SymbolSource<R: Reader> {
+    Names(NameIndexIter<R>),
+    PubNames(PubNamesEntryIter<R>),
+    Units(CompilationUnitHeadersIter<R>),
+}
+
+impl<'a, R: Reader> SymbolIter<'a, R> {
+    /// Advance the iterator to the next matching entry.
+    #[allow(clippy::type_complexity)]
+    pub fn next(&mut self) -> Result<Option<(DebugInfoOffset<R::Offset>, UnitOffset<R::Offset>)>> {
+        loop {
+            if let Some(symbol) = self.pending.pop() {
+                return Ok(Some(symbol));
+            }
+            match self.source {
+                SymbolSource::Names(ref mut indexes) => {
+                    let index = match indexes.next()? {
+                        Some(index) => index,
+                        None => return Ok(None),
+                    };
+                    let name = match index.lookup(self.name, &self.dwarf.debug_str)? {
+                        Some(name) => name,
+                        None => continue,
+                    };
+                    let mut entries = index.entries(name.entry_offset())?;
+                    while let Some(entry) = entries.next()? {
+                        let die_offset = match entry.die_offset() {
+                            Some(die_offset) => die_offset,
+                            None => continue,
+                        };
+                        let unit_offset = match (index.entry_comp_unit(&entry)?, entry.type_unit())
+                        {
+                            (Some(unit_offset), _) => unit_offset,
+                            (None, Some(type_unit))
+                                if type_unit < u64::from(index.local_type_unit_count()) =>
+                            {
+                                index.local_type_unit(type_unit as u32)?
+                            }
+                            // Foreign type units are not in `.debug_info`.
+                            _ => continue,
+                        };
+                        self.pending.push((unit_offset, die_offset));
+                    }
+                    self.pending.reverse();
+                }
+                SymbolSource::PubNames(ref mut items) => {
+                    let entry = match items.next()? {
+                        Some(entry) => entry,
+                        None => return Ok(None),
+                    };
+                    if entry.name().to_slice()? == self.name {
+                        return Ok(Some((entry.unit_header_offset(), entry.die_offset())));
+                    }
+                }
+                SymbolSource::Units(ref mut units) => {
+                    let header = match units.next()? {
+                        Some(header) => header,
+                        None => return Ok(None),
+                    };
+                    let unit_offset = header.offset();
+                    let abbreviations = header.abbreviations(&self.dwarf.debug_abbrev)?;
+                    let mut str_offsets_base = default_str_offsets_base(header.encoding());
+                    let mut entries = header.entries_raw(&abbreviations, None)?;
+                    let mut root = true;
+                    while !entries.is_empty() {
+                        let offset = entries.next_offset();
+                        let abbrev = match entries.read_abbreviation()? {
+                            Some(abbrev) => abbrev,
+                            None => continue,
+                        };
+                        let mut name = None;
+                        for spec in abbrev.attributes() {
+                            let attr = entries.read_attribute(*spec)?;
+                            match (attr.name(), attr.value()) {
+                                (constants::DW_AT_name, value) => name = Some(value),
+                                (
+                                    constants::DW_AT_str_offsets_base,
+                                    AttributeValue::DebugStrOffsetsBase(base),
+                                ) if root => str_offsets_base = base,
+                                _ => {}
+                            }
+                        }
+                        root = false;
+                        let name = match name {
+                            Some(AttributeValue::DebugStrOffsetsIndex(index)) => {
+                                let offset = self.dwarf.debug_str_offsets.get_str_offset(
+                                    header.format(),
+                                    str_offsets_base,
+                                    index,
+                                )?;
+                                self.dwarf.debug_str.get_str(offset)?
+                            }
+                            Some(value) => self.dwarf.attr_section_string(value)?,
+                            None => continue,
+                        };
+                        if name.to_slice()? == self.name {
+                            self.pending.push((unit_offset, offset));
+                        }
+                    }
+                    self.pending.reverse();
+                }
+            }
+        }
+    }
+}
+
+impl<'a, R: Reader> FallibleIterator for SymbolIter<'a, R> {
+    type Item = (DebugInfoOffset<R::Offset>, UnitOffset<R::Offset>);
+    type Error = Error;
+
+    #[inline]
+    fn next(&mut self) -> ::std::result::Result<Option<Self::Item>, Self::Error> {
+        SymbolIter::next(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read::abbrev::tests::AbbrevSectionMethods;
+    use crate::read::{DebugRanges, EndianArcSlice, EndianSlice, RawLocListEntry, RawRngListEntry};
+    use crate::test_util::GimliSectionMethods;
+    use crate::vec::Vec;
+    use crate::{Endianity, Format, LittleEndian};
+    use test_assembler::{Endian, Label, LabelMaker, Section};
+
+    /// Append a compilation unit to `section`. The unit header is followed by
+    /// the entries written by `entries`, which must start with the root entry.
+    fn append_unit<F>(section: Section, format: Format, version: u16, entries: F) -> Section
+    where
+        F: FnOnce(Section) -> Section,
+    {
+        let length = Label::new();
+        let start = Label::new();
+        let end = Label::new();
+        let section = section
+            .initial_length(format, &length, &start)
+            .L16(version);
+        let section = if version >= 5 {
+            section
+                .D8(constants::DW_UT_compile.0)
+                .D8(8)
+                .word(format.word_size(), 0)
+        } else {
+            section.word(format.word_size(), 0).D8(8)
+        };
+        let section = entries(section).mark(&end);
+        length.set_const((&end - &start) as u64);
+        section
+    }
+
+    /// Return a `.debug_info` section containing a single 32-bit compilation
+    /// unit with the given entries.
+    fn info_section<F>(version: u16, entries: F) -> Vec<u8>
+    where
+        F: FnOnce(Section) -> Section,
+    {
+        append_unit(
+            Section::with_endian(Endian::Little),
+            Format::Dwarf32,
+            version,
+            entries,
+        )
+        .get_contents()
+        .unwrap()
+    }
+
+    /// Return a `Dwarf` containing the given `.debug_abbrev` and `.debug_info`
+    /// sections.
+    fn abbrev_info_dwarf<'a>(
+        abbrev_buf: &'a [u8],
+        info_buf: &'a [u8],
+    ) -> Dwarf<EndianSlice<'a, LittleEndian>> {
+        Dwarf {
+            debug_abbrev: DebugAbbrev::new(abbrev_buf, LittleEndian),
+            debug_info: DebugInfo::new(info_buf, LittleEndian),
+            ..Default::default()
+        }
+    }
+
+    /// Return the first unit in `.debug_info`.
+    fn first_unit<R: Reader>(dwarf: &Dwarf<R>) -> Unit<R> {
+        let header = dwarf.units().next().unwrap().unwrap();
+        dwarf.unit(header).unwrap()
+    }
+
+    /// Return the `.debug_abbrev` and `.debug_info` sections for a DWARF 4
+    /// unit with the given root attributes.
+    fn root_sections<F1, F2>(abbrev_attrs: F1, die_attrs: F2) -> (Vec<u8>, Vec<u8>)
+    where
+        F1: Fn(Section) -> Section,
+        F2: Fn(Section) -> Section,
+    {
+        let abbrev = Section::with_endian(Endian::Little).abbrev(
+            1,
+            constants::DW_TAG_compile_unit,
+            constants::DW_CHILDREN_no,
+        );
+        let abbrev_buf = abbrev_attrs(abbrev)
+            .abbrev_attr_null()
+            .abbrev_null()
+            .get_contents()
+            .unwrap();
+        let info_buf = info_section(4, |s| die_attrs(s.uleb(1)));
+        (abbrev_buf, info_buf)
+    }
+
+    fn entries_abbrev() -> Vec<u8> {
+        Section::with_endian(Endian::Little)
+            .abbrev(
+                1,
+                constants::DW_TAG_compile_unit,
+                constants::DW_CHILDREN_yes,
+            )
+            .abbrev_attr_null()
+            .abbrev(2, constants::DW_TAG_variable, constants::DW_CHILDREN_no)
+            .abbrev_attr(constants::DW_AT_type, constants::DW_FORM_ref_addr)
+            .abbrev_attr_null()
+            .abbrev(3, constants::DW_TAG_base_type, constants::DW_CHILDREN_no)
+            .abbrev_attr(constants::DW_AT_byte_size, constants::DW_FORM_data1)
+            .abbrev_attr_null()
+            .abbrev_null()
+            .get_contents()
+            .unwrap()
+    }
+
+    /// Return the `.debug_abbrev`, `.debug_info` and `.debug_line` sections
+    /// for a DWARF 4 unit with a line program.
+    fn line_program_sections() -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+        let abbrev_buf = Section::with_endian(Endian::Little)
+            .abbrev(1, constants::DW_TAG_compile_unit, constants::DW_CHILDREN_no)
+            .abbrev_attr(constants::DW_AT_stmt_list, constants::DW_FORM_sec_offset)
+            .abbrev_attr(constants::DW_AT_comp_dir, constants::DW_FORM_string)
+            .abbrev_attr_null()
+            .abbrev_null()
+            .get_contents()
+            .unwrap();
+
+        let info_buf = info_section(4, |s| s.uleb(1).L32(0).append_bytes(b"/comp_dir\0"));
+
+        let length = Label::new();
+        let start = Label::new();
+        let header_length = Label::new();
+        let header_start = Label::new();
+        let header_end = Label::new();
+        let end = Label::new();
+        let set_address = |section: Section, address: u64| {
+            section
+                .D8(0)
+                .uleb(9)
+                .D8(constants::DW_LNE_set_address.0)
+                .L64(address)
+        };
+        let end_sequence =
+            |section: Section| section.D8(0).uleb(1).D8(constants::DW_LNE_end_sequence.0);
+        let line = Section::with_endian(Endian::Little)
+            .L32(&length)
+            .mark(&start)
+            .L16(4)
+            .L32(&header_length)
+            .mark(&header_start)
+            // Minimum instruction length.
+            .D8(1)
+            // Maximum operations per instruction.
+            .D8(1)
+            // Default is_stmt.
+            .D8(1)
+            // Line base.
+            .D8(-5i8 as u8)
+            // Line range.
+            .D8(14)
+            // Opcode base.
+            .D8(13)
+            // Standard opcode lengths.
+            .append_bytes(&[0, 1, 1, 1, 1, 0, 0, 0, 1, 0, 0, 1])
+            // Include directories.
+            .append_bytes(b"include\0")
+            .D8(0)
+            // File names.
+            .append_bytes(b"a.c\0")
+            .uleb(0)
+            .uleb(0)
+            .uleb(0)
+            .append_bytes(b"b.h\0")
+            .uleb(1)
+            .uleb(0)
+            .uleb(0)
+            .D8(0)
+            .mark(&header_end);
+        // First sequence.
+        let line = set_address(line, 0x1000)
+            .D8(constants::DW_LNS_advance_line.0)
+            .sleb(9)
+            .D8(constants::DW_LNS_copy.0)
+            .D8(constants::DW_LNS_advance_pc.0)
+            .uleb(0x10)
+            .D8(constants::DW_LNS_set_column.0)
+            .uleb(4)
+            .D8(constants::DW_LNS_set_file.0)
+            .uleb(2)
+            .D8(constants::DW_LNS_advance_line.0)
+            .sleb(5)
+            .D8(constants::DW_LNS_copy.0)
+            .D8(constants::DW_LNS_advance_pc.0)
+            .uleb(0x10);
+        let line = end_sequence(line);
+        // Second sequence, at a lower address.
+        let line = set_address(line, 0x800)
+            .D8(constants::DW_LNS_advance_line.0)
+            .sleb(19)
+            .D8(constants::DW_LNS_copy.0)
+            .D8(constants::DW_LNS_advance_pc.0)
+            .uleb(0x8);
+        let line = end_sequence(line).mark(&end);
+        length.set_const((&end - &start) as u64);
+        header_length.set_const((&header_end - &header_start) as u64);
+        let line_buf = line.get_contents().unwrap();
+
+        (abbrev_buf, info_buf, line_buf)
+    }
+
+    /// Ensure that `Dwarf<R>` is covariant wrt R.
+    #[test]
+    fn test_dwarf_variance() {
+        /// This only needs to compile.
+        fn _f<'a: 'b, 'b, E: Endianity>(x: Dwarf<EndianSlice<'a, E>>) -> Dwarf<EndianSlice<'b, E>> {
+            x
+        }
+    }
+
+    /// Ensure that `Unit<R>` is covariant wrt R.
+    #[test]
+    fn test_dwarf_unit_variance() {
+        /// This only needs to compile.
+        fn _f<'a: 'b, 'b, E: Endianity>(x: Unit<EndianSlice<'a, E>>) -> Unit<EndianSlice<'b, E>> {
+            x
+        }
+    }
+
+    /// Ensure that `Dwarf<R>` and `Unit<R>` are `Send` and `Sync` if `R` is.
+    #[test]
+    fn test_send_sync() {
+        /// This only needs to compile.
+        fn _assert_send_sync<T: Send + Sync>() {}
+        fn _f<'a, E: Endianity + Send + Sync>() {
+            _assert_send_sync::<Dwarf<EndianSlice<'a, E>>>();
+            _assert_send_sync::<Unit<EndianSlice<'a, E>>>();
+            _assert_send_sync::<Dwarf<EndianArcSlice<E>>>();
+            _assert_send_sync::<Unit<EndianArcSlice<E>>>();
+        }
+    }
+
+    #[test]
+    fn test_dwarf_clone() {
+        let (abbrev_buf, info_buf) = root_sections(|s| s, |s| s);
+        let dwarf = Dwarf {
+            sup: Some(Arc::new(Dwarf::default())),
+            ..abbrev_info_dwarf(&abbrev_buf, &info_buf)
+        };
+        let clone = dwarf.clone();
+        let header = clone.units().next().unwrap().unwrap();
+        assert_eq!(header, dwarf.units().next().unwrap().unwrap());
+        assert!(clone.unit(header).is_ok());
+        // The supplementary file is shared.
+        assert!(Arc::ptr_eq(
+            dwarf.sup.as_ref().unwrap(),
+            clone.sup.as_ref().unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_format_error() {
+        let owned_dwarf =
+            Dwarf::load(|_| -> Result<_> { Ok(vec![1, 2]) }, |_| Ok(vec![1, 2])).unwrap();
+        let dwarf = owned_dwarf.borrow(|section| EndianSlice::new(&section, LittleEndian));
+
+        match dwarf.debug_str.get_str(DebugStrOffset(1)) {
+            Ok(r) => panic!("Unexpected str {:?}", r),
+            Err(e) => {
+                assert_eq!(
+                    dwarf.format_error(e),
+                    "Hit the end of input before it was expected at .debug_str+0x1"
+                );
+            }
+        }
+        match dwarf.debug_str_sup.get_str(DebugStrOffset(1)) {
+            Ok(r) => panic!("Unexpected str {:?}", r),
+            Err(e) => {
+                assert_eq!(
+                    dwarf.format_error(e),
+                    "Hit the end of input before it was expected at .debug_str(sup)+0x1"
+                );
+            }
+        }
+        assert_eq!(dwarf.format_error(Error::Io), Error::Io.description());
+    }
+
+    #[test]
+    fn test_die_from_offset() {
+        let abbrev_buf = entries_abbrev();
+
+        let base_type = Label::new();
+        let unit2 = Label::new();
+        let variable = Label::new();
+        let base_type_ref = Label::new();
+        let section = Section::with_endian(Endian::Little);
+        let section_start = section.start();
+        // First unit, containing a base type.
+        let info = append_unit(section, Format::Dwarf32, 4, |s| {
+            s.uleb(1).mark(&base_type).uleb(3).D8(4).D8(0)
+        });
+        // Second unit, containing a variable that refers to the base type.
+        let info = append_unit(info.mark(&unit2), Format::Dwarf32, 4, |s| {
+            s.uleb(1)
+                .mark(&variable)
+                .uleb(2)
+                .L32(&base_type_ref)
+                .D8(0)
+        });
+        base_type_ref.set_const((&base_type - &section_start) as u64);
+        let info_buf = info.get_contents().unwrap();
+        let base_type_offset = (&base_type - &section_start) as usize;
+        let unit2_offset = (&unit2 - &section_start) as usize;
+        let variable_offset = (&variable - &section_start) as usize;
+
+        let dwarf = abbrev_info_dwarf(&abbrev_buf, &info_buf);
+
+        // Follow the reference from the second unit to the first unit.
+        let (unit, offset) = dwarf
+            .die_from_offset(DebugInfoOffset(variable_offset))
+            .unwrap();
+        assert_eq!(
+            unit.offset,
+            UnitSectionOffset::DebugInfoOffset(DebugInfoOffset(unit2_offset))
+        );
+        let entry = unit.entry(offset).unwrap();
+        assert_eq!(entry.tag(), constants::DW_TAG_variable);
+        let target = match entry.attr_value(constants::DW_AT_type).unwrap() {
+            Some(AttributeValue::DebugInfoRef(target)) => target,
+            otherwise => panic!("Unexpected attribute value {:?}", otherwise),
+        };
+        assert_eq!(target, DebugInfoOffset(base_type_offset));
+
+        let (unit, offset) = dwarf.die_from_offset(target).unwrap();
+        assert_eq!(
+            unit.offset,
+            UnitSectionOffset::DebugInfoOffset(DebugInfoOffset(0))
+        );
+        assert_eq!(offset, UnitOffset(base_type_offset));
+        let entry = unit.entry(offset).unwrap();
+        assert_eq!(entry.tag(), constants::DW_TAG_base_type);
+        assert_eq!(
+            entry.attr_value(constants::DW_AT_byte_size).unwrap(),
+            Some(AttributeValue::Udata(4))
+        );
+
+        // Offsets within a unit header do not refer to an entry.
+        assert_eq!(
+            dwarf.die_from_offset(DebugInfoOffset(unit2_offset)).err(),
+            Some(Error::NoEntryAtGivenOffset)
+        );
+        assert_eq!(
+            dwarf.die_from_offset(DebugInfoOffset(info_buf.len())).err(),
+            Some(Error::NoEntryAtGivenOffset)
+        );
+
+        // The null entry terminating the children is not an entry.
+        assert_eq!(
+            unit.entry(UnitOffset(base_type_offset + 2)).err(),
+            Some(Error::NoEntryAtGivenOffset)
+        );
+        assert_eq!(
+            unit.entry(UnitOffset(0)).err(),
+            Some(Error::OffsetOutOfBounds)
+        );
+    }
+
+    #[test]
+    fn test_die_from_offset_line_program() {
+        let (abbrev_buf, info_buf, line_buf) = line_program_sections();
+        let dwarf = Dwarf {
+            debug_line: DebugLine::new(&line_buf, LittleEndian),
+            ..abbrev_info_dwarf(&abbrev_buf, &info_buf)
+        };
+
+        // Following a reference does not parse the line program.
+        let (unit, offset) = dwarf.die_from_offset(DebugInfoOffset(11)).unwrap();
+        assert_eq!(offset, UnitOffset(11));
+        assert!(unit.line_program.is_none());
+
+        // But imported units need it for their entries.
+        let (unit, _) = dwarf
+            .imported_unit(AttributeValue::DebugInfoRef(DebugInfoOffset(11)))
+            .unwrap()
+            .unwrap();
+        assert!(unit.line_program.is_some());
+    }
+
+    #[test]
+    fn test_entry_global_offset() {
+        let abbrev_buf = entries_abbrev();
+
+        let base_type1 = Label::new();
+        let unit2 = Label::new();
+        let base_type2 = Label::new();
+        let section = Section::with_endian(Endian::Little);
+        let section_start = section.start();
+        let info = append_unit(section, Format::Dwarf32, 4, |s| {
+            s.uleb(1).mark(&base_type1).uleb(3).D8(4).D8(0)
+        });
+        let info = append_unit(info.mark(&unit2), Format::Dwarf64, 4, |s| {
+            s.uleb(1).mark(&base_type2).uleb(3).D8(8).D8(0)
+        });
+        let info_buf = info.get_contents().unwrap();
+        let unit2_offset = (&unit2 - &section_start) as usize;
+        // The root entries immediately follow the 32-bit and 64-bit unit headers.
+        let expect = [
+            11,
+            (&base_type1 - &section_start) as usize,
+            unit2_offset + 23,
+            (&base_type2 - &section_start) as usize,
+        ];
+
+        let dwarf = abbrev_info_dwarf(&abbrev_buf, &info_buf);
+
+        let mut offsets = Vec::new();
+        let mut units = dwarf.units();
+        while let Some(header) = units.next().unwrap() {
+            let unit = dwarf.unit(header).unwrap();
+            let mut entries = unit.entries();
+            while let Some((_, entry)) = entries.next_dfs().unwrap() {
+                let offset = match unit.entry_global_offset(entry.offset()) {
+                    UnitSectionOffset::DebugInfoOffset(offset) => offset,
+                    otherwise => panic!("Unexpected offset {:?}", otherwise),
+                };
+                let (found, unit_offset) = dwarf.die_from_offset(offset).unwrap();
+                assert_eq!(found.offset, unit.offset);
+                assert_eq!(unit_offset, entry.offset());
+                offsets.push(offset.0);
+            }
+        }
+        assert_eq!(offsets, expect);
+    }
+
+    #[test]
+    fn test_type_die_from_offset() {
+        let abbrev_buf = entries_abbrev();
+
+        let section = Section::with_endian(Endian::Little);
+        let section_start = section.start();
+        let mut types = section;
+        let mut base_types = Vec::new();
+        let mut unit_starts = Vec::new();
+        for size in &[1, 2] {
+            let unit = Label::new();
+            let length = Label::new();
+            let start = Label::new();
+            let end = Label::new();
+            let base_type = Label::new();
+            let type_offset = Label::new();
+            types = types
+                .mark(&unit)
+                .L32(&length)
+                .mark(&start)
+                .L16(4)
+                .L32(0)
+                .D8(8)
+                .L64(*size)
+                .L32(&type_offset)
+                .uleb(1)
+                .mark(&base_type)
+                .uleb(3)
+                .D8(*size as u8)
+                .D8(0)
+                .mark(&end);
+            length.set_const((&end - &start) as u64);
+            type_offset.set_const((&base_type - &unit) as u64);
+            unit_starts.push(unit);
+            base_types.push(base_type);
+        }
+        let types_buf = types.get_contents().unwrap();
+
+        let dwarf = Dwarf {
+            debug_abbrev: DebugAbbrev::new(&abbrev_buf, LittleEndian),
+            debug_types: DebugTypes::new(&types_buf, LittleEndian),
+            ..Default::default()
+        };
+
+        for (i, (unit_start, base_type)) in unit_starts.iter().zip(&base_types).enumerate() {
+            let unit_offset = (unit_start - &section_start) as usize;
+            let offset = (base_type - &section_start) as usize;
+            let (unit, offset) = dwarf
+                .type_die_from_offset(DebugTypesOffset(offset))
+                .unwrap();
+            assert_eq!(
+                unit.offset,
+                UnitSectionOffset::DebugTypesOffset(DebugTypesOffset(unit_offset))
+            );
+            let entry = unit.entry(offset).unwrap();
+            assert_eq!(entry.tag(), constants::DW_TAG_base_type);
+            assert_eq!(
+                entry.attr_value(constants::DW_AT_byte_size).unwrap(),
+                Some(AttributeValue::Udata(i as u64 + 1))
+            );
+        }
+
+        assert_eq!(
+            dwarf.type_die_from_offset(DebugTypesOffset(0)).err(),
+            Some(Error::NoEntryAtGivenOffset)
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_units() {
+        use rayon::prelude::*;
+
+        let abbrev_buf = Section::with_endian(Endian::Little)
+            .abbrev(1, constants::DW_TAG_compile_unit, constants::DW_CHILDREN_no)
+            .abbrev_attr(constants::DW_AT_name, constants::DW_FORM_string)
+            .abbrev_attr_null()
+            .abbrev_null()
+            .get_contents()
+            .unwrap();
+        let mut info = Section::with_endian(Endian::Little);
+        for name in &["a.c", "b.c", "c.c"] {
+            info = append_unit(info, Format::Dwarf32, 4, |s| {
+                s.uleb(1).append_bytes(name.as_bytes()).D8(0)
+            });
+        }
+        // A truncated unit header.
+        let info_buf = info.L32(0x100).L16(4).get_contents().unwrap();
+        let dwarf = abbrev_info_dwarf(&abbrev_buf, &info_buf);
+
+        let units: Vec<_> = dwarf
+            .par_units()
+            .map(|unit| unit.map(|unit| unit.name.map(|name| name.slice())))
+            .collect();
+        assert_eq!(units.len(), 4);
+        assert_eq!(units[0], Ok(Some(&b"a.c"[..])));
+        assert_eq!(units[1], Ok(Some(&b"b.c"[..])));
+        assert_eq!(units[2], Ok(Some(&b"c.c"[..])));
+        match units[3] {
+            Err(Error::UnexpectedEof(_)) => {}
+            ref otherwise => panic!("Unexpected result: {:?}", otherwise),
+        }
+    }
+
+    fn unit_ranges<F1, F2>(abbrev_attrs: F1, die_attrs: F2, debug_addr: &[u8]) -> Result<Vec<Range>>
+    where
+        F1: Fn(Section) -> Section,
+        F2: Fn(Section) -> Section,
+    {
+        let (abbrev_buf, info_buf) = root_sections(abbrev_attrs, die_attrs);
+        let dwarf = Dwarf {
+            debug_addr: DebugAddr::from(EndianSlice::new(debug_addr, LittleEndian)),
+            ..abbrev_info_dwarf(&abbrev_buf, &info_buf)
+        };
+        let header = dwarf.units().next()?.unwrap();
+        let unit = dwarf.unit(header)?;
+        dwarf.unit_ranges(&unit)?.collect()
+    }
+
+    #[test]
+    fn test_unit_ranges_high_pc_address() {
+        let ranges = unit_ranges(
+            |s| {
+                s.abbrev_attr(constants::DW_AT_low_pc, constants::DW_FORM_addr)
+                    .abbrev_attr(constants::DW_AT_high_pc, constants::DW_FORM_addr)
+            },
+            |s| s.L64(0x1000).L64(0x1100),
+            &[],
+        );
+        assert_eq!(
+            ranges,
+            Ok(vec![Range {
+                begin: 0x1000,
+                end: 0x1100
+            }])
+        );
+    }
+
+    #[test]
+    fn test_unit_ranges_high_pc_offset() {
+        let ranges = unit_ranges(
+            |s| {
+                s.abbrev_attr(constants::DW_AT_low_pc, constants::DW_FORM_addr)
+                    .abbrev_attr(constants::DW_AT_high_pc, constants::DW_FORM_data4)
+            },
+            |s| s.L64(0x1000).L32(0x100),
+            &[],
+        );
+        assert_eq!(
+            ranges,
+            Ok(vec![Range {
+                begin: 0x1000,
+                end: 0x1100
+            }])
+        );
+    }
+
+    #[test]
+    fn test_unit_ranges_addrx() {
+        let debug_addr = Section::with_endian(Endian::Little)
+            .L64(0)
+            .L64(0x2000)
+            .L64(0x2400)
+            .get_contents()
+            .unwrap();
+        let ranges = unit_ranges(
+            |s| {
+                s.abbrev_attr(constants::DW_AT_low_pc, constants::DW_FORM_addrx1)
+                    .abbrev_attr(constants::DW_AT_high_pc, constants::DW_FORM_addrx1)
+            },
+            |s| s.D8(1).D8(2),
+            &debug_addr,
+        );
+        assert_eq!(
+            ranges,
+            Ok(vec![Range {
+                begin: 0x2000,
+                end: 0x2400
+            }])
+        );
+
+        let ranges = unit_ranges(
+            |s| {
+                s.abbrev_attr(constants::DW_AT_low_pc, constants::DW_FORM_addrx1)
+                    .abbrev_attr(constants::DW_AT_high_pc, constants::DW_FORM_udata)
+            },
+            |s| s.D8(1).uleb(0x10),
+            &debug_addr,
+        );
+        assert_eq!(
+            ranges,
+            Ok(vec![Range {
+                begin: 0x2000,
+                end: 0x2010
+            }])
+        );
+    }
+
+    #[test]
+    fn test_unit_ranges_none() {
+        let ranges = unit_ranges(
+            |s| s.abbrev_attr(constants::DW_AT_name, constants::DW_FORM_string),
+            |s| s.append_bytes(b"a.c\0"),
+            &[],
+        );
+        assert_eq!(ranges, Ok(vec![]));
+
+        let ranges = unit_ranges(
+            |s| s.abbrev_attr(constants::DW_AT_low_pc, constants::DW_FORM_addr),
+            |s| s.L64(0x1000),
+            &[],
+        );
+        assert_eq!(ranges, Ok(vec![]));
+        // `DW_AT_low_pc` forms that aren't addresses are ignored.
+        let ranges = unit_ranges(
+            |s| {
+                s.abbrev_attr(constants::DW_AT_low_pc, constants::DW_FORM_udata)
+                    .abbrev_attr(constants::DW_AT_high_pc, constants::DW_FORM_udata)
+            },
+            |s| s.uleb(0x1000).uleb(0x100),
+            &[],
+        );
+        assert_eq!(ranges, Ok(vec![]));
+    }
+
+    #[test]
+    fn test_unit_pc_range() {
+        fn pc_range<F1, F2>(
+            abbrev_attrs: F1,
+            die_attrs: F2,
+            debug_addr: &[u8],
+        ) -> Option<std::ops::Range<u64>>
+        where
+            F1: Fn(Section) -> Section,
+            F2: Fn(Section) -> Section,
+        {
+            let (abbrev_buf, info_buf) = root_sections(abbrev_attrs, die_attrs);
+            let dwarf = Dwarf {
+                debug_addr: DebugAddr::from(EndianSlice::new(debug_addr, LittleEndian)),
+                ..abbrev_info_dwarf(&abbrev_buf, &info_buf)
+            };
+            let unit = first_unit(&dwarf);
+            assert_eq!(unit.high_pc, unit.pc_range().map(|range| range.end));
+            unit.pc_range()
+        }
+
+        let range = pc_range(
+            |s| {
+                s.abbrev_attr(constants::DW_AT_low_pc, constants::DW_FORM_addr)
+                    .abbrev_attr(constants::DW_AT_high_pc, constants::DW_FORM_addr)
+            },
+            |s| s.L64(0x1000).L64(0x1100),
+            &[],
+        );
+        assert_eq!(range, Some(0x1000..0x1100));
+
+        let range = pc_range(
+            |s| {
+                s.abbrev_attr(constants::DW_AT_high_pc, constants::DW_FORM_data4)
+                    .abbrev_attr(constants::DW_AT_low_pc, constants::DW_FORM_addr)
+            },
+            |s| s.L32(0x100).L64(0x1000),
+            &[],
+        );
+        assert_eq!(range, Some(0x1000..0x1100));
+
+        let range = pc_range(
+            |s| s.abbrev_attr(constants::DW_AT_high_pc, constants::DW_FORM_data4),
+            |s| s.L32(0x100),
+            &[],
+        );
+        assert_eq!(range, None);
+
+        let range = pc_range(
+            |s| s.abbrev_attr(constants::DW_AT_low_pc, constants::DW_FORM_addr),
+            |s| s.L64(0x1000),
+            &[],
+        );
+        assert_eq!(range, None);
+
+        // The index is resolved using `DW_AT_addr_base`, regardless of
+        // the order of the attributes.
+        let debug_addr = Section::with_endian(Endian::Little)
+            .L64(0)
+            .L64(0x2000)
+            .L64(0x2400)
+            .get_contents()
+            .unwrap();
+        let range = pc_range(
+            |s| {
+                s.abbrev_attr(constants::DW_AT_low_pc, constants::DW_FORM_addrx1)
+                    .abbrev_attr(constants::DW_AT_high_pc, constants::DW_FORM_data4)
+                    .abbrev_attr(constants::DW_AT_addr_base, constants::DW_FORM_sec_offset)
+            },
+            |s| s.D8(0).L32(0x100).L32(8),
+            &debug_addr,
+        );
+        assert_eq!(range, Some(0x2000..0x2100));
+    }
+
+    #[test]
+    fn test_unit_root() {
+        let (abbrev_buf, info_buf) = root_sections(
+            |s| {
+                s.abbrev_attr(constants::DW_AT_name, constants::DW_FORM_string)
+                    .abbrev_attr(constants::DW_AT_producer, constants::DW_FORM_string)
+            },
+            |s| s.append_bytes(b"a.c\0").append_bytes(b"cc\0"),
+        );
+        let dwarf = abbrev_info_dwarf(&abbrev_buf, &info_buf);
+        let unit = first_unit(&dwarf);
+        assert_eq!(unit.root_offset(), UnitOffset(11));
+        assert_eq!(unit.language, None);
+
+        let root = unit.root().unwrap();
+        assert_eq!(root.offset(), unit.root_offset());
+        assert_eq!(root.tag(), constants::DW_TAG_compile_unit);
+        assert_eq!(
+            root.attr_value(constants::DW_AT_producer).unwrap(),
+            Some(AttributeValue::String(EndianSlice::new(
+                b"cc",
+                LittleEndian
+            )))
+        );
+    }
+
+    #[test]
+    fn test_unit_language() {
+        let (abbrev_buf, info_buf) = root_sections(
+            |s| s.abbrev_attr(constants::DW_AT_language, constants::DW_FORM_data2),
+            |s| s.L16(constants::DW_LANG_Rust.0),
+        );
+        let dwarf = abbrev_info_dwarf(&abbrev_buf, &info_buf);
+        let unit = first_unit(&dwarf);
+        assert_eq!(unit.language, Some(constants::DW_LANG_Rust));
+    }
+
+    #[test]
+    fn test_unit_dwo() {
+        let (abbrev_buf, info_buf) = root_sections(
+            |s| {
+                s.abbrev_attr(constants::DW_AT_GNU_dwo_name, constants::DW_FORM_string)
+                    .abbrev_attr(constants::DW_AT_GNU_dwo_id, constants::DW_FORM_data8)
+            },
+            |s| s.append_bytes(b"a.dwo\0").L64(0x1234),
+        );
+        let dwarf = abbrev_info_dwarf(&abbrev_buf, &info_buf);
+        let unit = first_unit(&dwarf);
+        assert_eq!(unit.dwo_id, Some(DwoId(0x1234)));
+        let dwo_name = dwarf.attr_string(&unit, unit.dwo_name.unwrap()).unwrap();
+        assert_eq!(dwo_name, EndianSlice::new(b"a.dwo", LittleEndian));
+
+        // A DWARF 5 skeleton unit, where the header identifier takes precedence.
+        let abbrev_buf = Section::with_endian(Endian::Little)
+            .abbrev(
+                1,
+                constants::DW_TAG_skeleton_unit,
+                constants::DW_CHILDREN_no,
+            )
+            .abbrev_attr(constants::DW_AT_dwo_name, constants::DW_FORM_string)
+            .abbrev_attr(constants::DW_AT_GNU_dwo_id, constants::DW_FORM_data8)
+            .abbrev_attr_null()
+            .abbrev_null()
+            .get_contents()
+            .unwrap();
+        let length = Label::new();
+        let start = Label::new();
+        let end = Label::new();
+        let info = Section::with_endian(Endian::Little)
+            .L32(&length)
+            .mark(&start)
+            .L16(5)
+            .D8(constants::DW_UT_skeleton.0)
+            .D8(8)
+            .L32(0)
+            .L64(0x5678)
+            .uleb(1)
+            .append_bytes(b"b.dwo\0")
+            .L64(0x1234)
+            .mark(&end);
+        length.set_const((&end - &start) as u64);
+        let info_buf = info.get_contents().unwrap();
+        let dwarf = abbrev_info_dwarf(&abbrev_buf, &info_buf);
+        let unit = first_unit(&dwarf);
+        assert_eq!(unit.dwo_id, Some(DwoId(0x5678)));
+        assert_eq!(
+            unit.dwo_name,
+            Some(AttributeValue::String(EndianSlice::new(
+                b"b.dwo",
+                LittleEndian
+            )))
+        );
+    }
+
+    #[test]
+    fn test_sup_attr_string() {
+        let (abbrev_buf, info_buf) = root_sections(
+            |s| s.abbrev_attr(constants::DW_AT_name, constants::DW_FORM_strp_sup),
+            |s| s.L32(4),
+        );
+        let debug_str_sup = b"bad\0bad.c\0";
+        let sup_debug_str = b"sup\0sup.c\0";
+
+        let mut dwarf = Dwarf {
+            debug_str_sup: DebugStr::new(debug_str_sup, LittleEndian),
+            ..abbrev_info_dwarf(&abbrev_buf, &info_buf)
+        };
+        let unit = first_unit(&dwarf);
+        assert_eq!(unit.name, Some(EndianSlice::new(b"bad.c", LittleEndian)));
+
+        dwarf.sup = Some(Arc::new(Dwarf {
+            debug_str: DebugStr::new(sup_debug_str, LittleEndian),
+            ..Default::default()
+        }));
+        let unit = first_unit(&dwarf);
+        assert_eq!(unit.name, Some(EndianSlice::new(b"sup.c", LittleEndian)));
+    }
+
+    #[test]
+    fn test_sup_die_from_offset() {
+        let (sup_abbrev_buf, sup_info_buf) = root_sections(
+            |s| s.abbrev_attr(constants::DW_AT_byte_size, constants::DW_FORM_data1),
+            |s| s.D8(4),
+        );
+        let (abbrev_buf, info_buf) = root_sections(
+            |s| s.abbrev_attr(constants::DW_AT_type, constants::DW_FORM_ref_sup4),
+            |s| s.L32(11),
+        );
+
+        let mut dwarf = abbrev_info_dwarf(&abbrev_buf, &info_buf);
+        let unit = first_unit(&dwarf);
+        let target = match unit.root().unwrap().attr_value(constants::DW_AT_type) {
+            Ok(Some(AttributeValue::DebugInfoRefSup(target))) => target,
+            otherwise => panic!("Unexpected attribute value {:?}", otherwise),
+        };
+        assert_eq!(target, DebugInfoOffset(11));
+        assert_eq!(
+            dwarf.sup_die_from_offset(target).err(),
+            Some(Error::MissingSupplementaryFile)
+        );
+
+        dwarf.sup = Some(Arc::new(abbrev_info_dwarf(&sup_abbrev_buf, &sup_info_buf)));
+        let (sup_unit, offset) = dwarf.sup_die_from_offset(target).unwrap();
+        assert_eq!(offset, UnitOffset(11));
+        let mut entries = sup_unit.entries_at_offset(offset).unwrap();
+        let (_, entry) = entries.next_dfs().unwrap().unwrap();
+        assert_eq!(entry.tag(), constants::DW_TAG_compile_unit);
+        assert_eq!(
+            entry.attr_value(constants::DW_AT_byte_size).unwrap(),
+            Some(AttributeValue::Udata(4))
+        );
+        assert_eq!(
+            dwarf.sup_die_from_offset(DebugInfoOffset(0)).err(),
+            Some(Error::NoEntryAtGivenOffset)
+        );
+    }
+
+    #[test]
+    fn test_unit_producer() {
+        let (abbrev_buf, info_buf) = root_sections(
+            |s| s.abbrev_attr(constants::DW_AT_producer, constants::DW_FORM_strp),
+            |s| s.L32(4),
+        );
+        let debug_str = b"a.c\0clang version 15.0.0\0";
+
+        let dwarf = Dwarf {
+            debug_str: DebugStr::new(debug_str, LittleEndian),
+            ..abbrev_info_dwarf(&abbrev_buf, &info_buf)
+        };
+        let unit = first_unit(&dwarf);
+        assert_eq!(
+            unit.producer,
+            Some(AttributeValue::DebugStrRef(DebugStrOffset(4)))
+        );
+        assert_eq!(
+            unit.producer_string(&dwarf),
+            Ok(Some(EndianSlice::new(
+                b"clang version 15.0.0",
+                LittleEndian
+            )))
+        );
+
+        let (abbrev_buf, info_buf) = root_sections(|s| s, |s| s);
+        let dwarf = abbrev_info_dwarf(&abbrev_buf, &info_buf);
+        let unit = first_unit(&dwarf);
+        assert_eq!(unit.producer, None);
+        assert_eq!(unit.producer_string(&dwarf), Ok(None));
+    }
+
+    #[test]
+    fn test_imported_unit() {
+        let abbrev_buf = Section::with_endian(Endian::Little)
+            .abbrev(1, constants::DW_TAG_partial_unit, constants::DW_CHILDREN_no)
+            .abbrev_attr_null()
+            .abbrev(
+                2,
+                constants::DW_TAG_compile_unit,
+                constants::DW_CHILDREN_yes,
+            )
+            .abbrev_attr_null()
+            .abbrev(
+                3,
+                constants::DW_TAG_imported_unit,
+                constants::DW_CHILDREN_no,
+            )
+            .abbrev_attr(constants::DW_AT_import, constants::DW_FORM_ref_addr)
+            .abbrev_attr_null()
+            .abbrev_null()
+            .get_contents()
+            .unwrap();
+
+        let mut info = Section::with_endian(Endian::Little);
+        for die in 1..3 {
+            info = append_unit(info, Format::Dwarf32, 4, |s| {
+                if die == 2 {
+                    s.uleb(die).uleb(3).L32(0x0b).uleb(0)
+                } else {
+                    s.uleb(die)
+                }
+            });
+        }
+        let info_buf = info.get_contents().unwrap();
+
+        let dwarf = abbrev_info_dwarf(&abbrev_buf, &info_buf);
+        let header = dwarf.units().nth(1).unwrap().unwrap();
+        let unit = dwarf.unit(header).unwrap();
+        let mut entries = unit.entries();
+        entries.next_dfs().unwrap();
+        let (_, entry) = entries.next_dfs().unwrap().unwrap();
+        assert_eq!(entry.tag(), constants::DW_TAG_imported_unit);
+        let import = entry.attr_value(constants::DW_AT_import).unwrap().unwrap();
+
+        let (imported, offset) = dwarf.imported_unit(import).unwrap().unwrap();
+        assert_eq!(
+            imported.offset,
+            UnitSectionOffset::DebugInfoOffset(DebugInfoOffset(0))
+        );
+        let root = imported.entry(offset).unwrap();
+        assert_eq!(root.tag(), constants::DW_TAG_partial_unit);
+
+        assert!(dwarf
+            .imported_unit(AttributeValue::UnitRef(UnitOffset(0x0b)))
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            dwarf
+                .imported_unit(AttributeValue::DebugInfoRefSup(DebugInfoOffset(0x0b)))
+                .err(),
+            Some(Error::MissingSupplementaryFile)
+        );
+    }
+
+    #[test]
+    fn test_resolve_attribute() {
+        #[rustfmt::skip]
+        let abbrev_buf = Section::with_endian(Endian::Little)
+            .abbrev(1, constants::DW_TAG_compile_unit, constants::DW_CHILDREN_yes)
+                .abbrev_attr_null()
+            .abbrev(2, constants::DW_TAG_subprogram, constants::DW_CHILDREN_no)
+                .abbrev_attr(constants::DW_AT_name, constants::DW_FORM_string)
+                .abbrev_attr_null()
+            .abbrev(3, constants::DW_TAG_subprogram, constants::DW_CHILDREN_no)
+                .abbrev_attr(constants::DW_AT_abstract_origin, constants::DW_FORM_ref4)
+                .abbrev_attr_null()
+            .abbrev(4, constants::DW_TAG_inlined_subroutine, constants::DW_CHILDREN_no)
+                .abbrev_attr(constants::DW_AT_specification, constants::DW_FORM_ref_addr)
+                .abbrev_attr_null()
+            .abbrev_null()
+            .get_contents()
+            .unwrap();
+        let (_, info_buf) = root_sections(
+            |s| s,
+            |s| {
+                s.uleb(2)
+                    .append_bytes(b"foo\0")
+                    // References the entry at offset 12.
+                    .uleb(3)
+                    .L32(12)
+                    // References the entry at offset 17.
+                    .uleb(4)
+                    .L32(17)
+                    // References itself.
+                    .uleb(3)
+                    .L32(27)
+                    .uleb(0)
+            },
+        );
+        let dwarf = abbrev_info_dwarf(&abbrev_buf, &info_buf);
+        let unit = first_unit(&dwarf);
+        let name = AttributeValue::String(EndianSlice::new(b"foo", LittleEndian));
+
+        for &offset in &[12, 17, 22] {
+            let entry = unit.entry(UnitOffset(offset)).unwrap();
+            match dwarf.resolve_attribute(&unit, &entry, constants::DW_AT_name) {
+                // The `DW_FORM_ref_addr` reference is to the same unit.
+                Ok(Some((None, value))) => assert_eq!(value, name),
+                otherwise => panic!("Unexpected result: {:?}", otherwise),
+            }
+            assert!(dwarf
+                .resolve_attribute(&unit, &entry, constants::DW_AT_decl_line)
+                .unwrap()
+                .is_none());
+        }
+
+        let entry = unit.entry(UnitOffset(27)).unwrap();
+        assert!(dwarf
+            .resolve_attribute(&unit, &entry, constants::DW_AT_name)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_resolve_attribute_other_unit() {
+        #[rustfmt::skip]
+        let abbrev_buf = Section::with_endian(Endian::Little)
+            .abbrev(1, constants::DW_TAG_compile_unit, constants::DW_CHILDREN_yes)
+                .abbrev_attr_null()
+            .abbrev(2, constants::DW_TAG_subprogram, constants::DW_CHILDREN_no)
+                .abbrev_attr(constants::DW_AT_name, constants::DW_FORM_string)
+                .abbrev_attr(constants::DW_AT_type, constants::DW_FORM_ref4)
+                .abbrev_attr_null()
+            .abbrev(3, constants::DW_TAG_inlined_subroutine, constants::DW_CHILDREN_no)
+                .abbrev_attr(constants::DW_AT_abstract_origin, constants::DW_FORM_ref_addr)
+                .abbrev_attr_null()
+            .abbrev_null()
+            .get_contents()
+            .unwrap();
+
+        let mut info = Section::with_endian(Endian::Little);
+        for die in 2..4 {
+            info = append_unit(info, Format::Dwarf32, 4, |s| {
+                let s = s.uleb(1);
+                let s = if die == 2 {
+                    // The subprogram is at offset 12, and its type refers to itself.
+                    s.uleb(2).append_bytes(b"foo\0").L32(12)
+                } else {
+                    // References the subprogram in the first unit.
+                    s.uleb(3).L32(12)
+                };
+                s.uleb(0)
+            });
+        }
+        let info_buf = info.get_contents().unwrap();
+
+        let dwarf = abbrev_info_dwarf(&abbrev_buf, &info_buf);
+        let header = dwarf.units().nth(1).unwrap().unwrap();
+        let unit = dwarf.unit(header).unwrap();
+        let entry = unit.entry(UnitOffset(12)).unwrap();
+        assert_eq!(entry.tag(), constants::DW_TAG_inlined_subroutine);
+
+        let (other_unit, value) = dwarf
+            .resolve_attribute(&unit, &entry, constants::DW_AT_type)
+            .unwrap()
+            .unwrap();
+        let other_unit = other_unit.unwrap();
+        assert_eq!(
+            other_unit.offset,
+            UnitSectionOffset::DebugInfoOffset(DebugInfoOffset(0))
+        );
+        assert_eq!(value, AttributeValue::UnitRef(UnitOffset(12)));
+        // The reference is relative to the unit that the value was found in.
+        let offset = match value {
+            AttributeValue::UnitRef(offset) => offset,
+            _ => unreachable!(),
+        };
+        let target = other_unit.entry(offset).unwrap();
+        assert_eq!(target.tag(), constants::DW_TAG_subprogram);
+    }
+
+    #[test]
+    fn test_inlined_frames() {
+        let (_, _, line_buf) = line_program_sections();
+        #[rustfmt::skip]
+        let abbrev_buf = Section::with_endian(Endian::Little)
+            .abbrev(1, constants::DW_TAG_compile_unit, constants::DW_CHILDREN_yes)
+                .abbrev_attr(constants::DW_AT_stmt_list, constants::DW_FORM_sec_offset)
+                .abbrev_attr_null()
+            .abbrev(2, constants::DW_TAG_subprogram, constants::DW_CHILDREN_no)
+                .abbrev_attr(constants::DW_AT_name, constants::DW_FORM_string)
+                .abbrev_attr_null()
+            .abbrev(3, constants::DW_TAG_subprogram, constants::DW_CHILDREN_yes)
+                .abbrev_attr(constants::DW_AT_low_pc, constants::DW_FORM_addr)
+                .abbrev_attr(constants::DW_AT_high_pc, constants::DW_FORM_udata)
+                .abbrev_attr_null()
+            .abbrev(4, constants::DW_TAG_inlined_subroutine, constants::DW_CHILDREN_yes)
+                .abbrev_attr(constants::DW_AT_abstract_origin, constants::DW_FORM_ref4)
+                .abbrev_attr(constants::DW_AT_low_pc, constants::DW_FORM_addr)
+                .abbrev_attr(constants::DW_AT_high_pc, constants::DW_FORM_udata)
+                .abbrev_attr(constants::DW_AT_call_file, constants::DW_FORM_udata)
+                .abbrev_attr(constants::DW_AT_call_line, constants::DW_FORM_udata)
+                .abbrev_attr(constants::DW_AT_call_column, constants::DW_FORM_udata)
+                .abbrev_attr_null()
+            .abbrev(5, constants::DW_TAG_subprogram, constants::DW_CHILDREN_no)
+                .abbrev_attr(constants::DW_AT_low_pc, constants::DW_FORM_udata)
+                .abbrev_attr_null()
+            .abbrev(6, constants::DW_TAG_structure_type, constants::DW_CHILDREN_yes)
+                .abbrev_attr_null()
+            .abbrev_null()
+            .get_contents()
+            .unwrap();
+        let (_, info_buf) = root_sections(
+            |s| s,
+            |s| {
+                s.L32(0)
+                    // Offset 16: the abstract origin.
+                    .uleb(2)
+                    .append_bytes(b"foo\0")
+                    // Offset 21: a function with a `DW_AT_low_pc` that is not an address.
+                    .uleb(5)
+                    .uleb(0x1000)
+                    // Offset 24: the function containing the inlined calls.
+                    .uleb(3)
+                    .L64(0x1000)
+                    .uleb(0x100)
+                    // Offset 35: the outer inlined call.
+                    .uleb(4)
+                    .L32(16)
+                    .L64(0x1010)
+                    .uleb(0x40)
+                    .uleb(1)
+                    .uleb(10)
+                    .uleb(2)
+                    // Offset 52: the inner inlined call.
+                    .uleb(4)
+                    .L32(16)
+                    .L64(0x1020)
+                    .uleb(0x10)
+                    .uleb(2)
+                    .uleb(20)
+                    .uleb(4)
+                    .uleb(0)
+                    .uleb(0)
+                    .uleb(0)
+                    // Offset 72: types are not searched for functions.
+                    .uleb(6)
+                    .uleb(3)
+                    .L64(0x2000)
+                    .uleb(0x100)
+                    .uleb(4)
+                    .L32(16)
+                    .L64(0x2000)
+                    .uleb(0x10)
+                    .uleb(1)
+                    .uleb(30)
+                    .uleb(2)
+                    .uleb(0)
+                    .uleb(0)
+                    .uleb(0)
+            },
+        );
+        let dwarf = Dwarf {
+            debug_line: DebugLine::new(&line_buf, LittleEndian),
+            ..abbrev_info_dwarf(&abbrev_buf, &info_buf)
+        };
+        let unit = first_unit(&dwarf);
+        let frame = |offset, call_file: &'static [u8], call_line, call_column| InlineFrame {
+            offset: UnitOffset(offset),
+            name: Some(EndianSlice::new(b"foo", LittleEndian)),
+            call_file: Some(EndianSlice::new(call_file, LittleEndian)),
+            call_line: Some(call_line),
+            call_column: Some(call_column),
+        };
+
+        assert_eq!(
+            dwarf.inlined_frames(&unit, 0x1028),
+            Ok(vec![frame(35, b"a.c", 10, 2), frame(52, b"b.h", 20, 4)])
+        );
+        assert_eq!(
+            dwarf.inlined_frames(&unit, 0x1040),
+            Ok(vec![frame(35, b"a.c", 10, 2)])
+        );
+        assert_eq!(dwarf.inlined_frames(&unit, 0x1080), Ok(vec![]));
+        assert_eq!(dwarf.inlined_frames(&unit, 0x2000), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_inlined_frames_other_unit() {
+        #[rustfmt::skip]
+        let abbrev_buf = Section::with_endian(Endian::Little)
+            .abbrev(1, constants::DW_TAG_compile_unit, constants::DW_CHILDREN_yes)
+                .abbrev_attr(constants::DW_AT_str_offsets_base, constants::DW_FORM_sec_offset)
+                .abbrev_attr_null()
+            .abbrev(2, constants::DW_TAG_subprogram, constants::DW_CHILDREN_no)
+                .abbrev_attr(constants::DW_AT_name, constants::DW_FORM_strx1)
+                .abbrev_attr_null()
+            .abbrev(3, constants::DW_TAG_subprogram, constants::DW_CHILDREN_yes)
+                .abbrev_attr(constants::DW_AT_low_pc, constants::DW_FORM_addr)
+                .abbrev_attr(constants::DW_AT_high_pc, constants::DW_FORM_udata)
+                .abbrev_attr_null()
+            .abbrev(4, constants::DW_TAG_inlined_subroutine, constants::DW_CHILDREN_no)
+                .abbrev_attr(constants::DW_AT_abstract_origin, constants::DW_FORM_ref_addr)
+                .abbrev_attr(constants::DW_AT_low_pc, constants::DW_FORM_addr)
+                .abbrev_attr(constants::DW_AT_high_pc, constants::DW_FORM_udata)
+                .abbrev_attr_null()
+            .abbrev_null()
+            .get_contents()
+            .unwrap();
+
+        // Each unit uses a different set of string offsets.
+        let mut info = Section::with_endian(Endian::Little);
+        for &str_offsets_base in &[0, 4] {
+            info = append_unit(info, Format::Dwarf32, 4, |s| {
+                let s = s.uleb(1).L32(str_offsets_base);
+                let s = if str_offsets_base == 0 {
+                    // Offset 16: the abstract origin, named using the first string offset.
+                    s.uleb(2).D8(0)
+                } else {
+                    // The inlined call references the abstract origin in the first unit.
+                    s.uleb(3)
+                        .L64(0x1000)
+                        .uleb(0x100)
+                        .uleb(4)
+                        .L32(16)
+                        .L64(0x1010)
+                        .uleb(0x10)
+                        .uleb(0)
+                };
+                s.uleb(0)
+            });
+        }
+        let info_buf = info.get_contents().unwrap();
+        let str_offsets_buf = Section::with_endian(Endian::Little)
+            .L32(0)
+            .L32(4)
+            .get_contents()
+            .unwrap();
+
+        let dwarf = Dwarf {
+            debug_str: DebugStr::new(b"foo\0bar\0", LittleEndian),
+            debug_str_offsets: DebugStrOffsets::from(EndianSlice::new(
+                &str_offsets_buf,
+                LittleEndian,
+            )),
+            ..abbrev_info_dwarf(&abbrev_buf, &info_buf)
+        };
+        let header = dwarf.units().nth(1).unwrap().unwrap();
+        let unit = dwarf.unit(header).unwrap();
+        let frames = dwarf.inlined_frames(&unit, 0x1010).unwrap();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(
+            frames[0].name,
+            Some(EndianSlice::new(b"foo", LittleEndian))
+        );
+    }
+
+    #[test]
+    fn test_find_symbol() {
+        #[rustfmt::skip]
+        let abbrev_buf = Section::with_endian(Endian::Little)
+            .abbrev(1, constants::DW_TAG_compile_unit, constants::DW_CHILDREN_yes)
+                .abbrev_attr_null()
+            .abbrev(2, constants::DW_TAG_subprogram, constants::DW_CHILDREN_no)
+                .abbrev_attr(constants::DW_AT_name, constants::DW_FORM_string)
+                .abbrev_attr_null()
+            .abbrev(3, constants::DW_TAG_subprogram, constants::DW_CHILDREN_no)
+                .abbrev_attr(constants::DW_AT_name, constants::DW_FORM_strp_sup)
+                .abbrev_attr_null()
+            .abbrev_null()
+            .get_contents()
+            .unwrap();
+        let (_, info_buf) = root_sections(
+            |s| s,
+            |s| {
+                // Offset 12.
+                s.uleb(2)
+                    .append_bytes(b"foo\0")
+                    // Offset 17.
+                    .uleb(2)
+                    .append_bytes(b"bar\0")
+                    // Offset 22.
+                    .uleb(2)
+                    .append_bytes(b"foo\0")
+                    // Offset 27.
+                    .uleb(3)
+                    .L32(0)
+                    .uleb(0)
+            },
+        );
+        let mut dwarf = abbrev_info_dwarf(&abbrev_buf, &info_buf);
+        let try_find = |dwarf: &Dwarf<_>, name| -> Result<Vec<_>> {
+            dwarf
+                .find_symbol(name)?
+                .map(|(unit, entry)| Ok((unit.0, entry.0)))
+                .collect()
+        };
+        let find = |dwarf: &Dwarf<_>, name| try_find(dwarf, name).unwrap();
+
+        // Names that can't be read are errors, rather than being skipped.
+        assert!(try_find(&dwarf, "foo").is_err());
+
+        // Without an index, the entries are scanned.
+        let debug_str_sup_buf = b"baz\0";
+        dwarf.debug_str_sup = DebugStr::new(debug_str_sup_buf, LittleEndian);
+        assert_eq!(find(&dwarf, "foo"), [(0, 12), (0, 22)]);
+        assert_eq!(find(&dwarf, "baz"), [(0, 27)]);
+        assert_eq!(find(&dwarf, "qux"), []);
+
+        let length = Label::new();
+        let start = Label::new();
+        let end = Label::new();
+        let pubnames_buf = Section::with_endian(Endian::Little)
+            .L32(&length)
+            .mark(&start)
+            .L16(2)
+            .L32(0)
+            .L32(info_buf.len() as u32)
+            .L32(22)
+            .append_bytes(b"foo\0")
+            .L32(17)
+            .append_bytes(b"bar\0")
+            .L32(0)
+            .mark(&end);
+        length.set_const((&end - &start) as u64);
+        let pubnames_buf = pubnames_buf.get_contents().unwrap();
+        dwarf.debug_pubnames = DebugPubNames::new(&pubnames_buf, LittleEndian);
+
+        // Only the indexed entries are found.
+        assert_eq!(find(&dwarf, "foo"), [(0, 22)]);
+        assert_eq!(find(&dwarf, "bar"), [(0, 17)]);
+
+        let debug_str_buf = b"foo\0";
+        let length = Label::new();
+        let start = Label::new();
+        let end = Label::new();
+        let abbrev_size = Label::new();
+        let abbrev_start = Label::new();
+        let abbrev_end = Label::new();
+        let names_buf = Section::with_endian(Endian::Little)
+            .L32(&length)
+            .mark(&start)
+            // Version.
+            .L16(5)
+            // Padding.
+            .L16(0)
+            // Unit counts.
+            .L32(1)
+            .L32(0)
+            .L32(0)
+            // Bucket count.
+            .L32(0)
+            // Name count.
+            .L32(1)
+            .L32(&abbrev_size)
+            // Augmentation string.
+            .L32(0)
+            // Compilation units.
+            .L32(0)
+            // String offsets.
+            .L32(0)
+            // Entry offsets.
+            .L32(0)
+            .mark(&abbrev_start)
+            .uleb(1)
+            .uleb(constants::DW_TAG_subprogram.0)
+            .uleb(u64::from(constants::DW_IDX_die_offset.0))
+            .uleb(constants::DW_FORM_ref4.0)
+            .uleb(0)
+            .uleb(0)
+            .uleb(0)
+            .mark(&abbrev_end)
+            .uleb(1)
+            .L32(12)
+            .uleb(1)
+            .L32(22)
+            .uleb(0)
+            .mark(&end);
+        length.set_const((&end - &start) as u64);
+        abbrev_size.set_const((&abbrev_end - &abbrev_start) as u64);
+        let names_buf = names_buf.get_contents().unwrap();
+        dwarf.debug_str = DebugStr::new(debug_str_buf, LittleEndian);
+        dwarf.debug_names = DebugNames::new(&names_buf, LittleEndian);
+
+        // `.debug_names` takes precedence over `.debug_pubnames`.
+        assert_eq!(find(&dwarf, "foo"), [(0, 12), (0, 22)]);
+        assert_eq!(find(&dwarf, "bar"), []);
+        // Names are compared case sensitively.
+        assert_eq!(find(&dwarf, "FOO"), []);
+    }
+
+    #[test]
+    fn test_find_symbol_str_offsets_base() {
+        #[rustfmt::skip]
+        let abbrev_buf = Section::with_endian(Endian::Little)
+            .abbrev(1, constants::DW_TAG_compile_unit, constants::DW_CHILDREN_yes)
+                // The name is before the base it depends on.
+                .abbrev_attr(constants::DW_AT_name, constants::DW_FORM_strx1)
+                .abbrev_attr(constants::DW_AT_str_offsets_base, constants::DW_FORM_sec_offset)
+                .abbrev_attr_null()
+            .abbrev(2, constants::DW_TAG_subprogram, constants::DW_CHILDREN_no)
+                .abbrev_attr(constants::DW_AT_name, constants::DW_FORM_strx1)
+                .abbrev_attr_null()
+            .abbrev_null()
+            .get_contents()
+            .unwrap();
+
+        // Each unit uses a different set of string offsets.
+        let mut info = Section::with_endian(Endian::Little);
+        for &str_offsets_base in &[0, 4] {
+            info = append_unit(info, Format::Dwarf32, 4, |s| {
+                // Offset 11.
+                s.uleb(1)
+                    .D8(0)
+                    .L32(str_offsets_base)
+                    // Offset 17.
+                    .uleb(2)
+                    .D8(1)
+                    .uleb(0)
+            });
+        }
+        let info_buf = info.get_contents().unwrap();
+        let str_offsets_buf = Section::with_endian(Endian::Little)
+            .L32(0)
+            .L32(4)
+            .L32(8)
+            .get_contents()
+            .unwrap();
+
+        let dwarf = Dwarf {
+            debug_str: DebugStr::new(b"foo\0bar\0baz\0", LittleEndian),
+            debug_str_offsets: DebugStrOffsets::from(EndianSlice::new(
+                &str_offsets_buf,
+                LittleEndian,
+            )),
+            ..abbrev_info_dwarf(&abbrev_buf, &info_buf)
+        };
+        let find = |name| -> Vec<_> {
+            dwarf
+                .find_symbol(name)
+                .unwrap()
+                .map(|(unit, entry)| Ok((unit.0, entry.0)))
+                .collect()
+                .unwrap()
+        };
+        assert_eq!(find("foo"), [(0, 11)]);
+        assert_eq!(find("bar"), [(0, 17), (20, 11)]);
+        assert_eq!(find("baz"), [(20, 17)]);
+    }
+
+    #[test]
+    fn test_extract_unit_attrs() {
+        let (abbrev_buf, info_buf) = root_sections(
+            |s| {
+                s.abbrev_attr(constants::DW_AT_low_pc, constants::DW_FORM_addr)
+                    .abbrev_attr(constants::DW_AT_high_pc, constants::DW_FORM_udata)
+                    .abbrev_attr(
+                        constants::DW_AT_str_offsets_base,
+                        constants::DW_FORM_sec_offset,
+                    )
+                    .abbrev_attr(
+                        constants::DW_AT_GNU_addr_base,
+                        constants::DW_FORM_sec_offset,
+                    )
+                    .abbrev_attr(
+                        constants::DW_AT_loclists_base,
+                        constants::DW_FORM_sec_offset,
+                    )
+                    .abbrev_attr(
+                        constants::DW_AT_rnglists_base,
+                        constants::DW_FORM_sec_offset,
+                    )
+                    .abbrev_attr(constants::DW_AT_GNU_dwo_id, constants::DW_FORM_data8)
+            },
+            |s| {
+                s.L64(0x1000)
+                    .uleb(0x100)
+                    .L32(0x10)
+                    .L32(0x20)
+                    .L32(0x30)
+                    .L32(0x40)
+                    .L64(0x1234)
+            },
+        );
+        let dwarf = abbrev_info_dwarf(&abbrev_buf, &info_buf);
+        let unit = first_unit(&dwarf);
+
+        let root = unit.entry(UnitOffset(11)).unwrap();
+        let attrs = extract_unit_attrs(&root).unwrap();
+        assert_eq!(attrs.low_pc, Some(AttributeValue::Addr(0x1000)));
+        assert_eq!(attrs.size, Some(0x100));
+        assert_eq!(attrs.str_offsets_base, Some(unit.str_offsets_base));
+        assert_eq!(attrs.addr_base, Some(unit.addr_base));
+        assert_eq!(attrs.loclists_base, Some(unit.loclists_base));
+        assert_eq!(attrs.rnglists_base, Some(unit.rnglists_base));
+        assert_eq!(attrs.dwo_id, unit.dwo_id);
+
+        assert_eq!(unit.low_pc, 0x1000);
+        assert_eq!(unit.high_pc, Some(0x1100));
+        assert_eq!(unit.str_offsets_base, DebugStrOffsetsBase(0x10));
+        assert_eq!(unit.addr_base, DebugAddrBase(0x20));
+        assert_eq!(unit.loclists_base, DebugLocListsBase(0x30));
+        assert_eq!(unit.rnglists_base, DebugRngListsBase(0x40));
+        assert_eq!(unit.dwo_id, Some(DwoId(0x1234)));
+    }
+
+    #[test]
+    fn test_attr_string_lossy() {
+        let (abbrev_buf, info_buf) = root_sections(
+            |s| {
+                s.abbrev_attr(constants::DW_AT_name, constants::DW_FORM_strp)
+                    .abbrev_attr(constants::DW_AT_producer, constants::DW_FORM_strp)
+            },
+            |s| s.L32(0).L32(4),
+        );
+        let debug_str = b"a.c\0b\xffc\0";
+
+        let dwarf = Dwarf {
+            debug_str: DebugStr::new(debug_str, LittleEndian),
+            ..abbrev_info_dwarf(&abbrev_buf, &info_buf)
+        };
+        let unit = first_unit(&dwarf);
+        let mut entries = unit.entries();
+        let (_, entry) = entries.next_dfs().unwrap().unwrap();
+
+        let name = entry.attr_value(constants::DW_AT_name).unwrap().unwrap();
+        assert_eq!(dwarf.attr_str(&unit, name.clone()), Ok("a.c"));
+        assert_eq!(dwarf.attr_string_lossy(&unit, name), Ok("a.c".into()));
+
+        let producer = entry
+            .attr_value(constants::DW_AT_producer)
+            .unwrap()
+            .unwrap();
+        assert_eq!(dwarf.attr_str(&unit, producer.clone()), Err(Error::BadUtf8));
+        assert_eq!(
+            dwarf.attr_string_lossy(&unit, producer),
+            Ok("b\u{fffd}c".into())
+        );
+    }
+
+    #[test]
+    fn test_type_unit_for_signature() {
+        let abbrev_buf = Section::with_endian(Endian::Little)
+            .abbrev(1, constants::DW_TAG_type_unit, constants::DW_CHILDREN_yes)
+            .abbrev_attr_null()
+            .abbrev(2, constants::DW_TAG_base_type, constants::DW_CHILDREN_no)
+            .abbrev_attr(constants::DW_AT_name, constants::DW_FORM_string)
+            .abbrev_attr_null()
+            .abbrev(3, constants::DW_TAG_compile_unit, constants::DW_CHILDREN_no)
+            .abbrev_attr_null()
+            .abbrev_null()
+            .get_contents()
+            .unwrap();
+
+        // A DWARF 4 type unit in `.debug_types`.
+        let length = Label::new();
+        let start = Label::new();
+        let end = Label::new();
+        let types_buf = Section::with_endian(Endian::Little)
+            .L32(&length)
+            .mark(&start)
+            .L16(4)
+            .L32(0)
+            .D8(8)
+            .L64(0x1111)
+            .L32(24)
+            .uleb(1)
+            .uleb(2)
+            .append_bytes(b"int\0")
+            .uleb(0)
+            .mark(&end);
+        length.set_const((&end - &start) as u64);
+        let types_buf = types_buf.get_contents().unwrap();
+
+        // A DWARF 4 compilation unit, followed by a DWARF 5 type unit in `.debug_info`.
+        let length2 = Label::new();
+        let start2 = Label::new();
+        let end2 = Label::new();
+        let info_buf = append_unit(
+            Section::with_endian(Endian::Little),
+            Format::Dwarf32,
+            4,
+            |s| s.uleb(3),
+        )
+        .L32(&length2)
+        .mark(&start2)
+        .L16(5)
+        .D8(constants::DW_UT_type.0)
+        .D8(8)
+        .L32(0)
+        .L64(0x2222)
+        .L32(25)
+        .uleb(1)
+        .uleb(2)
+        .append_bytes(b"long\0")
+        .uleb(0)
+        .mark(&end2);
+        length2.set_const((&end2 - &start2) as u64);
+        let info_buf = info_buf.get_contents().unwrap();
+
+        let dwarf = Dwarf {
+            debug_types: DebugTypes::new(&types_buf, LittleEndian),
+            ..abbrev_info_dwarf(&abbrev_buf, &info_buf)
+        };
+
+        let type_name = |signature| {
+            let (unit, offset) = dwarf
+                .type_unit_for_signature(DebugTypeSignature(signature))
+                .unwrap()?;
+            let entry = unit.entry(offset).unwrap();
+            assert_eq!(entry.tag(), constants::DW_TAG_base_type);
+            let name = entry.attr_value(constants::DW_AT_name).unwrap().unwrap();
+            Some(dwarf.attr_string(&unit, name).unwrap().slice())
+        };
+        assert_eq!(type_name(0x1111), Some(&b"int"[..]));
+        assert_eq!(type_name(0x2222), Some(&b"long"[..]));
+        assert_eq!(type_name(0x3333), None);
+
+        let mut units = dwarf.units();
+        let header = units.next().unwrap().unwrap();
+        assert_eq!(header.type_signature(), None);
+        assert_eq!(header.type_offset(), None);
+        let header = units.next().unwrap().unwrap();
+        assert_eq!(header.type_signature(), Some(DebugTypeSignature(0x2222)));
+        assert_eq!(header.type_offset(), Some(UnitOffset(25)));
+        assert!(units.next().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_sup_borrow() {
+        let owned = Dwarf {
+            debug_str: DebugStr::from(b"main\0".to_vec()),
+            sup: Some(Arc::new(Dwarf {
+                debug_str: DebugStr::from(b"sup\0".to_vec()),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+        let dwarf = owned.borrow(|section| EndianSlice::new(section, LittleEndian));
+        assert_eq!(
+            dwarf.debug_str.get_str(DebugStrOffset(0)),
+            Ok(EndianSlice::new(b"main", LittleEndian))
+        );
+        let sup = dwarf.sup().unwrap();
+        assert_eq!(
+            sup.debug_str.get_str(DebugStrOffset(0)),
+            Ok(EndianSlice::new(b"sup", LittleEndian))
+        );
+        assert!(sup.sup().is_none());
+    }
+
+    #[test]
+    fn test_raw_ranges_locations() {
+        let (abbrev_buf, info_buf) = root_sections(
+            |s| s.abbrev_attr(constants::DW_AT_low_pc, constants::DW_FORM_addr),
+            |s| s.L64(0x1000),
+        );
+        let ranges_buf = Section::with_endian(Endian::Little)
+            .L64(0x10)
+            .L64(0x20)
+            .L64(0)
+            .L64(0)
+            .get_contents()
+            .unwrap();
+        let loc_buf = Section::with_endian(Endian::Little)
+            .L64(!0)
+            .L64(0x2000)
+            .L64(0)
+            .L64(0)
+            .get_contents()
+            .unwrap();
+        let dwarf = Dwarf {
+            ranges: RangeLists::new(
+                DebugRanges::new(&ranges_buf, LittleEndian),
+                DebugRngLists::new(&[], LittleEndian),
+            ),
+            locations: LocationLists::new(
+                DebugLoc::new(&loc_buf, LittleEndian),
+                DebugLocLists::new(&[], LittleEndian),
+            ),
+            ..abbrev_info_dwarf(&abbrev_buf, &info_buf)
+        };
+        let unit = first_unit(&dwarf);
+
+        // The raw entry is not relative to the unit's `DW_AT_low_pc`.
+        let mut raw = dwarf.raw_ranges(&unit, RangeListsOffset(0)).unwrap();
+        match raw.next() {
+            Ok(Some(RawRngListEntry::AddressOrOffsetPair {
+                begin: 0x10,
+                end: 0x20,
+            })) => {}
+            otherwise => panic!("Unexpected raw range: {:?}", otherwise),
+        }
+        assert!(raw.next().unwrap().is_none());
+        let mut ranges = dwarf.ranges(&unit, RangeListsOffset(0)).unwrap();
+        assert_eq!(
+            ranges.next(),
+            Ok(Some(Range {
+                begin: 0x1010,
+                end: 0x1020
+            }))
+        );
+
+        let mut raw = dwarf.raw_locations(&unit, LocationListsOffset(0)).unwrap();
+        match raw.next() {
+            Ok(Some(RawLocListEntry::BaseAddress { addr: 0x2000 })) => {}
+            otherwise => panic!("Unexpected raw location: {:?}", otherwise),
+        }
+        assert!(raw.next().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_ranges_locations_with_base() {
+        // A unit without a `DW_AT_low_pc`.
+        let (abbrev_buf, info_buf) = root_sections(|s| s, |s| s);
+        let ranges_buf = Section::with_endian(Endian::Little)
+            .L64(0x10)
+            .L64(0x20)
+            .L64(!0)
+            .L64(0x4000)
+            .L64(0x1)
+            .L64(0x2)
+            .L64(0)
+            .L64(0)
+            .get_contents()
+            .unwrap();
+        let loc_buf = Section::with_endian(Endian::Little)
+            .L64(0x10)
+            .L64(0x20)
+            .L16(1)
+            .D8(constants::DW_OP_reg0.0)
+            .L64(!0)
+            .L64(0x5000)
+            .L64(0x1)
+            .L64(0x2)
+            .L16(1)
+            .D8(constants::DW_OP_reg1.0)
+            .L64(0)
+            .L64(0)
+            .get_contents()
+            .unwrap();
+        let dwarf = Dwarf {
+            ranges: RangeLists::new(
+                DebugRanges::new(&ranges_buf, LittleEndian),
+                DebugRngLists::new(&[], LittleEndian),
+            ),
+            locations: LocationLists::new(
+                DebugLoc::new(&loc_buf, LittleEndian),
+                DebugLocLists::new(&[], LittleEndian),
+            ),
+            ..abbrev_info_dwarf(&abbrev_buf, &info_buf)
+        };
+        let unit = first_unit(&dwarf);
+        assert_eq!(unit.low_pc, 0);
+
+        // The base address entry overrides the given base address.
+        let mut ranges = dwarf
+            .ranges_with_base(&unit, RangeListsOffset(0), 0x3000)
+            .unwrap();
+        let mut result = Vec::new();
+        while let Some(range) = ranges.next().unwrap() {
+            result.push(range);
+        }
+        assert_eq!(
+            result,
+            vec![
+                Range {
+                    begin: 0x3010,
+                    end: 0x3020
+                },
+                Range {
+                    begin: 0x4001,
+                    end: 0x4002
+                },
+            ]
+        );
+
+        let mut locations = dwarf
+            .locations_with_base(&unit, LocationListsOffset(0), 0x3000)
+            .unwrap();
+        let mut result = Vec::new();
+        while let Some(location) = locations.next().unwrap() {
+            result.push(location.range);
+        }
+        assert_eq!(
+            result,
+            vec![
+                Range {
+                    begin: 0x3010,
+                    end: 0x3020
+                },
+                Range {
+                    begin: 0x5001,
+                    end: 0x5002
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_operation() {
+        let (abbrev_buf, info_buf) = root_sections(
+            |s| s.abbrev_attr(constants::DW_AT_addr_base, constants::DW_FORM_sec_offset),
+            |s| s.L32(8),
+        );
+        let addr_buf = Section::with_endian(Endian::Little)
+            .L64(0)
+            .L64(0x1000)
+            .L64(0x2000)
+            .get_contents()
+            .unwrap();
+        let dwarf = Dwarf {
+            debug_addr: DebugAddr::from(EndianSlice::new(&addr_buf, LittleEndian)),
+            ..abbrev_info_dwarf(&abbrev_buf, &info_buf)
+        };
+        let unit = first_unit(&dwarf);
+
+        assert_eq!(
+            dwarf.resolve_operation(
+                &unit,
+                Operation::AddressIndex {
+                    index: DebugAddrIndex(1)
+                }
+            ),
+            Ok(Operation::Address { address: 0x2000 })
+        );
+        assert_eq!(
+            dwarf.resolve_operation(
+                &unit,
+                Operation::ConstantIndex {
+                    index: DebugAddrIndex(0)
+                }
+            ),
+            Ok(Operation::Literal { value: 0x1000 })
+        );
+        assert_eq!(
+            dwarf.resolve_operation(&unit, Operation::Plus),
+            Ok(Operation::Plus)
+        );
+        assert_eq!(
+            dwarf.resolve_operation(
+                &unit,
+                Operation::AddressIndex {
+                    index: DebugAddrIndex(2)
+                }
+            ),
+            Err(Error::InvalidAddressIndex(2, 8))
+        );
+        assert_eq!(
+            dwarf.resolve_operation(
+                &unit,
+                Operation::ConstantIndex {
+                    index: DebugAddrIndex(5)
+                }
+            ),
+            Err(Error::InvalidAddressIndex(5, 8))
+        );
+    }
+
+    #[test]
+    fn test_location_at() {
+        let abbrev_buf = Section::with_endian(Endian::Little)
+            .abbrev(1, constants::DW_TAG_compile_unit, constants::DW_CHILDREN_no)
+            .abbrev_attr(constants::DW_AT_low_pc, constants::DW_FORM_addr)
+            .abbrev_attr_null()
+            .abbrev_null()
+            .get_contents()
+            .unwrap();
+
+        let info_buf = info_section(5, |s| s.uleb(1).L64(0x1000));
+
+        let length = Label::new();
+        let start = Label::new();
+        let list = Label::new();
+        let empty_list = Label::new();
+        let end = Label::new();
+        let loclists_buf = Section::with_endian(Endian::Little)
+            .L32(&length)
+            .mark(&start)
+            .L16(5)
+            .D8(8)
+            .D8(0)
+            .L32(0)
+            .mark(&list)
+            .D8(constants::DW_LLE_offset_pair.0)
+            .uleb(0x10)
+            .uleb(0x20)
+            .uleb(1)
+            .D8(constants::DW_OP_reg0.0)
+            .D8(constants::DW_LLE_default_location.0)
+            .uleb(1)
+            .D8(constants::DW_OP_reg1.0)
+            .D8(constants::DW_LLE_start_length.0)
+            .L64(0x2000)
+            .uleb(0x10)
+            .uleb(1)
+            .D8(constants::DW_OP_reg2.0)
+            .D8(constants::DW_LLE_end_of_list.0)
+            .mark(&empty_list)
+            .D8(constants::DW_LLE_end_of_list.0)
+            .mark(&end);
+        length.set_const((&end - &start) as u64);
+        loclists_buf.start().set_const(0);
+        let list = LocationListsOffset(list.value().unwrap() as usize);
+        let empty_list = LocationListsOffset(empty_list.value().unwrap() as usize);
+        let loclists_buf = loclists_buf.get_contents().unwrap();
+
+        let dwarf = Dwarf {
+            locations: LocationLists::new(
+                DebugLoc::new(&[], LittleEndian),
+                DebugLocLists::new(&loclists_buf, LittleEndian),
+            ),
+            ..abbrev_info_dwarf(&abbrev_buf, &info_buf)
+        };
+        let unit = first_unit(&dwarf);
+
+        let location_at = |offset, address| {
+            dwarf
+                .location_at(&unit, offset, address)
+                .unwrap()
+                .map(|expression| expression.0.slice())
+        };
+        assert_eq!(
+            location_at(list, 0x1010),
+            Some(&[constants::DW_OP_reg0.0][..])
+        );
+        // An explicit range takes precedence over an earlier default location.
+        assert_eq!(
+            location_at(list, 0x200f),
+            Some(&[constants::DW_OP_reg2.0][..])
+        );
+        assert_eq!(
+            location_at(list, 0x1020),
+            Some(&[constants::DW_OP_reg1.0][..])
+        );
+        assert_eq!(location_at(empty_list, 0x1010), None);
+    }
+
+    #[test]
+    fn test_unit_line_rows() {
+        let (abbrev_buf, info_buf, line_buf) = line_program_sections();
+        let dwarf = Dwarf {
+            debug_line: DebugLine::new(&line_buf, LittleEndian),
+            ..abbrev_info_dwarf(&abbrev_buf, &info_buf)
+        };
+        let unit = first_unit(&dwarf);
+
+        let addresses = || {
+            let mut addresses = Vec::new();
+            let mut rows = unit.line_rows().unwrap();
+            while let Some((_, row)) = rows.next_row().unwrap() {
+                addresses.push(row.address());
+            }
+            addresses
+        };
+        let first = addresses();
+        assert!(!first.is_empty());
+        // Each call starts from the beginning of the line program.
+        assert_eq!(addresses(), first);
+
+        let (abbrev_buf, info_buf) = root_sections(|s| s, |s| s);
+        let dwarf = abbrev_info_dwarf(&abbrev_buf, &info_buf);
+        let unit = first_unit(&dwarf);
+        assert!(unit.line_rows().is_none());
+    }
+
+    #[test]
+    fn test_find_location() {
+        let (abbrev_buf, info_buf, line_buf) = line_program_sections();
+        let dwarf = Dwarf {
+            debug_line: DebugLine::new(&line_buf, LittleEndian),
+            ..abbrev_info_dwarf(&abbrev_buf, &info_buf)
+        };
+        let unit = first_unit(&dwarf);
+
+        let location = |probe| {
+            dwarf.find_location(&unit, probe).unwrap().map(|location| {
+                (
+                    location.file.map(|file| file.slice()),
+                    location.line,
+                    location.column,
+                )
+            })
+        };
+        assert_eq!(location(0x7ff), None);
+        assert_eq!(location(0x800), Some((Some(&b"a.c"[..]), Some(20), None)));
+        assert_eq!(location(0x807), Some((Some(&b"a.c"[..]), Some(20), None)));
+        assert_eq!(location(0x808), None);
+        assert_eq!(location(0x1000), Some((Some(&b"a.c"[..]), Some(10), None)));
+        assert_eq!(location(0x100f), Some((Some(&b"a.c"[..]), Some(10), None)));
+        assert_eq!(
+            location(0x1010),
+            Some((Some(&b"b.h"[..]), Some(15), Some(4)))
+        );
+        assert_eq!(
+            location(0x101f),
+            Some((Some(&b"b.h"[..]), Some(15), Some(4)))
+        );
+        assert_eq!(location(0x1020), None);
+    }
+
+    #[test]
+    fn test_file_name_string() {
+        /// Return the names of the first directory and file in a DWARF 5 line
+        /// program whose `DW_LNCT_path` entries use the given form.
+        fn names<F>(form: constants::DwForm, path: F) -> Result<(Vec<u8>, Vec<u8>)>
+        where
+            F: Fn(Section, usize) -> Section,
+        {
+            let (abbrev_buf, info_buf) = root_sections(
+                |s| s.abbrev_attr(constants::DW_AT_stmt_list, constants::DW_FORM_sec_offset),
+                |s| s.L32(0),
+            );
 
-    /// The `DW_AT_low_pc` attribute of the unit. Defaults to 0.
-    pub low_pc: u64,
+            let length = Label::new();
+            let start = Label::new();
+            let header_length = Label::new();
+            let header_start = Label::new();
+            let end = Label::new();
+            let line = Section::with_endian(Endian::Little)
+                .L32(&length)
+                .mark(&start)
+                .L16(5)
+                // Address size and segment selector size.
+                .D8(8)
+                .D8(0)
+                .L32(&header_length)
+                .mark(&header_start)
+                // Minimum instruction length, maximum operations per instruction,
+                // default is_stmt, line base, line range and opcode base.
+                .D8(1)
+                .D8(1)
+                .D8(1)
+                .D8(-5i8 as u8)
+                .D8(14)
+                .D8(13)
+                .append_bytes(&[0, 1, 1, 1, 1, 0, 0, 0, 1, 0, 0, 1])
+                // Directory entry formats and directories.
+                .D8(1)
+                .uleb(constants::DW_LNCT_path.0.into())
+                .uleb(form.0)
+                .uleb(1);
+            let line = path(line, 0)
+                // File name entry formats and file names.
+                .D8(2)
+                .uleb(constants::DW_LNCT_path.0.into())
+                .uleb(form.0)
+                .uleb(constants::DW_LNCT_directory_index.0.into())
+                .uleb(constants::DW_FORM_udata.0)
+                .uleb(1);
+            let line = path(line, 1).uleb(0).mark(&end);
+            length.set_const((&end - &start) as u64);
+            header_length.set_const((&end - &header_start) as u64);
+            let line_buf = line.get_contents().unwrap();
 
-    /// The `DW_AT_str_offsets_base` attribute of the unit. Defaults to 0.
-    pub str_offsets_base: DebugStrOffsetsBase<Offset>,
+            let debug_str_offsets_buf = Section::with_endian(Endian::Little)
+                .L32(1)
+                .L32(9)
+                .get_contents()
+                .unwrap();
+            let dwarf = Dwarf {
+                debug_line: DebugLine::new(&line_buf, LittleEndian),
+                debug_line_str: DebugLineStr::from(EndianSlice::new(
+                    b"line_dir\0line.c\0",
+                    LittleEndian,
+                )),
+                debug_str: DebugStr::new(b"\0str_dir\0str.c\0", LittleEndian),
+                debug_str_offsets: DebugStrOffsets::from(EndianSlice::new(
+                    &debug_str_offsets_buf,
+                    LittleEndian,
+                )),
+                debug_str_sup: DebugStr::new(b"sup_dir\0sup.c\0", LittleEndian),
+                ..abbrev_info_dwarf(&abbrev_buf, &info_buf)
+            };
+            let header = dwarf.units().next().unwrap().unwrap();
+            let unit = dwarf.unit(header)?;
+            let header = unit.line_program.as_ref().unwrap().header();
+            assert_eq!(dwarf.directory_string(&unit, header, 1)?, None);
+            assert_eq!(dwarf.file_name_string(&unit, header, 1)?, None);
+            let directory = dwarf.directory_string(&unit, header, 0)?.unwrap();
+            let file = dwarf.file_name_string(&unit, header, 0)?.unwrap();
+            assert_eq!(dwarf.file_name(&unit, 0)?, Some(file));
+            Ok((directory.slice().to_vec(), file.slice().to_vec()))
+        }
 
-    /// The `DW_AT_addr_base` attribute of the unit. Defaults to 0.
-    pub addr_base: DebugAddrBase<Offset>,
+        let to_vec = |dir: &[u8], file: &[u8]| Ok((dir.to_vec(), file.to_vec()));
+        assert_eq!(
+            names(constants::DW_FORM_string, |s: Section, i| {
+                s.append_bytes([&b"dir\0"[..], &b"a.c\0"[..]][i])
+            }),
+            to_vec(b"dir", b"a.c")
+        );
+        assert_eq!(
+            names(constants::DW_FORM_strp, |s, i| s.L32([1, 9][i])),
+            to_vec(b"str_dir", b"str.c")
+        );
+        assert_eq!(
+            names(constants::DW_FORM_strp_sup, |s, i| s.L32([0, 8][i])),
+            to_vec(b"sup_dir", b"sup.c")
+        );
+        assert_eq!(
+            names(constants::DW_FORM_line_strp, |s, i| s.L32([0, 9][i])),
+            to_vec(b"line_dir", b"line.c")
+        );
+        assert_eq!(
+            names(constants::DW_FORM_strx1, |s, i| s.D8(i as u8)),
+            to_vec(b"str_dir", b"str.c")
+        );
+    }
 
-    /// The `DW_AT_loclists_base` attribute of the unit. Defaults to 0.
-    pub loclists_base: DebugLocListsBase<Offset>,
+    #[test]
+    fn test_decl_location() {
+        let (_, _, line_buf) = line_program_sections();
+        let (abbrev_buf, info_buf) = root_sections(
+            |s| {
+                s.abbrev_attr(constants::DW_AT_stmt_list, constants::DW_FORM_sec_offset)
+                    .abbrev_attr(constants::DW_AT_decl_file, constants::DW_FORM_data1)
+                    .abbrev_attr(constants::DW_AT_decl_line, constants::DW_FORM_udata)
+            },
+            |s| s.L32(0).D8(2).uleb(300),
+        );
+        let dwarf = Dwarf {
+            debug_line: DebugLine::new(&line_buf, LittleEndian),
+            ..abbrev_info_dwarf(&abbrev_buf, &info_buf)
+        };
+        let unit = first_unit(&dwarf);
+        let mut entries = unit.entries();
+        let (_, entry) = entries.next_dfs().unwrap().unwrap();
+        assert_eq!(
+            dwarf.decl_location(&unit, &entry),
+            Ok(Some(DeclLocation {
+                file: Some(EndianSlice::new(b"b.h", LittleEndian)),
+                line: Some(300),
+                column: None,
+            }))
+        );
 
-    /// The `DW_AT_rnglists_base` attribute of the unit. Defaults to 0.
-    pub rnglists_base: DebugRngListsBase<Offset>,
+        // File 0 is not the compilation unit's file before DWARF 5.
+        let (abbrev_buf, info_buf) = root_sections(
+            |s| {
+                s.abbrev_attr(constants::DW_AT_name, constants::DW_FORM_string)
+                    .abbrev_attr(constants::DW_AT_stmt_list, constants::DW_FORM_sec_offset)
+                    .abbrev_attr(constants::DW_AT_decl_file, constants::DW_FORM_data1)
+                    .abbrev_attr(constants::DW_AT_decl_line, constants::DW_FORM_udata)
+            },
+            |s| s.append_bytes(b"a.c\0").L32(0).D8(0).uleb(300),
+        );
+        let dwarf = Dwarf {
+            debug_line: DebugLine::new(&line_buf, LittleEndian),
+            ..abbrev_info_dwarf(&abbrev_buf, &info_buf)
+        };
+        let unit = first_unit(&dwarf);
+        let mut entries = unit.entries();
+        let (_, entry) = entries.next_dfs().unwrap().unwrap();
+        assert_eq!(
+            dwarf.decl_location(&unit, &entry),
+            Ok(Some(DeclLocation {
+                file: None,
+                line: Some(300),
+                column: None,
+            }))
+        );
 
-    /// The line number program of the unit.
-    pub line_program: Option<IncompleteLineProgram<R, Offset>>,
-}
+        let (abbrev_buf, info_buf) = root_sections(
+            |s| s.abbrev_attr(constants::DW_AT_decl_column, constants::DW_FORM_data2),
+            |s| s.L16(7),
+        );
+        let dwarf = abbrev_info_dwarf(&abbrev_buf, &info_buf);
+        let unit = first_unit(&dwarf);
+        let mut entries = unit.entries();
+        let (_, entry) = entries.next_dfs().unwrap().unwrap();
+        assert_eq!(
+            dwarf.decl_location(&unit, &entry),
+            Ok(Some(DeclLocation {
+                file: None,
+                line: None,
+                column: Some(7),
+            }))
+        );
 
-impl<R: Reader> Unit<R> {
-    /// Construct a new `Unit` from the given compilation unit header.
-    #[inline]
-    pub fn new(dwarf: &Dwarf<R>, header: CompilationUnitHeader<R>) -> Result<Self> {
-        Self::new_internal(
-            dwarf,
-            UnitSectionOffset::DebugInfoOffset(header.offset()),
-            header.header(),
-        )
+        let (abbrev_buf, info_buf) = root_sections(|s| s, |s| s);
+        let dwarf = abbrev_info_dwarf(&abbrev_buf, &info_buf);
+        let unit = first_unit(&dwarf);
+        let mut entries = unit.entries();
+        let (_, entry) = entries.next_dfs().unwrap().unwrap();
+        assert_eq!(dwarf.decl_location(&unit, &entry), Ok(None));
     }
 
-    /// Construct a new `Unit` from the given type unit header.
-    #[inline]
-    pub fn new_type_unit(dwarf: &Dwarf<R>, header: TypeUnitHeader<R>) -> Result<Self> {
-        Self::new_internal(
-            dwarf,
-            UnitSectionOffset::DebugTypesOffset(header.offset()),
-            header.header(),
-        )
-    }
+    #[test]
+    fn test_die_ranges_location() {
+        let (abbrev_buf, info_buf) = root_sections(
+            |s| {
+                s.abbrev_attr(constants::DW_AT_low_pc, constants::DW_FORM_addr)
+                    .abbrev_attr(constants::DW_AT_ranges, constants::DW_FORM_sec_offset)
+                    .abbrev_attr(constants::DW_AT_location, constants::DW_FORM_sec_offset)
+            },
+            |s| s.L64(0x1000).L32(0).L32(0),
+        );
+        let ranges_buf = Section::with_endian(Endian::Little)
+            .L64(0x10)
+            .L64(0x20)
+            .L64(0)
+            .L64(0)
+            .get_contents()
+            .unwrap();
+        let loc_buf = Section::with_endian(Endian::Little)
+            .L64(0x30)
+            .L64(0x40)
+            .L16(1)
+            .D8(constants::DW_OP_reg0.0)
+            .L64(0)
+            .L64(0)
+            .get_contents()
+            .unwrap();
+        let dwarf = Dwarf {
+            ranges: RangeLists::new(
+                DebugRanges::new(&ranges_buf, LittleEndian),
+                DebugRngLists::new(&[], LittleEndian),
+            ),
+            locations: LocationLists::new(
+                DebugLoc::new(&loc_buf, LittleEndian),
+                DebugLocLists::new(&[], LittleEndian),
+            ),
+            ..abbrev_info_dwarf(&abbrev_buf, &info_buf)
+        };
+        let unit = first_unit(&dwarf);
+        let entry = unit.root().unwrap();
 
-    fn new_internal(
-        dwarf: &Dwarf<R>,
-        offset: UnitSectionOffset<R::Offset>,
-        header: UnitHeader<R>,
-    ) -> Result<Self> {
-        let abbreviations = header.abbreviations(&dwarf.debug_abbrev)?;
-        let mut unit = Unit {
-            offset,
-            header,
-            abbreviations,
-            name: None,
-            comp_dir: None,
-            low_pc: 0,
-            // Defaults to 0 for GNU extensions.
-            str_offsets_base: DebugStrOffsetsBase(R::Offset::from_u8(0)),
-            addr_base: DebugAddrBase(R::Offset::from_u8(0)),
-            loclists_base: DebugLocListsBase(R::Offset::from_u8(0)),
-            rnglists_base: DebugRngListsBase(R::Offset::from_u8(0)),
-            line_program: None,
+        // `DW_AT_ranges` takes precedence over `DW_AT_low_pc`.
+        let mut ranges = dwarf.die_ranges(&unit, &entry).unwrap();
+        assert_eq!(
+            ranges.next(),
+            Ok(Some(Range {
+                begin: 0x1010,
+                end: 0x1020
+            }))
+        );
+        assert_eq!(ranges.next(), Ok(None));
+
+        let mut locations = match dwarf.die_location(&unit, &entry) {
+            Ok(Some(DieLocation::List(locations))) => locations,
+            otherwise => panic!("Unexpected result: {:?}", otherwise),
         };
-        let mut name = None;
-        let mut comp_dir = None;
-        let mut line_program_offset = None;
+        let location = locations.next().unwrap().unwrap();
+        assert_eq!(
+            location.range,
+            Range {
+                begin: 0x1030,
+                end: 0x1040
+            }
+        );
+        assert_eq!(locations.next(), Ok(None));
 
-        {
-            let mut cursor = unit.header.entries(&unit.abbreviations);
-            cursor.next_dfs()?;
-            let root = cursor.current().ok_or(Error::MissingUnitDie)?;
-            let mut attrs = root.attrs();
-            while let Some(attr) = attrs.next()? {
-                match attr.name() {
-                    constants::DW_AT_name => {
-                        name = Some(attr.value());
-                    }
-                    constants::DW_AT_comp_dir => {
-                        comp_dir = Some(attr.value());
-                    }
-                    constants::DW_AT_low_pc => {
-                        if let AttributeValue::Addr(address) = attr.value() {
-                            unit.low_pc = address;
-                        }
-                    }
-                    constants::DW_AT_stmt_list => {
-                        if let AttributeValue::DebugLineRef(offset) = attr.value() {
-                            line_program_offset = Some(offset);
-                        }
-                    }
-                    constants::DW_AT_str_offsets_base => {
-                        if let AttributeValue::DebugStrOffsetsBase(base) = attr.value() {
-                            unit.str_offsets_base = base;
-                        }
-                    }
-                    constants::DW_AT_addr_base => {
-                        if let AttributeValue::DebugAddrBase(base) = attr.value() {
-                            unit.addr_base = base;
-                        }
-                    }
-                    constants::DW_AT_loclists_base => {
-                        if let AttributeValue::DebugLocListsBase(base) = attr.value() {
-                            unit.loclists_base = base;
-                        }
-                    }
-                    constants::DW_AT_rnglists_base => {
-                        if let AttributeValue::DebugRngListsBase(base) = attr.value() {
-                            unit.rnglists_base = base;
-                        }
-                    }
-                    _ => {}
-                }
+        let (abbrev_buf, info_buf) = root_sections(
+            |s| {
+                s.abbrev_attr(constants::DW_AT_low_pc, constants::DW_FORM_addr)
+                    .abbrev_attr(constants::DW_AT_high_pc, constants::DW_FORM_udata)
+                    .abbrev_attr(constants::DW_AT_location, constants::DW_FORM_exprloc)
+            },
+            |s| {
+                s.L64(0x1000)
+                    .uleb(0x100)
+                    .uleb(1)
+                    .D8(constants::DW_OP_reg0.0)
+            },
+        );
+        let dwarf = abbrev_info_dwarf(&abbrev_buf, &info_buf);
+        let unit = first_unit(&dwarf);
+        let entry = unit.root().unwrap();
+        let mut ranges = dwarf.die_ranges(&unit, &entry).unwrap();
+        assert_eq!(
+            ranges.next(),
+            Ok(Some(Range {
+                begin: 0x1000,
+                end: 0x1100
+            }))
+        );
+        assert_eq!(ranges.next(), Ok(None));
+        match dwarf.die_location(&unit, &entry) {
+            Ok(Some(DieLocation::Expression(expression))) => {
+                assert_eq!(expression.0.slice(), &[constants::DW_OP_reg0.0][..]);
             }
+            otherwise => panic!("Unexpected result: {:?}", otherwise),
         }
 
-        unit.name = match name {
-            Some(val) => Some(dwarf.attr_string(&unit, val)?),
-            None => None,
-        };
-        unit.comp_dir = match comp_dir {
-            Some(val) => Some(dwarf.attr_string(&unit, val)?),
-            None => None,
+        let (abbrev_buf, info_buf) = root_sections(|s| s, |s| s);
+        let dwarf = abbrev_info_dwarf(&abbrev_buf, &info_buf);
+        let unit = first_unit(&dwarf);
+        let entry = unit.root().unwrap();
+        assert_eq!(dwarf.die_ranges(&unit, &entry).unwrap().next(), Ok(None));
+        assert!(dwarf.die_location(&unit, &entry).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_file_name() {
+        let (abbrev_buf, info_buf, line_buf) = line_program_sections();
+        let dwarf = Dwarf {
+            debug_line: DebugLine::new(&line_buf, LittleEndian),
+            ..abbrev_info_dwarf(&abbrev_buf, &info_buf)
         };
-        unit.line_program = match line_program_offset {
-            Some(offset) => Some(dwarf.debug_line.program(
-                offset,
-                unit.header.address_size(),
-                unit.comp_dir.clone(),
-                unit.name.clone(),
-            )?),
-            None => None,
+        let unit = first_unit(&dwarf);
+
+        let file_name = |index| dwarf.file_name(&unit, index).unwrap().map(|x| x.slice());
+        let file_directory = |index| {
+            dwarf
+                .file_directory(&unit, index)
+                .unwrap()
+                .map(|x| x.slice())
         };
-        Ok(unit)
+        // File index 0 is the unit's file, which is unknown because there is no `DW_AT_name`.
+        assert_eq!(file_name(0), None);
+        assert_eq!(file_name(1), Some(&b"a.c"[..]));
+        assert_eq!(file_directory(1), Some(&b"/comp_dir"[..]));
+        assert_eq!(file_name(2), Some(&b"b.h"[..]));
+        assert_eq!(file_directory(2), Some(&b"include"[..]));
+        assert_eq!(file_name(3), None);
+        assert_eq!(file_directory(3), None);
     }
 
-    /// Return the encoding parameters for this unit.
-    #[inline]
-    pub fn encoding(&self) -> Encoding {
-        self.header.encoding()
-    }
+    #[test]
+    fn test_file_name_v5() {
+        let abbrev_buf = Section::with_endian(Endian::Little)
+            .abbrev(1, constants::DW_TAG_compile_unit, constants::DW_CHILDREN_no)
+            .abbrev_attr(constants::DW_AT_stmt_list, constants::DW_FORM_sec_offset)
+            .abbrev_attr_null()
+            .abbrev_null()
+            .get_contents()
+            .unwrap();
 
-    /// Navigate this unit's `DebuggingInformationEntry`s.
-    #[inline]
-    pub fn entries(&self) -> EntriesCursor<R> {
-        self.header.entries(&self.abbreviations)
-    }
+        let info_buf = info_section(5, |s| s.uleb(1).L32(0));
 
-    /// Navigate this unit's `DebuggingInformationEntry`s
-    /// starting at the given offset.
-    #[inline]
-    pub fn entries_at_offset(&self, offset: UnitOffset<R::Offset>) -> Result<EntriesCursor<R>> {
-        self.header.entries_at_offset(&self.abbreviations, offset)
-    }
+        let line_str_buf = b"/comp_dir\0a.c\0include\0b.h\0";
 
-    /// Navigate this unit's `DebuggingInformationEntry`s as a tree
-    /// starting at the given offset.
-    #[inline]
-    pub fn entries_tree(&self, offset: Option<UnitOffset<R::Offset>>) -> Result<EntriesTree<R>> {
-        self.header.entries_tree(&self.abbreviations, offset)
-    }
-}
+        let length = Label::new();
+        let start = Label::new();
+        let header_length = Label::new();
+        let header_start = Label::new();
+        let end = Label::new();
+        let line = Section::with_endian(Endian::Little)
+            .L32(&length)
+            .mark(&start)
+            .L16(5)
+            // Address size.
+            .D8(8)
+            // Segment selector size.
+            .D8(0)
+            .L32(&header_length)
+            .mark(&header_start)
+            .D8(1)
+            .D8(1)
+            .D8(1)
+            .D8(-5i8 as u8)
+            .D8(14)
+            .D8(13)
+            .append_bytes(&[0, 1, 1, 1, 1, 0, 0, 0, 1, 0, 0, 1])
+            // Directory entry format.
+            .D8(1)
+            .uleb(constants::DW_LNCT_path.0.into())
+            .uleb(constants::DW_FORM_line_strp.0)
+            // Directories.
+            .uleb(2)
+            .L32(0)
+            .L32(14)
+            // File name entry format.
+            .D8(2)
+            .uleb(constants::DW_LNCT_path.0.into())
+            .uleb(constants::DW_FORM_line_strp.0)
+            .uleb(constants::DW_LNCT_directory_index.0.into())
+            .uleb(constants::DW_FORM_udata.0)
+            // File names.
+            .uleb(2)
+            .L32(10)
+            .uleb(0)
+            .L32(22)
+            .uleb(1)
+            .mark(&end);
+        length.set_const((&end - &start) as u64);
+        header_length.set_const((&end - &header_start) as u64);
+        let line_buf = line.get_contents().unwrap();
 
-impl<T: ReaderOffset> UnitSectionOffset<T> {
-    /// Convert an offset to be relative to the start of the given unit,
-    /// instead of relative to the start of the section.
-    /// Returns `None` if the offset is not within the unit entries.
-    pub fn to_unit_offset<R>(&self, unit: &Unit<R>) -> Option<UnitOffset<T>>
-    where
-        R: Reader<Offset = T>,
-    {
-        let (offset, unit_offset) = match (self, unit.offset) {
-            (
-                UnitSectionOffset::DebugInfoOffset(offset),
-                UnitSectionOffset::DebugInfoOffset(unit_offset),
-            ) => (offset.0, unit_offset.0),
-            (
-                UnitSectionOffset::DebugTypesOffset(offset),
-                UnitSectionOffset::DebugTypesOffset(unit_offset),
-            ) => (offset.0, unit_offset.0),
-            _ => return None,
+        let dwarf = Dwarf {
+            debug_line: DebugLine::new(&line_buf, LittleEndian),
+            debug_line_str: DebugLineStr::from(EndianSlice::new(line_str_buf, LittleEndian)),
+            ..abbrev_info_dwarf(&abbrev_buf, &info_buf)
         };
-        let offset = match offset.checked_sub(unit_offset) {
-            Some(offset) => UnitOffset(offset),
-            None => return None,
+        let unit = first_unit(&dwarf);
+
+        let file_name = |index| dwarf.file_name(&unit, index).unwrap().map(|x| x.slice());
+        let file_directory = |index| {
+            dwarf
+                .file_directory(&unit, index)
+                .unwrap()
+                .map(|x| x.slice())
         };
-        if !unit.header.is_valid_offset(offset) {
-            return None;
-        }
-        Some(offset)
+        // DWARF 5 file indices are 0-based, and directory 0 is explicit.
+        assert_eq!(file_name(0), Some(&b"a.c"[..]));
+        assert_eq!(file_directory(0), Some(&b"/comp_dir"[..]));
+        assert_eq!(file_name(1), Some(&b"b.h"[..]));
+        assert_eq!(file_directory(1), Some(&b"include"[..]));
+        assert_eq!(file_name(2), None);
+        assert_eq!(file_directory(2), None);
     }
-}
 
-impl<T: ReaderOffset> UnitOffset<T> {
-    /// Convert an offset to be relative to the start of the .debug_info section,
-    /// instead of relative to the start of the given compilation unit.
-    pub fn to_unit_section_offset<R>(&self, unit: &Unit<R>) -> UnitSectionOffset<T>
-    where
-        R: Reader<Offset = T>,
-    {
-        match unit.offset {
-            UnitSectionOffset::DebugInfoOffset(unit_offset) => {
-                UnitSectionOffset::DebugInfoOffset(DebugInfoOffset(unit_offset.0 + self.0))
-            }
-            UnitSectionOffset::DebugTypesOffset(unit_offset) => {
-                UnitSectionOffset::DebugTypesOffset(DebugTypesOffset(unit_offset.0 + self.0))
-            }
-        }
-    }
-}
+    #[test]
+    fn test_dwarf_package() {
+        let skeleton_abbrev_buf = Section::with_endian(Endian::Little)
+            .abbrev(1, constants::DW_TAG_compile_unit, constants::DW_CHILDREN_no)
+            .abbrev_attr(constants::DW_AT_GNU_dwo_id, constants::DW_FORM_data8)
+            .abbrev_attr(
+                constants::DW_AT_GNU_addr_base,
+                constants::DW_FORM_sec_offset,
+            )
+            .abbrev_attr_null()
+            .abbrev_null()
+            .get_contents()
+            .unwrap();
+        let mut skeleton_attrs = Vec::new();
+        skeleton_attrs.extend_from_slice(&0x2222u64.to_le_bytes());
+        skeleton_attrs.extend_from_slice(&8u32.to_le_bytes());
+        let skeleton_info_buf = info_section(4, |s| s.uleb(1).append_bytes(&skeleton_attrs));
+        let addr_buf = Section::with_endian(Endian::Little)
+            .L64(0)
+            .L64(0x1000)
+            .L64(0x2000)
+            .get_contents()
+            .unwrap();
+        let parent = Dwarf {
+            debug_addr: DebugAddr::from(EndianSlice::new(&addr_buf, LittleEndian)),
+            ..abbrev_info_dwarf(&skeleton_abbrev_buf, &skeleton_info_buf)
+        };
+        let skeleton = first_unit(&parent);
+        assert_eq!(skeleton.addr_base, DebugAddrBase(8));
 
-/// An iterator for the address ranges of a `DebuggingInformationEntry`.
-///
-/// Returned by `Dwarf::die_ranges` and `Dwarf::unit_ranges`.
-#[derive(Debug)]
-pub struct RangeIter<R: Reader>(RangeIterInner<R>);
+        // Each unit in the package has its own abbreviations and string offsets.
+        let abbrev = Section::with_endian(Endian::Little)
+            .abbrev(1, constants::DW_TAG_compile_unit, constants::DW_CHILDREN_no)
+            .abbrev_attr(constants::DW_AT_name, constants::DW_FORM_GNU_str_index)
+            .abbrev_attr(constants::DW_AT_low_pc, constants::DW_FORM_GNU_addr_index)
+            .abbrev_attr_null()
+            .abbrev_null()
+            .get_contents()
+            .unwrap();
+        let abbrev_buf = [&abbrev[..], &abbrev[..]].concat();
+        let unit1 = info_section(4, |s| s.uleb(1).append_bytes(&[0, 0]));
+        let unit2 = info_section(4, |s| s.uleb(1).append_bytes(&[0, 1]));
+        let info_buf = [&unit1[..], &unit2[..]].concat();
+        let str_offsets_buf = Section::with_endian(Endian::Little)
+            .L32(0)
+            .L32(4)
+            .get_contents()
+            .unwrap();
+        let str_buf = b"one\0two\0";
 
-#[derive(Debug)]
-enum RangeIterInner<R: Reader> {
-    Single(Option<Range>),
-    List(RngListIter<R>),
-}
+        #[rustfmt::skip]
+        let cu_index_buf = Section::with_endian(Endian::Little)
+            // Header.
+            .L32(2).L32(3).L32(2).L32(4)
+            // Slots.
+            .L64(0).L64(0x1111).L64(0x2222).L64(0)
+            .L32(0).L32(1).L32(2).L32(0)
+            // Sections.
+            .L32(1).L32(3).L32(6)
+            // Offsets.
+            .L32(0).L32(0).L32(0)
+            .L32(unit1.len() as u32).L32(abbrev.len() as u32).L32(4)
+            // Sizes.
+            .L32(unit1.len() as u32).L32(abbrev.len() as u32).L32(4)
+            .L32(unit2.len() as u32).L32(abbrev.len() as u32).L32(4)
+            .get_contents()
+            .unwrap();
 
-impl<R: Reader> Default for RangeIter<R> {
-    fn default() -> Self {
-        RangeIter(RangeIterInner::Single(None))
-    }
-}
+        let package = DwarfPackage::load(
+            |id| -> Result<_> {
+                let data = match id {
+                    SectionId::DebugCuIndex => &cu_index_buf[..],
+                    SectionId::DebugAbbrev => &abbrev_buf[..],
+                    SectionId::DebugInfo => &info_buf[..],
+                    SectionId::DebugStr => &str_buf[..],
+                    SectionId::DebugStrOffsets => &str_offsets_buf[..],
+                    _ => &[],
+                };
+                Ok(EndianSlice::new(data, LittleEndian))
+            },
+            EndianSlice::new(&[], LittleEndian),
+        )
+        .unwrap();
+        assert_eq!(package.cu_index.unit_count(), 2);
+        assert_eq!(package.tu_index.unit_count(), 0);
 
-impl<R: Reader> RangeIter<R> {
-    /// Advance the iterator to the next range.
-    pub fn next(&mut self) -> Result<Option<Range>> {
-        match self.0 {
-            RangeIterInner::Single(ref mut range) => Ok(range.take()),
-            RangeIterInner::List(ref mut list) => list.next(),
+        let mut root = skeleton.entries();
+        root.next_dfs().unwrap();
+        let dwo_id = root
+            .current()
+            .unwrap()
+            .attr_value(constants::DW_AT_GNU_dwo_id)
+            .unwrap()
+            .and_then(|attr| attr.udata_value())
+            .unwrap();
+        let dwo = package.find_cu(DwoId(dwo_id), &parent).unwrap().unwrap();
+        assert_eq!(dwo.file_type, DwarfFileType::Dwo);
+        let mut units = dwo.units();
+        let mut unit = dwo.unit(units.next().unwrap().unwrap()).unwrap();
+        assert!(units.next().unwrap().is_none());
+        unit.copy_relocated_attributes(&skeleton);
+        assert_eq!(unit.name.map(|x| x.slice()), Some(&b"two"[..]));
+
+        let mut root = unit.entries();
+        root.next_dfs().unwrap();
+        let low_pc = root
+            .current()
+            .unwrap()
+            .attr_value(constants::DW_AT_low_pc)
+            .unwrap();
+        match low_pc {
+            Some(AttributeValue::DebugAddrIndex(index)) => {
+                assert_eq!(dwo.address(&unit, index), Ok(0x2000));
+            }
+            otherwise => panic!("Unexpected low_pc: {:?}", otherwise),
         }
-    }
-}
 
-impl<R: Reader> FallibleIterator for RangeIter<R> {
-    type Item = Range;
-    type Error = Error;
+        let dwo = package.find_cu(DwoId(0x1111), &parent).unwrap().unwrap();
+        let unit = first_unit(&dwo);
+        assert_eq!(unit.name.map(|x| x.slice()), Some(&b"one"[..]));
 
-    #[inline]
-    fn next(&mut self) -> ::std::result::Result<Option<Self::Item>, Self::Error> {
-        RangeIter::next(self)
+        assert!(package.find_cu(DwoId(0x3333), &parent).unwrap().is_none());
+        assert!(package
+            .find_tu(DebugTypeSignature(0x1111), &parent)
+            .unwrap()
+            .is_none());
+        assert!(package.cu_sections(3, &parent).is_err());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::read::EndianSlice;
-    use crate::{Endianity, LittleEndian};
+    #[test]
+    fn test_make_dwo_ranges_base() {
+        let skeleton_abbrev_buf = Section::with_endian(Endian::Little)
+            .abbrev(1, constants::DW_TAG_compile_unit, constants::DW_CHILDREN_no)
+            .abbrev_attr(
+                constants::DW_AT_GNU_ranges_base,
+                constants::DW_FORM_sec_offset,
+            )
+            .abbrev_attr_null()
+            .abbrev_null()
+            .get_contents()
+            .unwrap();
+        let skeleton_info_buf = info_section(4, |s| s.uleb(1).L32(0x10));
+        let ranges_buf = Section::with_endian(Endian::Little)
+            .L64(0)
+            .L64(0)
+            .L64(0x1000)
+            .L64(0x1100)
+            .L64(0)
+            .L64(0)
+            .get_contents()
+            .unwrap();
+        let parent = Dwarf {
+            ranges: RangeLists::new(
+                DebugRanges::new(&ranges_buf, LittleEndian),
+                DebugRngLists::new(&[], LittleEndian),
+            ),
+            ..abbrev_info_dwarf(&skeleton_abbrev_buf, &skeleton_info_buf)
+        };
+        let skeleton = first_unit(&parent);
+
+        let abbrev_buf = Section::with_endian(Endian::Little)
+            .abbrev(1, constants::DW_TAG_compile_unit, constants::DW_CHILDREN_no)
+            .abbrev_attr(constants::DW_AT_ranges, constants::DW_FORM_sec_offset)
+            .abbrev_attr_null()
+            .abbrev_null()
+            .get_contents()
+            .unwrap();
+        let info_buf = info_section(4, |s| s.uleb(1).L32(0));
+        let mut dwo = abbrev_info_dwarf(&abbrev_buf, &info_buf);
+        dwo.make_dwo(&parent);
+        let mut unit = first_unit(&dwo);
+        unit.copy_relocated_attributes(&skeleton);
+
+        let mut ranges = dwo.unit_ranges(&unit).unwrap();
+        assert_eq!(
+            ranges.next(),
+            Ok(Some(Range {
+                begin: 0x1000,
+                end: 0x1100,
+            }))
+        );
+        assert_eq!(ranges.next(), Ok(None));
+    }
 
-    /// Ensure that `Dwarf<R>` is covariant wrt R.
     #[test]
-    fn test_dwarf_variance() {
-        /// This only needs to compile.
-        fn _f<'a: 'b, 'b, E: Endianity>(x: Dwarf<EndianSlice<'a, E>>) -> Dwarf<EndianSlice<'b, E>> {
-            x
-        }
+    fn test_load_dwo() {
+        let (abbrev_buf, info_buf) = root_sections(
+            |s| s.abbrev_attr(constants::DW_AT_name, constants::DW_FORM_strp),
+            |s| s.L32(4),
+        );
+        let str_buf = b"bad\0a.c\0";
+        let addr_buf = [0x00, 0x10, 0, 0, 0, 0, 0, 0];
+        let parent = Dwarf {
+            debug_addr: DebugAddr::from(EndianSlice::new(&addr_buf, LittleEndian)),
+            debug_str: DebugStr::new(b"parent\0", LittleEndian),
+            ..Default::default()
+        };
+
+        // The `.debug_addr` section is always taken from the parent.
+        let dwo_addr_buf = [0xff; 8];
+        let dwo = Dwarf::load_dwo(
+            |id| -> Result<_> {
+                let buf: &[u8] = match id {
+                    SectionId::DebugAbbrev => &abbrev_buf,
+                    SectionId::DebugAddr => &dwo_addr_buf,
+                    SectionId::DebugInfo => &info_buf,
+                    SectionId::DebugStr => str_buf,
+                    _ => &[],
+                };
+                Ok(EndianSlice::new(buf, LittleEndian))
+            },
+            &parent,
+        )
+        .unwrap();
+        assert_eq!(dwo.file_type, DwarfFileType::Dwo);
+
+        let unit = first_unit(&dwo);
+        assert_eq!(unit.name, Some(EndianSlice::new(b"a.c", LittleEndian)));
+        assert_eq!(dwo.address(&unit, DebugAddrIndex(0)), Ok(0x1000));
     }
 
-    /// Ensure that `Unit<R>` is covariant wrt R.
     #[test]
-    fn test_dwarf_unit_variance() {
-        /// This only needs to compile.
-        fn _f<'a: 'b, 'b, E: Endianity>(x: Unit<EndianSlice<'a, E>>) -> Unit<EndianSlice<'b, E>> {
-            x
-        }
+    fn test_dwo_str_offsets_base() {
+        let abbrev_buf = Section::with_endian(Endian::Little)
+            .abbrev(1, constants::DW_TAG_compile_unit, constants::DW_CHILDREN_no)
+            .abbrev_attr(constants::DW_AT_name, constants::DW_FORM_strx1)
+            .abbrev_attr_null()
+            .abbrev_null()
+            .get_contents()
+            .unwrap();
+        let info_buf = info_section(5, |s| s.uleb(1).D8(1));
+        // The `DW_AT_str_offsets_base` is implicitly after the header.
+        let str_offsets_buf = Section::with_endian(Endian::Little)
+            .L32(12)
+            .L16(5)
+            .L16(0)
+            .L32(0)
+            .L32(4)
+            .get_contents()
+            .unwrap();
+        let str_buf = b"one\0two\0";
+        let mut dwo = Dwarf {
+            debug_str: DebugStr::new(str_buf, LittleEndian),
+            debug_str_offsets: DebugStrOffsets::from(EndianSlice::new(
+                &str_offsets_buf,
+                LittleEndian,
+            )),
+            ..abbrev_info_dwarf(&abbrev_buf, &info_buf)
+        };
+        dwo.make_dwo(&Dwarf::default());
+        let unit = first_unit(&dwo);
+        assert_eq!(unit.str_offsets_base, DebugStrOffsetsBase(8));
+        assert_eq!(
+            unit.str_offsets_count,
+            Some((DebugStrOffsetsBase(8), Ok(2)))
+        );
+        assert_eq!(unit.name.map(|x| x.slice()), Some(&b"two"[..]));
+        assert_eq!(
+            dwo.string_offset(&unit, DebugStrOffsetsIndex(1)),
+            Ok(DebugStrOffset(4))
+        );
+        assert_eq!(
+            dwo.string_offset(&unit, DebugStrOffsetsIndex(2)),
+            Err(Error::OffsetOutOfBounds)
+        );
     }
 
     #[test]
-    fn test_format_error() {
-        let owned_dwarf =
-            Dwarf::load(|_| -> Result<_> { Ok(vec![1, 2]) }, |_| Ok(vec![1, 2])).unwrap();
-        let dwarf = owned_dwarf.borrow(|section| EndianSlice::new(&section, LittleEndian));
+    fn test_str_offsets_implicit_base() {
+        let abbrev_buf = Section::with_endian(Endian::Little)
+            .abbrev(1, constants::DW_TAG_compile_unit, constants::DW_CHILDREN_no)
+            .abbrev_attr(constants::DW_AT_name, constants::DW_FORM_strx1)
+            .abbrev_attr_null()
+            .abbrev_null()
+            .get_contents()
+            .unwrap();
+        let info_buf = info_section(5, |s| s.uleb(1).D8(0));
+        let str_offsets_buf = Section::with_endian(Endian::Little)
+            .L32(12)
+            .L16(5)
+            .L16(0)
+            .L32(0)
+            .L32(4)
+            .get_contents()
+            .unwrap();
+        let str_buf = b"one\0two\0";
+        let dwarf = Dwarf {
+            debug_str: DebugStr::new(str_buf, LittleEndian),
+            debug_str_offsets: DebugStrOffsets::from(EndianSlice::new(
+                &str_offsets_buf,
+                LittleEndian,
+            )),
+            ..abbrev_info_dwarf(&abbrev_buf, &info_buf)
+        };
+        // Without a `DW_AT_str_offsets_base`, the base is after the first header.
+        let mut unit = first_unit(&dwarf);
+        assert_eq!(unit.str_offsets_base, DebugStrOffsetsBase(8));
+        assert_eq!(
+            unit.str_offsets_count,
+            Some((DebugStrOffsetsBase(8), Ok(2)))
+        );
+        assert_eq!(unit.name.map(|x| x.slice()), Some(&b"one"[..]));
+        assert_eq!(
+            dwarf.string_offset(&unit, DebugStrOffsetsIndex(1)),
+            Ok(DebugStrOffset(4))
+        );
+        assert_eq!(
+            dwarf.string_offset(&unit, DebugStrOffsetsIndex(2)),
+            Err(Error::OffsetOutOfBounds)
+        );
 
-        match dwarf.debug_str.get_str(DebugStrOffset(1)) {
-            Ok(r) => panic!("Unexpected str {:?}", r),
-            Err(e) => {
-                assert_eq!(
-                    dwarf.format_error(e),
-                    "Hit the end of input before it was expected at .debug_str+0x1"
-                );
-            }
-        }
-        match dwarf.debug_str_sup.get_str(DebugStrOffset(1)) {
-            Ok(r) => panic!("Unexpected str {:?}", r),
-            Err(e) => {
-                assert_eq!(
-                    dwarf.format_error(e),
-                    "Hit the end of input before it was expected at .debug_str(sup)+0x1"
-                );
-            }
-        }
-        assert_eq!(dwarf.format_error(Error::Io), Error::Io.description());
+        // A header that failed to parse is not parsed again.
+        unit.str_offsets_count = Some((unit.str_offsets_base, Err(Error::UnknownVersion(6))));
+        assert_eq!(
+            dwarf.string_offset(&unit, DebugStrOffsetsIndex(1)),
+            Err(Error::UnknownVersion(6))
+        );
+
+        // Changing the base ignores the count parsed for the old base.
+        unit.str_offsets_base = DebugStrOffsetsBase(12);
+        assert_eq!(
+            dwarf.string_offset(&unit, DebugStrOffsetsIndex(0)),
+            Err(Error::UnknownVersion(0))
+        );
     }
 }
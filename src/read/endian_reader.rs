@@ -626,4 +626,33 @@ mod tests {
         assert_eq!(arr, &BAD_UTF8[1..3]);
         assert_eq!(reader.bytes(), &BAD_UTF8[3..]);
     }
+
+    #[test]
+    fn test_dwarf_arc_slice() {
+        use crate::common::{DebugStrOffset, SectionId};
+        use crate::endianity::RunTimeEndian;
+        use crate::read::Dwarf;
+        use crate::vec::Vec;
+
+        // The sections are owned, so the `Dwarf` has no lifetime and can be
+        // sent to another thread.
+        let load = |data: Vec<u8>| EndianArcSlice::new(Arc::from(data), RunTimeEndian::Little);
+        let dwarf = Dwarf::load(
+            |id| -> Result<_> {
+                Ok(load(match id {
+                    SectionId::DebugStr => b"foo\0bar\0".to_vec(),
+                    _ => Vec::new(),
+                }))
+            },
+            |_| Ok(load(Vec::new())),
+        )
+        .unwrap();
+        let name = std::thread::spawn(move || {
+            let name = dwarf.debug_str.get_str(DebugStrOffset(4)).unwrap();
+            name.to_string().unwrap().into_owned()
+        })
+        .join()
+        .unwrap();
+        assert_eq!(name, "bar");
+    }
 }
@@ -1,6 +1,8 @@
 //! Working with byte slices that have an associated endianity.
 
+#[cfg(feature = "alloc")]
 use crate::borrow::Cow;
+#[cfg(feature = "alloc")]
 use crate::string::String;
 use std::ops::{Deref, Index, Range, RangeFrom, RangeTo};
 use std::str;
@@ -82,6 +84,7 @@ where
 
     /// Converts the slice to a string, including invalid characters,
     /// using `String::from_utf8_lossy`.
+    #[cfg(feature = "alloc")]
     #[inline]
     pub fn to_string_lossy(&self) -> Cow<'input, str> {
         String::from_utf8_lossy(self.slice)
@@ -284,11 +287,13 @@ where
         Ok(EndianSlice::new(slice, self.endian))
     }
 
+    #[cfg(feature = "alloc")]
     #[inline]
     fn to_slice(&self) -> Result<Cow<[u8]>> {
         Ok(self.slice.into())
     }
 
+    #[cfg(feature = "alloc")]
     #[inline]
     fn to_string(&self) -> Result<Cow<str>> {
         match str::from_utf8(self.slice) {
@@ -297,6 +302,7 @@ where
         }
     }
 
+    #[cfg(feature = "alloc")]
     #[inline]
     fn to_string_lossy(&self) -> Result<Cow<str>> {
         Ok(String::from_utf8_lossy(self.slice))
@@ -313,7 +319,34 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::endianity::NativeEndian;
+    use crate::endianity::{BigEndian, LittleEndian, NativeEndian};
+
+    #[test]
+    fn test_endian_slice_read_u24() {
+        let slice = &[1, 2, 3, 4];
+        let mut eb = EndianSlice::new(slice, LittleEndian);
+        assert_eq!(eb.read_u24(), Ok(0x03_0201));
+        assert_eq!(eb.len(), 1);
+        let mut eb = EndianSlice::new(slice, BigEndian);
+        assert_eq!(eb.read_u24(), Ok(0x01_0203));
+        match eb.read_u24() {
+            Err(Error::UnexpectedEof(_)) => {}
+            otherwise => panic!("Unexpected result: {:?}", otherwise),
+        }
+    }
+
+    #[test]
+    fn test_endian_slice_split() {
+        let slice = &[1, 2, 3, 4, 5];
+        let mut eb = EndianSlice::new(slice, NativeEndian);
+        assert_eq!(eb.split(2), Ok(EndianSlice::new(&slice[..2], NativeEndian)));
+        assert_eq!(eb, EndianSlice::new(&slice[2..], NativeEndian));
+        match eb.split(4) {
+            Err(Error::UnexpectedEof(_)) => {}
+            otherwise => panic!("Unexpected result: {:?}", otherwise),
+        }
+        assert_eq!(eb, EndianSlice::new(&slice[2..], NativeEndian));
+    }
 
     #[test]
     fn test_endian_slice_split_at() {
@@ -1,4 +1,5 @@
 use crate::vec::Vec;
+use std::cmp::Ordering;
 use std::fmt;
 use std::result;
 
@@ -105,9 +106,7 @@ impl<T> DebugLine<T> {
 }
 
 impl<R> Section<R> for DebugLine<R> {
-    fn id() -> SectionId {
-        SectionId::DebugLine
-    }
+    const ID: SectionId = SectionId::DebugLine;
 
     fn reader(&self) -> &R {
         &self.debug_line_section
@@ -259,6 +258,71 @@ where
             }
         }
     }
+
+    /// Convert this into an iterator over the address ranges covered by
+    /// each row.
+    pub fn ranges(self) -> LineRowRanges<R, Program, Offset> {
+        LineRowRanges {
+            rows: self,
+            prev: None,
+            row: None,
+        }
+    }
+}
+
+/// Iterates over the address ranges that are covered by the rows of a line
+/// number program.
+///
+/// Each row covers the addresses from its own address up to the address of
+/// the next row in the same sequence. Rows that do not cover any addresses
+/// are skipped, as are the `end_sequence` rows.
+#[derive(Debug, Clone)]
+pub struct LineRowRanges<R, Program, Offset = <R as Reader>::Offset>
+where
+    Program: LineProgram<R, Offset>,
+    R: Reader<Offset = Offset>,
+    Offset: ReaderOffset,
+{
+    rows: LineRows<R, Program, Offset>,
+    prev: Option<LineRow>,
+    row: Option<LineRow>,
+}
+
+impl<R, Program, Offset> LineRowRanges<R, Program, Offset>
+where
+    Program: LineProgram<R, Offset>,
+    R: Reader<Offset = Offset>,
+    Offset: ReaderOffset,
+{
+    /// Get a reference to the header for this line number program.
+    #[inline]
+    pub fn header(&self) -> &LineProgramHeader<R, Offset> {
+        self.rows.header()
+    }
+
+    /// Execute the line number program until the next row that covers a
+    /// non-empty address range.
+    ///
+    /// Returns the range of addresses and the row that covers them.
+    ///
+    /// Unfortunately, the references mean that this cannot be a
+    /// `FallibleIterator`.
+    pub fn next_range(&mut self) -> Result<Option<(std::ops::Range<u64>, &LineRow)>> {
+        let range = loop {
+            let row = match self.rows.next_row()? {
+                Some((_, row)) => *row,
+                None => return Ok(None),
+            };
+            let next = if row.end_sequence() { None } else { Some(row) };
+            if let Some(prev) = std::mem::replace(&mut self.prev, next) {
+                if prev.address() < row.address() {
+                    self.row = Some(prev);
+                    break prev.address()..row.address();
+                }
+            }
+        };
+        Ok(self.row.as_ref().map(|row| (range, row)))
+    }
 }
 
 /// Deprecated. `Opcode` has been renamed to `LineInstruction`.
@@ -1188,6 +1252,39 @@ where
         }
     }
 
+    /// Iterate over the directories that can be found using `directory`,
+    /// along with their directory indices.
+    ///
+    /// For DWARF version <= 4, the compilation unit directory has index 0 and
+    /// is only included if it is known, and the entries of
+    /// `include_directories` start at index 1. For DWARF version 5, the
+    /// entries of `include_directories` start at index 0, and the first entry
+    /// is the compilation unit directory.
+    pub fn directories(&self) -> impl Iterator<Item = (u64, AttributeValue<R, Offset>)> + '_ {
+        let (comp_dir, first) = if self.encoding.version <= 4 {
+            (
+                self.comp_dir
+                    .clone()
+                    .map(|dir| (0, AttributeValue::String(dir))),
+                1,
+            )
+        } else {
+            (None, 0)
+        };
+        comp_dir.into_iter().chain(
+            self.include_directories
+                .iter()
+                .enumerate()
+                .map(move |(index, dir)| (first + index as u64, dir.clone())),
+        )
+    }
+
+    /// Return the number of directories returned by `directories`.
+    pub fn directory_count(&self) -> usize {
+        let comp_dir = self.encoding.version <= 4 && self.comp_dir.is_some();
+        self.include_directories.len() + comp_dir as usize
+    }
+
     /// Get the format of a file name entry.
     pub fn file_name_entry_format(&self) -> &[FileEntryFormat] {
         &self.file_name_entry_format[..]
@@ -1229,6 +1326,27 @@ where
         &self.file_names[..]
     }
 
+    /// Iterate over the source files that appear in this header's line program,
+    /// along with their file indices.
+    ///
+    /// For DWARF version <= 4, the entries of `file_names` start at index 1.
+    /// The compilation unit file that `file` returns for index 0 is not included,
+    /// because index 0 is not valid for these versions.
+    /// For DWARF version 5, the entries start at index 0, and the first entry
+    /// is the primary source file of the compilation unit.
+    pub fn files(&self) -> impl Iterator<Item = (u64, &FileEntry<R, Offset>)> + '_ {
+        let first = if self.encoding.version <= 4 { 1 } else { 0 };
+        self.file_names
+            .iter()
+            .enumerate()
+            .map(move |(index, file)| (first + index as u64, file))
+    }
+
+    /// Return the number of source files returned by `files`.
+    pub fn file_count(&self) -> usize {
+        self.file_names.len()
+    }
+
     /// The source file with the given file index.
     ///
     /// A file index of 0 corresponds to the compilation unit file.
@@ -1293,7 +1411,7 @@ where
         }
 
         if version >= 5 {
-            address_size = rest.read_u8()?;
+            address_size = rest.read_address_size()?;
             let segment_selector_size = rest.read_u8()?;
             if segment_selector_size != 0 {
                 return Err(Error::UnsupportedSegmentSize);
@@ -1456,6 +1574,9 @@ where
     /// the line number program that can later be used with
     /// `CompleteLineProgram::resume_from`.
     ///
+    /// The sequences are returned in the order they appear in the program.
+    /// Use `IncompleteLineProgram::complete` to sort and filter them.
+    ///
     /// ```
     /// # fn foo() {
     /// use gimli::{IncompleteLineProgram, EndianSlice, NativeEndian};
@@ -1473,7 +1594,19 @@ where
     /// ```
     #[allow(clippy::type_complexity)]
     pub fn sequences(self) -> Result<(CompleteLineProgram<R, Offset>, Vec<LineSequence<R>>)> {
+        self.execute(false)
+    }
+
+    /// Execute the line number program, and optionally cache the rows of each
+    /// sequence for `CompleteLineProgram::find_row`.
+    #[allow(clippy::type_complexity)]
+    fn execute(
+        self,
+        cache_rows: bool,
+    ) -> Result<(CompleteLineProgram<R, Offset>, Vec<LineSequence<R>>)> {
         let mut sequences = Vec::new();
+        let mut sequence_rows = Vec::new();
+        let mut current_rows = Vec::new();
         let mut rows = self.rows();
         let mut instructions = rows.instructions.clone();
         let mut sequence_start_addr = None;
@@ -1484,6 +1617,9 @@ where
             }
 
             let row = &rows.row;
+            if cache_rows && !row.end_sequence() {
+                current_rows.push(*row);
+            }
             if row.end_sequence() {
                 sequence_end_addr = row.address();
             } else if sequence_start_addr.is_none() {
@@ -1494,22 +1630,81 @@ where
             }
 
             // We just finished a sequence.
-            sequences.push(LineSequence {
+            let sequence = LineSequence {
                 // In theory one could have multiple DW_LNE_end_sequence instructions
                 // in a row.
                 start: sequence_start_addr.unwrap_or(0),
                 end: sequence_end_addr,
                 instructions: instructions.remove_trailing(&rows.instructions)?,
-            });
+            };
+            if cache_rows {
+                sequence_rows.push(LineSequenceRows {
+                    start: sequence.start,
+                    end: sequence.end,
+                    rows: current_rows,
+                });
+                current_rows = Vec::new();
+            }
+            sequences.push(sequence);
             sequence_start_addr = None;
             instructions = rows.instructions.clone();
         }
 
         let program = CompleteLineProgram {
             header: rows.program.header,
+            sequence_rows,
         };
         Ok((program, sequences))
     }
+
+    /// Execute the line number program, completing the `IncompleteLineProgram`
+    /// into a `CompleteLineProgram` that can be used to efficiently find the
+    /// row for an address with `CompleteLineProgram::find_row`.
+    ///
+    /// This also returns the sequences within the line number program, sorted
+    /// by their start address. Sequences that do not cover any addresses are
+    /// omitted, as are sequences that start at a tombstone address (`-1` or
+    /// `-2`, truncated to the address size) for code that was discarded by
+    /// the linker.
+    ///
+    /// ```
+    /// # fn foo() {
+    /// use gimli::{IncompleteLineProgram, EndianSlice, NativeEndian};
+    ///
+    /// fn get_line_number_program<'a>() -> IncompleteLineProgram<EndianSlice<'a, NativeEndian>> {
+    ///     // Get a line number program from some offset in a
+    ///     // `.debug_line` section...
+    /// #   unimplemented!()
+    /// }
+    ///
+    /// let program = get_line_number_program();
+    /// let (program, _) = program.complete().unwrap();
+    /// if let Some(row) = program.find_row(0x1000) {
+    ///     println!("0x1000 is at line {:?}", row.line());
+    /// }
+    /// # }
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn complete(self) -> Result<(CompleteLineProgram<R, Offset>, Vec<LineSequence<R>>)> {
+        let (mut program, mut sequences) = self.execute(true)?;
+        let address_size = program.header.address_size();
+        sequences.retain(|sequence| is_live_sequence(sequence.start, sequence.end, address_size));
+        sequences.sort_by_key(|sequence| sequence.start);
+        program
+            .sequence_rows
+            .retain(|sequence| is_live_sequence(sequence.start, sequence.end, address_size));
+        program.sequence_rows.sort_by_key(|sequence| sequence.start);
+        Ok((program, sequences))
+    }
+}
+
+/// Return true if the sequence covers addresses, and does not start at a
+/// tombstone address for code that was discarded by the linker.
+fn is_live_sequence(start: u64, end: u64, address_size: u8) -> bool {
+    let tombstone = (!0u64)
+        .checked_shr(64 - u32::from(address_size.min(8)) * 8)
+        .unwrap_or(0);
+    start < end && start != tombstone && start != tombstone.wrapping_sub(1)
 }
 
 /// Deprecated. `CompleteLineNumberProgram` has been renamed to `CompleteLineProgram`.
@@ -1526,6 +1721,15 @@ where
     Offset: ReaderOffset,
 {
     header: LineProgramHeader<R, Offset>,
+    sequence_rows: Vec<LineSequenceRows>,
+}
+
+/// The rows of a sequence, cached when the program is completed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct LineSequenceRows {
+    start: u64,
+    end: u64,
+    rows: Vec<LineRow>,
 }
 
 impl<R, Offset> CompleteLineProgram<R, Offset>
@@ -1565,6 +1769,42 @@ where
     ) -> ResumedLineRows<'program, R, Offset> {
         ResumedLineRows::resume(self, sequence)
     }
+
+    /// Find the row that covers the given address.
+    ///
+    /// This uses the rows that were cached when the program was completed by
+    /// `IncompleteLineProgram::complete`. It always returns `None` if the
+    /// program was completed by `IncompleteLineProgram::sequences`.
+    pub fn find_row(&self, address: u64) -> Option<&LineRow> {
+        // Find the last sequence that starts at or before the address.
+        let index = self
+            .sequence_rows
+            .binary_search_by(|sequence| {
+                if sequence.start <= address {
+                    Ordering::Less
+                } else {
+                    Ordering::Greater
+                }
+            })
+            .unwrap_err();
+        let sequence = &self.sequence_rows[index.checked_sub(1)?];
+        if address >= sequence.end {
+            return None;
+        }
+
+        // Find the last row that starts at or before the address.
+        let index = sequence
+            .rows
+            .binary_search_by(|row| {
+                if row.address() <= address {
+                    Ordering::Less
+                } else {
+                    Ordering::Greater
+                }
+            })
+            .unwrap_err();
+        sequence.rows.get(index.checked_sub(1)?)
+    }
 }
 
 /// An entry in the `LineProgramHeader`'s `file_names` set.
@@ -1866,7 +2106,7 @@ fn parse_attribute<R: Reader>(
             AttributeValue::DebugStrOffsetsIndex(DebugStrOffsetsIndex(index))
         }
         constants::DW_FORM_strx3 => {
-            let index = input.read_uint(3).and_then(R::Offset::from_u64)?;
+            let index = input.read_u24().map(R::Offset::from_u32)?;
             AttributeValue::DebugStrOffsetsIndex(DebugStrOffsetsIndex(index))
         }
         constants::DW_FORM_strx4 => {
@@ -1884,7 +2124,7 @@ mod tests {
     use super::*;
     use crate::constants;
     use crate::endianity::LittleEndian;
-    use crate::read::{EndianSlice, Error};
+    use crate::read::{DebugLineStr, DebugStr, EndianSlice, Error};
     use crate::test_util::GimliSectionMethods;
     use std::u8;
     use test_assembler::{Endian, Label, LabelMaker, Section};
@@ -2168,6 +2408,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_files_directories() {
+        let mut header = make_test_header(EndianSlice::new(&[], LittleEndian));
+        header.comp_dir = Some(EndianSlice::new(b"/comp", LittleEndian));
+        header.include_directories = vec![AttributeValue::String(EndianSlice::new(
+            b"dir",
+            LittleEndian,
+        ))];
+
+        let files: Vec<_> = header.files().map(|(index, _)| index).collect();
+        assert_eq!(files, [1, 2]);
+        assert_eq!(header.file_count(), 2);
+        for (index, file) in header.files() {
+            assert_eq!(header.file(index), Some(file));
+        }
+        let directories: Vec<_> = header.directories().collect();
+        assert_eq!(
+            directories,
+            [
+                (
+                    0,
+                    AttributeValue::String(EndianSlice::new(b"/comp", LittleEndian))
+                ),
+                (
+                    1,
+                    AttributeValue::String(EndianSlice::new(b"dir", LittleEndian))
+                ),
+            ]
+        );
+        assert_eq!(header.directory_count(), 2);
+        for (index, directory) in header.directories() {
+            assert_eq!(header.directory(index), Some(directory));
+        }
+
+        header.encoding.version = 5;
+        let files: Vec<_> = header.files().map(|(index, _)| index).collect();
+        assert_eq!(files, [0, 1]);
+        for (index, file) in header.files() {
+            assert_eq!(header.file(index), Some(file));
+        }
+        let directories: Vec<_> = header.directories().collect();
+        assert_eq!(
+            directories,
+            [(
+                0,
+                AttributeValue::String(EndianSlice::new(b"dir", LittleEndian))
+            )]
+        );
+        assert_eq!(header.directory_count(), 1);
+        for (index, directory) in header.directories() {
+            assert_eq!(header.directory(index), Some(directory));
+        }
+    }
+
     fn make_test_program(
         buf: EndianSlice<LittleEndian>,
     ) -> IncompleteLineProgram<EndianSlice<LittleEndian>> {
@@ -2176,6 +2470,239 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_line_row_ranges() {
+        #[rustfmt::skip]
+        let bytes = [
+            // DW_LNE_set_address 0x1000
+            0, 9, 2, 0x00, 0x10, 0, 0, 0, 0, 0, 0,
+            // Zero length row at line 1.
+            1,
+            // Line 2 covers 0x1000..0x1010.
+            3, 1, 1,
+            // Line 3 covers 0x1010..0x1020.
+            2, 0x10, 3, 1, 1,
+            // DW_LNE_end_sequence at 0x1020
+            2, 0x10, 0, 1, 1,
+            // DW_LNE_set_address 0x800
+            0, 9, 2, 0x00, 0x08, 0, 0, 0, 0, 0, 0,
+            // Line 1 covers 0x800..0x808.
+            1,
+            // DW_LNE_end_sequence at 0x808
+            2, 0x08, 0, 1, 1,
+        ];
+        let program = make_test_program(EndianSlice::new(&bytes, LittleEndian));
+
+        let mut ranges = program.rows().ranges();
+        let mut result = Vec::new();
+        while let Some((range, row)) = ranges.next_range().unwrap() {
+            result.push((range, row.line()));
+        }
+        assert_eq!(
+            result,
+            vec![
+                (0x1000..0x1010, Some(2)),
+                (0x1010..0x1020, Some(3)),
+                (0x800..0x808, Some(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_line_rows_unknown_opcodes() {
+        #[rustfmt::skip]
+        let bytes = [
+            // DW_LNE_set_address 0x1000
+            0, 9, 2, 0x00, 0x10, 0, 0, 0, 0, 0, 0,
+            // A vendor extended opcode with 3 bytes of operands.
+            0, 4, 0x80, 1, 2, 3,
+            // An unknown standard opcode with 2 operands.
+            OPCODE_BASE, 0x81, 0x01, 5,
+            // Line 2 covers 0x1000..0x1010.
+            3, 1, 1,
+            // DW_LNE_end_sequence at 0x1010
+            2, 0x10, 0, 1, 1,
+        ];
+        let mut standard_opcode_lengths = STANDARD_OPCODE_LENGTHS.to_vec();
+        standard_opcode_lengths.push(2);
+        let mut program = make_test_program(EndianSlice::new(&bytes, LittleEndian));
+        program.header.opcode_base += 1;
+        program.header.standard_opcode_lengths =
+            EndianSlice::new(&standard_opcode_lengths, LittleEndian);
+
+        let mut instructions = program.header().instructions();
+        instructions.next_instruction(program.header()).unwrap();
+        assert_eq!(
+            instructions.next_instruction(program.header()).unwrap(),
+            Some(LineInstruction::UnknownExtended(
+                constants::DW_LNE_lo_user,
+                EndianSlice::new(&[1, 2, 3], LittleEndian)
+            ))
+        );
+        assert_eq!(
+            instructions.next_instruction(program.header()).unwrap(),
+            Some(LineInstruction::UnknownStandardN(
+                constants::DwLns(OPCODE_BASE),
+                EndianSlice::new(&[0x81, 0x01, 5], LittleEndian)
+            ))
+        );
+
+        // Unknown opcodes do not affect the rows.
+        let mut rows = program.rows();
+        let mut result = Vec::new();
+        while let Some((_, row)) = rows.next_row().unwrap() {
+            result.push((row.address(), row.line(), row.end_sequence()));
+        }
+        assert_eq!(
+            result,
+            vec![(0x1000, Some(2), false), (0x1010, Some(2), true)]
+        );
+    }
+
+    #[test]
+    fn test_line_rows_discriminator() {
+        #[rustfmt::skip]
+        let bytes = [
+            // DW_LNE_set_address 0x1000
+            0, 9, 2, 0x00, 0x10, 0, 0, 0, 0, 0, 0,
+            // DW_LNE_set_discriminator 3
+            0, 2, 4, 3,
+            // Two rows at line 1.
+            1, 2, 0x10, 1,
+            // DW_LNE_end_sequence at 0x1020
+            2, 0x10, 0, 1, 1,
+        ];
+        let program = make_test_program(EndianSlice::new(&bytes, LittleEndian));
+
+        let mut rows = program.rows();
+        let mut result = Vec::new();
+        while let Some((_, row)) = rows.next_row().unwrap() {
+            result.push((row.address(), row.line(), row.discriminator()));
+        }
+        // The discriminator is reset after each row.
+        assert_eq!(
+            result,
+            vec![
+                (0x1000, Some(1), 3),
+                (0x1010, Some(1), 0),
+                (0x1020, Some(1), 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_line_rows_op_index() {
+        #[rustfmt::skip]
+        let bytes = [
+            // DW_LNE_set_address 0x1000
+            0, 9, 2, 0x00, 0x10, 0, 0, 0, 0, 0, 0,
+            // Special opcodes with an operation advance of 0, 2 and 2.
+            16, 40, 40,
+            // DW_LNS_const_add_pc, which has an operation advance of 20.
+            8, 1,
+            // Special opcode with an operation advance of 1.
+            28,
+            // DW_LNS_fixed_advance_pc 0x10
+            9, 0x10, 0x00, 1,
+            // DW_LNE_end_sequence
+            0, 1, 1,
+        ];
+        let mut program = make_test_program(EndianSlice::new(&bytes, LittleEndian));
+        program.header.line_encoding = LineEncoding {
+            minimum_instruction_length: 4,
+            maximum_operations_per_instruction: 3,
+            ..program.header.line_encoding
+        };
+
+        let mut rows = program.rows();
+        let mut result = Vec::new();
+        while let Some((_, row)) = rows.next_row().unwrap() {
+            result.push((row.address(), row.op_index()));
+        }
+        assert_eq!(
+            result,
+            vec![
+                (0x1000, 0),
+                (0x1000, 2),
+                (0x1004, 1),
+                (0x1020, 0),
+                (0x1020, 1),
+                (0x1030, 0),
+                (0x1030, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_complete_find_row() {
+        #[rustfmt::skip]
+        let bytes = [
+            // DW_LNE_set_address 0x1000
+            0, 9, 2, 0x00, 0x10, 0, 0, 0, 0, 0, 0,
+            // Line 1 covers 0x1000..0x1010, line 2 covers 0x1010..0x1020.
+            1, 2, 0x10, 3, 1, 1,
+            // DW_LNE_end_sequence at 0x1020
+            2, 0x10, 0, 1, 1,
+            // DW_LNE_set_address 0x800
+            0, 9, 2, 0x00, 0x08, 0, 0, 0, 0, 0, 0,
+            // Zero length row at line 1, then line 5 covers 0x800..0x808.
+            1, 3, 4, 1,
+            // DW_LNE_end_sequence at 0x808
+            2, 0x08, 0, 1, 1,
+            // An empty sequence at 0x2000.
+            0, 9, 2, 0x00, 0x20, 0, 0, 0, 0, 0, 0,
+            1, 0, 1, 1,
+            // A sequence at the tombstone address.
+            0, 9, 2, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            1, 2, 0x01, 0, 1, 1,
+        ];
+        let program = make_test_program(EndianSlice::new(&bytes, LittleEndian));
+
+        let (program, sequences) = program.complete().unwrap();
+        let sequences: Vec<_> = sequences
+            .iter()
+            .map(|sequence| (sequence.start, sequence.end))
+            .collect();
+        assert_eq!(sequences, vec![(0x800, 0x808), (0x1000, 0x1020)]);
+
+        check_find_row(&program);
+
+        // `sequences` leaves the sequences unsorted and unfiltered, and
+        // does not cache the rows for `find_row`.
+        let program = make_test_program(EndianSlice::new(&bytes, LittleEndian));
+        let (program, sequences) = program.sequences().unwrap();
+        let sequences: Vec<_> = sequences
+            .iter()
+            .map(|sequence| (sequence.start, sequence.end))
+            .collect();
+        assert_eq!(
+            sequences,
+            vec![
+                (0x1000, 0x1020),
+                (0x800, 0x808),
+                (0x2000, 0x2000),
+                (0xffff_ffff_ffff_fffe, 0xffff_ffff_ffff_ffff),
+            ]
+        );
+        assert_eq!(program.find_row(0x800), None);
+        assert_eq!(program.find_row(0x1000), None);
+
+        fn check_find_row(program: &CompleteLineProgram<EndianSlice<LittleEndian>>) {
+            let line = |address| program.find_row(address).map(LineRow::line);
+            assert_eq!(line(0x7ff), None);
+            assert_eq!(line(0x800), Some(Some(5)));
+            assert_eq!(line(0x807), Some(Some(5)));
+            assert_eq!(line(0x808), None);
+            assert_eq!(line(0x1000), Some(Some(1)));
+            assert_eq!(line(0x100f), Some(Some(1)));
+            assert_eq!(line(0x1010), Some(Some(2)));
+            assert_eq!(line(0x101f), Some(Some(2)));
+            assert_eq!(line(0x1020), None);
+            assert_eq!(line(0x2000), None);
+            assert_eq!(line(0xffff_ffff_ffff_fffe), None);
+        }
+    }
+
     #[test]
     fn test_parse_special_opcodes() {
         for i in OPCODE_BASE..u8::MAX {
@@ -2998,4 +3525,167 @@ mod tests {
             assert_eq!(header.file(0), Some(&expected_file_names[0]));
         }
     }
+
+    #[test]
+    fn test_parse_debug_line_v5_file_entry_order() {
+        let md5 = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+        let length = Label::new();
+        let header_length = Label::new();
+        let start = Label::new();
+        let header_start = Label::new();
+        let end = Label::new();
+        let header_end = Label::new();
+        let section = Section::with_endian(Endian::Little)
+            .initial_length(Format::Dwarf32, &length, &start)
+            .D16(5)
+            // Address size.
+            .D8(4)
+            // Segment selector size.
+            .D8(0)
+            .L32(&header_length)
+            .mark(&header_start)
+            // Minimum instruction length.
+            .D8(1)
+            // Maximum operations per byte.
+            .D8(1)
+            // Default is_stmt.
+            .D8(1)
+            // Line base.
+            .D8(0)
+            // Line range.
+            .D8(1)
+            // Opcode base.
+            .D8(1)
+            // Directory entry format count.
+            .D8(1)
+            .uleb(constants::DW_LNCT_path.0 as u64)
+            .uleb(constants::DW_FORM_string.0 as u64)
+            // Directory count.
+            .D8(1)
+            .append_bytes(b"dir\0")
+            // File entry format count, with the path in neither the first nor
+            // the last column, and an unknown content type.
+            .D8(6)
+            .uleb(constants::DW_LNCT_MD5.0 as u64)
+            .uleb(constants::DW_FORM_data16.0 as u64)
+            .uleb(constants::DW_LNCT_size.0 as u64)
+            .uleb(constants::DW_FORM_udata.0 as u64)
+            .uleb(constants::DW_LNCT_path.0 as u64)
+            .uleb(constants::DW_FORM_string.0 as u64)
+            .uleb(constants::DW_LNCT_lo_user.0 as u64)
+            .uleb(constants::DW_FORM_data2.0 as u64)
+            .uleb(constants::DW_LNCT_timestamp.0 as u64)
+            .uleb(constants::DW_FORM_data4.0 as u64)
+            .uleb(constants::DW_LNCT_directory_index.0 as u64)
+            .uleb(constants::DW_FORM_udata.0 as u64)
+            // File count.
+            .D8(1)
+            .append_bytes(&md5)
+            .uleb(0x1234)
+            .append_bytes(b"file\0")
+            .L16(0xffff)
+            .L32(0x5678_9abc)
+            .uleb(0)
+            .mark(&header_end)
+            .mark(&end);
+        length.set_const((&end - &start) as u64);
+        header_length.set_const((&header_end - &header_start) as u64);
+        let section = section.get_contents().unwrap();
+
+        let input = &mut EndianSlice::new(&section, LittleEndian);
+        let header = LineProgramHeader::parse(input, DebugLineOffset(0), 0, None, None)
+            .expect("should parse header ok");
+        assert!(header.file_has_md5());
+        assert!(header.file_has_timestamp());
+        assert!(header.file_has_size());
+
+        let file = header.file(0).unwrap();
+        assert_eq!(
+            file.path_name(),
+            AttributeValue::String(EndianSlice::new(b"file", LittleEndian))
+        );
+        assert_eq!(file.directory_index(), 0);
+        assert_eq!(file.timestamp(), 0x5678_9abc);
+        assert_eq!(file.size(), 0x1234);
+        assert_eq!(file.md5(), &md5);
+    }
+
+    #[test]
+    fn test_parse_debug_line_v5_line_strp() {
+        for format in vec![Format::Dwarf32, Format::Dwarf64] {
+            let length = Label::new();
+            let header_length = Label::new();
+            let start = Label::new();
+            let header_start = Label::new();
+            let end = Label::new();
+            let header_end = Label::new();
+            let section = Section::with_endian(Endian::Little)
+                .initial_length(format, &length, &start)
+                .D16(5)
+                // Address size.
+                .D8(8)
+                // Segment selector size.
+                .D8(0)
+                .word_label(format.word_size(), &header_length)
+                .mark(&header_start)
+                // Minimum instruction length.
+                .D8(1)
+                // Maximum operations per byte.
+                .D8(1)
+                // Default is_stmt.
+                .D8(1)
+                // Line base.
+                .D8(-5i8 as u8)
+                // Line range.
+                .D8(14)
+                // Opcode base.
+                .D8(1)
+                // Directory entry format count.
+                .D8(1)
+                .uleb(constants::DW_LNCT_path.0 as u64)
+                .uleb(constants::DW_FORM_line_strp.0 as u64)
+                // Directory count.
+                .uleb(2)
+                .word(format.word_size(), 0x10)
+                .word(format.word_size(), 0x20)
+                // File entry format count.
+                .D8(2)
+                .uleb(constants::DW_LNCT_path.0 as u64)
+                .uleb(constants::DW_FORM_line_strp.0 as u64)
+                .uleb(constants::DW_LNCT_directory_index.0 as u64)
+                .uleb(constants::DW_FORM_udata.0 as u64)
+                // File count.
+                .uleb(1)
+                .word(format.word_size(), 0x30)
+                .uleb(1)
+                .mark(&header_end)
+                .mark(&end);
+            length.set_const((&end - &start) as u64);
+            header_length.set_const((&header_end - &header_start) as u64);
+            let section = section.get_contents().unwrap();
+
+            let input = &mut EndianSlice::new(&section, LittleEndian);
+            let header = LineProgramHeader::parse(input, DebugLineOffset(0), 0, None, None)
+                .expect("should parse header ok");
+            assert_eq!(header.line_base(), -5);
+            assert_eq!(
+                header.include_directories(),
+                &[
+                    AttributeValue::DebugLineStrRef(DebugLineStrOffset(0x10)),
+                    AttributeValue::DebugLineStrRef(DebugLineStrOffset(0x20)),
+                ]
+            );
+            let file = header.file(0).unwrap();
+            assert_eq!(
+                file.path_name(),
+                AttributeValue::DebugLineStrRef(DebugLineStrOffset(0x30))
+            );
+            assert_eq!(file.directory_index(), 1);
+            assert_eq!(
+                file.directory(&header),
+                Some(AttributeValue::DebugLineStrRef(DebugLineStrOffset(0x20)))
+            );
+            assert!(!header.file_has_md5());
+        }
+    }
 }
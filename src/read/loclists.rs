@@ -41,9 +41,7 @@ where
 }
 
 impl<R> Section<R> for DebugLoc<R> {
-    fn id() -> SectionId {
-        SectionId::DebugLoc
-    }
+    const ID: SectionId = SectionId::DebugLoc;
 
     fn reader(&self) -> &R {
         &self.section
@@ -87,9 +85,7 @@ where
 }
 
 impl<R> Section<R> for DebugLocLists<R> {
-    fn id() -> SectionId {
-        SectionId::DebugLocLists
-    }
+    const ID: SectionId = SectionId::DebugLocLists;
 
     fn reader(&self) -> &R {
         &self.section
@@ -142,7 +138,7 @@ fn parse_header<R: Reader>(input: &mut R) -> Result<LocListsHeader> {
         return Err(Error::UnknownVersion(u64::from(version)));
     }
 
-    let address_size = input.read_u8()?;
+    let address_size = input.read_address_size()?;
     let segment_selector_size = input.read_u8()?;
     if segment_selector_size != 0 {
         return Err(Error::UnsupportedSegmentSize);
@@ -178,6 +174,19 @@ impl<R> LocationLists<R> {
     }
 }
 
+impl<R: Default> LocationLists<R> {
+    /// Construct a new `LocationLists` instance from the data in the `.debug_loclists`
+    /// section, with an empty `.debug_loc` section.
+    ///
+    /// This is sufficient if all units use DWARF version 5.
+    pub fn from_debug_loclists(debug_loclists: DebugLocLists<R>) -> LocationLists<R> {
+        LocationLists {
+            debug_loc: Default::default(),
+            debug_loclists,
+        }
+    }
+}
+
 impl<T> LocationLists<T> {
     /// Create a `LocationLists` that references the data in `self`.
     ///
@@ -263,6 +272,9 @@ impl<R: Reader> LocationLists<R> {
     /// This is an offset that points to the first entry following the header.
     ///
     /// The `index` is the value of a `DW_FORM_loclistx` attribute.
+    ///
+    /// Only the `.debug_loclists` section is used, since the `.debug_loc`
+    /// section does not have an offset table.
     pub fn get_offset(
         &self,
         unit_encoding: Encoding,
@@ -480,6 +492,14 @@ impl<R: Reader> FallibleIterator for RawLocListIter<R> {
 /// This iterator internally handles processing of base address selection entries
 /// and list end entries.  Thus, it only returns location entries that are valid
 /// and already adjusted for the base address.
+///
+/// Base addresses given by `DW_LLE_base_addressx` entries are read from `.debug_addr`.
+///
+/// A `DW_LLE_default_location` entry is returned with a range covering the entire
+/// address space. Its expression only applies to addresses that are not covered by
+/// any other entry in the list, regardless of the order of the entries, so consumers
+/// must not let a default entry override the ranges of earlier entries.
+/// `Dwarf::location_at` implements this lookup.
 #[derive(Debug)]
 pub struct LocListIter<R: Reader> {
     raw: RawLocListIter<R>,
@@ -512,12 +532,19 @@ impl<R: Reader> LocListIter<R> {
 
     /// Advance the iterator to the next location.
     pub fn next(&mut self) -> Result<Option<LocationListEntry<R>>> {
+        Ok(self.next_entry()?.map(|(location, _)| location))
+    }
+
+    /// Advance the iterator to the next location, and also return whether
+    /// the location is from a `DW_LLE_default_location` entry.
+    pub(crate) fn next_entry(&mut self) -> Result<Option<(LocationListEntry<R>, bool)>> {
         loop {
             let raw_loc = match self.raw.next()? {
                 Some(loc) => loc,
                 None => return Ok(None),
             };
 
+            let mut is_default = false;
             let (range, data) = match raw_loc {
                 RawLocListEntry::BaseAddress { addr } => {
                     self.base_address = addr;
@@ -538,16 +565,19 @@ impl<R: Reader> LocListIter<R> {
                     data,
                 } => {
                     let begin = self.get_address(begin)?;
-                    let end = begin + length;
+                    let end = begin.wrapping_add(length);
                     (Range { begin, end }, data)
                 }
-                RawLocListEntry::DefaultLocation { data } => (
-                    Range {
-                        begin: 0,
-                        end: u64::max_value(),
-                    },
-                    data,
-                ),
+                RawLocListEntry::DefaultLocation { data } => {
+                    is_default = true;
+                    (
+                        Range {
+                            begin: 0,
+                            end: u64::MAX,
+                        },
+                        data,
+                    )
+                }
                 RawLocListEntry::AddressOrOffsetPair { begin, end, data }
                 | RawLocListEntry::OffsetPair { begin, end, data } => {
                     let mut range = Range { begin, end };
@@ -562,7 +592,7 @@ impl<R: Reader> LocListIter<R> {
                 } => (
                     Range {
                         begin,
-                        end: begin + length,
+                        end: begin.wrapping_add(length),
                     },
                     data,
                 ),
@@ -573,7 +603,7 @@ impl<R: Reader> LocListIter<R> {
                 return Err(Error::InvalidLocationAddressRange);
             }
 
-            return Ok(Some(LocationListEntry { range, data }));
+            return Ok(Some((LocationListEntry { range, data }, is_default)));
         }
     }
 }
@@ -591,6 +621,8 @@ impl<R: Reader> FallibleIterator for LocListIter<R> {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct LocationListEntry<R: Reader> {
     /// The address range that this location is valid for.
+    ///
+    /// For a `DW_LLE_default_location` entry, this is the entire address space.
     pub range: Range,
 
     /// The data containing a single location description.
@@ -1382,6 +1414,82 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_locations_addrx() {
+        let encoding = Encoding {
+            format: Format::Dwarf32,
+            version: 5,
+            address_size: 4,
+        };
+        let section = Section::with_endian(Endian::Little)
+            .L32(0x0100_0000)
+            .L32(0x0200_0000);
+        let buf = section.get_contents().unwrap();
+        let debug_addr = &DebugAddr::from(EndianSlice::new(&buf, LittleEndian));
+        let debug_addr_base = DebugAddrBase(0);
+
+        #[rustfmt::skip]
+        let section = Section::with_endian(Endian::Little)
+            // A StartxEndx with an empty range.
+            .L8(2).uleb(1).uleb(1).uleb(4).L32(1)
+            // A StartxLength.
+            .L8(3).uleb(0).uleb(0x100).uleb(4).L32(2)
+            // A StartxLength that overflows.
+            .L8(3).uleb(1).uleb(0xffff_ffff_ffff_ffff).uleb(4).L32(3)
+            // A StartxLength with an index past the end of `.debug_addr`.
+            .L8(3).uleb(2).uleb(0x100).uleb(4).L32(4);
+        let buf = section.get_contents().unwrap();
+        let debug_loc = DebugLoc::new(&[], LittleEndian);
+        let debug_loclists = DebugLocLists::new(&buf, LittleEndian);
+        let loclists = LocationLists::new(debug_loc, debug_loclists);
+        let mut locations = loclists
+            .locations(
+                LocationListsOffset(0),
+                encoding,
+                0,
+                debug_addr,
+                debug_addr_base,
+            )
+            .unwrap();
+
+        assert_eq!(
+            locations.next(),
+            Ok(Some(LocationListEntry {
+                range: Range {
+                    begin: 0x0200_0000,
+                    end: 0x0200_0000,
+                },
+                data: Expression(EndianSlice::new(&[1, 0, 0, 0], LittleEndian)),
+            }))
+        );
+        assert_eq!(
+            locations.next(),
+            Ok(Some(LocationListEntry {
+                range: Range {
+                    begin: 0x0100_0000,
+                    end: 0x0100_0100,
+                },
+                data: Expression(EndianSlice::new(&[2, 0, 0, 0], LittleEndian)),
+            }))
+        );
+        assert_eq!(locations.next(), Err(Error::InvalidLocationAddressRange));
+        assert_eq!(locations.next(), Ok(None));
+
+        let mut locations = loclists
+            .locations(
+                LocationListsOffset(34),
+                encoding,
+                0,
+                debug_addr,
+                debug_addr_base,
+            )
+            .unwrap();
+        match locations.next() {
+            Err(Error::UnexpectedEof(_)) => {}
+            otherwise => panic!("Unexpected result: {:?}", otherwise),
+        }
+    }
+
     #[test]
     fn test_get_offset() {
         for format in vec![Format::Dwarf32, Format::Dwarf64] {
@@ -1411,9 +1519,8 @@ mod tests {
             length.set_const((&end - &start) as u64);
             let section = section.get_contents().unwrap();
 
-            let debug_loc = DebugLoc::from(EndianSlice::new(&[], LittleEndian));
             let debug_loclists = DebugLocLists::from(EndianSlice::new(&section, LittleEndian));
-            let locations = LocationLists::new(debug_loc, debug_loclists);
+            let locations = LocationLists::from_debug_loclists(debug_loclists);
 
             let base = DebugLocListsBase((&first - &zero) as usize);
             assert_eq!(
@@ -29,60 +29,38 @@ pub trait LookupParser<R: Reader> {
 }
 
 #[derive(Clone, Debug)]
-pub struct DebugLookup<R, Parser>
-where
-    R: Reader,
-    Parser: LookupParser<R>,
-{
-    input_buffer: R,
-    phantom: PhantomData<Parser>,
-}
-
-impl<R, Parser> From<R> for DebugLookup<R, Parser>
+pub struct LookupEntryIter<R, Parser>
 where
     R: Reader,
     Parser: LookupParser<R>,
 {
-    fn from(input_buffer: R) -> Self {
-        DebugLookup {
-            input_buffer,
-            phantom: PhantomData,
-        }
-    }
+    current_set: Option<(R, Parser::Header)>, // Only none at the very beginning and end.
+    remaining_input: R,
 }
 
-impl<R, Parser> DebugLookup<R, Parser>
+impl<R, Parser> LookupEntryIter<R, Parser>
 where
     R: Reader,
     Parser: LookupParser<R>,
 {
-    pub fn items(&self) -> LookupEntryIter<R, Parser> {
+    /// Iterate the entries of all sets in `input`.
+    pub fn new(input: R) -> Self {
         LookupEntryIter {
             current_set: None,
-            remaining_input: self.input_buffer.clone(),
+            remaining_input: input,
         }
     }
 
-    pub fn reader(&self) -> &R {
-        &self.input_buffer
+    /// Iterate the entries of a single set.
+    pub fn new_set(input: R, header: Parser::Header) -> Self {
+        let mut remaining_input = input.clone();
+        remaining_input.empty();
+        LookupEntryIter {
+            current_set: Some((input, header)),
+            remaining_input,
+        }
     }
-}
 
-#[derive(Clone, Debug)]
-pub struct LookupEntryIter<R, Parser>
-where
-    R: Reader,
-    Parser: LookupParser<R>,
-{
-    current_set: Option<(R, Parser::Header)>, // Only none at the very beginning and end.
-    remaining_input: R,
-}
-
-impl<R, Parser> LookupEntryIter<R, Parser>
-where
-    R: Reader,
-    Parser: LookupParser<R>,
-{
     /// Advance the iterator and return the next entry.
     ///
     /// Returns the newly parsed entry as `Ok(Some(Parser::Entry))`. Returns
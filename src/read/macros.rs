@@ -0,0 +1,714 @@
+use crate::common::{
+    DebugLineOffset, DebugMacinfoOffset, DebugMacroOffset, DebugStrOffset, DebugStrOffsetsIndex,
+    Format, SectionId,
+};
+use crate::constants;
+use crate::endianity::Endianity;
+#[cfg(feature = "alloc")]
+use crate::read::{AttributeValue, Dwarf, Unit};
+use crate::read::{EndianSlice, Error, Reader, ReaderOffset, Result, Section};
+use fallible_iterator::FallibleIterator;
+
+/// The `DebugMacinfo` struct represents the macro information found in the
+/// `.debug_macinfo` section.
+///
+/// This section is used by DWARF versions 2 to 4. DWARF version 5 uses the
+/// `.debug_macro` section instead.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DebugMacinfo<R> {
+    section: R,
+}
+
+impl<'input, Endian> DebugMacinfo<EndianSlice<'input, Endian>>
+where
+    Endian: Endianity,
+{
+    /// Construct a new `DebugMacinfo` instance from the data in the
+    /// `.debug_macinfo` section.
+    ///
+    /// It is the caller's responsibility to read the `.debug_macinfo` section
+    /// and present it as a `&[u8]` slice. That means using some ELF loader on
+    /// Linux, a Mach-O loader on OSX, etc.
+    ///
+    /// ```
+    /// use gimli::{DebugMacinfo, LittleEndian};
+    ///
+    /// # let buf = [];
+    /// # let read_debug_macinfo_section_somehow = || &buf;
+    /// let debug_macinfo = DebugMacinfo::new(read_debug_macinfo_section_somehow(), LittleEndian);
+    /// ```
+    pub fn new(section: &'input [u8], endian: Endian) -> Self {
+        Self::from(EndianSlice::new(section, endian))
+    }
+}
+
+impl<R: Reader> DebugMacinfo<R> {
+    /// Iterate over the macro information entries at the given offset.
+    ///
+    /// The `offset` is the value of the `DW_AT_macro_info` attribute of a
+    /// compilation unit.
+    ///
+    /// ```
+    /// use gimli::{DebugMacinfo, DebugMacinfoOffset, LittleEndian, MacroEntry};
+    ///
+    /// let buf = [0x01, 0x02, b'A', b' ', b'1', 0x00, 0x00];
+    /// let debug_macinfo = DebugMacinfo::new(&buf, LittleEndian);
+    /// let mut entries = debug_macinfo.get_macinfo(DebugMacinfoOffset(0)).unwrap();
+    /// match entries.next().unwrap() {
+    ///     Some(MacroEntry::Define { line, .. }) => assert_eq!(line, 2),
+    ///     _ => unreachable!(),
+    /// }
+    /// assert!(entries.next().unwrap().is_none());
+    /// ```
+    pub fn get_macinfo(&self, offset: DebugMacinfoOffset<R::Offset>) -> Result<MacroIter<R>> {
+        let mut input = self.section.clone();
+        input.skip(offset.0)?;
+        Ok(MacroIter {
+            input,
+            format: Format::Dwarf32,
+            is_macro: false,
+        })
+    }
+}
+
+impl<T> DebugMacinfo<T> {
+    /// Create a `DebugMacinfo` section that references the data in `self`.
+    ///
+    /// This is useful when `R` implements `Reader` but `T` does not.
+    ///
+    /// ## Example Usage
+    ///
+    /// ```rust,no_run
+    /// # let load_section = || unimplemented!();
+    /// // Read the DWARF section into a `Vec` with whatever object loader you're using.
+    /// let owned_section: gimli::DebugMacinfo<Vec<u8>> = load_section();
+    /// // Create a reference to the DWARF section.
+    /// let section = owned_section.borrow(|section| {
+    ///     gimli::EndianSlice::new(&section, gimli::LittleEndian)
+    /// });
+    /// ```
+    pub fn borrow<'a, F, R>(&'a self, mut borrow: F) -> DebugMacinfo<R>
+    where
+        F: FnMut(&'a T) -> R,
+    {
+        borrow(&self.section).into()
+    }
+}
+
+impl<R> Section<R> for DebugMacinfo<R> {
+    const ID: SectionId = SectionId::DebugMacinfo;
+
+    fn reader(&self) -> &R {
+        &self.section
+    }
+}
+
+impl<R> From<R> for DebugMacinfo<R> {
+    fn from(section: R) -> Self {
+        DebugMacinfo { section }
+    }
+}
+
+/// The `DebugMacro` struct represents the macro information found in the
+/// `.debug_macro` section.
+///
+/// This section is used by DWARF version 5, and by the GNU extensions to
+/// DWARF version 4.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DebugMacro<R> {
+    section: R,
+}
+
+impl<'input, Endian> DebugMacro<EndianSlice<'input, Endian>>
+where
+    Endian: Endianity,
+{
+    /// Construct a new `DebugMacro` instance from the data in the
+    /// `.debug_macro` section.
+    ///
+    /// It is the caller's responsibility to read the `.debug_macro` section
+    /// and present it as a `&[u8]` slice. That means using some ELF loader on
+    /// Linux, a Mach-O loader on OSX, etc.
+    ///
+    /// ```
+    /// use gimli::{DebugMacro, LittleEndian};
+    ///
+    /// # let buf = [];
+    /// # let read_debug_macro_section_somehow = || &buf;
+    /// let debug_macro = DebugMacro::new(read_debug_macro_section_somehow(), LittleEndian);
+    /// ```
+    pub fn new(section: &'input [u8], endian: Endian) -> Self {
+        Self::from(EndianSlice::new(section, endian))
+    }
+}
+
+impl<R: Reader> DebugMacro<R> {
+    /// Parse the header of the macro unit at the given offset.
+    ///
+    /// The `offset` is the value of the `DW_AT_macros` or `DW_AT_GNU_macros`
+    /// attribute of a compilation unit, or the offset of a
+    /// `DW_MACRO_import` entry.
+    pub fn header(&self, offset: DebugMacroOffset<R::Offset>) -> Result<MacroUnitHeader<R>> {
+        let mut input = self.section.clone();
+        input.skip(offset.0)?;
+
+        let version = input.read_u16()?;
+        if version != 4 && version != 5 {
+            return Err(Error::UnknownVersion(u64::from(version)));
+        }
+
+        let flags = input.read_u8()?;
+        let format = if flags & MACRO_OFFSET_SIZE_FLAG != 0 {
+            Format::Dwarf64
+        } else {
+            Format::Dwarf32
+        };
+        let debug_line_offset = if flags & MACRO_DEBUG_LINE_OFFSET_FLAG != 0 {
+            Some(DebugLineOffset(input.read_offset(format)?))
+        } else {
+            None
+        };
+        if flags & MACRO_OPCODE_OPERANDS_TABLE_FLAG != 0 {
+            // Skip the table. We only use it for entry types that we don't
+            // know, and we return an error for those instead.
+            let count = input.read_u8()?;
+            for _ in 0..count {
+                let _opcode = input.read_u8()?;
+                let operands = R::Offset::from_u64(input.read_uleb128()?)?;
+                input.skip(operands)?;
+            }
+        }
+
+        Ok(MacroUnitHeader {
+            version,
+            format,
+            debug_line_offset,
+            entries: input,
+        })
+    }
+
+    /// Iterate over the macro information entries of the macro unit at the
+    /// given offset.
+    ///
+    /// This is a convenience method for parsing the header with `header` and
+    /// then calling `MacroUnitHeader::entries`.
+    pub fn get_macros(&self, offset: DebugMacroOffset<R::Offset>) -> Result<MacroIter<R>> {
+        self.header(offset).map(|header| header.entries())
+    }
+}
+
+impl<T> DebugMacro<T> {
+    /// Create a `DebugMacro` section that references the data in `self`.
+    ///
+    /// This is useful when `R` implements `Reader` but `T` does not.
+    ///
+    /// ## Example Usage
+    ///
+    /// ```rust,no_run
+    /// # let load_section = || unimplemented!();
+    /// // Read the DWARF section into a `Vec` with whatever object loader you're using.
+    /// let owned_section: gimli::DebugMacro<Vec<u8>> = load_section();
+    /// // Create a reference to the DWARF section.
+    /// let section = owned_section.borrow(|section| {
+    ///     gimli::EndianSlice::new(&section, gimli::LittleEndian)
+    /// });
+    /// ```
+    pub fn borrow<'a, F, R>(&'a self, mut borrow: F) -> DebugMacro<R>
+    where
+        F: FnMut(&'a T) -> R,
+    {
+        borrow(&self.section).into()
+    }
+}
+
+impl<R> Section<R> for DebugMacro<R> {
+    const ID: SectionId = SectionId::DebugMacro;
+
+    fn reader(&self) -> &R {
+        &self.section
+    }
+}
+
+impl<R> From<R> for DebugMacro<R> {
+    fn from(section: R) -> Self {
+        DebugMacro { section }
+    }
+}
+
+const MACRO_OFFSET_SIZE_FLAG: u8 = 0x01;
+const MACRO_DEBUG_LINE_OFFSET_FLAG: u8 = 0x02;
+const MACRO_OPCODE_OPERANDS_TABLE_FLAG: u8 = 0x04;
+
+/// The header of a macro unit in the `.debug_macro` section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacroUnitHeader<R: Reader> {
+    version: u16,
+    format: Format,
+    debug_line_offset: Option<DebugLineOffset<R::Offset>>,
+    entries: R,
+}
+
+impl<R: Reader> MacroUnitHeader<R> {
+    /// Return the version of the macro unit.
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    /// Return the DWARF format of the offsets in the macro unit.
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Return the offset of the line number program in the `.debug_line`
+    /// section, if it is present.
+    ///
+    /// The file indices of `MacroEntry::StartFile` entries refer to the
+    /// file names of this line number program.
+    pub fn debug_line_offset(&self) -> Option<DebugLineOffset<R::Offset>> {
+        self.debug_line_offset
+    }
+
+    /// Iterate over the entries of the macro unit.
+    pub fn entries(&self) -> MacroIter<R> {
+        MacroIter {
+            input: self.entries.clone(),
+            format: self.format,
+            is_macro: true,
+        }
+    }
+}
+
+/// The string of a macro definition or undefinition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MacroString<R, Offset = <R as Reader>::Offset>
+where
+    R: Reader<Offset = Offset>,
+    Offset: ReaderOffset,
+{
+    /// The string is directly embedded in the entry.
+    Direct(R),
+    /// An offset into the `.debug_str` section.
+    StringPointer(DebugStrOffset<Offset>),
+    /// An index into the `.debug_str_offsets` entries for the compilation
+    /// unit.
+    IndirectStringPointer(DebugStrOffsetsIndex<Offset>),
+    /// An offset into the `.debug_str` section of the supplementary object
+    /// file.
+    Supplementary(DebugStrOffset<Offset>),
+}
+
+#[cfg(feature = "alloc")]
+impl<R: Reader> MacroString<R> {
+    /// Return the string, resolving any string section references using
+    /// `dwarf`.
+    ///
+    /// The `unit` is the compilation unit that refers to the macro
+    /// information, and is only used for `IndirectStringPointer`.
+    pub fn string(&self, dwarf: &Dwarf<R>, unit: &Unit<R>) -> Result<R> {
+        let value = match *self {
+            MacroString::Direct(ref string) => return Ok(string.clone()),
+            MacroString::StringPointer(offset) => AttributeValue::DebugStrRef(offset),
+            MacroString::IndirectStringPointer(index) => {
+                AttributeValue::DebugStrOffsetsIndex(index)
+            }
+            MacroString::Supplementary(offset) => AttributeValue::DebugStrRefSup(offset),
+        };
+        dwarf.attr_string(unit, value)
+    }
+}
+
+/// An entry in the `.debug_macinfo` or `.debug_macro` section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MacroEntry<R, Offset = <R as Reader>::Offset>
+where
+    R: Reader<Offset = Offset>,
+    Offset: ReaderOffset,
+{
+    /// A macro definition.
+    Define {
+        /// The line number of the definition.
+        line: u64,
+        /// The macro name and definition.
+        text: MacroString<R, Offset>,
+    },
+    /// A macro undefinition.
+    Undef {
+        /// The line number of the undefinition.
+        line: u64,
+        /// The macro name.
+        text: MacroString<R, Offset>,
+    },
+    /// The start of a source file.
+    StartFile {
+        /// The line number of the include directive, or 0 for the primary
+        /// source file.
+        line: u64,
+        /// The index of the file in the line number program header.
+        file: u64,
+    },
+    /// The end of the source file started by the matching `StartFile`.
+    EndFile,
+    /// The entries of another macro unit in the `.debug_macro` section are
+    /// included at this point.
+    Import {
+        /// The offset of the macro unit.
+        offset: DebugMacroOffset<Offset>,
+    },
+    /// The entries of a macro unit in the `.debug_macro` section of the
+    /// supplementary object file are included at this point.
+    ImportSup {
+        /// The offset of the macro unit in the supplementary object file.
+        offset: DebugMacroOffset<Offset>,
+    },
+    /// A vendor extension in the `.debug_macinfo` section.
+    VendorExt {
+        /// The vendor defined constant.
+        numeric: u64,
+        /// The vendor defined string.
+        string: R,
+    },
+}
+
+/// An iterator over the entries in the `.debug_macinfo` or `.debug_macro`
+/// section.
+///
+/// This is returned by `DebugMacinfo::get_macinfo` and
+/// `MacroUnitHeader::entries`.
+///
+/// Entry types that are not defined by the DWARF standard are not supported,
+/// and result in `Error::UnknownMacroType`.
+#[derive(Debug, Clone)]
+pub struct MacroIter<R: Reader> {
+    input: R,
+    format: Format,
+    is_macro: bool,
+}
+
+impl<R: Reader> MacroIter<R> {
+    /// Advance the iterator to the next entry.
+    ///
+    /// Returns `Ok(None)` when the end of the entries is reached.
+    pub fn next(&mut self) -> Result<Option<MacroEntry<R>>> {
+        if self.input.is_empty() {
+            return Ok(None);
+        }
+        let entry = if self.is_macro {
+            self.parse_macro()
+        } else {
+            self.parse_macinfo()
+        };
+        match entry {
+            Ok(None) => {
+                self.input.empty();
+                Ok(None)
+            }
+            Ok(Some(entry)) => Ok(Some(entry)),
+            Err(e) => {
+                self.input.empty();
+                Err(e)
+            }
+        }
+    }
+
+    fn parse_macinfo(&mut self) -> Result<Option<MacroEntry<R>>> {
+        let input = &mut self.input;
+        let entry = match constants::DwMacinfo(input.read_u8()?) {
+            constants::DwMacinfo(0) => return Ok(None),
+            constants::DW_MACINFO_define => MacroEntry::Define {
+                line: input.read_uleb128()?,
+                text: MacroString::Direct(input.read_null_terminated_slice()?),
+            },
+            constants::DW_MACINFO_undef => MacroEntry::Undef {
+                line: input.read_uleb128()?,
+                text: MacroString::Direct(input.read_null_terminated_slice()?),
+            },
+            constants::DW_MACINFO_start_file => MacroEntry::StartFile {
+                line: input.read_uleb128()?,
+                file: input.read_uleb128()?,
+            },
+            constants::DW_MACINFO_end_file => MacroEntry::EndFile,
+            constants::DW_MACINFO_vendor_ext => MacroEntry::VendorExt {
+                numeric: input.read_uleb128()?,
+                string: input.read_null_terminated_slice()?,
+            },
+            otherwise => return Err(Error::UnknownMacroType(otherwise.0)),
+        };
+        Ok(Some(entry))
+    }
+
+    fn parse_macro(&mut self) -> Result<Option<MacroEntry<R>>> {
+        let format = self.format;
+        let input = &mut self.input;
+        let entry = match constants::DwMacro(input.read_u8()?) {
+            constants::DwMacro(0) => return Ok(None),
+            constants::DW_MACRO_define => MacroEntry::Define {
+                line: input.read_uleb128()?,
+                text: MacroString::Direct(input.read_null_terminated_slice()?),
+            },
+            constants::DW_MACRO_undef => MacroEntry::Undef {
+                line: input.read_uleb128()?,
+                text: MacroString::Direct(input.read_null_terminated_slice()?),
+            },
+            constants::DW_MACRO_start_file => MacroEntry::StartFile {
+                line: input.read_uleb128()?,
+                file: input.read_uleb128()?,
+            },
+            constants::DW_MACRO_end_file => MacroEntry::EndFile,
+            constants::DW_MACRO_define_strp => MacroEntry::Define {
+                line: input.read_uleb128()?,
+                text: MacroString::StringPointer(DebugStrOffset(input.read_offset(format)?)),
+            },
+            constants::DW_MACRO_undef_strp => MacroEntry::Undef {
+                line: input.read_uleb128()?,
+                text: MacroString::StringPointer(DebugStrOffset(input.read_offset(format)?)),
+            },
+            constants::DW_MACRO_import => MacroEntry::Import {
+                offset: DebugMacroOffset(input.read_offset(format)?),
+            },
+            constants::DW_MACRO_define_sup => MacroEntry::Define {
+                line: input.read_uleb128()?,
+                text: MacroString::Supplementary(DebugStrOffset(input.read_offset(format)?)),
+            },
+            constants::DW_MACRO_undef_sup => MacroEntry::Undef {
+                line: input.read_uleb128()?,
+                text: MacroString::Supplementary(DebugStrOffset(input.read_offset(format)?)),
+            },
+            constants::DW_MACRO_import_sup => MacroEntry::ImportSup {
+                offset: DebugMacroOffset(input.read_offset(format)?),
+            },
+            constants::DW_MACRO_define_strx => MacroEntry::Define {
+                line: input.read_uleb128()?,
+                text: MacroString::IndirectStringPointer(DebugStrOffsetsIndex(
+                    R::Offset::from_u64(input.read_uleb128()?)?,
+                )),
+            },
+            constants::DW_MACRO_undef_strx => MacroEntry::Undef {
+                line: input.read_uleb128()?,
+                text: MacroString::IndirectStringPointer(DebugStrOffsetsIndex(
+                    R::Offset::from_u64(input.read_uleb128()?)?,
+                )),
+            },
+            otherwise => return Err(Error::UnknownMacroType(otherwise.0)),
+        };
+        Ok(Some(entry))
+    }
+}
+
+impl<R: Reader> FallibleIterator for MacroIter<R> {
+    type Item = MacroEntry<R>;
+    type Error = Error;
+
+    fn next(&mut self) -> ::std::result::Result<Option<Self::Item>, Self::Error> {
+        MacroIter::next(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::endianity::LittleEndian;
+    use crate::test_util::GimliSectionMethods;
+    use test_assembler::{Endian, Section};
+
+    #[test]
+    fn test_macinfo() {
+        let section = Section::with_endian(Endian::Little)
+            .D8(constants::DW_MACINFO_start_file.0)
+            .uleb(0)
+            .uleb(1)
+            .D8(constants::DW_MACINFO_define.0)
+            .uleb(1)
+            .append_bytes(b"FOO 1\0")
+            .D8(constants::DW_MACINFO_undef.0)
+            .uleb(2)
+            .append_bytes(b"FOO\0")
+            .D8(constants::DW_MACINFO_vendor_ext.0)
+            .uleb(3)
+            .append_bytes(b"bar\0")
+            .D8(constants::DW_MACINFO_end_file.0)
+            .D8(0)
+            .D8(0xfe);
+        let buf = section.get_contents().unwrap();
+        let debug_macinfo = DebugMacinfo::new(&buf, LittleEndian);
+
+        let mut entries = debug_macinfo.get_macinfo(DebugMacinfoOffset(0)).unwrap();
+        let s = |string| EndianSlice::new(string, LittleEndian);
+        assert_eq!(
+            entries.next(),
+            Ok(Some(MacroEntry::StartFile { line: 0, file: 1 }))
+        );
+        assert_eq!(
+            entries.next(),
+            Ok(Some(MacroEntry::Define {
+                line: 1,
+                text: MacroString::Direct(s(b"FOO 1")),
+            }))
+        );
+        assert_eq!(
+            entries.next(),
+            Ok(Some(MacroEntry::Undef {
+                line: 2,
+                text: MacroString::Direct(s(b"FOO")),
+            }))
+        );
+        assert_eq!(
+            entries.next(),
+            Ok(Some(MacroEntry::VendorExt {
+                numeric: 3,
+                string: s(b"bar"),
+            }))
+        );
+        assert_eq!(entries.next(), Ok(Some(MacroEntry::EndFile)));
+        assert_eq!(entries.next(), Ok(None));
+        assert_eq!(entries.next(), Ok(None));
+
+        let offset = DebugMacinfoOffset(buf.len() - 1);
+        let mut entries = debug_macinfo.get_macinfo(offset).unwrap();
+        assert_eq!(entries.next(), Err(Error::UnknownMacroType(0xfe)));
+        assert_eq!(entries.next(), Ok(None));
+    }
+
+    #[test]
+    fn test_macro() {
+        for &format in &[Format::Dwarf32, Format::Dwarf64] {
+            let flags = match format {
+                Format::Dwarf32 => 0x06,
+                Format::Dwarf64 => 0x07,
+            };
+            let section = Section::with_endian(Endian::Little)
+                // Padding so that the unit is not at offset 0.
+                .D8(0)
+                .L16(5)
+                .D8(flags)
+                .word(format.word_size(), 0x10)
+                // Opcode operands table with one entry with one operand.
+                .D8(1)
+                .D8(0xe0)
+                .uleb(1)
+                .D8(constants::DW_FORM_udata.0 as u8)
+                .D8(constants::DW_MACRO_start_file.0)
+                .uleb(0)
+                .uleb(1)
+                .D8(constants::DW_MACRO_define.0)
+                .uleb(1)
+                .append_bytes(b"FOO 1\0")
+                .D8(constants::DW_MACRO_define_strp.0)
+                .uleb(2)
+                .word(format.word_size(), 0x20)
+                .D8(constants::DW_MACRO_undef_strx.0)
+                .uleb(3)
+                .uleb(4)
+                .D8(constants::DW_MACRO_undef_sup.0)
+                .uleb(4)
+                .word(format.word_size(), 0x30)
+                .D8(constants::DW_MACRO_import.0)
+                .word(format.word_size(), 0x40)
+                .D8(constants::DW_MACRO_import_sup.0)
+                .word(format.word_size(), 0x50)
+                .D8(constants::DW_MACRO_end_file.0)
+                .D8(0);
+            let buf = section.get_contents().unwrap();
+            let debug_macro = DebugMacro::new(&buf, LittleEndian);
+
+            let header = debug_macro.header(DebugMacroOffset(1)).unwrap();
+            assert_eq!(header.version(), 5);
+            assert_eq!(header.format(), format);
+            assert_eq!(header.debug_line_offset(), Some(DebugLineOffset(0x10)));
+
+            let mut entries = header.entries();
+            assert_eq!(
+                entries.next(),
+                Ok(Some(MacroEntry::StartFile { line: 0, file: 1 }))
+            );
+            assert_eq!(
+                entries.next(),
+                Ok(Some(MacroEntry::Define {
+                    line: 1,
+                    text: MacroString::Direct(EndianSlice::new(b"FOO 1", LittleEndian)),
+                }))
+            );
+            assert_eq!(
+                entries.next(),
+                Ok(Some(MacroEntry::Define {
+                    line: 2,
+                    text: MacroString::StringPointer(DebugStrOffset(0x20)),
+                }))
+            );
+            assert_eq!(
+                entries.next(),
+                Ok(Some(MacroEntry::Undef {
+                    line: 3,
+                    text: MacroString::IndirectStringPointer(DebugStrOffsetsIndex(4)),
+                }))
+            );
+            assert_eq!(
+                entries.next(),
+                Ok(Some(MacroEntry::Undef {
+                    line: 4,
+                    text: MacroString::Supplementary(DebugStrOffset(0x30)),
+                }))
+            );
+            assert_eq!(
+                entries.next(),
+                Ok(Some(MacroEntry::Import {
+                    offset: DebugMacroOffset(0x40),
+                }))
+            );
+            assert_eq!(
+                entries.next(),
+                Ok(Some(MacroEntry::ImportSup {
+                    offset: DebugMacroOffset(0x50),
+                }))
+            );
+            assert_eq!(entries.next(), Ok(Some(MacroEntry::EndFile)));
+            assert_eq!(entries.next(), Ok(None));
+        }
+    }
+
+    #[test]
+    fn test_macro_version() {
+        let buf = [0x03, 0x00, 0x00];
+        let debug_macro = DebugMacro::new(&buf, LittleEndian);
+        assert_eq!(
+            debug_macro.header(DebugMacroOffset(0)),
+            Err(Error::UnknownVersion(3))
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_macro_string() {
+        use crate::read::{DebugAbbrev, DebugInfo, DebugStr, Dwarf};
+
+        #[rustfmt::skip]
+        let abbrev_buf = [
+            // Code 1, DW_TAG_compile_unit, DW_CHILDREN_no, no attributes.
+            0x01, 0x11, 0x00, 0x00, 0x00,
+            0x00,
+        ];
+        #[rustfmt::skip]
+        let info_buf = [
+            // Unit length, version 4, abbreviation offset, address size.
+            0x08, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x08,
+            // Root entry.
+            0x01,
+        ];
+        let dwarf = Dwarf {
+            debug_abbrev: DebugAbbrev::new(&abbrev_buf, LittleEndian),
+            debug_info: DebugInfo::new(&info_buf, LittleEndian),
+            debug_str: DebugStr::new(b"FOO\0BAR 1\0", LittleEndian),
+            ..Default::default()
+        };
+        let unit = dwarf.unit(dwarf.units().next().unwrap().unwrap()).unwrap();
+        let text = MacroString::StringPointer(DebugStrOffset(4));
+        assert_eq!(
+            text.string(&dwarf, &unit),
+            Ok(EndianSlice::new(b"BAR 1", LittleEndian))
+        );
+        let text = MacroString::Direct(EndianSlice::new(b"FOO", LittleEndian));
+        assert_eq!(
+            text.string(&dwarf, &unit),
+            Ok(EndianSlice::new(b"FOO", LittleEndian))
+        );
+    }
+}
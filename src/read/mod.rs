@@ -88,6 +88,10 @@
 //!
 //!   * [`DebugLocLists`](./struct.DebugLocLists.html): The `.debug_loclists` section.
 //!
+//!   * [`DebugMacinfo`](./struct.DebugMacinfo.html): The `.debug_macinfo` section.
+//!
+//!   * [`DebugMacro`](./struct.DebugMacro.html): The `.debug_macro` section.
+//!
 //!   * [`DebugPubNames`](./struct.DebugPubNames.html): The `.debug_pubnames`
 //!   section.
 //!
@@ -174,13 +178,27 @@ pub use self::addr::*;
 mod cfi;
 pub use self::cfi::*;
 
+#[cfg(feature = "compression")]
+mod compression;
+#[cfg(feature = "compression")]
+pub use self::compression::*;
+
+#[cfg(feature = "alloc")]
 mod dwarf;
+#[cfg(feature = "alloc")]
 pub use self::dwarf::*;
 
+#[cfg(feature = "std")]
+mod dump;
+#[cfg(feature = "std")]
+pub use self::dump::*;
+
 mod endian_slice;
 pub use self::endian_slice::*;
 
+#[cfg(feature = "alloc")]
 mod endian_reader;
+#[cfg(feature = "alloc")]
 pub use self::endian_reader::*;
 
 mod reader;
@@ -189,10 +207,20 @@ pub use self::reader::*;
 mod abbrev;
 pub use self::abbrev::*;
 
+#[cfg(feature = "alloc")]
+mod apple;
+#[cfg(feature = "alloc")]
+pub use self::apple::*;
+
 mod aranges;
 pub use self::aranges::*;
 
+mod index;
+pub use self::index::*;
+
+#[cfg(feature = "alloc")]
 mod line;
+#[cfg(feature = "alloc")]
 pub use self::line::*;
 
 mod loclists;
@@ -200,6 +228,14 @@ pub use self::loclists::*;
 
 mod lookup;
 
+mod macros;
+pub use self::macros::*;
+
+#[cfg(feature = "alloc")]
+mod names;
+#[cfg(feature = "alloc")]
+pub use self::names::*;
+
 mod op;
 pub use self::op::*;
 
@@ -215,6 +251,9 @@ pub use self::rnglists::*;
 mod str;
 pub use self::str::*;
 
+mod sup;
+pub use self::sup::*;
+
 mod unit;
 pub use self::unit::*;
 
@@ -369,7 +408,10 @@ pub enum Error {
     UnsupportedUnitType,
     /// Ranges using AddressIndex are not supported yet.
     UnsupportedAddressIndex,
-    /// Nonzero segment selector sizes aren't supported yet.
+    /// The segment selector size is not supported.
+    ///
+    /// This is returned for sizes that can't be read as an address, and for
+    /// sections where nonzero segment selector sizes aren't supported yet.
     UnsupportedSegmentSize,
     /// A compilation unit or type unit is missing its top level DIE.
     MissingUnitDie,
@@ -379,12 +421,77 @@ pub enum Error {
     MissingFileEntryFormatPath,
     /// Expected an attribute value to be a string form.
     ExpectedStringAttributeValue,
+    /// An attribute in a name index abbreviation has an invalid `DW_IDX_*` value.
+    InvalidNameAttributeIndex(u64),
+    /// An Apple accelerator table has an invalid magic number.
+    InvalidAppleAccelMagic(u32),
+    /// An Apple accelerator table uses an unknown hash function.
+    UnknownHashFunction(u16),
+    /// A compressed section uses an unsupported compression type.
+    UnsupportedCompressionType(u32),
+    /// The data in a compressed section is invalid.
+    InvalidCompressedData,
+    /// The slot count in a unit index is invalid.
+    InvalidIndexSlotCount,
+    /// The section count in a unit index is invalid.
+    InvalidIndexSectionCount,
+    /// A unit index contains an unknown section identifier.
+    UnknownIndexSection(u32),
+    /// The row index for a unit index is invalid.
+    InvalidIndexRow,
+    /// The `is_supplementary` flag in the `.debug_sup` section is invalid.
+    BadSupplementaryFlag,
+    /// A string was not terminated by a null byte within the maximum length.
+    UnterminatedString,
+    /// A reference to the supplementary object file was found, but no
+    /// supplementary object file is available.
+    MissingSupplementaryFile,
+    /// The debugging information entries are nested deeper than the maximum
+    /// depth.
+    NestingTooDeep,
+    /// Found an unknown macro information entry type.
+    UnknownMacroType(u8),
+    /// An index into the `.debug_addr` section is out of bounds.
+    ///
+    /// The values are the index and the `DW_AT_addr_base` it is relative to.
+    InvalidAddressIndex(u64, u64),
 }
 
 impl fmt::Display for Error {
-    #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> ::std::result::Result<(), fmt::Error> {
-        Debug::fmt(self, f)
+        // Include the data for the variants that have any, except for
+        // `UnexpectedEof`, since its identifier is only meaningful for a reader.
+        // Use `Dwarf::format_error` to display the offset for that variant.
+        let description = self.description().trim_end_matches('.');
+        match *self {
+            Error::UnknownVersion(version) => write!(f, "{}: {}", description, version),
+            Error::UnknownStandardOpcode(opcode) => write!(f, "{}: {}", description, opcode),
+            Error::UnknownExtendedOpcode(opcode) => write!(f, "{}: {}", description, opcode),
+            Error::UnsupportedAddressSize(size)
+            | Error::UnsupportedOffsetSize(size)
+            | Error::UnsupportedFieldSize(size) => write!(f, "{}: {}", description, size),
+            Error::BadBranchTarget(target) => write!(f, "{}: 0x{:x}", description, target),
+            Error::InvalidExpression(opcode) => write!(f, "{}: {}", description, opcode),
+            Error::InvalidExpressionTerminator(offset) => {
+                write!(f, "{}: at offset 0x{:x}", description, offset)
+            }
+            Error::UnknownCallFrameInstruction(instruction) => {
+                write!(f, "{}: {}", description, instruction)
+            }
+            Error::UnsupportedRegister(register) => write!(f, "{}: {}", description, register),
+            Error::InvalidNameAttributeIndex(index) => write!(f, "{}: {}", description, index),
+            Error::InvalidAppleAccelMagic(magic) => write!(f, "{}: 0x{:x}", description, magic),
+            Error::UnknownHashFunction(function) => write!(f, "{}: {}", description, function),
+            Error::UnsupportedCompressionType(kind) => write!(f, "{}: {}", description, kind),
+            Error::UnknownIndexSection(section) => write!(f, "{}: {}", description, section),
+            Error::UnknownMacroType(kind) => write!(f, "{}: 0x{:x}", description, kind),
+            Error::InvalidAddressIndex(index, base) => write!(
+                f,
+                "{}: index {} with base 0x{:x}",
+                description, index, base
+            ),
+            _ => f.write_str(self.description()),
+        }
     }
 }
 
@@ -522,6 +629,53 @@ impl Error {
             Error::ExpectedStringAttributeValue => {
                 "Expected an attribute value to be a string form."
             }
+            Error::InvalidNameAttributeIndex(_) => {
+                "An attribute in a name index abbreviation has an invalid DW_IDX value."
+            }
+            Error::InvalidAppleAccelMagic(_) => {
+                "An Apple accelerator table has an invalid magic number."
+            }
+            Error::UnknownHashFunction(_) => {
+                "An Apple accelerator table uses an unknown hash function."
+            }
+            Error::UnsupportedCompressionType(_) => {
+                "A compressed section uses an unsupported compression type."
+            }
+            Error::InvalidCompressedData => "The data in a compressed section is invalid.",
+            Error::InvalidIndexSlotCount => "The slot count in a unit index is invalid.",
+            Error::InvalidIndexSectionCount => "The section count in a unit index is invalid.",
+            Error::UnknownIndexSection(_) => "A unit index contains an unknown section identifier.",
+            Error::InvalidIndexRow => "The row index for a unit index is invalid.",
+            Error::BadSupplementaryFlag => {
+                "The `is_supplementary` flag in the `.debug_sup` section is invalid."
+            }
+            Error::UnterminatedString => {
+                "A string was not terminated by a null byte within the maximum length."
+            }
+            Error::MissingSupplementaryFile => {
+                "A reference to the supplementary object file was found, but no \
+                 supplementary object file is available."
+            }
+            Error::NestingTooDeep => {
+                "The debugging information entries are nested deeper than the maximum depth."
+            }
+            Error::UnknownMacroType(_) => "Found an unknown macro information entry type.",
+            Error::InvalidAddressIndex(..) => {
+                "An index into the `.debug_addr` section is out of bounds."
+            }
+        }
+    }
+
+    /// Return the identifier of the reader offset at which the error occurred,
+    /// if it is known.
+    ///
+    /// The identifier can be converted to a section and offset using
+    /// `Dwarf::lookup_offset_id`, or `Reader::lookup_offset_id` if the
+    /// section is already known.
+    pub fn offset_id(&self) -> Option<ReaderOffsetId> {
+        match *self {
+            Error::UnexpectedEof(id) => Some(id),
+            _ => None,
         }
     }
 }
@@ -556,14 +710,26 @@ pub type Result<T> = result::Result<T, Error>;
 /// let debug_info: DebugInfo<_> = Section::load(loader).unwrap();
 /// ```
 pub trait Section<R>: From<R> {
+    /// The section id for this type.
+    const ID: SectionId;
+
     /// Returns the section id for this type.
-    fn id() -> SectionId;
+    #[inline]
+    fn id() -> SectionId {
+        Self::ID
+    }
 
     /// Returns the ELF section name for this type.
     fn section_name() -> &'static str {
         Self::id().name()
     }
 
+    /// Returns the ELF section name (if any) for this type when used in a dwo
+    /// file.
+    fn dwo_section_name() -> Option<&'static str> {
+        Self::id().dwo_name()
+    }
+
     /// Try to load the section using the given loader function.
     fn load<F, E>(f: F) -> std::result::Result<Self, E>
     where
@@ -651,16 +817,76 @@ mod tests {
 
     #[test]
     fn test_parse_initial_length_unknown_reserved_value() {
-        let section = Section::with_endian(Endian::Little).L32(0xffff_fffe);
+        for value in 0xffff_fff0..0xffff_ffff {
+            let section = Section::with_endian(Endian::Little).L32(value);
+            let buf = section.get_contents().unwrap();
+
+            let input = &mut EndianSlice::new(&buf, LittleEndian);
+            match input.read_initial_length() {
+                Err(Error::UnknownReservedLength) => assert!(true),
+                otherwise => panic!("Unexpected result: {:?}", otherwise),
+            };
+        }
+    }
+
+    #[test]
+    fn test_parse_initial_length_32_max() {
+        let section = Section::with_endian(Endian::Little).L32(0xffff_ffef);
         let buf = section.get_contents().unwrap();
 
         let input = &mut EndianSlice::new(&buf, LittleEndian);
         match input.read_initial_length() {
-            Err(Error::UnknownReservedLength) => assert!(true),
+            Ok((length, format)) => {
+                assert_eq!(input.len(), 0);
+                assert_eq!(format, Format::Dwarf32);
+                assert_eq!(0xffff_ffef, length);
+            }
             otherwise => panic!("Unexpected result: {:?}", otherwise),
         };
     }
 
+    #[test]
+    fn test_error_display() {
+        use crate::string::ToString;
+
+        assert_eq!(
+            Error::UnknownVersion(6).to_string(),
+            "Found an unknown DWARF version: 6"
+        );
+        assert_eq!(
+            Error::InvalidExpression(constants::DwOp(0xff)).to_string(),
+            "Invalid opcode in DWARF expression: DW_OP_unknown(0xff)"
+        );
+        assert_eq!(
+            Error::InvalidAppleAccelMagic(0x1234).to_string(),
+            "An Apple accelerator table has an invalid magic number: 0x1234"
+        );
+        assert_eq!(
+            Error::InvalidAddressIndex(3, 8).to_string(),
+            "An index into the `.debug_addr` section is out of bounds: index 3 with base 0x8"
+        );
+        assert_eq!(
+            Error::UnexpectedEof(ReaderOffsetId(1)).to_string(),
+            "Hit the end of input before it was expected"
+        );
+        assert_eq!(
+            Error::UnknownAbbreviation.to_string(),
+            Error::UnknownAbbreviation.description()
+        );
+    }
+
+    #[test]
+    fn test_error_offset_id() {
+        let buf = [0x01, 0x02];
+        let section = EndianSlice::new(&buf, LittleEndian);
+        let mut input = section;
+        input.skip(1).unwrap();
+        let err = input.read_u16().unwrap_err();
+        let id = err.offset_id().unwrap();
+        assert_eq!(section.lookup_offset_id(id), Some(1));
+        assert_eq!(Error::Io.offset_id(), None);
+    }
+
     #[test]
     fn test_parse_initial_length_incomplete() {
         let buf = [0xff, 0xff, 0xff]; // Need at least 4 bytes.
@@ -746,4 +972,35 @@ mod tests {
             otherwise => panic!("Unexpected result: {:?}", otherwise),
         };
     }
+
+    #[test]
+    fn test_section_names() {
+        type R<'a> = EndianSlice<'a, LittleEndian>;
+        assert_eq!(
+            <DebugInfo<R> as super::Section<R>>::ID,
+            SectionId::DebugInfo
+        );
+        assert_eq!(
+            <DebugInfo<R> as super::Section<R>>::section_name(),
+            ".debug_info"
+        );
+        assert_eq!(
+            <DebugInfo<R> as super::Section<R>>::dwo_section_name(),
+            Some(".debug_info.dwo")
+        );
+        assert_eq!(
+            <DebugCuIndex<R> as super::Section<R>>::dwo_section_name(),
+            Some(".debug_cu_index")
+        );
+        assert_eq!(
+            <DebugAranges<R> as super::Section<R>>::dwo_section_name(),
+            None
+        );
+        assert_eq!(SectionId::DebugStrOffsets.name(), ".debug_str_offsets");
+        assert_eq!(
+            SectionId::DebugStrOffsets.dwo_name(),
+            Some(".debug_str_offsets.dwo")
+        );
+        assert_eq!(SectionId::DebugAddr.dwo_name(), None);
+    }
 }
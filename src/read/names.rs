@@ -0,0 +1,1168 @@
+//! Functions for parsing the DWARF 5 `.debug_names` section.
+
+use crate::collections::btree_map;
+use crate::vec::Vec;
+use fallible_iterator::FallibleIterator;
+
+use crate::common::{DebugInfoOffset, DebugStrOffset, DebugTypeSignature, Format, SectionId};
+use crate::constants;
+use crate::endianity::Endianity;
+use crate::read::{
+    DebugStr, EndianSlice, Error, Reader, ReaderOffset, Result, Section, UnitOffset,
+};
+
+/// The `DebugNames` struct represents the DWARF 5 name index information
+/// found in the `.debug_names` section.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DebugNames<R> {
+    section: R,
+}
+
+impl<'input, Endian> DebugNames<EndianSlice<'input, Endian>>
+where
+    Endian: Endianity,
+{
+    /// Construct a new `DebugNames` instance from the data in the `.debug_names`
+    /// section.
+    ///
+    /// It is the caller's responsibility to read the `.debug_names` section and
+    /// present it as a `&[u8]` slice. That means using some ELF loader on
+    /// Linux, a Mach-O loader on OSX, etc.
+    ///
+    /// ```
+    /// use gimli::{DebugNames, LittleEndian};
+    ///
+    /// # let buf = [];
+    /// # let read_debug_names_section_somehow = || &buf;
+    /// let debug_names = DebugNames::new(read_debug_names_section_somehow(), LittleEndian);
+    /// ```
+    pub fn new(debug_names_section: &'input [u8], endian: Endian) -> Self {
+        Self::from(EndianSlice::new(debug_names_section, endian))
+    }
+}
+
+impl<R: Reader> DebugNames<R> {
+    /// Iterate the name indexes in the `.debug_names` section.
+    ///
+    /// A name index may cover a single unit, or multiple units.
+    ///
+    /// ```
+    /// use gimli::{DebugNames, DebugStr, LittleEndian};
+    ///
+    /// # let buf = [];
+    /// # let read_debug_names_section_somehow = || &buf;
+    /// # let read_debug_str_section_somehow = || &buf;
+    /// let debug_names = DebugNames::new(read_debug_names_section_somehow(), LittleEndian);
+    /// let debug_str = DebugStr::new(read_debug_str_section_somehow(), LittleEndian);
+    ///
+    /// let mut indexes = debug_names.indexes();
+    /// while let Some(index) = indexes.next().unwrap() {
+    ///     if let Some(name) = index.lookup(b"main", &debug_str).unwrap() {
+    ///         let mut entries = index.entries(name.entry_offset()).unwrap();
+    ///         while let Some(entry) = entries.next().unwrap() {
+    ///             println!("main has tag {} at {:?}", entry.tag(), entry.die_offset());
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Can be [used with
+    /// `FallibleIterator`](./index.html#using-with-fallibleiterator).
+    pub fn indexes(&self) -> NameIndexIter<R> {
+        NameIndexIter {
+            input: self.section.clone(),
+        }
+    }
+}
+
+impl<T> DebugNames<T> {
+    /// Create a `DebugNames` section that references the data in `self`.
+    ///
+    /// This is useful when `R` implements `Reader` but `T` does not.
+    ///
+    /// ## Example Usage
+    ///
+    /// ```rust,no_run
+    /// # let load_section = || unimplemented!();
+    /// // Read the DWARF section into a `Vec` with whatever object loader you're using.
+    /// let owned_section: gimli::DebugNames<Vec<u8>> = load_section();
+    /// // Create a reference to the DWARF section.
+    /// let section = owned_section.borrow(|section| {
+    ///     gimli::EndianSlice::new(&section, gimli::LittleEndian)
+    /// });
+    /// ```
+    pub fn borrow<'a, F, R>(&'a self, mut borrow: F) -> DebugNames<R>
+    where
+        F: FnMut(&'a T) -> R,
+    {
+        borrow(&self.section).into()
+    }
+}
+
+impl<R> Section<R> for DebugNames<R> {
+    const ID: SectionId = SectionId::DebugNames;
+
+    fn reader(&self) -> &R {
+        &self.section
+    }
+}
+
+impl<R> From<R> for DebugNames<R> {
+    fn from(section: R) -> Self {
+        DebugNames { section }
+    }
+}
+
+/// An iterator over the name indexes of a `.debug_names` section.
+///
+/// Can be [used with
+/// `FallibleIterator`](./index.html#using-with-fallibleiterator).
+#[derive(Clone, Debug)]
+pub struct NameIndexIter<R: Reader> {
+    input: R,
+}
+
+impl<R: Reader> NameIndexIter<R> {
+    /// Advance the iterator to the next name index.
+    pub fn next(&mut self) -> Result<Option<NameIndex<R>>> {
+        if self.input.is_empty() {
+            return Ok(None);
+        }
+
+        match NameIndex::parse(&mut self.input) {
+            Ok(index) => Ok(Some(index)),
+            Err(e) => {
+                self.input.empty();
+                Err(e)
+            }
+        }
+    }
+}
+
+impl<R: Reader> FallibleIterator for NameIndexIter<R> {
+    type Item = NameIndex<R>;
+    type Error = Error;
+
+    fn next(&mut self) -> ::std::result::Result<Option<Self::Item>, Self::Error> {
+        NameIndexIter::next(self)
+    }
+}
+
+/// A single name index in the `.debug_names` section.
+#[derive(Debug, Clone)]
+pub struct NameIndex<R: Reader> {
+    format: Format,
+    version: u16,
+    comp_unit_count: u32,
+    local_type_unit_count: u32,
+    foreign_type_unit_count: u32,
+    bucket_count: u32,
+    name_count: u32,
+    abbrev_table_size: u32,
+    augmentation_string: R,
+    comp_units: R,
+    local_type_units: R,
+    foreign_type_units: R,
+    buckets: R,
+    hashes: R,
+    string_offsets: R,
+    entry_offsets: R,
+    abbreviations: NameAbbreviations,
+    entry_pool: R,
+}
+
+impl<R: Reader> NameIndex<R> {
+    fn parse(input: &mut R) -> Result<NameIndex<R>> {
+        let (length, format) = input.read_initial_length()?;
+        let mut rest = input.split(length)?;
+
+        let version = rest.read_u16()?;
+        if version != 5 {
+            return Err(Error::UnknownVersion(u64::from(version)));
+        }
+        // Padding.
+        rest.read_u16()?;
+
+        let comp_unit_count = rest.read_u32()?;
+        let local_type_unit_count = rest.read_u32()?;
+        let foreign_type_unit_count = rest.read_u32()?;
+        let bucket_count = rest.read_u32()?;
+        let name_count = rest.read_u32()?;
+        let abbrev_table_size = rest.read_u32()?;
+        let augmentation_string_size = rest.read_u32()?;
+        let augmentation_string = rest.split(R::Offset::from_u32(augmentation_string_size))?;
+
+        let word_size = u64::from(format.word_size());
+        let comp_units = split_array(&mut rest, comp_unit_count, word_size)?;
+        let local_type_units = split_array(&mut rest, local_type_unit_count, word_size)?;
+        let foreign_type_units = split_array(&mut rest, foreign_type_unit_count, 8)?;
+        let buckets = split_array(&mut rest, bucket_count, 4)?;
+        // The hash table is optional, and the hashes are omitted if there are no buckets.
+        let hashes = if bucket_count != 0 {
+            split_array(&mut rest, name_count, 4)?
+        } else {
+            split_array(&mut rest, 0, 4)?
+        };
+        let string_offsets = split_array(&mut rest, name_count, word_size)?;
+        let entry_offsets = split_array(&mut rest, name_count, word_size)?;
+        let mut abbrev_table = rest.split(R::Offset::from_u32(abbrev_table_size))?;
+        let abbreviations = NameAbbreviations::parse(&mut abbrev_table)?;
+
+        Ok(NameIndex {
+            format,
+            version,
+            comp_unit_count,
+            local_type_unit_count,
+            foreign_type_unit_count,
+            bucket_count,
+            name_count,
+            abbrev_table_size,
+            augmentation_string,
+            comp_units,
+            local_type_units,
+            foreign_type_units,
+            buckets,
+            hashes,
+            string_offsets,
+            entry_offsets,
+            abbreviations,
+            entry_pool: rest,
+        })
+    }
+
+    /// Return the format of this name index.
+    #[inline]
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Return the version of this name index.
+    #[inline]
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    /// Return the number of compilation units covered by this name index.
+    #[inline]
+    pub fn comp_unit_count(&self) -> u32 {
+        self.comp_unit_count
+    }
+
+    /// Return the number of type units in the `.debug_info` section covered
+    /// by this name index.
+    #[inline]
+    pub fn local_type_unit_count(&self) -> u32 {
+        self.local_type_unit_count
+    }
+
+    /// Return the number of type units in other object files covered
+    /// by this name index.
+    #[inline]
+    pub fn foreign_type_unit_count(&self) -> u32 {
+        self.foreign_type_unit_count
+    }
+
+    /// Return the number of buckets in the hash table.
+    ///
+    /// This is zero if the name index does not have a hash table.
+    #[inline]
+    pub fn bucket_count(&self) -> u32 {
+        self.bucket_count
+    }
+
+    /// Return the number of names in this name index.
+    #[inline]
+    pub fn name_count(&self) -> u32 {
+        self.name_count
+    }
+
+    /// Return the size in bytes of the abbreviations table.
+    #[inline]
+    pub fn abbrev_table_size(&self) -> u32 {
+        self.abbrev_table_size
+    }
+
+    /// Return the augmentation string of this name index.
+    ///
+    /// This may include trailing null padding.
+    #[inline]
+    pub fn augmentation_string(&self) -> &R {
+        &self.augmentation_string
+    }
+
+    /// Return the parsed abbreviations for the entries in this name index.
+    #[inline]
+    pub fn abbreviations(&self) -> &NameAbbreviations {
+        &self.abbreviations
+    }
+
+    /// Return the offset of the compilation unit with the given index.
+    pub fn comp_unit(&self, index: u32) -> Result<DebugInfoOffset<R::Offset>> {
+        let mut input = self.comp_units.clone();
+        skip_array(
+            &mut input,
+            index,
+            self.comp_unit_count,
+            self.format.word_size(),
+        )?;
+        input.read_offset(self.format).map(DebugInfoOffset)
+    }
+
+    /// Return the offset of the local type unit with the given index.
+    pub fn local_type_unit(&self, index: u32) -> Result<DebugInfoOffset<R::Offset>> {
+        let mut input = self.local_type_units.clone();
+        skip_array(
+            &mut input,
+            index,
+            self.local_type_unit_count,
+            self.format.word_size(),
+        )?;
+        input.read_offset(self.format).map(DebugInfoOffset)
+    }
+
+    /// Return the signature of the foreign type unit with the given index.
+    pub fn foreign_type_unit(&self, index: u32) -> Result<DebugTypeSignature> {
+        let mut input = self.foreign_type_units.clone();
+        skip_array(&mut input, index, self.foreign_type_unit_count, 8)?;
+        input.read_u64().map(DebugTypeSignature)
+    }
+
+    /// Return the entry in the name table with the given index.
+    pub fn name(&self, index: u32) -> Result<NameTableEntry<R::Offset>> {
+        let word_size = self.format.word_size();
+
+        let hash = if self.bucket_count != 0 {
+            let mut hashes = self.hashes.clone();
+            skip_array(&mut hashes, index, self.name_count, 4)?;
+            Some(hashes.read_u32()?)
+        } else {
+            None
+        };
+
+        let mut string_offsets = self.string_offsets.clone();
+        skip_array(&mut string_offsets, index, self.name_count, word_size)?;
+        let name = DebugStrOffset(string_offsets.read_offset(self.format)?);
+
+        let mut entry_offsets = self.entry_offsets.clone();
+        skip_array(&mut entry_offsets, index, self.name_count, word_size)?;
+        let entry_offset = NameEntryOffset(entry_offsets.read_offset(self.format)?);
+
+        Ok(NameTableEntry {
+            index,
+            hash,
+            name,
+            entry_offset,
+        })
+    }
+
+    /// Iterate the entries in the name table.
+    ///
+    /// Can be [used with
+    /// `FallibleIterator`](./index.html#using-with-fallibleiterator).
+    pub fn names(&self) -> NameTableIter<'_, R> {
+        NameTableIter {
+            index: self,
+            next: 0,
+        }
+    }
+
+    /// Find the entry in the name table for the given name.
+    ///
+    /// If this name index has a hash table, then it is used to find the
+    /// candidate entries. Otherwise, all entries are searched.
+    pub fn lookup(
+        &self,
+        name: &[u8],
+        debug_str: &DebugStr<R>,
+    ) -> Result<Option<NameTableEntry<R::Offset>>> {
+        if self.bucket_count == 0 {
+            let mut names = self.names();
+            while let Some(entry) = names.next()? {
+                if debug_str.get_str(entry.name)?.to_slice()? == name {
+                    return Ok(Some(entry));
+                }
+            }
+            return Ok(None);
+        }
+
+        let hash = hash(name);
+        let bucket = hash % self.bucket_count;
+        let mut buckets = self.buckets.clone();
+        skip_array(&mut buckets, bucket, self.bucket_count, 4)?;
+        // Name indexes in the buckets are 1-based, and 0 means an empty bucket.
+        let start = buckets.read_u32()?;
+        if start == 0 {
+            return Ok(None);
+        }
+        for index in start - 1..self.name_count {
+            let entry = self.name(index)?;
+            let entry_hash = entry.hash.unwrap_or(0);
+            if entry_hash % self.bucket_count != bucket {
+                break;
+            }
+            if entry_hash == hash && debug_str.get_str(entry.name)?.to_slice()? == name {
+                return Ok(Some(entry));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Iterate the series of entries in the entry pool starting at the given offset.
+    ///
+    /// Can be [used with
+    /// `FallibleIterator`](./index.html#using-with-fallibleiterator).
+    pub fn entries(&self, offset: NameEntryOffset<R::Offset>) -> Result<NameEntryIter<'_, R>> {
+        let mut input = self.entry_pool.clone();
+        input.skip(offset.0)?;
+        Ok(NameEntryIter { index: self, input })
+    }
+
+    /// Parse the entry at the given offset in the entry pool.
+    ///
+    /// This can be used to follow `DW_IDX_parent` references.
+    pub fn entry(&self, offset: NameEntryOffset<R::Offset>) -> Result<NameEntry<R::Offset>> {
+        self.entries(offset)?
+            .next()?
+            .ok_or(Error::NoEntryAtGivenOffset)
+    }
+
+    /// Return the offset of the compilation unit containing the given entry.
+    ///
+    /// If the entry does not have a `DW_IDX_compile_unit` attribute and this
+    /// name index covers a single compilation unit, then that unit is returned.
+    pub fn entry_comp_unit(
+        &self,
+        entry: &NameEntry<R::Offset>,
+    ) -> Result<Option<DebugInfoOffset<R::Offset>>> {
+        match entry.compile_unit {
+            Some(index) => {
+                if index >= u64::from(self.comp_unit_count) {
+                    return Err(Error::OffsetOutOfBounds);
+                }
+                self.comp_unit(index as u32).map(Some)
+            }
+            None if self.comp_unit_count == 1 && entry.type_unit.is_none() => {
+                self.comp_unit(0).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+fn split_array<R: Reader>(input: &mut R, count: u32, size: u64) -> Result<R> {
+    let len = R::Offset::from_u64(u64::from(count) * size)?;
+    input.split(len)
+}
+
+fn skip_array<R: Reader>(input: &mut R, index: u32, count: u32, size: u8) -> Result<()> {
+    if index >= count {
+        return Err(Error::OffsetOutOfBounds);
+    }
+    input.skip(R::Offset::from_u64(u64::from(index) * u64::from(size))?)
+}
+
+/// The hash function used by the `.debug_names` hash table.
+///
+/// This is the DJB hash of the name after case folding.
+/// Only ASCII case folding is supported.
+fn hash(name: &[u8]) -> u32 {
+    name.iter().fold(5381u32, |hash, c| {
+        hash.wrapping_mul(33)
+            .wrapping_add(u32::from(c.to_ascii_lowercase()))
+    })
+}
+
+/// An iterator over the name table of a name index.
+///
+/// Can be [used with
+/// `FallibleIterator`](./index.html#using-with-fallibleiterator).
+#[derive(Clone, Debug)]
+pub struct NameTableIter<'index, R: Reader> {
+    index: &'index NameIndex<R>,
+    next: u32,
+}
+
+impl<'index, R: Reader> NameTableIter<'index, R> {
+    /// Advance the iterator to the next entry in the name table.
+    pub fn next(&mut self) -> Result<Option<NameTableEntry<R::Offset>>> {
+        if self.next >= self.index.name_count {
+            return Ok(None);
+        }
+
+        match self.index.name(self.next) {
+            Ok(entry) => {
+                self.next += 1;
+                Ok(Some(entry))
+            }
+            Err(e) => {
+                self.next = self.index.name_count;
+                Err(e)
+            }
+        }
+    }
+}
+
+impl<'index, R: Reader> FallibleIterator for NameTableIter<'index, R> {
+    type Item = NameTableEntry<R::Offset>;
+    type Error = Error;
+
+    fn next(&mut self) -> ::std::result::Result<Option<Self::Item>, Self::Error> {
+        NameTableIter::next(self)
+    }
+}
+
+/// An offset into the entry pool of a name index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NameEntryOffset<T = usize>(pub T);
+
+/// An entry in the name table of a name index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NameTableEntry<T = usize> {
+    index: u32,
+    hash: Option<u32>,
+    name: DebugStrOffset<T>,
+    entry_offset: NameEntryOffset<T>,
+}
+
+impl<T: Copy> NameTableEntry<T> {
+    /// Return the index of this entry in the name table.
+    #[inline]
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Return the hash of the name, if the name index has a hash table.
+    #[inline]
+    pub fn hash(&self) -> Option<u32> {
+        self.hash
+    }
+
+    /// Return the offset of the name in the `.debug_str` section.
+    #[inline]
+    pub fn name(&self) -> DebugStrOffset<T> {
+        self.name
+    }
+
+    /// Return the offset of the series of entries for this name in the entry pool.
+    #[inline]
+    pub fn entry_offset(&self) -> NameEntryOffset<T> {
+        self.entry_offset
+    }
+}
+
+/// An iterator over a series of entries in the entry pool of a name index.
+///
+/// The series is terminated by a null entry.
+///
+/// Can be [used with
+/// `FallibleIterator`](./index.html#using-with-fallibleiterator).
+#[derive(Clone, Debug)]
+pub struct NameEntryIter<'index, R: Reader> {
+    index: &'index NameIndex<R>,
+    input: R,
+}
+
+impl<'index, R: Reader> NameEntryIter<'index, R> {
+    /// Advance the iterator to the next entry.
+    pub fn next(&mut self) -> Result<Option<NameEntry<R::Offset>>> {
+        if self.input.is_empty() {
+            return Ok(None);
+        }
+
+        match self.parse_entry() {
+            Ok(Some(entry)) => Ok(Some(entry)),
+            Ok(None) => {
+                self.input.empty();
+                Ok(None)
+            }
+            Err(e) => {
+                self.input.empty();
+                Err(e)
+            }
+        }
+    }
+
+    fn parse_entry(&mut self) -> Result<Option<NameEntry<R::Offset>>> {
+        let offset = NameEntryOffset(self.input.offset_from(&self.index.entry_pool));
+        let code = self.input.read_uleb128()?;
+        if code == 0 {
+            return Ok(None);
+        }
+        let abbrev = self
+            .index
+            .abbreviations
+            .get(code)
+            .ok_or(Error::UnknownAbbreviation)?;
+
+        let mut entry = NameEntry {
+            offset,
+            code,
+            tag: abbrev.tag,
+            attrs: Vec::with_capacity(abbrev.attributes.len()),
+            compile_unit: None,
+            type_unit: None,
+            die_offset: None,
+            parent: None,
+            type_hash: None,
+        };
+        for spec in &abbrev.attributes {
+            let value = parse_name_attribute_value(&mut self.input, spec.form, self.index.format)?;
+            match spec.name {
+                constants::DW_IDX_compile_unit => entry.compile_unit = Some(value),
+                constants::DW_IDX_type_unit => entry.type_unit = Some(value),
+                constants::DW_IDX_die_offset => {
+                    entry.die_offset = Some(UnitOffset(R::Offset::from_u64(value)?));
+                }
+                // `DW_FORM_flag_present` means that the parent is not indexed.
+                constants::DW_IDX_parent if spec.form != constants::DW_FORM_flag_present => {
+                    entry.parent = Some(NameEntryOffset(R::Offset::from_u64(value)?));
+                }
+                constants::DW_IDX_type_hash => entry.type_hash = Some(value),
+                _ => {}
+            }
+            entry.attrs.push(NameAttribute {
+                name: spec.name,
+                form: spec.form,
+                value,
+            });
+        }
+        Ok(Some(entry))
+    }
+}
+
+impl<'index, R: Reader> FallibleIterator for NameEntryIter<'index, R> {
+    type Item = NameEntry<R::Offset>;
+    type Error = Error;
+
+    fn next(&mut self) -> ::std::result::Result<Option<Self::Item>, Self::Error> {
+        NameEntryIter::next(self)
+    }
+}
+
+pub(crate) fn parse_name_attribute_value<R: Reader>(
+    input: &mut R,
+    form: constants::DwForm,
+    format: Format,
+) -> Result<u64> {
+    match form {
+        constants::DW_FORM_flag_present => Ok(1),
+        constants::DW_FORM_data1 | constants::DW_FORM_ref1 | constants::DW_FORM_flag => {
+            input.read_u8().map(u64::from)
+        }
+        constants::DW_FORM_data2 | constants::DW_FORM_ref2 => input.read_u16().map(u64::from),
+        constants::DW_FORM_data4 | constants::DW_FORM_ref4 => input.read_u32().map(u64::from),
+        constants::DW_FORM_data8 | constants::DW_FORM_ref8 | constants::DW_FORM_ref_sig8 => {
+            input.read_u64()
+        }
+        constants::DW_FORM_udata | constants::DW_FORM_ref_udata => input.read_uleb128(),
+        constants::DW_FORM_sec_offset => input.read_offset(format).map(|x| x.into_u64()),
+        _ => Err(Error::UnknownForm),
+    }
+}
+
+/// An entry in the entry pool of a name index.
+///
+/// This describes a single debugging information entry with a given name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NameEntry<T = usize> {
+    offset: NameEntryOffset<T>,
+    code: u64,
+    tag: constants::DwTag,
+    attrs: Vec<NameAttribute>,
+    compile_unit: Option<u64>,
+    type_unit: Option<u64>,
+    die_offset: Option<UnitOffset<T>>,
+    parent: Option<NameEntryOffset<T>>,
+    type_hash: Option<u64>,
+}
+
+impl<T: Copy> NameEntry<T> {
+    /// Return the offset of this entry in the entry pool.
+    #[inline]
+    pub fn offset(&self) -> NameEntryOffset<T> {
+        self.offset
+    }
+
+    /// Return the abbreviation code of this entry.
+    #[inline]
+    pub fn code(&self) -> u64 {
+        self.code
+    }
+
+    /// Return the tag of the debugging information entry.
+    #[inline]
+    pub fn tag(&self) -> constants::DwTag {
+        self.tag
+    }
+
+    /// Return all of the attributes of this entry.
+    #[inline]
+    pub fn attrs(&self) -> &[NameAttribute] {
+        &self.attrs
+    }
+
+    /// Return the `DW_IDX_compile_unit` attribute.
+    ///
+    /// This is an index into the list of compilation units of the name index.
+    /// Use `NameIndex::entry_comp_unit` to handle the implicit value when the
+    /// name index covers a single compilation unit.
+    #[inline]
+    pub fn compile_unit(&self) -> Option<u64> {
+        self.compile_unit
+    }
+
+    /// Return the `DW_IDX_type_unit` attribute.
+    ///
+    /// This is an index into the list of local type units, followed by
+    /// the list of foreign type units, of the name index.
+    #[inline]
+    pub fn type_unit(&self) -> Option<u64> {
+        self.type_unit
+    }
+
+    /// Return the `DW_IDX_die_offset` attribute.
+    ///
+    /// This is the offset of the debugging information entry within its unit.
+    #[inline]
+    pub fn die_offset(&self) -> Option<UnitOffset<T>> {
+        self.die_offset
+    }
+
+    /// Return the `DW_IDX_parent` attribute.
+    ///
+    /// This is the offset of the entry for the parent in the entry pool.
+    /// Returns `None` if the attribute is not present, or if it has the
+    /// `DW_FORM_flag_present` form which indicates that the parent is not indexed.
+    #[inline]
+    pub fn parent(&self) -> Option<NameEntryOffset<T>> {
+        self.parent
+    }
+
+    /// Return the `DW_IDX_type_hash` attribute.
+    #[inline]
+    pub fn type_hash(&self) -> Option<u64> {
+        self.type_hash
+    }
+}
+
+/// An attribute of an entry in the entry pool of a name index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NameAttribute {
+    name: constants::DwIdx,
+    form: constants::DwForm,
+    value: u64,
+}
+
+impl NameAttribute {
+    /// Return the name of this attribute.
+    #[inline]
+    pub fn name(&self) -> constants::DwIdx {
+        self.name
+    }
+
+    /// Return the form of this attribute.
+    #[inline]
+    pub fn form(&self) -> constants::DwForm {
+        self.form
+    }
+
+    /// Return the value of this attribute.
+    ///
+    /// `DW_FORM_flag_present` has a value of 1.
+    #[inline]
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+}
+
+/// The abbreviations for the entries in a name index.
+#[derive(Debug, Default, Clone)]
+pub struct NameAbbreviations {
+    map: btree_map::BTreeMap<u64, NameAbbreviation>,
+}
+
+impl NameAbbreviations {
+    /// Get the abbreviation associated with the given code.
+    #[inline]
+    pub fn get(&self, code: u64) -> Option<&NameAbbreviation> {
+        self.map.get(&code)
+    }
+
+    fn parse<R: Reader>(input: &mut R) -> Result<NameAbbreviations> {
+        let mut abbrevs = NameAbbreviations::default();
+        while let Some(abbrev) = NameAbbreviation::parse(input)? {
+            match abbrevs.map.entry(abbrev.code) {
+                btree_map::Entry::Occupied(_) => return Err(Error::DuplicateAbbreviationCode),
+                btree_map::Entry::Vacant(entry) => {
+                    entry.insert(abbrev);
+                }
+            }
+        }
+        Ok(abbrevs)
+    }
+}
+
+/// An abbreviation for the entries in a name index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NameAbbreviation {
+    code: u64,
+    tag: constants::DwTag,
+    attributes: Vec<NameAttributeSpecification>,
+}
+
+impl NameAbbreviation {
+    /// Get this abbreviation's code.
+    #[inline]
+    pub fn code(&self) -> u64 {
+        self.code
+    }
+
+    /// Get this abbreviation's tag.
+    #[inline]
+    pub fn tag(&self) -> constants::DwTag {
+        self.tag
+    }
+
+    /// Get this abbreviation's attributes.
+    #[inline]
+    pub fn attributes(&self) -> &[NameAttributeSpecification] {
+        &self.attributes[..]
+    }
+
+    /// Parse an abbreviation. Returns `None` for the null abbreviation.
+    fn parse<R: Reader>(input: &mut R) -> Result<Option<NameAbbreviation>> {
+        let code = input.read_uleb128()?;
+        if code == 0 {
+            return Ok(None);
+        }
+
+        let tag = input.read_uleb128()?;
+        if tag == 0 {
+            return Err(Error::AbbreviationTagZero);
+        }
+
+        let mut attributes = Vec::new();
+        loop {
+            let name = input.read_uleb128()?;
+            let form = input.read_uleb128()?;
+            if name == 0 {
+                if form == 0 {
+                    break;
+                }
+                return Err(Error::ExpectedZero);
+            }
+            if form == 0 {
+                return Err(Error::AttributeFormZero);
+            }
+            let idx = name as u16;
+            if u64::from(idx) != name {
+                return Err(Error::InvalidNameAttributeIndex(name));
+            }
+            attributes.push(NameAttributeSpecification {
+                name: constants::DwIdx(idx),
+                form: constants::DwForm(form),
+            });
+        }
+
+        Ok(Some(NameAbbreviation {
+            code,
+            tag: constants::DwTag(tag),
+            attributes,
+        }))
+    }
+}
+
+/// The description of an attribute in an abbreviation for the entries in a name index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NameAttributeSpecification {
+    name: constants::DwIdx,
+    form: constants::DwForm,
+}
+
+impl NameAttributeSpecification {
+    /// Get the attribute's name.
+    #[inline]
+    pub fn name(&self) -> constants::DwIdx {
+        self.name
+    }
+
+    /// Get the attribute's form.
+    #[inline]
+    pub fn form(&self) -> constants::DwForm {
+        self.form
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::endianity::LittleEndian;
+    use crate::test_util::GimliSectionMethods;
+    use test_assembler::{Endian, Label, LabelMaker, Section};
+
+    #[test]
+    fn test_hash() {
+        // The DJB hash, with ASCII case folding.
+        assert_eq!(hash(b""), 5381);
+        assert_eq!(hash(b"main"), 0x7c9a_7f6a);
+        assert_eq!(hash(b"MAIN"), hash(b"main"));
+    }
+
+    #[test]
+    fn test_debug_names() {
+        let debug_str_buf = b"main\0foo\0Bar\0";
+        let debug_str = DebugStr::new(debug_str_buf, LittleEndian);
+
+        // The names sorted by hash bucket, with their string offset.
+        let bucket_count = 2;
+        let mut names = vec![(&b"main"[..], 0u32), (&b"foo"[..], 5), (&b"Bar"[..], 9)];
+        names.sort_by_key(|&(name, _)| hash(name) % bucket_count);
+
+        let length = Label::new();
+        let start = Label::new();
+        let end = Label::new();
+        let abbrev_size = Label::new();
+        let abbrev_start = Label::new();
+        let abbrev_end = Label::new();
+        let pool = Label::new();
+        let entries: Vec<_> = names.iter().map(|_| Label::new()).collect();
+        let entry_offsets: Vec<_> = names.iter().map(|_| Label::new()).collect();
+
+        let mut section = Section::with_endian(Endian::Little)
+            // First name index.
+            .L32(&length)
+            .mark(&start)
+            // Version.
+            .L16(5)
+            // Padding.
+            .L16(0)
+            // Unit counts.
+            .L32(2)
+            .L32(0)
+            .L32(1)
+            .L32(bucket_count)
+            .L32(names.len() as u32)
+            .L32(&abbrev_size)
+            // Augmentation string.
+            .L32(8)
+            .append_bytes(b"LLVM0700")
+            // Compilation units.
+            .L32(0x10)
+            .L32(0x80)
+            // Foreign type units.
+            .L64(0x1234);
+        // Buckets.
+        for bucket in 0..bucket_count {
+            let index = names
+                .iter()
+                .position(|&(name, _)| hash(name) % bucket_count == bucket)
+                .map(|i| i as u32 + 1)
+                .unwrap_or(0);
+            section = section.L32(index);
+        }
+        for &(name, _) in &names {
+            section = section.L32(hash(name));
+        }
+        for &(_, offset) in &names {
+            section = section.L32(offset);
+        }
+        for offset in &entry_offsets {
+            section = section.L32(offset);
+        }
+        section = section
+            .mark(&abbrev_start)
+            .uleb(1)
+            .uleb(constants::DW_TAG_subprogram.0)
+            .uleb(u64::from(constants::DW_IDX_compile_unit.0))
+            .uleb(constants::DW_FORM_data1.0)
+            .uleb(u64::from(constants::DW_IDX_die_offset.0))
+            .uleb(constants::DW_FORM_ref4.0)
+            .uleb(u64::from(constants::DW_IDX_parent.0))
+            .uleb(constants::DW_FORM_flag_present.0)
+            .uleb(0)
+            .uleb(0)
+            .uleb(2)
+            .uleb(constants::DW_TAG_structure_type.0)
+            .uleb(u64::from(constants::DW_IDX_type_unit.0))
+            .uleb(constants::DW_FORM_data1.0)
+            .uleb(u64::from(constants::DW_IDX_die_offset.0))
+            .uleb(constants::DW_FORM_ref4.0)
+            .uleb(u64::from(constants::DW_IDX_type_hash.0))
+            .uleb(constants::DW_FORM_data8.0)
+            .uleb(0)
+            .uleb(0)
+            .uleb(0)
+            .mark(&abbrev_end)
+            .mark(&pool);
+        for (&(name, _), entry) in names.iter().zip(&entries) {
+            section = section.mark(entry);
+            section = match name {
+                b"main" => section.uleb(1).D8(1).L32(0x2a),
+                b"foo" => section.uleb(1).D8(0).L32(0x30).uleb(1).D8(1).L32(0x40),
+                b"Bar" => section.uleb(2).D8(0).L32(0x1c).L64(0x5678),
+                _ => unreachable!(),
+            };
+            section = section.uleb(0);
+        }
+        section = section.mark(&end);
+        length.set_const((&end - &start) as u64);
+        abbrev_size.set_const((&abbrev_end - &abbrev_start) as u64);
+        for (entry, offset) in entries.iter().zip(&entry_offsets) {
+            offset.set_const((entry - &pool) as u64);
+        }
+
+        // Second name index, without a hash table.
+        let length = Label::new();
+        let start = Label::new();
+        let end = Label::new();
+        let abbrev_size = Label::new();
+        let abbrev_start = Label::new();
+        let abbrev_end = Label::new();
+        section = section
+            .L32(&length)
+            .mark(&start)
+            .L16(5)
+            .L16(0)
+            .L32(1)
+            .L32(0)
+            .L32(0)
+            .L32(0)
+            .L32(1)
+            .L32(&abbrev_size)
+            .L32(0)
+            .L32(0x100)
+            .L32(0)
+            .L32(0)
+            .mark(&abbrev_start)
+            .uleb(1)
+            .uleb(constants::DW_TAG_variable.0)
+            .uleb(u64::from(constants::DW_IDX_die_offset.0))
+            .uleb(constants::DW_FORM_udata.0)
+            .uleb(0)
+            .uleb(0)
+            .uleb(0)
+            .mark(&abbrev_end)
+            .uleb(1)
+            .uleb(0x33)
+            .uleb(0)
+            .mark(&end);
+        length.set_const((&end - &start) as u64);
+        abbrev_size.set_const((&abbrev_end - &abbrev_start) as u64);
+
+        let buf = section.get_contents().unwrap();
+        let debug_names = DebugNames::new(&buf, LittleEndian);
+        let mut indexes = debug_names.indexes();
+
+        let index = indexes.next().unwrap().unwrap();
+        assert_eq!(index.format(), Format::Dwarf32);
+        assert_eq!(index.version(), 5);
+        assert_eq!(index.comp_unit_count(), 2);
+        assert_eq!(index.local_type_unit_count(), 0);
+        assert_eq!(index.foreign_type_unit_count(), 1);
+        assert_eq!(index.bucket_count(), 2);
+        assert_eq!(index.name_count(), 3);
+        assert_eq!(index.augmentation_string().slice(), b"LLVM0700");
+        assert_eq!(index.comp_unit(0), Ok(DebugInfoOffset(0x10)));
+        assert_eq!(index.comp_unit(1), Ok(DebugInfoOffset(0x80)));
+        assert_eq!(index.comp_unit(2), Err(Error::OffsetOutOfBounds));
+        assert_eq!(index.local_type_unit(0), Err(Error::OffsetOutOfBounds));
+        assert_eq!(index.foreign_type_unit(0), Ok(DebugTypeSignature(0x1234)));
+
+        let abbrev = index.abbreviations().get(1).unwrap();
+        assert_eq!(abbrev.tag(), constants::DW_TAG_subprogram);
+        assert_eq!(abbrev.attributes().len(), 3);
+        assert!(index.abbreviations().get(3).is_none());
+
+        let table: Vec<_> = index.names().collect().unwrap();
+        assert_eq!(table.len(), 3);
+        for (i, (entry, &(name, offset))) in table.iter().zip(&names).enumerate() {
+            assert_eq!(entry.index(), i as u32);
+            assert_eq!(entry.hash(), Some(hash(name)));
+            assert_eq!(entry.name(), DebugStrOffset(offset as usize));
+            assert_eq!(debug_str.get_str(entry.name()).unwrap().slice(), name);
+        }
+
+        let main = index.lookup(b"main", &debug_str).unwrap().unwrap();
+        let entries: Vec<_> = index
+            .entries(main.entry_offset())
+            .unwrap()
+            .collect()
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].tag(), constants::DW_TAG_subprogram);
+        assert_eq!(entries[0].compile_unit(), Some(1));
+        assert_eq!(entries[0].die_offset(), Some(UnitOffset(0x2a)));
+        assert_eq!(entries[0].parent(), None);
+        assert_eq!(
+            entries[0].attrs()[2],
+            NameAttribute {
+                name: constants::DW_IDX_parent,
+                form: constants::DW_FORM_flag_present,
+                value: 1,
+            }
+        );
+        assert_eq!(
+            index.entry_comp_unit(&entries[0]),
+            Ok(Some(DebugInfoOffset(0x80)))
+        );
+        assert_eq!(index.entry(main.entry_offset()), Ok(entries[0].clone()));
+
+        let foo = index.lookup(b"foo", &debug_str).unwrap().unwrap();
+        let entries: Vec<_> = index
+            .entries(foo.entry_offset())
+            .unwrap()
+            .collect()
+            .unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            index.entry_comp_unit(&entries[0]),
+            Ok(Some(DebugInfoOffset(0x10)))
+        );
+        assert_eq!(entries[0].die_offset(), Some(UnitOffset(0x30)));
+        assert_eq!(
+            index.entry_comp_unit(&entries[1]),
+            Ok(Some(DebugInfoOffset(0x80)))
+        );
+        assert_eq!(entries[1].die_offset(), Some(UnitOffset(0x40)));
+
+        let bar = index.lookup(b"Bar", &debug_str).unwrap().unwrap();
+        let entry = index.entry(bar.entry_offset()).unwrap();
+        assert_eq!(entry.tag(), constants::DW_TAG_structure_type);
+        assert_eq!(entry.type_unit(), Some(0));
+        assert_eq!(entry.type_hash(), Some(0x5678));
+        assert_eq!(index.entry_comp_unit(&entry), Ok(None));
+
+        // Names are case sensitive, even though the hash is not.
+        assert_eq!(index.lookup(b"bar", &debug_str), Ok(None));
+        assert_eq!(index.lookup(b"missing", &debug_str), Ok(None));
+
+        let index = indexes.next().unwrap().unwrap();
+        assert_eq!(index.comp_unit_count(), 1);
+        assert_eq!(index.bucket_count(), 0);
+        assert_eq!(index.name_count(), 1);
+        assert_eq!(index.augmentation_string().slice(), b"");
+        let main = index.lookup(b"main", &debug_str).unwrap().unwrap();
+        assert_eq!(main.hash(), None);
+        let entry = index.entry(main.entry_offset()).unwrap();
+        assert_eq!(entry.tag(), constants::DW_TAG_variable);
+        assert_eq!(entry.compile_unit(), None);
+        assert_eq!(entry.die_offset(), Some(UnitOffset(0x33)));
+        assert_eq!(
+            index.entry_comp_unit(&entry),
+            Ok(Some(DebugInfoOffset(0x100)))
+        );
+        assert_eq!(index.lookup(b"foo", &debug_str), Ok(None));
+
+        assert!(indexes.next().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_debug_names_version() {
+        let section = Section::with_endian(Endian::Little).L32(4).L16(4).L16(0);
+        let buf = section.get_contents().unwrap();
+        let debug_names = DebugNames::new(&buf, LittleEndian);
+        let mut indexes = debug_names.indexes();
+        assert_eq!(indexes.next().err(), Some(Error::UnknownVersion(4)));
+        assert!(indexes.next().unwrap().is_none());
+    }
+}
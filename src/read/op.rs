@@ -1,11 +1,17 @@
 //! Functions for parsing and evaluating DWARF expressions.
 
+use fallible_iterator::FallibleIterator;
+
+#[cfg(feature = "alloc")]
 use crate::vec::Vec;
+#[cfg(feature = "alloc")]
 use std::mem;
 
 use crate::common::{DebugAddrIndex, DebugInfoOffset, Encoding, Register};
 use crate::constants;
-use crate::read::{Error, Reader, ReaderOffset, Result, UnitOffset, Value, ValueType};
+#[cfg(feature = "alloc")]
+use crate::read::ValueType;
+use crate::read::{Error, Reader, ReaderOffset, Result, UnitOffset, Value};
 
 /// A reference to a DIE, either relative to the current CU or
 /// relative to the section.
@@ -246,6 +252,7 @@ where
     },
 }
 
+#[cfg(feature = "alloc")]
 #[derive(Debug)]
 enum OperationEvaluationResult<R: Reader> {
     Piece,
@@ -753,6 +760,7 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
 #[derive(Debug)]
 enum EvaluationState<R: Reader> {
     Start(Option<u64>),
@@ -762,6 +770,7 @@ enum EvaluationState<R: Reader> {
     Waiting(EvaluationWaiting<R>),
 }
 
+#[cfg(feature = "alloc")]
 #[derive(Debug)]
 enum EvaluationWaiting<R: Reader> {
     Memory,
@@ -782,6 +791,7 @@ enum EvaluationWaiting<R: Reader> {
 /// The state of an `Evaluation` after evaluating a DWARF expression.
 /// The evaluation is either `Complete`, or it requires more data
 /// to continue, as described by the variant.
+#[cfg(feature = "alloc")]
 #[derive(Debug, PartialEq)]
 pub enum EvaluationResult<R: Reader> {
     /// The `Evaluation` is complete, and `Evaluation::result()` can be called.
@@ -883,10 +893,73 @@ impl<R: Reader> Expression<R> {
     /// let mut eval = expression.evaluation(unit.encoding());
     /// let mut result = eval.evaluate().unwrap();
     /// ```
+    #[cfg(feature = "alloc")]
     #[inline]
     pub fn evaluation(self, encoding: Encoding) -> Evaluation<R> {
         Evaluation::new(self.0, encoding)
     }
+
+    /// Return an iterator for the operations in the expression.
+    ///
+    /// This decodes the operations without evaluating them.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gimli::{Encoding, EndianSlice, Expression, Format, LittleEndian, Operation};
+    /// # let encoding = Encoding { format: Format::Dwarf32, version: 4, address_size: 8 };
+    /// let bytecode = [gimli::DW_OP_addr.0, 0x78, 0x56, 0x34, 0x12, 0, 0, 0, 0];
+    /// let expression = Expression(EndianSlice::new(&bytecode, LittleEndian));
+    /// let mut operations = expression.operations(encoding);
+    /// assert_eq!(
+    ///     operations.next().unwrap(),
+    ///     Some(Operation::Address { address: 0x1234_5678 })
+    /// );
+    /// assert_eq!(operations.next().unwrap(), None);
+    /// ```
+    #[inline]
+    pub fn operations(self, encoding: Encoding) -> OperationIter<R> {
+        OperationIter {
+            input: self.0.clone(),
+            bytecode: self.0,
+            encoding,
+        }
+    }
+}
+
+/// An iterator for the operations in an expression.
+#[derive(Debug, Clone)]
+pub struct OperationIter<R: Reader> {
+    input: R,
+    bytecode: R,
+    encoding: Encoding,
+}
+
+impl<R: Reader> OperationIter<R> {
+    /// Read the next operation in the expression.
+    ///
+    /// Returns `Error::InvalidExpression` for unknown opcodes, after which
+    /// the iterator is empty.
+    pub fn next(&mut self) -> Result<Option<Operation<R>>> {
+        if self.input.is_empty() {
+            return Ok(None);
+        }
+        match Operation::parse(&mut self.input, &self.bytecode, self.encoding) {
+            Ok(operation) => Ok(Some(operation)),
+            Err(e) => {
+                self.input.empty();
+                Err(e)
+            }
+        }
+    }
+}
+
+impl<R: Reader> FallibleIterator for OperationIter<R> {
+    type Item = Operation<R>;
+    type Error = Error;
+
+    fn next(&mut self) -> ::std::result::Result<Option<Self::Item>, Self::Error> {
+        OperationIter::next(self)
+    }
 }
 
 /// A DWARF expression evaluator.
@@ -934,6 +1007,7 @@ impl<R: Reader> Expression<R> {
 /// let result = eval.result();
 /// println!("{:?}", result);
 /// ```
+#[cfg(feature = "alloc")]
 #[derive(Debug)]
 pub struct Evaluation<R: Reader> {
     bytecode: R,
@@ -961,6 +1035,7 @@ pub struct Evaluation<R: Reader> {
     result: Vec<Piece<R>>,
 }
 
+#[cfg(feature = "alloc")]
 impl<R: Reader> Evaluation<R> {
     /// Create a new DWARF expression evaluator.
     ///
@@ -1833,6 +1908,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_operation_iter() {
+        let encoding = encoding4();
+        #[rustfmt::skip]
+        let bytes = [
+            constants::DW_OP_breg3.0, 0x7f,
+            constants::DW_OP_plus_uconst.0, 0x10,
+            constants::DW_OP_skip.0, 0xfa, 0xff,
+            0xff,
+            constants::DW_OP_nop.0,
+        ];
+        let bytecode = EndianSlice::new(&bytes, LittleEndian);
+        let mut operations = Expression(bytecode).operations(encoding);
+        assert_eq!(
+            operations.next(),
+            Ok(Some(Operation::RegisterOffset {
+                register: Register(3),
+                offset: -1,
+                base_type: UnitOffset(0),
+            }))
+        );
+        assert_eq!(
+            operations.next(),
+            Ok(Some(Operation::PlusConstant { value: 0x10 }))
+        );
+        // The skip target is relative to the entire expression.
+        assert_eq!(
+            operations.next(),
+            Ok(Some(Operation::Skip {
+                target: bytecode.range_from(1..)
+            }))
+        );
+        assert_eq!(
+            operations.next(),
+            Err(Error::InvalidExpression(constants::DwOp(0xff)))
+        );
+        // The iterator is empty after an error.
+        assert_eq!(operations.next(), Ok(None));
+    }
+
     fn check_op_parse_simple<'input>(
         input: &'input [u8],
         expect: &Operation<EndianSlice<'input, LittleEndian>>,
@@ -2,7 +2,7 @@ use fallible_iterator::FallibleIterator;
 
 use crate::common::{DebugInfoOffset, SectionId};
 use crate::endianity::Endianity;
-use crate::read::lookup::{DebugLookup, LookupEntryIter, PubStuffEntry, PubStuffParser};
+use crate::read::lookup::{LookupEntryIter, PubStuffEntry, PubStuffParser};
 use crate::read::{EndianSlice, Error, Reader, Result, Section, UnitOffset};
 
 /// A single parsed pubname.
@@ -48,8 +48,10 @@ impl<R: Reader> PubStuffEntry<R> for PubNamesEntry<R> {
 
 /// The `DebugPubNames` struct represents the DWARF public names information
 /// found in the `.debug_pubnames` section.
-#[derive(Debug, Clone)]
-pub struct DebugPubNames<R: Reader>(DebugLookup<R, PubStuffParser<R, PubNamesEntry<R>>>);
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DebugPubNames<R> {
+    section: R,
+}
 
 impl<'input, Endian> DebugPubNames<EndianSlice<'input, Endian>>
 where
@@ -92,23 +94,72 @@ impl<R: Reader> DebugPubNames<R> {
     /// }
     /// ```
     pub fn items(&self) -> PubNamesEntryIter<R> {
-        PubNamesEntryIter(self.0.items())
+        PubNamesEntryIter(LookupEntryIter::new(self.section.clone()))
+    }
+
+    /// Find the first pubname with the given name.
+    ///
+    /// This performs a linear search of the `.debug_pubnames` section.
+    ///
+    /// ```
+    /// use gimli::{DebugPubNames, LittleEndian};
+    ///
+    /// # let buf = [];
+    /// # let read_debug_pubnames_section_somehow = || &buf;
+    /// let debug_pubnames =
+    ///     DebugPubNames::new(read_debug_pubnames_section_somehow(), LittleEndian);
+    ///
+    /// if let Some(pubname) = debug_pubnames.find(b"main").unwrap() {
+    ///   println!("main is in the unit at {:?}", pubname.unit_header_offset());
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn find(&self, name: &[u8]) -> Result<Option<PubNamesEntry<R>>> {
+        let mut items = self.items();
+        while let Some(entry) = items.next()? {
+            if entry.name().to_slice()? == name {
+                return Ok(Some(entry));
+            }
+        }
+        Ok(None)
     }
 }
 
-impl<R: Reader> Section<R> for DebugPubNames<R> {
-    fn id() -> SectionId {
-        SectionId::DebugPubNames
+impl<T> DebugPubNames<T> {
+    /// Create a `DebugPubNames` section that references the data in `self`.
+    ///
+    /// This is useful when `R` implements `Reader` but `T` does not.
+    ///
+    /// ## Example Usage
+    ///
+    /// ```rust,no_run
+    /// # let load_section = || unimplemented!();
+    /// // Read the DWARF section into a `Vec` with whatever object loader you're using.
+    /// let owned_section: gimli::DebugPubNames<Vec<u8>> = load_section();
+    /// // Create a reference to the DWARF section.
+    /// let section = owned_section.borrow(|section| {
+    ///     gimli::EndianSlice::new(&section, gimli::LittleEndian)
+    /// });
+    /// ```
+    pub fn borrow<'a, F, R>(&'a self, mut borrow: F) -> DebugPubNames<R>
+    where
+        F: FnMut(&'a T) -> R,
+    {
+        borrow(&self.section).into()
     }
+}
+
+impl<R> Section<R> for DebugPubNames<R> {
+    const ID: SectionId = SectionId::DebugPubNames;
 
     fn reader(&self) -> &R {
-        self.0.reader()
+        &self.section
     }
 }
 
-impl<R: Reader> From<R> for DebugPubNames<R> {
-    fn from(debug_pubnames_section: R) -> Self {
-        DebugPubNames(DebugLookup::from(debug_pubnames_section))
+impl<R> From<R> for DebugPubNames<R> {
+    fn from(section: R) -> Self {
+        DebugPubNames { section }
     }
 }
 
@@ -2,7 +2,7 @@ use fallible_iterator::FallibleIterator;
 
 use crate::common::{DebugInfoOffset, SectionId};
 use crate::endianity::Endianity;
-use crate::read::lookup::{DebugLookup, LookupEntryIter, PubStuffEntry, PubStuffParser};
+use crate::read::lookup::{LookupEntryIter, PubStuffEntry, PubStuffParser};
 use crate::read::{EndianSlice, Error, Reader, Result, Section, UnitOffset};
 
 /// A single parsed pubtype.
@@ -48,8 +48,10 @@ impl<R: Reader> PubStuffEntry<R> for PubTypesEntry<R> {
 
 /// The `DebugPubTypes` struct represents the DWARF public types information
 /// found in the `.debug_info` section.
-#[derive(Debug, Clone)]
-pub struct DebugPubTypes<R: Reader>(DebugLookup<R, PubStuffParser<R, PubTypesEntry<R>>>);
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DebugPubTypes<R> {
+    section: R,
+}
 
 impl<'input, Endian> DebugPubTypes<EndianSlice<'input, Endian>>
 where
@@ -92,23 +94,72 @@ impl<R: Reader> DebugPubTypes<R> {
     /// }
     /// ```
     pub fn items(&self) -> PubTypesEntryIter<R> {
-        PubTypesEntryIter(self.0.items())
+        PubTypesEntryIter(LookupEntryIter::new(self.section.clone()))
+    }
+
+    /// Find the first pubtype with the given name.
+    ///
+    /// This performs a linear search of the `.debug_pubtypes` section.
+    ///
+    /// ```
+    /// use gimli::{DebugPubTypes, LittleEndian};
+    ///
+    /// # let buf = [];
+    /// # let read_debug_pubtypes_section_somehow = || &buf;
+    /// let debug_pubtypes =
+    ///     DebugPubTypes::new(read_debug_pubtypes_section_somehow(), LittleEndian);
+    ///
+    /// if let Some(pubtype) = debug_pubtypes.find(b"main").unwrap() {
+    ///   println!("main is in the unit at {:?}", pubtype.unit_header_offset());
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn find(&self, name: &[u8]) -> Result<Option<PubTypesEntry<R>>> {
+        let mut items = self.items();
+        while let Some(entry) = items.next()? {
+            if entry.name().to_slice()? == name {
+                return Ok(Some(entry));
+            }
+        }
+        Ok(None)
     }
 }
 
-impl<R: Reader> Section<R> for DebugPubTypes<R> {
-    fn id() -> SectionId {
-        SectionId::DebugPubTypes
+impl<T> DebugPubTypes<T> {
+    /// Create a `DebugPubTypes` section that references the data in `self`.
+    ///
+    /// This is useful when `R` implements `Reader` but `T` does not.
+    ///
+    /// ## Example Usage
+    ///
+    /// ```rust,no_run
+    /// # let load_section = || unimplemented!();
+    /// // Read the DWARF section into a `Vec` with whatever object loader you're using.
+    /// let owned_section: gimli::DebugPubTypes<Vec<u8>> = load_section();
+    /// // Create a reference to the DWARF section.
+    /// let section = owned_section.borrow(|section| {
+    ///     gimli::EndianSlice::new(&section, gimli::LittleEndian)
+    /// });
+    /// ```
+    pub fn borrow<'a, F, R>(&'a self, mut borrow: F) -> DebugPubTypes<R>
+    where
+        F: FnMut(&'a T) -> R,
+    {
+        borrow(&self.section).into()
     }
+}
+
+impl<R> Section<R> for DebugPubTypes<R> {
+    const ID: SectionId = SectionId::DebugPubTypes;
 
     fn reader(&self) -> &R {
-        self.0.reader()
+        &self.section
     }
 }
 
-impl<R: Reader> From<R> for DebugPubTypes<R> {
-    fn from(debug_pubtypes_section: R) -> Self {
-        DebugPubTypes(DebugLookup::from(debug_pubtypes_section))
+impl<R> From<R> for DebugPubTypes<R> {
+    fn from(section: R) -> Self {
+        DebugPubTypes { section }
     }
 }
 
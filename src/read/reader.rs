@@ -1,3 +1,4 @@
+#[cfg(feature = "alloc")]
 use crate::borrow::Cow;
 use std::fmt::Debug;
 use std::hash::Hash;
@@ -19,6 +20,9 @@ pub struct ReaderOffsetId(pub u64);
 /// A trait for offsets with a DWARF section.
 ///
 /// This allows consumers to choose a size that is appropriate for their address space.
+///
+/// Offsets are ordered by their numeric value, so offset newtypes such as
+/// `DebugInfoOffset<R::Offset>` can be used as keys in a `BTreeMap`.
 pub trait ReaderOffset:
     Debug + Copy + Eq + Ord + Hash + Add<Output = Self> + AddAssign + Sub<Output = Self>
 {
@@ -204,6 +208,20 @@ impl ReaderOffset for usize {
 /// | [`EndianRcSlice`](./struct.EndianRcSlice.html)    | Reference counted | No          | Shared ownership via reference counting, which alleviates the borrow restrictions of `EndianSlice` but imposes reference counting increments and decrements. Cannot be sent across threads, because the reference count is not atomic. |
 /// | [`EndianArcSlice`](./struct.EndianArcSlice.html)  | Reference counted | Yes         | The same as `EndianRcSlice`, but uses atomic reference counting, and therefore reference counting operations are slower but `EndianArcSlice`s may be sent across threads. |
 /// | [`EndianReader<T>`](./struct.EndianReader.html)   | Same as `T`       | Same as `T` | Escape hatch for easily defining your own type of `Reader`. |
+///
+/// ## Implementing `Reader`
+///
+/// A `Reader` does not need to store the section data contiguously in memory.
+/// `gimli` never performs pointer arithmetic on the underlying data: all
+/// offset calculations are done using `offset_from` and `ReaderOffset`.
+/// The operations that only adjust the view (`truncate`, `empty`, `skip`,
+/// `split` and `offset_from`) should not require the intervening bytes to be
+/// read, so an implementation may load pages of the section on demand in
+/// `read_slice`, `find`, `to_slice` and the string conversions.
+///
+/// The methods for reading integers, such as `read_u16`, `read_uleb128` and
+/// `read_address`, have default implementations in terms of `read_slice`.
+/// Multi-byte integers are decoded using the byte order given by `endian`.
 pub trait Reader: Debug + Clone {
     /// The endianity of bytes that are read.
     type Endian: Endianity;
@@ -221,6 +239,8 @@ pub trait Reader: Debug + Clone {
     fn empty(&mut self);
 
     /// Set the number of bytes remaining to the specified length.
+    ///
+    /// Returns `Error::UnexpectedEof` if fewer than `len` bytes remain.
     fn truncate(&mut self, len: Self::Offset) -> Result<()>;
 
     /// Return the offset of this reader's data relative to the start of
@@ -231,6 +251,10 @@ pub trait Reader: Debug + Clone {
     fn offset_from(&self, base: &Self) -> Self::Offset;
 
     /// Return an identifier for the current reader offset.
+    ///
+    /// This is used to report the position of errors such as
+    /// `Error::UnexpectedEof`. The section and offset for the identifier
+    /// can be found using `Dwarf::lookup_offset_id`.
     fn offset_id(&self) -> ReaderOffsetId;
 
     /// Return the offset corresponding to the given `id` if
@@ -242,12 +266,19 @@ pub trait Reader: Debug + Clone {
     fn find(&self, byte: u8) -> Result<Self::Offset>;
 
     /// Discard the specified number of bytes.
+    ///
+    /// Returns `Error::UnexpectedEof` if fewer than `len` bytes remain.
     fn skip(&mut self, len: Self::Offset) -> Result<()>;
 
     /// Split a reader in two.
     ///
     /// A new reader is returned that can be used to read the next
     /// `len` bytes, and `self` is advanced so that it reads the remainder.
+    /// This is useful for parsing a nested structure of known length, such
+    /// as a CIE or FDE, without reading past its end.
+    ///
+    /// Returns `Error::UnexpectedEof` if fewer than `len` bytes remain.
+    /// In that case, `self` is not modified.
     fn split(&mut self, len: Self::Offset) -> Result<Self>;
 
     /// Return all remaining data as a clone-on-write slice.
@@ -256,7 +287,17 @@ pub trait Reader: Debug + Clone {
     /// always return an owned vector.
     ///
     /// Does not advance the reader.
-    fn to_slice(&self) -> Result<Cow<[u8]>>;
+    ///
+    /// The default implementation reads the data into an owned vector.
+    /// Readers that are backed by contiguous memory should override this
+    /// to return a borrowed slice.
+    #[cfg(feature = "alloc")]
+    fn to_slice(&self) -> Result<Cow<'_, [u8]>> {
+        let len = usize::from_u64(self.len().into_u64())?;
+        let mut bytes = vec![0; len];
+        self.clone().read_slice(&mut bytes)?;
+        Ok(bytes.into())
+    }
 
     /// Convert all remaining data to a clone-on-write string.
     ///
@@ -266,6 +307,7 @@ pub trait Reader: Debug + Clone {
     /// Does not advance the reader.
     ///
     /// Returns an error if the data contains invalid characters.
+    #[cfg(feature = "alloc")]
     fn to_string(&self) -> Result<Cow<str>>;
 
     /// Convert all remaining data to a clone-on-write string, including invalid characters.
@@ -274,6 +316,7 @@ pub trait Reader: Debug + Clone {
     /// always return an owned string.
     ///
     /// Does not advance the reader.
+    #[cfg(feature = "alloc")]
     fn to_string_lossy(&self) -> Result<Cow<str>>;
 
     /// Read exactly `buf.len()` bytes into `buf`.
@@ -310,63 +353,73 @@ pub trait Reader: Debug + Clone {
         Ok(a[0] as i8)
     }
 
-    /// Read a u16.
+    /// Read a u16, using the byte order given by `endian`.
     #[inline]
     fn read_u16(&mut self) -> Result<u16> {
         let a: [u8; 2] = self.read_u8_array()?;
         Ok(self.endian().read_u16(&a))
     }
 
-    /// Read an i16.
+    /// Read an i16, using the byte order given by `endian`.
     #[inline]
     fn read_i16(&mut self) -> Result<i16> {
         let a: [u8; 2] = self.read_u8_array()?;
         Ok(self.endian().read_i16(&a))
     }
 
-    /// Read a u32.
+    /// Read a 24-bit unsigned integer, using the byte order given by `endian`.
+    ///
+    /// This is used for the `DW_FORM_strx3` and `DW_FORM_addrx3` forms.
+    #[inline]
+    fn read_u24(&mut self) -> Result<u32> {
+        let a: [u8; 3] = self.read_u8_array()?;
+        Ok(self.endian().read_uint(&a) as u32)
+    }
+
+    /// Read a u32, using the byte order given by `endian`.
     #[inline]
     fn read_u32(&mut self) -> Result<u32> {
         let a: [u8; 4] = self.read_u8_array()?;
         Ok(self.endian().read_u32(&a))
     }
 
-    /// Read an i32.
+    /// Read an i32, using the byte order given by `endian`.
     #[inline]
     fn read_i32(&mut self) -> Result<i32> {
         let a: [u8; 4] = self.read_u8_array()?;
         Ok(self.endian().read_i32(&a))
     }
 
-    /// Read a u64.
+    /// Read a u64, using the byte order given by `endian`.
     #[inline]
     fn read_u64(&mut self) -> Result<u64> {
         let a: [u8; 8] = self.read_u8_array()?;
         Ok(self.endian().read_u64(&a))
     }
 
-    /// Read an i64.
+    /// Read an i64, using the byte order given by `endian`.
     #[inline]
     fn read_i64(&mut self) -> Result<i64> {
         let a: [u8; 8] = self.read_u8_array()?;
         Ok(self.endian().read_i64(&a))
     }
 
-    /// Read a f32.
+    /// Read a f32, using the byte order given by `endian`.
     #[inline]
     fn read_f32(&mut self) -> Result<f32> {
         let a: [u8; 4] = self.read_u8_array()?;
         Ok(self.endian().read_f32(&a))
     }
 
-    /// Read a f64.
+    /// Read a f64, using the byte order given by `endian`.
     #[inline]
     fn read_f64(&mut self) -> Result<f64> {
         let a: [u8; 8] = self.read_u8_array()?;
         Ok(self.endian().read_f64(&a))
     }
 
-    /// Read an unsigned n-bytes integer u64.
+    /// Read an unsigned n-bytes integer u64, using the byte order given
+    /// by `endian`.
     ///
     /// # Panics
     ///
@@ -387,19 +440,25 @@ pub trait Reader: Debug + Clone {
     }
 
     /// Read an unsigned LEB128 encoded integer.
+    ///
+    /// LEB128 values are independent of the byte order.
     fn read_uleb128(&mut self) -> Result<u64> {
         leb128::read::unsigned(self)
     }
 
     /// Read a signed LEB128 encoded integer.
+    ///
+    /// LEB128 values are independent of the byte order.
     fn read_sleb128(&mut self) -> Result<i64> {
         leb128::read::signed(self)
     }
 
-    /// Read an initial length field.
+    /// Read an initial length field, using the byte order given by `endian`.
     ///
     /// This field is encoded as either a 32-bit length or
     /// a 64-bit length, and the returned `Format` indicates which.
+    /// Returns `Error::UnknownReservedLength` for the reserved values
+    /// `0xfffffff0` to `0xfffffffe`.
     fn read_initial_length(&mut self) -> Result<(Self::Offset, Format)> {
         const MAX_DWARF_32_UNIT_LENGTH: u32 = 0xffff_fff0;
         const DWARF_64_INITIAL_UNIT_LENGTH: u32 = 0xffff_ffff;
@@ -415,7 +474,20 @@ pub trait Reader: Debug + Clone {
         }
     }
 
+    /// Read the size of an address from a header, and check that it is
+    /// supported by `read_address`.
+    ///
+    /// Returns `Error::UnsupportedAddressSize` if the size is not 1, 2, 4 or 8.
+    fn read_address_size(&mut self) -> Result<u8> {
+        match self.read_u8()? {
+            size @ 1 | size @ 2 | size @ 4 | size @ 8 => Ok(size),
+            otherwise => Err(Error::UnsupportedAddressSize(otherwise)),
+        }
+    }
+
     /// Read an address-sized integer, and return it as a `u64`.
+    ///
+    /// The byte order is given by `endian`.
     fn read_address(&mut self, address_size: u8) -> Result<u64> {
         match address_size {
             1 => self.read_u8().map(u64::from),
@@ -429,7 +501,8 @@ pub trait Reader: Debug + Clone {
     /// Parse a word-sized integer according to the DWARF format.
     ///
     /// These are always used to encode section offsets or lengths,
-    /// and so have a type of `Self::Offset`.
+    /// and so have a type of `Self::Offset`. The byte order is given
+    /// by `endian`.
     fn read_word(&mut self, format: Format) -> Result<Self::Offset> {
         match format {
             Format::Dwarf32 => self.read_u32().map(Self::Offset::from_u32),
@@ -452,6 +525,7 @@ pub trait Reader: Debug + Clone {
     /// Parse a section offset of the given size.
     ///
     /// This is used for `DW_FORM_ref_addr` values in DWARF version 2.
+    /// The byte order is given by `endian`.
     fn read_sized_offset(&mut self, size: u8) -> Result<Self::Offset> {
         match size {
             1 => self.read_u8().map(u64::from),
@@ -463,3 +537,33 @@ pub trait Reader: Debug + Clone {
         .and_then(Self::Offset::from_u64)
     }
 }
+
+/// Compute a hash of the remaining data in a reader.
+///
+/// This uses the 64-bit FNV-1a algorithm. The result only depends on the
+/// bytes of the data, and is not affected by the endianity or the type of
+/// the reader, so it can be used to detect sections with identical contents,
+/// such as when deduplicating debugging information. The algorithm will not
+/// be changed in future versions.
+///
+/// This is not a cryptographic hash, and different data may produce the
+/// same hash.
+///
+/// ```
+/// use gimli::{hash_section, EndianSlice, LittleEndian};
+///
+/// let reader = EndianSlice::new(b"a", LittleEndian);
+/// assert_eq!(hash_section(&reader), Ok(0xaf63_dc4c_8601_ec8c));
+/// ```
+pub fn hash_section<R: Reader>(reader: &R) -> Result<u64> {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut input = reader.clone();
+    let mut hash = FNV_OFFSET_BASIS;
+    while !input.is_empty() {
+        hash ^= u64::from(input.read_u8()?);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    Ok(hash)
+}
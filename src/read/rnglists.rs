@@ -40,9 +40,7 @@ where
 }
 
 impl<R> Section<R> for DebugRanges<R> {
-    fn id() -> SectionId {
-        SectionId::DebugRanges
-    }
+    const ID: SectionId = SectionId::DebugRanges;
 
     fn reader(&self) -> &R {
         &self.section
@@ -87,9 +85,7 @@ where
 }
 
 impl<R> Section<R> for DebugRngLists<R> {
-    fn id() -> SectionId {
-        SectionId::DebugRngLists
-    }
+    const ID: SectionId = SectionId::DebugRngLists;
 
     fn reader(&self) -> &R {
         &self.section
@@ -142,7 +138,7 @@ fn parse_header<R: Reader>(input: &mut R) -> Result<RngListsHeader> {
         return Err(Error::UnknownVersion(u64::from(version)));
     }
 
-    let address_size = input.read_u8()?;
+    let address_size = input.read_address_size()?;
     let segment_selector_size = input.read_u8()?;
     if segment_selector_size != 0 {
         return Err(Error::UnsupportedSegmentSize);
@@ -176,6 +172,37 @@ impl<R> RangeLists<R> {
             debug_rnglists,
         }
     }
+
+    /// Return the `.debug_ranges` section.
+    pub fn debug_ranges(&self) -> &DebugRanges<R> {
+        &self.debug_ranges
+    }
+
+    /// Replace the `.debug_ranges` section.
+    ///
+    /// This is useful for `.dwo` files when using the GNU split-dwarf extension to DWARF 4,
+    /// because the range lists for the split units are in the parent file.
+    pub fn set_debug_ranges(&mut self, debug_ranges: DebugRanges<R>) {
+        self.debug_ranges = debug_ranges;
+    }
+
+    /// Return the `.debug_rnglists` section.
+    pub fn debug_rnglists(&self) -> &DebugRngLists<R> {
+        &self.debug_rnglists
+    }
+}
+
+impl<R: Default> RangeLists<R> {
+    /// Construct a new `RangeLists` instance from the data in the `.debug_rnglists`
+    /// section, with an empty `.debug_ranges` section.
+    ///
+    /// This is sufficient if all units use DWARF version 5.
+    pub fn from_debug_rnglists(debug_rnglists: DebugRngLists<R>) -> RangeLists<R> {
+        RangeLists {
+            debug_ranges: Default::default(),
+            debug_rnglists,
+        }
+    }
 }
 
 impl<T> RangeLists<T> {
@@ -265,6 +292,9 @@ impl<R: Reader> RangeLists<R> {
     ///
     /// The `unit_encoding` must match the compilation unit that the
     /// index was contained in.
+    ///
+    /// Only the `.debug_rnglists` section is used, since the `.debug_ranges`
+    /// section does not have an offset table.
     pub fn get_offset(
         &self,
         unit_encoding: Encoding,
@@ -449,12 +479,16 @@ impl<R: Reader> FallibleIterator for RawRngListIter<R> {
 /// This iterator internally handles processing of base addresses and different
 /// entry types.  Thus, it only returns range entries that are valid
 /// and already adjusted for the base address.
+///
+/// By default, all entries are returned. Use `set_tombstone` to skip
+/// entries for code that was discarded by the linker.
 #[derive(Debug)]
 pub struct RngListIter<R: Reader> {
     raw: RawRngListIter<R>,
     base_address: u64,
     debug_addr: DebugAddr<R>,
     debug_addr_base: DebugAddrBase<R::Offset>,
+    tombstone: Option<u64>,
 }
 
 impl<R: Reader> RngListIter<R> {
@@ -470,9 +504,34 @@ impl<R: Reader> RngListIter<R> {
             base_address,
             debug_addr,
             debug_addr_base,
+            tombstone: None,
         }
     }
 
+    /// Skip entries that were relocated to the given tombstone address.
+    ///
+    /// Linkers write a tombstone value in place of addresses that refer to
+    /// discarded sections. Common values are `0`, `!0` and `!0 - 1`.
+    /// The tombstone is truncated to the address size of the unit.
+    ///
+    /// An entry is skipped if its start address is the tombstone. Entries
+    /// that are offsets from a base address are instead skipped if the
+    /// base address is the tombstone, except for DWARF version 4 entries,
+    /// which may be either addresses or offsets. These are skipped if their
+    /// start address is the tombstone after the base address is added.
+    pub fn set_tombstone(&mut self, tombstone: u64) {
+        let address_size = self.raw.encoding.address_size;
+        let mask = (!0u64)
+            .checked_shr(64 - u32::from(address_size.min(8)) * 8)
+            .unwrap_or(0);
+        self.tombstone = Some(tombstone & mask);
+    }
+
+    #[inline]
+    fn is_tombstone(&self, address: u64) -> bool {
+        self.tombstone == Some(address)
+    }
+
     #[inline]
     fn get_address(&self, index: DebugAddrIndex<R::Offset>) -> Result<u64> {
         self.debug_addr
@@ -498,25 +557,51 @@ impl<R: Reader> RngListIter<R> {
                 }
                 RawRngListEntry::StartxEndx { begin, end } => {
                     let begin = self.get_address(begin)?;
+                    if self.is_tombstone(begin) {
+                        continue;
+                    }
                     let end = self.get_address(end)?;
                     Range { begin, end }
                 }
                 RawRngListEntry::StartxLength { begin, length } => {
                     let begin = self.get_address(begin)?;
-                    let end = begin + length;
+                    if self.is_tombstone(begin) {
+                        continue;
+                    }
+                    let end = begin.wrapping_add(length);
                     Range { begin, end }
                 }
-                RawRngListEntry::AddressOrOffsetPair { begin, end }
-                | RawRngListEntry::OffsetPair { begin, end } => {
+                RawRngListEntry::AddressOrOffsetPair { begin, end } => {
                     let mut range = Range { begin, end };
                     range.add_base_address(self.base_address, self.raw.encoding.address_size);
+                    if self.is_tombstone(range.begin) {
+                        continue;
+                    }
                     range
                 }
-                RawRngListEntry::StartEnd { begin, end } => Range { begin, end },
-                RawRngListEntry::StartLength { begin, length } => Range {
-                    begin,
-                    end: begin + length,
-                },
+                RawRngListEntry::OffsetPair { begin, end } => {
+                    if self.is_tombstone(self.base_address) {
+                        continue;
+                    }
+                    let mut range = Range { begin, end };
+                    range.add_base_address(self.base_address, self.raw.encoding.address_size);
+                    range
+                }
+                RawRngListEntry::StartEnd { begin, end } => {
+                    if self.is_tombstone(begin) {
+                        continue;
+                    }
+                    Range { begin, end }
+                }
+                RawRngListEntry::StartLength { begin, length } => {
+                    if self.is_tombstone(begin) {
+                        continue;
+                    }
+                    Range {
+                        begin,
+                        end: begin.wrapping_add(length),
+                    }
+                }
             };
 
             if range.begin > range.end {
@@ -602,6 +687,7 @@ mod tests {
     use super::*;
     use crate::endianity::LittleEndian;
     use crate::test_util::GimliSectionMethods;
+    use crate::vec::Vec;
     use test_assembler::{Endian, Label, LabelMaker, Section};
 
     #[test]
@@ -1303,6 +1389,242 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_ranges_addrx() {
+        let encoding = Encoding {
+            format: Format::Dwarf32,
+            version: 5,
+            address_size: 4,
+        };
+        let section = Section::with_endian(Endian::Little)
+            .L32(0x0100_0000)
+            .L32(0x0200_0000);
+        let buf = section.get_contents().unwrap();
+        let debug_addr = &DebugAddr::from(EndianSlice::new(&buf, LittleEndian));
+        let debug_addr_base = DebugAddrBase(0);
+
+        #[rustfmt::skip]
+        let section = Section::with_endian(Endian::Little)
+            // A StartxEndx with an empty range.
+            .L8(2).uleb(1).uleb(1)
+            // A StartxLength that overflows.
+            .L8(3).uleb(1).uleb(0xffff_ffff_ffff_ffff)
+            // A StartxEndx with an index past the end of `.debug_addr`.
+            .L8(2).uleb(0).uleb(2)
+            // A BaseAddressx with an index past the end of `.debug_addr`.
+            .L8(1).uleb(2);
+        let buf = section.get_contents().unwrap();
+        let debug_ranges = DebugRanges::new(&[], LittleEndian);
+        let debug_rnglists = DebugRngLists::new(&buf, LittleEndian);
+        let rnglists = RangeLists::new(debug_ranges, debug_rnglists);
+        let ranges = |offset| {
+            rnglists
+                .ranges(
+                    RangeListsOffset(offset),
+                    encoding,
+                    0,
+                    debug_addr,
+                    debug_addr_base,
+                )
+                .unwrap()
+        };
+
+        let mut iter = ranges(0);
+        assert_eq!(
+            iter.next(),
+            Ok(Some(Range {
+                begin: 0x0200_0000,
+                end: 0x0200_0000,
+            }))
+        );
+        assert_eq!(iter.next(), Err(Error::InvalidAddressRange));
+        assert_eq!(iter.next(), Ok(None));
+
+        for offset in &[14, 17] {
+            match ranges(*offset).next() {
+                Err(Error::UnexpectedEof(_)) => {}
+                otherwise => panic!("Unexpected result: {:?}", otherwise),
+            }
+        }
+    }
+
+    #[test]
+    fn test_ranges_tombstone() {
+        let encoding = Encoding {
+            format: Format::Dwarf32,
+            version: 5,
+            address_size: 4,
+        };
+        let section = Section::with_endian(Endian::Little)
+            .L32(0x0100_0000)
+            .L32(0xffff_ffff);
+        let buf = section.get_contents().unwrap();
+        let debug_addr = &DebugAddr::from(EndianSlice::new(&buf, LittleEndian));
+        let debug_addr_base = DebugAddrBase(0);
+
+        #[rustfmt::skip]
+        let section = Section::with_endian(Endian::Little)
+            // A tombstone StartEnd, then a normal StartEnd.
+            .L8(6).L32(0xffff_ffff).L32(0xffff_ffff)
+            .L8(6).L32(0x0100_0000).L32(0x0100_0100)
+            // A tombstone StartxLength, then a normal StartxLength.
+            .L8(3).uleb(1).uleb(0x100)
+            .L8(3).uleb(0).uleb(0x200)
+            // A tombstone base address followed by an OffsetPair.
+            .L8(5).L32(0xffff_ffff)
+            .L8(4).uleb(0).uleb(0x100)
+            // A normal base address followed by an OffsetPair.
+            .L8(5).L32(0x0200_0000)
+            .L8(4).uleb(0).uleb(0x300)
+            // A range end.
+            .L8(0);
+        let buf = section.get_contents().unwrap();
+        let debug_ranges = DebugRanges::new(&[], LittleEndian);
+        let debug_rnglists = DebugRngLists::new(&buf, LittleEndian);
+        let rnglists = RangeLists::new(debug_ranges, debug_rnglists);
+        let ranges = || {
+            rnglists
+                .ranges(
+                    RangeListsOffset(0),
+                    encoding,
+                    0,
+                    debug_addr,
+                    debug_addr_base,
+                )
+                .unwrap()
+        };
+
+        // By default, tombstones are not skipped.
+        assert_eq!(
+            ranges().next(),
+            Ok(Some(Range {
+                begin: 0xffff_ffff,
+                end: 0xffff_ffff,
+            }))
+        );
+
+        let mut iter = ranges();
+        iter.set_tombstone(!0);
+        assert_eq!(
+            iter.collect::<Vec<_>>(),
+            Ok(vec![
+                Range {
+                    begin: 0x0100_0000,
+                    end: 0x0100_0100,
+                },
+                Range {
+                    begin: 0x0100_0000,
+                    end: 0x0100_0200,
+                },
+                Range {
+                    begin: 0x0200_0000,
+                    end: 0x0200_0300,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_ranges_tombstone_address_size() {
+        let debug_ranges = DebugRanges::new(&[], LittleEndian);
+        let debug_rnglists = DebugRngLists::new(&[], LittleEndian);
+        let rnglists = RangeLists::new(debug_ranges, debug_rnglists);
+        let debug_addr = &DebugAddr::from(EndianSlice::new(&[], LittleEndian));
+        let tombstone = |address_size| {
+            let encoding = Encoding {
+                format: Format::Dwarf32,
+                version: 5,
+                address_size,
+            };
+            let mut ranges = rnglists
+                .ranges(
+                    RangeListsOffset(0),
+                    encoding,
+                    0,
+                    debug_addr,
+                    DebugAddrBase(0),
+                )
+                .unwrap();
+            ranges.set_tombstone(!0);
+            ranges.tombstone
+        };
+        // Invalid address sizes from untrusted headers must not panic.
+        assert_eq!(tombstone(0), Some(0));
+        assert_eq!(tombstone(2), Some(0xffff));
+        assert_eq!(tombstone(8), Some(!0));
+        assert_eq!(tombstone(32), Some(!0));
+    }
+
+    #[test]
+    fn test_ranges_tombstone_v4() {
+        #[rustfmt::skip]
+        let section = Section::with_endian(Endian::Little)
+            // An offset from the unit's base address of 0x1000.
+            .L32(0).L32(0x100)
+            // A base address selection entry for a tombstone base.
+            .L32(0xffff_ffff).L32(0xffff_fffe)
+            .L32(0).L32(1)
+            // A base address selection entry for a zero base.
+            .L32(0xffff_ffff).L32(0)
+            .L32(0x200).L32(0x300)
+            .L32(0).L32(0);
+        let buf = section.get_contents().unwrap();
+        let debug_ranges = DebugRanges::new(&buf, LittleEndian);
+        let debug_rnglists = DebugRngLists::new(&[], LittleEndian);
+        let rnglists = RangeLists::new(debug_ranges, debug_rnglists);
+        let debug_addr = &DebugAddr::from(EndianSlice::new(&[], LittleEndian));
+        let encoding = Encoding {
+            format: Format::Dwarf32,
+            version: 4,
+            address_size: 4,
+        };
+        let ranges = |tombstone| {
+            let mut ranges = rnglists
+                .ranges(
+                    RangeListsOffset(0),
+                    encoding,
+                    0x1000,
+                    debug_addr,
+                    DebugAddrBase(0),
+                )
+                .unwrap();
+            ranges.set_tombstone(tombstone);
+            ranges.collect::<Vec<_>>().unwrap()
+        };
+
+        assert_eq!(
+            ranges(!0 - 1),
+            vec![
+                Range {
+                    begin: 0x1000,
+                    end: 0x1100
+                },
+                Range {
+                    begin: 0x200,
+                    end: 0x300
+                },
+            ]
+        );
+        // The offset 0 from the unit's base address is not a tombstone.
+        assert_eq!(
+            ranges(0),
+            vec![
+                Range {
+                    begin: 0x1000,
+                    end: 0x1100
+                },
+                Range {
+                    begin: 0xffff_fffe,
+                    end: 0xffff_ffff
+                },
+                Range {
+                    begin: 0x200,
+                    end: 0x300
+                },
+            ]
+        );
+    }
+
     #[test]
     fn test_get_offset() {
         for format in vec![Format::Dwarf32, Format::Dwarf64] {
@@ -1332,9 +1654,8 @@ mod tests {
             length.set_const((&end - &start) as u64);
             let section = section.get_contents().unwrap();
 
-            let debug_ranges = DebugRanges::from(EndianSlice::new(&[], LittleEndian));
             let debug_rnglists = DebugRngLists::from(EndianSlice::new(&section, LittleEndian));
-            let ranges = RangeLists::new(debug_ranges, debug_rnglists);
+            let ranges = RangeLists::from_debug_rnglists(debug_rnglists);
 
             let base = DebugRngListsBase((&first - &zero) as usize);
             assert_eq!(
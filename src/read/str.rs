@@ -2,7 +2,7 @@ use crate::common::{
     DebugLineStrOffset, DebugStrOffset, DebugStrOffsetsBase, DebugStrOffsetsIndex, SectionId,
 };
 use crate::endianity::Endianity;
-use crate::read::{EndianSlice, Reader, ReaderOffset, Result, Section};
+use crate::read::{hash_section, EndianSlice, Error, Reader, ReaderOffset, Result, Section};
 use crate::Format;
 
 /// The `DebugStr` struct represents the DWARF strings
@@ -53,6 +53,42 @@ impl<R: Reader> DebugStr<R> {
         input.skip(offset.0)?;
         input.read_null_terminated_slice()
     }
+
+    /// Lookup a string from the `.debug_str` section by DebugStrOffset,
+    /// reading at most `max_len` bytes before the null terminator.
+    ///
+    /// Returns `Error::UnterminatedString` if there is no null terminator
+    /// within `max_len` bytes of the offset.
+    ///
+    /// ```
+    /// use gimli::{DebugStr, DebugStrOffset, Error, LittleEndian};
+    ///
+    /// let buf = [0x01, 0x02, 0x00];
+    /// let debug_str = DebugStr::new(&buf, LittleEndian);
+    /// assert!(debug_str.get_str_max(DebugStrOffset(0), 2).is_ok());
+    /// assert_eq!(
+    ///     debug_str.get_str_max(DebugStrOffset(0), 1),
+    ///     Err(Error::UnterminatedString)
+    /// );
+    /// ```
+    pub fn get_str_max(&self, offset: DebugStrOffset<R::Offset>, max_len: R::Offset) -> Result<R> {
+        let input = &mut self.debug_str_section.clone();
+        input.skip(offset.0)?;
+        let mut bounded = input.clone();
+        if bounded.len() > max_len {
+            bounded.truncate(max_len + R::Offset::from_u8(1))?;
+        }
+        let len = bounded.find(0).map_err(|_| Error::UnterminatedString)?;
+        input.split(len)
+    }
+
+    /// Compute a hash of the contents of the `.debug_str` section.
+    ///
+    /// Two sections with identical contents always have the same hash.
+    /// See `hash_section` for details of the algorithm.
+    pub fn hash(&self) -> Result<u64> {
+        hash_section(&self.debug_str_section)
+    }
 }
 
 impl<T> DebugStr<T> {
@@ -80,9 +116,7 @@ impl<T> DebugStr<T> {
 }
 
 impl<R> Section<R> for DebugStr<R> {
-    fn id() -> SectionId {
-        SectionId::DebugStr
-    }
+    const ID: SectionId = SectionId::DebugStr;
 
     fn reader(&self) -> &R {
         &self.debug_str_section
@@ -118,6 +152,8 @@ impl<R: Reader> DebugStrOffsets<R> {
     /// The `format` must be the DWARF format of the compilation unit. This format must
     /// match the header. However, note that we do not parse the header to validate this,
     /// since locating the header is unreliable, and the GNU extensions do not emit it.
+    /// For DWARF version 5, use `DebugStrOffsets::header` to validate the header and
+    /// the number of entries.
     pub fn get_str_offset(
         &self,
         format: Format,
@@ -131,6 +167,94 @@ impl<R: Reader> DebugStrOffsets<R> {
         )?)?;
         input.read_offset(format).map(DebugStrOffset)
     }
+
+    /// Parse the header of the set of entries at the given `base`.
+    ///
+    /// The `base` must be the `DW_AT_str_offsets_base` value from a DWARF
+    /// version 5 compilation unit DIE, or the implied base for a unit without
+    /// that attribute.
+    /// This is an offset that points to the first entry following the header,
+    /// so the header is located by subtracting the header size for the given
+    /// `format`: 8 bytes for 32-bit DWARF, and 16 bytes for 64-bit DWARF.
+    ///
+    /// The GNU extensions to DWARF 4 do not emit a header, so this cannot be
+    /// used for those units.
+    ///
+    /// ```
+    /// use gimli::{DebugStrOffsets, DebugStrOffsetsBase, EndianSlice, Format, LittleEndian};
+    ///
+    /// let buf = [
+    ///     // Length, version, padding.
+    ///     0x0c, 0x00, 0x00, 0x00, 0x05, 0x00, 0x00, 0x00,
+    ///     // Entries.
+    ///     0x00, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00,
+    /// ];
+    /// let debug_str_offsets = DebugStrOffsets::from(EndianSlice::new(&buf, LittleEndian));
+    /// let header = debug_str_offsets
+    ///     .header(Format::Dwarf32, DebugStrOffsetsBase(8))
+    ///     .unwrap();
+    /// assert_eq!(header.version(), 5);
+    /// assert_eq!(header.count(), 2);
+    /// ```
+    pub fn header(
+        &self,
+        format: Format,
+        base: DebugStrOffsetsBase<R::Offset>,
+    ) -> Result<StrOffsetsHeader<R::Offset>> {
+        // initial_length + version + padding
+        let header_size = R::Offset::from_u8(format.initial_length_size() + 2 + 2);
+        if base.0 < header_size {
+            return Err(Error::OffsetOutOfBounds);
+        }
+        let input = &mut self.section.clone();
+        input.skip(base.0 - header_size)?;
+
+        let (length, header_format) = input.read_initial_length()?;
+        if header_format != format {
+            return Err(Error::UnsupportedOffsetSize(header_format.word_size()));
+        }
+        input.truncate(length)?;
+        let version = input.read_u16()?;
+        if version != 5 {
+            return Err(Error::UnknownVersion(u64::from(version)));
+        }
+        let _padding = input.read_u16()?;
+        let count = R::Offset::from_u64(input.len().into_u64() / u64::from(format.word_size()))?;
+        Ok(StrOffsetsHeader {
+            format,
+            version,
+            count,
+        })
+    }
+}
+
+/// The header of a set of entries in the `.debug_str_offsets` section.
+///
+/// This is returned by `DebugStrOffsets::header`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StrOffsetsHeader<Offset = usize> {
+    format: Format,
+    version: u16,
+    count: Offset,
+}
+
+impl<Offset: ReaderOffset> StrOffsetsHeader<Offset> {
+    /// Return the DWARF format of the entries.
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Return the version of the header.
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    /// Return the number of entries in the set.
+    ///
+    /// A `DW_FORM_strx` index must be less than this count.
+    pub fn count(&self) -> Offset {
+        self.count
+    }
 }
 
 impl<T> DebugStrOffsets<T> {
@@ -158,9 +282,7 @@ impl<T> DebugStrOffsets<T> {
 }
 
 impl<R> Section<R> for DebugStrOffsets<R> {
-    fn id() -> SectionId {
-        SectionId::DebugStrOffsets
-    }
+    const ID: SectionId = SectionId::DebugStrOffsets;
 
     fn reader(&self) -> &R {
         &self.section
@@ -214,9 +336,7 @@ impl<T> DebugLineStr<T> {
 }
 
 impl<R> Section<R> for DebugLineStr<R> {
-    fn id() -> SectionId {
-        SectionId::DebugLineStr
-    }
+    const ID: SectionId = SectionId::DebugLineStr;
 
     fn reader(&self) -> &R {
         &self.section
@@ -236,6 +356,41 @@ mod tests {
     use crate::LittleEndian;
     use test_assembler::{Endian, Label, LabelMaker, Section};
 
+    #[test]
+    fn test_get_str_max() {
+        let buf = b"abc\0\0def";
+        let debug_str = DebugStr::new(buf, LittleEndian);
+
+        let get = |offset, max_len| {
+            debug_str
+                .get_str_max(DebugStrOffset(offset), max_len)
+                .map(|s| s.slice())
+        };
+        assert_eq!(get(0, 3), Ok(&b"abc"[..]));
+        assert_eq!(get(0, 100), Ok(&b"abc"[..]));
+        assert_eq!(get(0, 2), Err(Error::UnterminatedString));
+        assert_eq!(get(4, 0), Ok(&b""[..]));
+        assert_eq!(get(5, 3), Err(Error::UnterminatedString));
+        assert_eq!(get(5, 100), Err(Error::UnterminatedString));
+        match get(11, 1) {
+            Err(Error::UnexpectedEof(_)) => {}
+            otherwise => panic!("Unexpected result: {:?}", otherwise),
+        }
+    }
+
+    #[test]
+    fn test_hash() {
+        let hash = |buf| DebugStr::new(buf, LittleEndian).hash().unwrap();
+        assert_eq!(hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash(b"foobar"), 0x8594_4171_f739_67e8);
+        assert_eq!(hash(b"abc\0def\0"), hash(b"abc\0def\0"));
+        assert_ne!(hash(b"abc\0def\0"), hash(b"abc\0deg\0"));
+        assert_eq!(
+            DebugStr::new(b"abc\0", LittleEndian).hash(),
+            DebugStr::new(b"abc\0", crate::BigEndian).hash()
+        );
+    }
+
     #[test]
     fn test_get_str_offset() {
         for format in vec![Format::Dwarf32, Format::Dwarf64] {
@@ -270,4 +425,57 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_str_offsets_header() {
+        for format in vec![Format::Dwarf32, Format::Dwarf64] {
+            let zero = Label::new();
+            let length = Label::new();
+            let start = Label::new();
+            let first = Label::new();
+            let end = Label::new();
+            let mut section = Section::with_endian(Endian::Little)
+                .mark(&zero)
+                .initial_length(format, &length, &start)
+                .D16(5)
+                .D16(0)
+                .mark(&first);
+            for i in 0..20 {
+                section = section.word(format.word_size(), 1000 + i);
+            }
+            section = section.mark(&end);
+            // Trailing data from another set of entries.
+            section = section.word(format.word_size(), 0);
+            length.set_const((&end - &start) as u64);
+
+            let section = section.get_contents().unwrap();
+            let debug_str_offsets = DebugStrOffsets::from(EndianSlice::new(&section, LittleEndian));
+            let base = DebugStrOffsetsBase((&first - &zero) as usize);
+
+            let header = debug_str_offsets.header(format, base).unwrap();
+            assert_eq!(header.format(), format);
+            assert_eq!(header.version(), 5);
+            assert_eq!(header.count(), 20);
+
+            assert_eq!(
+                debug_str_offsets.header(format, DebugStrOffsetsBase(4)),
+                Err(Error::OffsetOutOfBounds)
+            );
+        }
+    }
+
+    #[test]
+    fn test_str_offsets_header_version() {
+        let section = Section::with_endian(Endian::Little)
+            .L32(4)
+            .L16(4)
+            .L16(0)
+            .get_contents()
+            .unwrap();
+        let debug_str_offsets = DebugStrOffsets::from(EndianSlice::new(&section, LittleEndian));
+        assert_eq!(
+            debug_str_offsets.header(Format::Dwarf32, DebugStrOffsetsBase(8)),
+            Err(Error::UnknownVersion(4))
+        );
+    }
 }
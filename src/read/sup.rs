@@ -0,0 +1,211 @@
+use crate::common::SectionId;
+use crate::endianity::Endianity;
+use crate::read::{EndianSlice, Error, Reader, ReaderOffset, Result, Section};
+
+/// The `DebugSup` struct represents the contents of the `.debug_sup` section,
+/// which links an object file with its supplementary object file.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DebugSup<R> {
+    section: R,
+}
+
+impl<'input, Endian> DebugSup<EndianSlice<'input, Endian>>
+where
+    Endian: Endianity,
+{
+    /// Construct a new `DebugSup` instance from the data in the `.debug_sup`
+    /// section.
+    ///
+    /// It is the caller's responsibility to read the `.debug_sup` section and
+    /// present it as a `&[u8]` slice. That means using some ELF loader on
+    /// Linux, a Mach-O loader on OSX, etc.
+    ///
+    /// ```
+    /// use gimli::{DebugSup, LittleEndian};
+    ///
+    /// # let buf = [];
+    /// # let read_debug_sup_section_somehow = || &buf;
+    /// let debug_sup = DebugSup::new(read_debug_sup_section_somehow(), LittleEndian);
+    /// ```
+    pub fn new(section: &'input [u8], endian: Endian) -> Self {
+        Self::from(EndianSlice::new(section, endian))
+    }
+}
+
+impl<R: Reader> DebugSup<R> {
+    /// Parse the contents of the `.debug_sup` section.
+    ///
+    /// Returns `Ok(None)` if the section is empty.
+    pub fn header(&self) -> Result<Option<DebugSupHeader<R>>> {
+        let mut input = self.section.clone();
+        if input.is_empty() {
+            return Ok(None);
+        }
+
+        let version = input.read_u16()?;
+        if version != 5 {
+            return Err(Error::UnknownVersion(u64::from(version)));
+        }
+
+        let is_supplementary = match input.read_u8()? {
+            0 => false,
+            1 => true,
+            _ => return Err(Error::BadSupplementaryFlag),
+        };
+        let filename = input.read_null_terminated_slice()?;
+        let checksum_len = R::Offset::from_u64(input.read_uleb128()?)?;
+        let checksum = input.split(checksum_len)?;
+        Ok(Some(DebugSupHeader {
+            version,
+            is_supplementary,
+            filename,
+            checksum,
+        }))
+    }
+}
+
+impl<T> DebugSup<T> {
+    /// Create a `DebugSup` section that references the data in `self`.
+    ///
+    /// This is useful when `R` implements `Reader` but `T` does not.
+    ///
+    /// ## Example Usage
+    ///
+    /// ```rust,no_run
+    /// # let load_section = || unimplemented!();
+    /// // Read the DWARF section into a `Vec` with whatever object loader you're using.
+    /// let owned_section: gimli::DebugSup<Vec<u8>> = load_section();
+    /// // Create a reference to the DWARF section.
+    /// let section = owned_section.borrow(|section| {
+    ///     gimli::EndianSlice::new(&section, gimli::LittleEndian)
+    /// });
+    /// ```
+    pub fn borrow<'a, F, R>(&'a self, mut borrow: F) -> DebugSup<R>
+    where
+        F: FnMut(&'a T) -> R,
+    {
+        borrow(&self.section).into()
+    }
+}
+
+impl<R> Section<R> for DebugSup<R> {
+    const ID: SectionId = SectionId::DebugSup;
+
+    fn reader(&self) -> &R {
+        &self.section
+    }
+}
+
+impl<R> From<R> for DebugSup<R> {
+    fn from(section: R) -> Self {
+        DebugSup { section }
+    }
+}
+
+/// The header of the `.debug_sup` section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DebugSupHeader<R> {
+    /// The version of the section.
+    pub version: u16,
+
+    /// Whether this file is a supplementary object file.
+    ///
+    /// If this is false, then `filename` and `checksum` identify the
+    /// supplementary object file used by this file.
+    pub is_supplementary: bool,
+
+    /// The name of the supplementary object file.
+    ///
+    /// This is empty if `is_supplementary` is true.
+    pub filename: R,
+
+    /// An implementation defined checksum of the supplementary object file.
+    pub checksum: R,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::endianity::LittleEndian;
+    use crate::test_util::GimliSectionMethods;
+    use test_assembler::{Endian, Section};
+
+    #[test]
+    fn test_debug_sup_header() {
+        let buf = Section::with_endian(Endian::Little)
+            .L16(5)
+            .D8(0)
+            .append_bytes(b"sup.debug\0")
+            .uleb(4)
+            .append_bytes(&[1, 2, 3, 4])
+            .get_contents()
+            .unwrap();
+        let debug_sup = DebugSup::new(&buf, LittleEndian);
+        assert_eq!(
+            debug_sup.header(),
+            Ok(Some(DebugSupHeader {
+                version: 5,
+                is_supplementary: false,
+                filename: EndianSlice::new(b"sup.debug", LittleEndian),
+                checksum: EndianSlice::new(&[1, 2, 3, 4], LittleEndian),
+            }))
+        );
+
+        let buf = Section::with_endian(Endian::Little)
+            .L16(5)
+            .D8(1)
+            .D8(0)
+            .uleb(0)
+            .get_contents()
+            .unwrap();
+        let debug_sup = DebugSup::new(&buf, LittleEndian);
+        assert_eq!(
+            debug_sup.header(),
+            Ok(Some(DebugSupHeader {
+                version: 5,
+                is_supplementary: true,
+                filename: EndianSlice::new(b"", LittleEndian),
+                checksum: EndianSlice::new(&[], LittleEndian),
+            }))
+        );
+    }
+
+    #[test]
+    fn test_debug_sup_header_empty() {
+        let debug_sup = DebugSup::new(&[], LittleEndian);
+        assert_eq!(debug_sup.header(), Ok(None));
+    }
+
+    #[test]
+    fn test_debug_sup_header_errors() {
+        let buf = Section::with_endian(Endian::Little)
+            .L16(4)
+            .D8(0)
+            .get_contents()
+            .unwrap();
+        let debug_sup = DebugSup::new(&buf, LittleEndian);
+        assert_eq!(debug_sup.header(), Err(Error::UnknownVersion(4)));
+
+        let buf = Section::with_endian(Endian::Little)
+            .L16(5)
+            .D8(2)
+            .get_contents()
+            .unwrap();
+        let debug_sup = DebugSup::new(&buf, LittleEndian);
+        assert_eq!(debug_sup.header(), Err(Error::BadSupplementaryFlag));
+
+        let buf = Section::with_endian(Endian::Little)
+            .L16(5)
+            .D8(0)
+            .append_bytes(b"sup\0")
+            .uleb(8)
+            .D8(1)
+            .get_contents()
+            .unwrap();
+        let debug_sup = DebugSup::new(&buf, LittleEndian);
+        match debug_sup.header() {
+            Err(Error::UnexpectedEof(_)) => {}
+            otherwise => panic!("Unexpected result: {:?}", otherwise),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! Functions for parsing DWARF `.debug_info` and `.debug_types` sections.
 
 use fallible_iterator::FallibleIterator;
+#[cfg(feature = "alloc")]
 use std::cell::Cell;
 use std::ops::{Range, RangeFrom, RangeTo};
 use std::{u16, u8};
@@ -9,15 +10,22 @@ use crate::common::{
     DebugAbbrevOffset, DebugAddrBase, DebugAddrIndex, DebugInfoOffset, DebugLineOffset,
     DebugLineStrOffset, DebugLocListsBase, DebugLocListsIndex, DebugMacinfoOffset,
     DebugRngListsBase, DebugRngListsIndex, DebugStrOffset, DebugStrOffsetsBase,
-    DebugStrOffsetsIndex, DebugTypeSignature, DebugTypesOffset, Encoding, Format,
+    DebugStrOffsetsIndex, DebugTypeSignature, DebugTypesOffset, DwoId, Encoding, Format,
     LocationListsOffset, RangeListsOffset, SectionId,
 };
 use crate::constants;
 use crate::endianity::Endianity;
+#[cfg(feature = "alloc")]
+use crate::read::abbrev::get_attribute_size;
+#[cfg(feature = "alloc")]
 use crate::read::{
-    Abbreviation, Abbreviations, AttributeSpecification, DebugAbbrev, DebugStr, EndianSlice, Error,
-    Expression, Reader, ReaderOffset, Result, Section,
+    Abbreviation, Abbreviations, AttributeSpecification, DebugAbbrev, DebugAddr,
 };
+use crate::read::{
+    DebugStr, EndianSlice, Error, Expression, Reader, ReaderOffset, Result, Section,
+};
+#[cfg(feature = "alloc")]
+use crate::vec::Vec;
 
 impl<T: ReaderOffset> DebugTypesOffset<T> {
     /// Convert an offset to be relative to the start of the given unit,
@@ -58,7 +66,7 @@ impl<T: ReaderOffset> DebugInfoOffset<T> {
 }
 
 /// An offset into the current compilation or type unit.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Ord, PartialOrd, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct UnitOffset<T = usize>(pub T);
 
 impl<T: ReaderOffset> UnitOffset<T> {
@@ -132,6 +140,7 @@ impl<R: Reader> DebugInfo<R> {
     /// `FallibleIterator`](./index.html#using-with-fallibleiterator).
     pub fn units(&self) -> CompilationUnitHeadersIter<R> {
         CompilationUnitHeadersIter {
+            section: self.debug_info_section.clone(),
             input: self.debug_info_section.clone(),
             offset: DebugInfoOffset(R::Offset::from_u8(0)),
         }
@@ -148,6 +157,30 @@ impl<R: Reader> DebugInfo<R> {
         input.skip(offset.0)?;
         CompilationUnitHeader::parse(input, offset)
     }
+
+    /// Get the CompilationUnitHeader for the unit that contains the given
+    /// offset in this .debug_info section.
+    ///
+    /// Unlike `header_from_offset`, the offset does not need to be the start
+    /// of a unit. This is useful for finding the unit of the target of a
+    /// `DW_FORM_ref_addr` attribute.
+    ///
+    /// This scans the unit headers from the start of the section, so callers
+    /// that perform many lookups should build their own index of the headers.
+    ///
+    /// Returns `Error::OffsetOutOfBounds` if no unit contains the offset.
+    pub fn header_containing_offset(
+        &self,
+        offset: DebugInfoOffset<R::Offset>,
+    ) -> Result<CompilationUnitHeader<R>> {
+        let mut units = self.units();
+        while let Some(header) = units.next()? {
+            if offset.0 < header.offset().0 + header.length_including_self() {
+                return Ok(header);
+            }
+        }
+        Err(Error::OffsetOutOfBounds)
+    }
 }
 
 impl<T> DebugInfo<T> {
@@ -175,9 +208,7 @@ impl<T> DebugInfo<T> {
 }
 
 impl<R> Section<R> for DebugInfo<R> {
-    fn id() -> SectionId {
-        SectionId::DebugInfo
-    }
+    const ID: SectionId = SectionId::DebugInfo;
 
     fn reader(&self) -> &R {
         &self.debug_info_section
@@ -196,11 +227,62 @@ impl<R> From<R> for DebugInfo<R> {
 /// `DebugInfo::units`](./struct.DebugInfo.html#method.units) for more detail.
 #[derive(Clone, Debug)]
 pub struct CompilationUnitHeadersIter<R: Reader> {
+    section: R,
     input: R,
     offset: DebugInfoOffset<R::Offset>,
 }
 
 impl<R: Reader> CompilationUnitHeadersIter<R> {
+    /// Reposition the iterator so that the next unit is the unit at the given offset.
+    ///
+    /// The lengths of the units before the offset are read to check that the
+    /// offset is the start of a unit. If it is not, then
+    /// `Error::OffsetOutOfBounds` is returned and the iterator is empty.
+    pub fn seek(&mut self, offset: DebugInfoOffset<R::Offset>) -> Result<()> {
+        self.input = self.section.clone();
+        self.offset = DebugInfoOffset(R::Offset::from_u8(0));
+        while self.offset.0 < offset.0 && !self.input.is_empty() {
+            self.skip_unit()?;
+        }
+        if self.offset != offset {
+            self.input.empty();
+            return Err(Error::OffsetOutOfBounds);
+        }
+        Ok(())
+    }
+
+    /// Advance the iterator past the next `n` units.
+    ///
+    /// Only the length of each unit is read, and the rest of the unit
+    /// header is not parsed. Stops early if the end of the section is reached.
+    pub fn skip_units(&mut self, n: usize) -> Result<()> {
+        for _ in 0..n {
+            if self.input.is_empty() {
+                break;
+            }
+            self.skip_unit()?;
+        }
+        Ok(())
+    }
+
+    fn skip_unit(&mut self) -> Result<()> {
+        let len = self.input.len();
+        let input = &mut self.input;
+        match input
+            .read_initial_length()
+            .and_then(|(length, _)| input.skip(length))
+        {
+            Ok(()) => {
+                self.offset.0 += len - self.input.len();
+                Ok(())
+            }
+            Err(e) => {
+                self.input.empty();
+                Err(e)
+            }
+        }
+    }
+
     /// Advance the iterator to the next unit header.
     pub fn next(&mut self) -> Result<Option<CompilationUnitHeader<R>>> {
         if self.input.is_empty() {
@@ -230,6 +312,51 @@ impl<R: Reader> FallibleIterator for CompilationUnitHeadersIter<R> {
     }
 }
 
+/// The type of a unit in the `.debug_info` section, and the fields of the
+/// unit header that are specific to that type.
+///
+/// Units in DWARF versions before 5 are always `UnitType::Compilation`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnitType<Offset: ReaderOffset> {
+    /// A full compilation unit (`DW_UT_compile`).
+    Compilation,
+    /// A type unit (`DW_UT_type`).
+    Type {
+        /// The unique type signature for this type unit.
+        type_signature: DebugTypeSignature,
+        /// The offset within this type unit where the type is defined.
+        type_offset: UnitOffset<Offset>,
+    },
+    /// A partial unit (`DW_UT_partial`).
+    Partial,
+    /// A skeleton unit (`DW_UT_skeleton`), with the identifier of its split
+    /// compilation unit.
+    Skeleton(DwoId),
+    /// A split compilation unit (`DW_UT_split_compile`), with its identifier.
+    SplitCompilation(DwoId),
+    /// A split type unit (`DW_UT_split_type`).
+    SplitType {
+        /// The unique type signature for this type unit.
+        type_signature: DebugTypeSignature,
+        /// The offset within this type unit where the type is defined.
+        type_offset: UnitOffset<Offset>,
+    },
+}
+
+impl<Offset: ReaderOffset> UnitType<Offset> {
+    /// Return the `DW_UT_*` constant for this unit type.
+    pub fn dw_ut(&self) -> constants::DwUt {
+        match *self {
+            UnitType::Compilation => constants::DW_UT_compile,
+            UnitType::Type { .. } => constants::DW_UT_type,
+            UnitType::Partial => constants::DW_UT_partial,
+            UnitType::Skeleton(_) => constants::DW_UT_skeleton,
+            UnitType::SplitCompilation(_) => constants::DW_UT_split_compile,
+            UnitType::SplitType { .. } => constants::DW_UT_split_type,
+        }
+    }
+}
+
 /// The header of a compilation unit's debugging information.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct CompilationUnitHeader<R, Offset = <R as Reader>::Offset>
@@ -239,6 +366,7 @@ where
 {
     header: UnitHeader<R, Offset>,
     offset: DebugInfoOffset<Offset>,
+    unit_type: UnitType<Offset>,
 }
 
 impl<R, Offset> CompilationUnitHeader<R, Offset>
@@ -248,7 +376,11 @@ where
 {
     /// Construct a new `CompilationUnitHeader`.
     pub fn new(header: UnitHeader<R, Offset>, offset: DebugInfoOffset<Offset>) -> Self {
-        CompilationUnitHeader { header, offset }
+        CompilationUnitHeader {
+            header,
+            offset,
+            unit_type: UnitType::Compilation,
+        }
     }
 
     /// Return the `UnitHeader` containing common unit header fields.
@@ -267,6 +399,44 @@ where
         self.offset
     }
 
+    /// Get the type of this unit, and the fields of its header that are
+    /// specific to that type.
+    pub fn unit_type(&self) -> UnitType<R::Offset> {
+        self.unit_type
+    }
+
+    /// Get the unique type signature of this unit, if it is a DWARF 5 type
+    /// unit (`DW_UT_type` or `DW_UT_split_type`).
+    pub fn type_signature(&self) -> Option<DebugTypeSignature> {
+        match self.unit_type {
+            UnitType::Type { type_signature, .. } | UnitType::SplitType { type_signature, .. } => {
+                Some(type_signature)
+            }
+            _ => None,
+        }
+    }
+
+    /// Get the offset within this unit of the entry that describes the type,
+    /// if it is a DWARF 5 type unit (`DW_UT_type` or `DW_UT_split_type`).
+    pub fn type_offset(&self) -> Option<UnitOffset<R::Offset>> {
+        match self.unit_type {
+            UnitType::Type { type_offset, .. } | UnitType::SplitType { type_offset, .. } => {
+                Some(type_offset)
+            }
+            _ => None,
+        }
+    }
+
+    /// Get the DWO identifier from the header of this unit, if it is a DWARF 5
+    /// skeleton or split compilation unit (`DW_UT_skeleton` or
+    /// `DW_UT_split_compile`).
+    pub fn dwo_id(&self) -> Option<DwoId> {
+        match self.unit_type {
+            UnitType::Skeleton(dwo_id) | UnitType::SplitCompilation(dwo_id) => Some(dwo_id),
+            _ => None,
+        }
+    }
+
     /// Get the length of the debugging info for this compilation unit, not
     /// including the byte length of the encoded length itself.
     pub fn unit_length(&self) -> R::Offset {
@@ -311,6 +481,7 @@ where
     }
 
     /// Navigate this compilation unit's `DebuggingInformationEntry`s.
+    #[cfg(feature = "alloc")]
     pub fn entries<'me, 'abbrev>(
         &'me self,
         abbreviations: &'abbrev Abbreviations,
@@ -320,6 +491,7 @@ where
 
     /// Navigate this compilation unit's `DebuggingInformationEntry`s
     /// starting at the given offset.
+    #[cfg(feature = "alloc")]
     pub fn entries_at_offset<'me, 'abbrev>(
         &'me self,
         abbreviations: &'abbrev Abbreviations,
@@ -330,6 +502,7 @@ where
 
     /// Navigate this compilation unit's `DebuggingInformationEntry`s as a tree
     /// starting at the given offset.
+    #[cfg(feature = "alloc")]
     pub fn entries_tree<'me, 'abbrev>(
         &'me self,
         abbreviations: &'abbrev Abbreviations,
@@ -338,6 +511,17 @@ where
         self.header.entries_tree(abbreviations, offset)
     }
 
+    /// Read the raw data that defines this compilation unit's Debugging Information
+    /// Entries, starting at the given offset.
+    #[cfg(feature = "alloc")]
+    pub fn entries_raw<'me, 'abbrev>(
+        &'me self,
+        abbreviations: &'abbrev Abbreviations,
+        offset: Option<UnitOffset<R::Offset>>,
+    ) -> Result<EntriesRaw<'abbrev, 'me, R>> {
+        self.header.entries_raw(abbreviations, offset)
+    }
+
     /// Parse this compilation unit's abbreviations.
     ///
     /// ```
@@ -416,6 +600,7 @@ where
     /// let debug_abbrev = DebugAbbrev::new(read_debug_abbrev_section_somehow(), LittleEndian);
     /// let abbrevs_for_unit = unit.abbreviations(&debug_abbrev).unwrap();
     /// ```
+    #[cfg(feature = "alloc")]
     pub fn abbreviations(&self, debug_abbrev: &DebugAbbrev<R>) -> Result<Abbreviations> {
         self.header.abbreviations(debug_abbrev)
     }
@@ -425,8 +610,12 @@ where
         input: &mut R,
         offset: DebugInfoOffset<R::Offset>,
     ) -> Result<CompilationUnitHeader<R>> {
-        let header = parse_unit_header(input)?;
-        Ok(CompilationUnitHeader { header, offset })
+        let (header, unit_type) = parse_unit_header_and_type(input)?;
+        Ok(CompilationUnitHeader {
+            header,
+            offset,
+            unit_type,
+        })
     }
 }
 
@@ -436,6 +625,11 @@ fn parse_compilation_unit_type<R: Reader>(input: &mut R) -> Result<constants::Dw
     Ok(constants::DwUt(val))
 }
 
+/// Parse the `dwo_id` in a skeleton or split compilation unit header.
+fn parse_dwo_id<R: Reader>(input: &mut R) -> Result<DwoId> {
+    Ok(DwoId(input.read_u64()?))
+}
+
 /// Parse the `debug_abbrev_offset` in the compilation unit header.
 fn parse_debug_abbrev_offset<R: Reader>(
     input: &mut R,
@@ -599,6 +793,7 @@ where
     }
 
     /// Navigate this unit's `DebuggingInformationEntry`s.
+    #[cfg(feature = "alloc")]
     pub fn entries<'me, 'abbrev>(
         &'me self,
         abbreviations: &'abbrev Abbreviations,
@@ -609,11 +804,15 @@ where
             abbreviations,
             cached_current: None,
             delta_depth: 0,
+            depth: 0,
+            max_depth: MAX_ENTRY_DEPTH,
+            lenient: false,
         }
     }
 
     /// Navigate this compilation unit's `DebuggingInformationEntry`s
     /// starting at the given offset.
+    #[cfg(feature = "alloc")]
     pub fn entries_at_offset<'me, 'abbrev>(
         &'me self,
         abbreviations: &'abbrev Abbreviations,
@@ -626,11 +825,15 @@ where
             abbreviations,
             cached_current: None,
             delta_depth: 0,
+            depth: 0,
+            max_depth: MAX_ENTRY_DEPTH,
+            lenient: false,
         })
     }
 
     /// Navigate this unit's `DebuggingInformationEntry`s as a tree
     /// starting at the given offset.
+    #[cfg(feature = "alloc")]
     pub fn entries_tree<'me, 'abbrev>(
         &'me self,
         abbreviations: &'abbrev Abbreviations,
@@ -643,7 +846,42 @@ where
         Ok(EntriesTree::new(input, self, abbreviations))
     }
 
+    /// Read the raw data that defines the Debugging Information Entries,
+    /// starting at the given offset.
+    #[cfg(feature = "alloc")]
+    pub fn entries_raw<'me, 'abbrev>(
+        &'me self,
+        abbreviations: &'abbrev Abbreviations,
+        offset: Option<UnitOffset<R::Offset>>,
+    ) -> Result<EntriesRaw<'abbrev, 'me, R>> {
+        let input = match offset {
+            Some(offset) => self.range_from(offset..)?,
+            None => self.entries_buf.clone(),
+        };
+        Ok(EntriesRaw {
+            input,
+            unit: self,
+            abbreviations,
+            depth: 0,
+        })
+    }
+
+    /// Read the `DebuggingInformationEntry` at the given offset.
+    ///
+    /// Returns `Error::NoEntryAtGivenOffset` if the offset refers to a null entry.
+    #[cfg(feature = "alloc")]
+    pub fn entry<'me, 'abbrev>(
+        &'me self,
+        abbreviations: &'abbrev Abbreviations,
+        offset: UnitOffset<R::Offset>,
+    ) -> Result<DebuggingInformationEntry<'abbrev, 'me, R>> {
+        let mut input = self.range_from(offset..)?;
+        DebuggingInformationEntry::parse(&mut input, self, abbreviations)?
+            .ok_or(Error::NoEntryAtGivenOffset)
+    }
+
     /// Parse this unit's abbreviations.
+    #[cfg(feature = "alloc")]
     pub fn abbreviations(&self, debug_abbrev: &DebugAbbrev<R>) -> Result<Abbreviations> {
         debug_abbrev.abbreviations(self.debug_abbrev_offset())
     }
@@ -651,24 +889,57 @@ where
 
 /// Parse a compilation unit header.
 fn parse_unit_header<R: Reader>(input: &mut R) -> Result<UnitHeader<R>> {
+    match parse_unit_header_and_type(input)? {
+        (header, UnitType::Compilation) => Ok(header),
+        _ => Err(Error::UnsupportedUnitType),
+    }
+}
+
+/// Parse a unit header, and return its common fields and its unit type.
+///
+/// The unit type is always `UnitType::Compilation` for DWARF versions before 5.
+fn parse_unit_header_and_type<R: Reader>(
+    input: &mut R,
+) -> Result<(UnitHeader<R>, UnitType<R::Offset>)> {
     let (unit_length, format) = input.read_initial_length()?;
     let mut rest = input.split(unit_length)?;
 
     let version = rest.read_u16()?;
     let offset;
     let address_size;
+    let unit_type;
     // DWARF 1 was very different, and is obsolete, so isn't supported by this
     // reader.
     if 2 <= version && version <= 4 {
+        unit_type = UnitType::Compilation;
         offset = parse_debug_abbrev_offset(&mut rest, format)?;
-        address_size = rest.read_u8()?;
+        address_size = rest.read_address_size()?;
     } else if version == 5 {
-        let unit_type = parse_compilation_unit_type(&mut rest)?;
-        if unit_type != constants::DW_UT_compile {
-            return Err(Error::UnsupportedUnitType);
-        }
-        address_size = rest.read_u8()?;
+        let dw_ut = parse_compilation_unit_type(&mut rest)?;
+        address_size = rest.read_address_size()?;
         offset = parse_debug_abbrev_offset(&mut rest, format)?;
+        unit_type = match dw_ut {
+            constants::DW_UT_compile => UnitType::Compilation,
+            constants::DW_UT_partial => UnitType::Partial,
+            constants::DW_UT_type | constants::DW_UT_split_type => {
+                let type_signature = parse_type_signature(&mut rest)?;
+                let type_offset = parse_type_offset(&mut rest, format)?;
+                if dw_ut == constants::DW_UT_type {
+                    UnitType::Type {
+                        type_signature,
+                        type_offset,
+                    }
+                } else {
+                    UnitType::SplitType {
+                        type_signature,
+                        type_offset,
+                    }
+                }
+            }
+            constants::DW_UT_skeleton => UnitType::Skeleton(parse_dwo_id(&mut rest)?),
+            constants::DW_UT_split_compile => UnitType::SplitCompilation(parse_dwo_id(&mut rest)?),
+            _ => return Err(Error::UnsupportedUnitType),
+        };
     } else {
         return Err(Error::UnknownVersion(u64::from(version)));
     }
@@ -678,12 +949,16 @@ fn parse_unit_header<R: Reader>(input: &mut R) -> Result<UnitHeader<R>> {
         address_size,
     };
 
-    Ok(UnitHeader::new(encoding, unit_length, offset, rest))
+    Ok((
+        UnitHeader::new(encoding, unit_length, offset, rest),
+        unit_type,
+    ))
 }
 
 /// A Debugging Information Entry (DIE).
 ///
 /// DIEs have a set of attributes and optionally have children DIEs as well.
+#[cfg(feature = "alloc")]
 #[derive(Clone, Debug)]
 pub struct DebuggingInformationEntry<'abbrev, 'unit, R, Offset = <R as Reader>::Offset>
 where
@@ -697,6 +972,7 @@ where
     unit: &'unit UnitHeader<R, Offset>,
 }
 
+#[cfg(feature = "alloc")]
 impl<'abbrev, 'unit, R, Offset> DebuggingInformationEntry<'abbrev, 'unit, R, Offset>
 where
     R: Reader<Offset = Offset>,
@@ -795,6 +1071,12 @@ where
         self.abbrev.tag()
     }
 
+    /// Return true if this entry has the given tag.
+    #[inline]
+    pub fn is(&self, tag: constants::DwTag) -> bool {
+        self.abbrev.tag() == tag
+    }
+
     /// Return true if this entry's type can have children, false otherwise.
     pub fn has_children(&self) -> bool {
         self.abbrev.has_children()
@@ -888,6 +1170,10 @@ where
 
     /// Find the first attribute in this entry which has the given name,
     /// and return it. Returns `Ok(None)` if no attribute is found.
+    ///
+    /// This parses the attributes up to the matching attribute, so it is
+    /// O(n) in the number of attributes. Callers that need to look up many
+    /// attributes of the same entry should use `attrs` or `attrs_map` instead.
     pub fn attr(&self, name: constants::DwAt) -> Result<Option<Attribute<R>>> {
         let mut attrs = self.attrs();
         while let Some(attr) = attrs.next()? {
@@ -912,6 +1198,68 @@ where
         self.attr(name).map(|attr| attr.map(|attr| attr.value()))
     }
 
+    /// Parse all of the attributes of this entry, and return their names and
+    /// normalized values.
+    ///
+    /// The attributes are returned in the order that they are declared in
+    /// the abbreviation.
+    pub fn attrs_map(&self) -> Result<Vec<(constants::DwAt, AttributeValue<R>)>> {
+        let mut map = Vec::with_capacity(self.abbrev.attributes().len());
+        let mut attrs = self.attrs();
+        while let Some(attr) = attrs.next()? {
+            map.push((attr.name(), attr.value()));
+        }
+        Ok(map)
+    }
+
+    /// Return the address range given by the `DW_AT_low_pc` and
+    /// `DW_AT_high_pc` attributes of this entry.
+    ///
+    /// If `DW_AT_high_pc` has an address class form then it is the absolute
+    /// end address, otherwise it is an offset from `DW_AT_low_pc`.
+    /// Addresses given as an index are looked up in `debug_addr` using
+    /// `addr_base`.
+    ///
+    /// Returns `Ok(None)` if either attribute is missing, or if
+    /// `DW_AT_low_pc` does not have an address class form.
+    pub fn pc_range(
+        &self,
+        debug_addr: &DebugAddr<R>,
+        addr_base: DebugAddrBase<Offset>,
+    ) -> Result<Option<Range<u64>>> {
+        let address_size = self.unit.address_size();
+        let mut low_pc = None;
+        let mut high_pc = None;
+        let mut size = None;
+        let mut attrs = self.attrs();
+        while let Some(attr) = attrs.next()? {
+            match attr.name() {
+                constants::DW_AT_low_pc => match attr.value() {
+                    AttributeValue::Addr(val) => low_pc = Some(val),
+                    AttributeValue::DebugAddrIndex(index) => {
+                        low_pc = Some(debug_addr.get_address(address_size, addr_base, index)?);
+                    }
+                    _ => {}
+                },
+                constants::DW_AT_high_pc => match attr.value() {
+                    AttributeValue::Addr(val) => high_pc = Some(val),
+                    AttributeValue::DebugAddrIndex(index) => {
+                        high_pc = Some(debug_addr.get_address(address_size, addr_base, index)?);
+                    }
+                    value => match value.udata_value() {
+                        Some(val) => size = Some(val),
+                        None => return Err(Error::UnsupportedAttributeForm),
+                    },
+                },
+                _ => {}
+            }
+        }
+        Ok(low_pc.and_then(|begin| {
+            let end = size.map(|size| begin.wrapping_add(size)).or(high_pc);
+            end.map(|end| begin..end)
+        }))
+    }
+
     /// Return the input buffer after the last attribute.
     #[allow(clippy::inline_always)]
     #[inline(always)]
@@ -927,6 +1275,21 @@ where
         }
     }
 
+    /// Return the input buffer after the last attribute, using only the
+    /// forms of the attributes to determine their sizes.
+    ///
+    /// Unlike `after_attrs`, this does not fail if an attribute value is
+    /// invalid but its size is known.
+    fn skip_attrs(&self) -> Result<R> {
+        let mut input = self.attrs_slice.clone();
+        if let Some(attrs_len) = self.attrs_len.get() {
+            input.skip(attrs_len)?;
+        } else {
+            skip_attributes(&mut input, self.unit.encoding(), self.abbrev.attributes())?;
+        }
+        Ok(input)
+    }
+
     /// Use the `DW_AT_sibling` attribute to find the input buffer for the
     /// next sibling. Returns `None` if the attribute is missing or invalid.
     fn sibling(&self) -> Option<R> {
@@ -980,6 +1343,10 @@ where
     Addr(u64),
 
     /// A slice of an arbitrary number of bytes.
+    ///
+    /// This is also used for `DW_FORM_data16`, in which case the slice is
+    /// always 16 bytes. This form is typically used for `DW_AT_const_value`
+    /// attributes of 128 bit constants.
     Block(R),
 
     /// A one byte constant data value. How to interpret the byte depends on context.
@@ -1144,6 +1511,7 @@ where
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub struct Attribute<R: Reader> {
     name: constants::DwAt,
+    form: constants::DwForm,
     value: AttributeValue<R>,
 }
 
@@ -1153,6 +1521,14 @@ impl<R: Reader> Attribute<R> {
         self.name
     }
 
+    /// Get the form that this attribute's value was encoded with.
+    ///
+    /// For `DW_FORM_indirect`, this is the form that was given in the
+    /// entry's data instead of the abbreviation.
+    pub fn form(&self) -> constants::DwForm {
+        self.form
+    }
+
     /// Get this attribute's raw value.
     pub fn raw_value(&self) -> AttributeValue<R> {
         self.value.clone()
@@ -1601,6 +1977,14 @@ impl<R: Reader> Attribute<R> {
             constants::DW_AT_loclists_base => {
                 loclistsptr!();
             }
+            constants::DW_AT_GNU_addr_base => {
+                addrptr!();
+            }
+            constants::DW_AT_GNU_ranges_base => {
+                // This is an offset into `.debug_ranges`, which we store
+                // as the range list base of the unit.
+                rnglistsptr!();
+            }
             _ => {}
         }
         self.value.clone()
@@ -1618,13 +2002,25 @@ impl<R: Reader> Attribute<R> {
         self.value.u16_value()
     }
 
+    /// Try to convert this attribute's value to a boolean.
+    ///
+    /// See `AttributeValue::flag_value` for the coercion rules.
+    #[inline]
+    pub fn flag_value(&self) -> Option<bool> {
+        self.value.flag_value()
+    }
+
     /// Try to convert this attribute's value to an unsigned integer.
+    ///
+    /// See `AttributeValue::udata_value` for the coercion rules.
     #[inline]
     pub fn udata_value(&self) -> Option<u64> {
         self.value.udata_value()
     }
 
     /// Try to convert this attribute's value to a signed integer.
+    ///
+    /// See `AttributeValue::sdata_value` for the coercion rules.
     #[inline]
     pub fn sdata_value(&self) -> Option<i64> {
         self.value.sdata_value()
@@ -1686,6 +2082,9 @@ where
     Offset: ReaderOffset,
 {
     /// Try to convert this attribute's value to a u8.
+    ///
+    /// This uses the same coercion rules as `udata_value`, and additionally
+    /// returns `None` if the value does not fit in a u8.
     pub fn u8_value(&self) -> Option<u8> {
         if let Some(value) = self.udata_value() {
             if value <= u64::from(u8::MAX) {
@@ -1705,7 +2104,27 @@ where
         None
     }
 
+    /// Try to convert this attribute's value to a boolean.
+    ///
+    /// `DW_FORM_flag` and `DW_FORM_flag_present` values are both parsed as
+    /// `AttributeValue::Flag`, so this returns `Some(true)` for
+    /// `DW_FORM_flag_present`, and whether the value is non-zero for
+    /// `DW_FORM_flag`. All other forms return `None`.
+    ///
+    /// Note that a missing flag attribute should usually be treated as false.
+    pub fn flag_value(&self) -> Option<bool> {
+        match *self {
+            AttributeValue::Flag(value) => Some(value),
+            _ => None,
+        }
+    }
+
     /// Try to convert this attribute's value to an unsigned integer.
+    ///
+    /// `DW_FORM_data1`, `DW_FORM_data2`, `DW_FORM_data4` and `DW_FORM_data8`
+    /// values are zero extended. `DW_FORM_udata` values are returned as is.
+    /// `DW_FORM_sdata` values are returned if they are non-negative.
+    /// All other forms (including `DW_FORM_block*`) return `None`.
     pub fn udata_value(&self) -> Option<u64> {
         Some(match *self {
             AttributeValue::Data1(data) => u64::from(data),
@@ -1725,6 +2144,14 @@ where
     }
 
     /// Try to convert this attribute's value to a signed integer.
+    ///
+    /// `DW_FORM_data1`, `DW_FORM_data2`, `DW_FORM_data4` and `DW_FORM_data8`
+    /// values are sign extended. `DW_FORM_sdata` values are returned as is.
+    /// `DW_FORM_udata` values are returned if they are less than or equal to
+    /// `i64::MAX`. All other forms (including `DW_FORM_block*`) return `None`.
+    ///
+    /// The fixed size data forms do not carry a signedness, so whether to
+    /// use this or `udata_value` depends on the attribute being read.
     pub fn sdata_value(&self) -> Option<i64> {
         Some(match *self {
             AttributeValue::Data1(data) => i64::from(data as i8),
@@ -1744,6 +2171,8 @@ where
     }
 
     /// Try to convert this attribute's value to an offset.
+    ///
+    /// Only `DW_FORM_sec_offset` values are converted.
     pub fn offset_value(&self) -> Option<R::Offset> {
         // While offsets will be DW_FORM_data4/8 in DWARF version 2/3,
         // these have already been converted to `SecOffset.
@@ -1810,28 +2239,90 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
 fn length_u8_value<R: Reader>(input: &mut R) -> Result<R> {
     let len = input.read_u8().map(R::Offset::from_u8)?;
     input.split(len)
 }
 
+#[cfg(feature = "alloc")]
 fn length_u16_value<R: Reader>(input: &mut R) -> Result<R> {
     let len = input.read_u16().map(R::Offset::from_u16)?;
     input.split(len)
 }
 
+#[cfg(feature = "alloc")]
 fn length_u32_value<R: Reader>(input: &mut R) -> Result<R> {
     let len = input.read_u32().map(R::Offset::from_u32)?;
     input.split(len)
 }
 
+#[cfg(feature = "alloc")]
 fn length_uleb128_value<R: Reader>(input: &mut R) -> Result<R> {
     let len = input.read_uleb128().and_then(R::Offset::from_u64)?;
     input.split(len)
 }
 
+/// Skip the values of the given attributes without interpreting them.
+///
+/// This only fails if the size of a value cannot be determined, such as for
+/// an unknown form, or if the input is too short.
+#[cfg(feature = "alloc")]
+fn skip_attributes<R: Reader>(
+    input: &mut R,
+    encoding: Encoding,
+    specs: &[AttributeSpecification],
+) -> Result<()> {
+    for spec in specs {
+        let mut form = spec.form();
+        loop {
+            if let Some(size) = get_attribute_size(form, encoding) {
+                input.skip(R::Offset::from_u8(size))?;
+                break;
+            }
+            match form {
+                constants::DW_FORM_indirect => {
+                    form = constants::DwForm(input.read_uleb128()?);
+                    continue;
+                }
+                constants::DW_FORM_block1 => {
+                    length_u8_value(input)?;
+                }
+                constants::DW_FORM_block2 => {
+                    length_u16_value(input)?;
+                }
+                constants::DW_FORM_block4 => {
+                    length_u32_value(input)?;
+                }
+                constants::DW_FORM_block | constants::DW_FORM_exprloc => {
+                    length_uleb128_value(input)?;
+                }
+                constants::DW_FORM_udata
+                | constants::DW_FORM_sdata
+                | constants::DW_FORM_ref_udata
+                | constants::DW_FORM_strx
+                | constants::DW_FORM_GNU_str_index
+                | constants::DW_FORM_addrx
+                | constants::DW_FORM_GNU_addr_index
+                | constants::DW_FORM_loclistx
+                | constants::DW_FORM_rnglistx => {
+                    // Skip the LEB128 bytes without checking for overflow.
+                    while input.read_u8()? & 0x80 != 0 {}
+                }
+                constants::DW_FORM_string => {
+                    input.read_null_terminated_slice()?;
+                }
+                _ => return Err(Error::UnknownForm),
+            }
+            break;
+        }
+    }
+    Ok(())
+}
+
 // Return true if the given `name` can be a section offset in DWARF version 2/3.
 // This is required to correctly handle relocations.
+#[cfg(feature = "alloc")]
 fn allow_section_offset(name: constants::DwAt, version: u16) -> bool {
     match name {
         constants::DW_AT_location
@@ -1851,6 +2342,7 @@ fn allow_section_offset(name: constants::DwAt, version: u16) -> bool {
     }
 }
 
+#[cfg(feature = "alloc")]
 pub(crate) fn parse_attribute<'unit, 'abbrev, R: Reader>(
     input: &mut R,
     unit: &'unit UnitHeader<R>,
@@ -2026,7 +2518,7 @@ pub(crate) fn parse_attribute<'unit, 'abbrev, R: Reader>(
                 AttributeValue::DebugStrOffsetsIndex(DebugStrOffsetsIndex(index))
             }
             constants::DW_FORM_strx3 => {
-                let index = input.read_uint(3).and_then(R::Offset::from_u64)?;
+                let index = input.read_u24().map(R::Offset::from_u32)?;
                 AttributeValue::DebugStrOffsetsIndex(DebugStrOffsetsIndex(index))
             }
             constants::DW_FORM_strx4 => {
@@ -2046,7 +2538,7 @@ pub(crate) fn parse_attribute<'unit, 'abbrev, R: Reader>(
                 AttributeValue::DebugAddrIndex(DebugAddrIndex(index))
             }
             constants::DW_FORM_addrx3 => {
-                let index = input.read_uint(3).and_then(R::Offset::from_u64)?;
+                let index = input.read_u24().map(R::Offset::from_u32)?;
                 AttributeValue::DebugAddrIndex(DebugAddrIndex(index))
             }
             constants::DW_FORM_addrx4 => {
@@ -2067,6 +2559,7 @@ pub(crate) fn parse_attribute<'unit, 'abbrev, R: Reader>(
         };
         let attr = Attribute {
             name: spec.name(),
+            form,
             value,
         };
         return Ok((attr, specs));
@@ -2081,6 +2574,7 @@ pub(crate) fn parse_attribute<'unit, 'abbrev, R: Reader>(
 ///
 /// Can be [used with
 /// `FallibleIterator`](./index.html#using-with-fallibleiterator).
+#[cfg(feature = "alloc")]
 #[derive(Clone, Copy, Debug)]
 pub struct AttrsIter<'abbrev, 'entry, 'unit, R>
 where
@@ -2093,6 +2587,7 @@ where
     entry: &'entry DebuggingInformationEntry<'abbrev, 'unit, R>,
 }
 
+#[cfg(feature = "alloc")]
 impl<'abbrev, 'entry, 'unit, R: Reader> AttrsIter<'abbrev, 'entry, 'unit, R> {
     /// Advance the iterator and return the next attribute.
     ///
@@ -2131,6 +2626,7 @@ impl<'abbrev, 'entry, 'unit, R: Reader> AttrsIter<'abbrev, 'entry, 'unit, R> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'abbrev, 'entry, 'unit, R: Reader> FallibleIterator for AttrsIter<'abbrev, 'entry, 'unit, R> {
     type Item = Attribute<R>;
     type Error = Error;
@@ -2140,6 +2636,122 @@ impl<'abbrev, 'entry, 'unit, R: Reader> FallibleIterator for AttrsIter<'abbrev,
     }
 }
 
+/// A raw reader of the data that defines the Debugging Information Entries.
+///
+/// `EntriesRaw` provides primitives to read the components of Debugging
+/// Information Entries (DIEs) without parsing each entry fully. A DIE consists
+/// of an abbreviation code, which is read with `read_abbreviation`, followed by
+/// the values of the attributes in the abbreviation. Each value is either read
+/// with `read_attribute`, or skipped with `skip_attributes`.
+///
+/// All of the attributes of an entry must be read or skipped, in the order
+/// given by the abbreviation, before reading the next abbreviation code.
+/// Skipping an attribute uses its form to determine its size, without
+/// interpreting its value. For variable-length forms, such as `DW_FORM_block`
+/// and `DW_FORM_exprloc`, this still requires reading the length, and for
+/// `DW_FORM_string` this requires searching for the null terminator.
+///
+/// `EntriesRaw` does not track the tree structure beyond the depth of the next
+/// entry, and does not use `DW_AT_sibling` attributes.
+///
+/// ```
+/// # fn example<R: gimli::Reader>(unit: &gimli::Unit<R>) -> gimli::Result<()> {
+/// let mut entries = unit.entries_raw(None)?;
+/// while !entries.is_empty() {
+///     let abbrev = if let Some(abbrev) = entries.read_abbreviation()? {
+///         abbrev
+///     } else {
+///         // Null entry with no attributes.
+///         continue;
+///     };
+///     match abbrev.tag() {
+///         gimli::DW_TAG_subprogram => {
+///             for spec in abbrev.attributes() {
+///                 let attr = entries.read_attribute(*spec)?;
+///                 // Examine the attribute.
+///             }
+///         }
+///         _ => entries.skip_attributes(abbrev.attributes())?,
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct EntriesRaw<'abbrev, 'unit, R>
+where
+    R: Reader,
+{
+    input: R,
+    unit: &'unit UnitHeader<R>,
+    abbreviations: &'abbrev Abbreviations,
+    depth: isize,
+}
+
+#[cfg(feature = "alloc")]
+impl<'abbrev, 'unit, R: Reader> EntriesRaw<'abbrev, 'unit, R> {
+    /// Return true if there is no more input.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.input.is_empty()
+    }
+
+    /// Return the unit offset at which the reader will read next.
+    ///
+    /// If you want the offset of the next entry, then this must be called prior to reading
+    /// the next entry.
+    pub fn next_offset(&self) -> UnitOffset<R::Offset> {
+        UnitOffset(self.unit.header_size() + self.input.offset_from(&self.unit.entries_buf))
+    }
+
+    /// Return the depth of the next entry.
+    ///
+    /// This depth is updated when `read_abbreviation` is called, using the
+    /// `has_children` field of the abbreviation and null entries.
+    #[inline]
+    pub fn next_depth(&self) -> isize {
+        self.depth
+    }
+
+    /// Read an abbreviation code and lookup the corresponding `Abbreviation`.
+    ///
+    /// Returns `Ok(None)` for null entries.
+    #[inline]
+    pub fn read_abbreviation(&mut self) -> Result<Option<&'abbrev Abbreviation>> {
+        let code = self.input.read_uleb128()?;
+        if code == 0 {
+            self.depth -= 1;
+            return Ok(None);
+        };
+        let abbrev = self
+            .abbreviations
+            .get(code)
+            .ok_or(Error::UnknownAbbreviation)?;
+        if abbrev.has_children() {
+            self.depth += 1;
+        }
+        Ok(Some(abbrev))
+    }
+
+    /// Read an attribute.
+    #[inline]
+    pub fn read_attribute(&mut self, spec: AttributeSpecification) -> Result<Attribute<R>> {
+        let specs = [spec];
+        parse_attribute(&mut self.input, self.unit, &specs).map(|(attr, _)| attr)
+    }
+
+    /// Skip all the attributes of an abbreviation.
+    #[inline]
+    pub fn skip_attributes(&mut self, specs: &[AttributeSpecification]) -> Result<()> {
+        skip_attributes(&mut self.input, self.unit.encoding(), specs)
+    }
+}
+
+/// The default maximum depth of entries for `EntriesCursor` and `EntriesTree`.
+#[cfg(feature = "alloc")]
+const MAX_ENTRY_DEPTH: usize = 1 << 16;
+
 /// A cursor into the Debugging Information Entries tree for a compilation unit.
 ///
 /// The `EntriesCursor` can traverse the DIE tree in DFS order using `next_dfs()`,
@@ -2153,6 +2765,7 @@ impl<'abbrev, 'entry, 'unit, R: Reader> FallibleIterator for AttrsIter<'abbrev,
 /// the entry following the current entry will be a sibling or child. `current()`
 /// will return `None` if the current entry is a null entry, which signifies the
 /// end of the current tree depth.
+#[cfg(feature = "alloc")]
 #[derive(Clone, Debug)]
 pub struct EntriesCursor<'abbrev, 'unit, R>
 where
@@ -2163,8 +2776,12 @@ where
     abbreviations: &'abbrev Abbreviations,
     cached_current: Option<DebuggingInformationEntry<'abbrev, 'unit, R>>,
     delta_depth: isize,
+    depth: isize,
+    max_depth: usize,
+    lenient: bool,
 }
 
+#[cfg(feature = "alloc")]
 impl<'abbrev, 'unit, R: Reader> EntriesCursor<'abbrev, 'unit, R> {
     /// Get a reference to the entry that the cursor is currently pointing to.
     ///
@@ -2175,13 +2792,53 @@ impl<'abbrev, 'unit, R: Reader> EntriesCursor<'abbrev, 'unit, R> {
         self.cached_current.as_ref()
     }
 
+    /// Set whether the cursor continues past entries that have invalid
+    /// attribute values.
+    ///
+    /// By default, moving the cursor past an entry parses all of the entry's
+    /// attributes, and returns an error if any attribute value is invalid.
+    /// If `lenient` is true, then the cursor instead uses the attribute forms
+    /// in the abbreviation to skip over the attributes without interpreting
+    /// their values. Errors for the invalid values are still returned when
+    /// iterating the attributes of the entry using
+    /// `DebuggingInformationEntry::attrs`, so callers can report them and
+    /// continue with the next entry.
+    ///
+    /// This can recover from errors for attributes that have a valid form,
+    /// such as `Error::BadUnsignedLeb128` and `Error::BadSignedLeb128`, or
+    /// offsets and indices that are too large for the reader's offset type.
+    ///
+    /// It cannot recover from errors that prevent determining the size of an
+    /// entry, and these errors stop the traversal in the same way as for the
+    /// default behavior. These errors include `Error::UnknownAbbreviation`,
+    /// `Error::UnknownForm`, and `Error::UnexpectedEof` for truncated input.
+    pub fn set_lenient(&mut self, lenient: bool) {
+        self.lenient = lenient;
+    }
+
+    /// Set the maximum depth of entries that the cursor will move to.
+    ///
+    /// The depth is relative to the entry that the cursor started at.
+    /// Moving the cursor to an entry that is nested deeper than this returns
+    /// `Error::NestingTooDeep`, and stops the traversal. This protects
+    /// consumers that recurse for each level of the tree from malicious input.
+    ///
+    /// Defaults to 65536.
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
     /// Move the cursor to the next DIE in the tree.
     ///
     /// Returns `Some` if there is a next entry, even if this entry is null.
     /// If there is no next entry, then `None` is returned.
     pub fn next_entry(&mut self) -> Result<Option<()>> {
         if let Some(ref current) = self.cached_current {
-            self.input = current.after_attrs()?;
+            self.input = if self.lenient {
+                current.skip_attrs()?
+            } else {
+                current.after_attrs()?
+            };
         }
 
         if self.input.is_empty() {
@@ -2190,6 +2847,14 @@ impl<'abbrev, 'unit, R: Reader> EntriesCursor<'abbrev, 'unit, R> {
             return Ok(None);
         }
 
+        self.depth += self.delta_depth;
+        if self.depth > 0 && self.depth as usize > self.max_depth {
+            self.input.empty();
+            self.delta_depth = 0;
+            self.cached_current = None;
+            return Err(Error::NestingTooDeep);
+        }
+
         match DebuggingInformationEntry::parse(&mut self.input, self.unit, self.abbreviations) {
             Ok(Some(entry)) => {
                 self.delta_depth = entry.has_children() as isize;
@@ -2472,6 +3137,7 @@ impl<'abbrev, 'unit, R: Reader> EntriesCursor<'abbrev, 'unit, R> {
                     // to it (which keeps us at the same depth).
                     self.input = sibling_input;
                     self.cached_current = None;
+                    self.delta_depth = 0;
                 } else {
                     // This entry has children, so the next entry is
                     // down one level.
@@ -2538,6 +3204,7 @@ impl<'abbrev, 'unit, R: Reader> EntriesCursor<'abbrev, 'unit, R> {
 ///     Ok(())
 /// }
 /// ```
+#[cfg(feature = "alloc")]
 #[derive(Clone, Debug)]
 pub struct EntriesTree<'abbrev, 'unit, R>
 where
@@ -2549,8 +3216,10 @@ where
     input: R,
     entry: Option<DebuggingInformationEntry<'abbrev, 'unit, R>>,
     depth: isize,
+    max_depth: usize,
 }
 
+#[cfg(feature = "alloc")]
 impl<'abbrev, 'unit, R: Reader> EntriesTree<'abbrev, 'unit, R> {
     fn new(root: R, unit: &'unit UnitHeader<R>, abbreviations: &'abbrev Abbreviations) -> Self {
         let input = root.clone();
@@ -2561,7 +3230,31 @@ impl<'abbrev, 'unit, R: Reader> EntriesTree<'abbrev, 'unit, R> {
             input,
             entry: None,
             depth: 0,
+            max_depth: MAX_ENTRY_DEPTH,
+        }
+    }
+
+    /// Set the maximum depth of entries in the tree.
+    ///
+    /// The depth is relative to the root of the tree. Moving to an entry that
+    /// is nested deeper than this returns `Error::NestingTooDeep`. This protects
+    /// consumers that recursively process each node's children from malicious
+    /// input.
+    ///
+    /// Defaults to 65536.
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
+    /// Increase the depth of the cursor, checking the maximum depth.
+    fn descend(&mut self) -> Result<()> {
+        self.depth += 1;
+        if self.depth > 0 && self.depth as usize > self.max_depth {
+            self.input.empty();
+            self.entry = None;
+            return Err(Error::NestingTooDeep);
         }
+        Ok(())
     }
 
     /// Returns the root node of the tree.
@@ -2590,8 +3283,8 @@ impl<'abbrev, 'unit, R: Reader> EntriesTree<'abbrev, 'unit, R> {
                     if !entry.has_children() {
                         return Ok(false);
                     }
-                    self.depth += 1;
                     self.input = entry.after_attrs()?;
+                    self.descend()?;
                 }
                 None => return Ok(false),
             }
@@ -2630,8 +3323,8 @@ impl<'abbrev, 'unit, R: Reader> EntriesTree<'abbrev, 'unit, R> {
                         } else {
                             // This entry has children, so the next entry is
                             // down one level.
-                            self.depth += 1;
                             self.input = entry.after_attrs()?;
+                            self.descend()?;
                         }
                     } else {
                         // This entry has no children, so next entry is at same depth.
@@ -2670,6 +3363,7 @@ impl<'abbrev, 'unit, R: Reader> EntriesTree<'abbrev, 'unit, R> {
 ///
 /// The root node of a tree can be obtained
 /// via [`EntriesTree::root`](./struct.EntriesTree.html#method.root).
+#[cfg(feature = "alloc")]
 #[derive(Debug)]
 pub struct EntriesTreeNode<'abbrev, 'unit, 'tree, R>
 where
@@ -2681,6 +3375,7 @@ where
     depth: isize,
 }
 
+#[cfg(feature = "alloc")]
 impl<'abbrev, 'unit, 'tree, R: Reader> EntriesTreeNode<'abbrev, 'unit, 'tree, R> {
     fn new(
         tree: &'tree mut EntriesTree<'abbrev, 'unit, R>,
@@ -2710,6 +3405,7 @@ impl<'abbrev, 'unit, 'tree, R: Reader> EntriesTreeNode<'abbrev, 'unit, 'tree, R>
 ///
 /// The items returned by this iterator are also `EntriesTreeNode`s,
 /// which allow recursive traversal of grandchildren, etc.
+#[cfg(feature = "alloc")]
 #[derive(Debug)]
 pub struct EntriesTreeIter<'abbrev, 'unit, 'tree, R>
 where
@@ -2722,6 +3418,7 @@ where
     empty: bool,
 }
 
+#[cfg(feature = "alloc")]
 impl<'abbrev, 'unit, 'tree, R: Reader> EntriesTreeIter<'abbrev, 'unit, 'tree, R> {
     fn new(
         tree: &'tree mut EntriesTree<'abbrev, 'unit, R>,
@@ -2815,9 +3512,7 @@ impl<T> DebugTypes<T> {
 }
 
 impl<R> Section<R> for DebugTypes<R> {
-    fn id() -> SectionId {
-        SectionId::DebugTypes
-    }
+    const ID: SectionId = SectionId::DebugTypes;
 
     fn reader(&self) -> &R {
         &self.debug_types_section
@@ -3004,6 +3699,7 @@ where
     }
 
     /// Navigate this type unit's `DebuggingInformationEntry`s.
+    #[cfg(feature = "alloc")]
     pub fn entries<'me, 'abbrev>(
         &'me self,
         abbreviations: &'abbrev Abbreviations,
@@ -3013,6 +3709,7 @@ where
 
     /// Navigate this type unit's `DebuggingInformationEntry`s
     /// starting at the given offset.
+    #[cfg(feature = "alloc")]
     pub fn entries_at_offset<'me, 'abbrev>(
         &'me self,
         abbreviations: &'abbrev Abbreviations,
@@ -3023,6 +3720,7 @@ where
 
     /// Navigate this type unit's `DebuggingInformationEntry`s as a tree
     /// starting at the given offset.
+    #[cfg(feature = "alloc")]
     pub fn entries_tree<'me, 'abbrev>(
         &'me self,
         abbreviations: &'abbrev Abbreviations,
@@ -3031,10 +3729,21 @@ where
         self.header.entries_tree(abbreviations, offset)
     }
 
-    /// Parse this type unit's abbreviations.
-    ///
-    /// ```
-    /// use gimli::DebugAbbrev;
+    /// Read the raw data that defines this type unit's Debugging Information
+    /// Entries, starting at the given offset.
+    #[cfg(feature = "alloc")]
+    pub fn entries_raw<'me, 'abbrev>(
+        &'me self,
+        abbreviations: &'abbrev Abbreviations,
+        offset: Option<UnitOffset<R::Offset>>,
+    ) -> Result<EntriesRaw<'abbrev, 'me, R>> {
+        self.header.entries_raw(abbreviations, offset)
+    }
+
+    /// Parse this type unit's abbreviations.
+    ///
+    /// ```
+    /// use gimli::DebugAbbrev;
     /// # use gimli::{DebugTypes, LittleEndian};
     /// # let types_buf = [
     /// #     // Type unit header
@@ -3113,6 +3822,7 @@ where
     /// let debug_abbrev = DebugAbbrev::new(read_debug_abbrev_section_somehow(), LittleEndian);
     /// let abbrevs_for_unit = unit.abbreviations(&debug_abbrev).unwrap();
     /// ```
+    #[cfg(feature = "alloc")]
     pub fn abbreviations(&self, debug_abbrev: &DebugAbbrev<R>) -> Result<Abbreviations> {
         self.header.abbreviations(debug_abbrev)
     }
@@ -3133,9 +3843,10 @@ fn parse_type_unit_header<R: Reader>(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::collections::BTreeMap;
     use crate::constants;
     use crate::constants::*;
-    use crate::endianity::{Endianity, LittleEndian};
+    use crate::endianity::{BigEndian, Endianity, LittleEndian};
     use crate::leb128;
     use crate::read::abbrev::tests::AbbrevSectionMethods;
     use crate::read::{
@@ -3381,6 +4092,7 @@ mod tests {
                 entries_buf: EndianSlice::new(expected_rest, LittleEndian),
             },
             offset: DebugInfoOffset(0),
+            unit_type: UnitType::Compilation,
         };
         let mut unit32 = CompilationUnitHeader {
             header: UnitHeader {
@@ -3394,6 +4106,7 @@ mod tests {
                 entries_buf: EndianSlice::new(expected_rest, LittleEndian),
             },
             offset: DebugInfoOffset(0),
+            unit_type: UnitType::Compilation,
         };
         let section = Section::with_endian(Endian::Little)
             .comp_unit(&mut unit64)
@@ -3408,6 +4121,120 @@ mod tests {
         assert_eq!(units.next(), Ok(None));
     }
 
+    #[test]
+    fn test_header_containing_offset() {
+        let expected_rest = &[1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let mut unit1 = CompilationUnitHeader {
+            header: UnitHeader {
+                encoding: Encoding {
+                    format: Format::Dwarf32,
+                    version: 4,
+                    address_size: 4,
+                },
+                unit_length: 0,
+                debug_abbrev_offset: DebugAbbrevOffset(0),
+                entries_buf: EndianSlice::new(expected_rest, LittleEndian),
+            },
+            offset: DebugInfoOffset(0),
+            unit_type: UnitType::Compilation,
+        };
+        let mut unit2 = unit1;
+        let section = Section::with_endian(Endian::Little)
+            .comp_unit(&mut unit1)
+            .comp_unit(&mut unit2);
+        let buf = section.get_contents().unwrap();
+        let debug_info = DebugInfo::new(&buf, LittleEndian);
+
+        let end1 = unit1.length_including_self();
+        let end2 = end1 + unit2.length_including_self();
+        assert_eq!(unit2.offset(), DebugInfoOffset(end1));
+        for &(offset, expect) in &[
+            (0, unit1),
+            (end1 - 1, unit1),
+            (end1, unit2),
+            (end2 - 1, unit2),
+        ] {
+            assert_eq!(
+                debug_info.header_containing_offset(DebugInfoOffset(offset)),
+                Ok(expect)
+            );
+        }
+        assert_eq!(
+            debug_info.header_containing_offset(DebugInfoOffset(end2)),
+            Err(Error::OffsetOutOfBounds)
+        );
+    }
+
+    #[test]
+    fn test_units_seek_skip() {
+        let expected_rest = &[1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let mut unit1 = CompilationUnitHeader {
+            header: UnitHeader {
+                encoding: Encoding {
+                    format: Format::Dwarf32,
+                    version: 4,
+                    address_size: 4,
+                },
+                unit_length: 0,
+                debug_abbrev_offset: DebugAbbrevOffset(0),
+                entries_buf: EndianSlice::new(expected_rest, LittleEndian),
+            },
+            offset: DebugInfoOffset(0),
+            unit_type: UnitType::Compilation,
+        };
+        let mut unit2 = unit1;
+        let mut unit3 = unit1;
+        let section = Section::with_endian(Endian::Little)
+            .comp_unit(&mut unit1)
+            .comp_unit(&mut unit2)
+            .comp_unit(&mut unit3);
+        let buf = section.get_contents().unwrap();
+        let debug_info = DebugInfo::new(&buf, LittleEndian);
+
+        let mut units = debug_info.units();
+        units.skip_units(2).unwrap();
+        assert_eq!(units.next(), Ok(Some(unit3)));
+        assert_eq!(units.next(), Ok(None));
+
+        units.seek(unit2.offset()).unwrap();
+        assert_eq!(units.next(), Ok(Some(unit2)));
+        units.seek(DebugInfoOffset(0)).unwrap();
+        assert_eq!(units.next(), Ok(Some(unit1)));
+
+        // Skipping past the end stops at the end.
+        units.skip_units(5).unwrap();
+        assert_eq!(units.next(), Ok(None));
+
+        // Seeking to an offset within a unit is an error.
+        assert_eq!(
+            units.seek(DebugInfoOffset(unit2.offset().0 + 1)),
+            Err(Error::OffsetOutOfBounds)
+        );
+        assert_eq!(units.next(), Ok(None));
+        assert_eq!(
+            units.seek(DebugInfoOffset(buf.len() + 1)),
+            Err(Error::OffsetOutOfBounds)
+        );
+    }
+
+    #[test]
+    fn test_offset_btreemap() {
+        fn check<T: ReaderOffset>() {
+            let mut map = BTreeMap::new();
+            for &offset in &[40u32, 10, 30, 20, 0xffff_0000] {
+                map.insert(DebugInfoOffset(T::from_u32(offset)), offset);
+            }
+            let range = DebugInfoOffset(T::from_u8(15))..DebugInfoOffset(T::from_u8(40));
+            let values: Vec<_> = map.range(range).map(|(_, &value)| value).collect();
+            assert_eq!(values, vec![20, 30]);
+            let values: Vec<_> = map.values().cloned().collect();
+            assert_eq!(values, vec![10, 20, 30, 40, 0xffff_0000]);
+        }
+        check::<u32>();
+        check::<u64>();
+        check::<usize>();
+    }
+
     #[test]
     fn test_unit_version_unknown_version() {
         let buf = [0x02, 0x00, 0x00, 0x00, 0xab, 0xcd];
@@ -3427,6 +4254,127 @@ mod tests {
         };
     }
 
+    #[test]
+    fn test_parse_unit_header_address_size() {
+        for &address_size in &[0, 3, 16, 0xff] {
+            // DWARF 4
+            let buf = [0x07, 0x00, 0x00, 0x00, 0x04, 0x00, 0, 0, 0, 0, address_size];
+            let rest = &mut EndianSlice::new(&buf, LittleEndian);
+            match parse_unit_header(rest) {
+                Err(Error::UnsupportedAddressSize(size)) => assert_eq!(size, address_size),
+                otherwise => panic!("Unexpected result: {:?}", otherwise),
+            };
+
+            // DWARF 5
+            let buf = [
+                0x08,
+                0x00,
+                0x00,
+                0x00,
+                0x05,
+                0x00,
+                0x01,
+                address_size,
+                0,
+                0,
+                0,
+                0,
+            ];
+            let rest = &mut EndianSlice::new(&buf, LittleEndian);
+            match parse_unit_header(rest) {
+                Err(Error::UnsupportedAddressSize(size)) => assert_eq!(size, address_size),
+                otherwise => panic!("Unexpected result: {:?}", otherwise),
+            };
+        }
+    }
+
+    #[test]
+    fn test_parse_unit_type() {
+        fn section(unit_type: DwUt, extra: &[u8]) -> Vec<u8> {
+            let length = Label::new();
+            let start = Label::new();
+            let end = Label::new();
+            let section = Section::with_endian(Endian::Little)
+                .L32(&length)
+                .mark(&start)
+                .L16(5)
+                .D8(unit_type.0)
+                .D8(8)
+                .L32(0)
+                .append_bytes(extra)
+                .D8(0)
+                .mark(&end);
+            length.set_const((&end - &start) as u64);
+            section.get_contents().unwrap()
+        }
+
+        fn parse(buf: &[u8]) -> Result<CompilationUnitHeader<EndianSlice<LittleEndian>>> {
+            DebugInfo::new(buf, LittleEndian)
+                .units()
+                .next()
+                .map(Option::unwrap)
+        }
+
+        let type_extra = [1, 2, 3, 4, 5, 6, 7, 8, 25, 0, 0, 0];
+        let dwo_extra = [1, 2, 3, 4, 5, 6, 7, 8];
+
+        let buf = section(DW_UT_compile, &[]);
+        let header = parse(&buf).unwrap();
+        assert_eq!(header.unit_type(), UnitType::Compilation);
+        assert_eq!(header.header_size(), 12);
+        assert_eq!(header.type_signature(), None);
+        assert_eq!(header.dwo_id(), None);
+
+        let buf = section(DW_UT_partial, &[]);
+        let header = parse(&buf).unwrap();
+        assert_eq!(header.unit_type(), UnitType::Partial);
+        assert_eq!(header.unit_type().dw_ut(), DW_UT_partial);
+
+        let buf = section(DW_UT_type, &type_extra);
+        let header = parse(&buf).unwrap();
+        assert_eq!(
+            header.unit_type(),
+            UnitType::Type {
+                type_signature: DebugTypeSignature(0x0807_0605_0403_0201),
+                type_offset: UnitOffset(25),
+            }
+        );
+        assert_eq!(header.header_size(), 24);
+        assert_eq!(
+            header.type_signature(),
+            Some(DebugTypeSignature(0x0807_0605_0403_0201))
+        );
+        assert_eq!(header.type_offset(), Some(UnitOffset(25)));
+
+        let buf = section(DW_UT_split_type, &type_extra);
+        let header = parse(&buf).unwrap();
+        assert_eq!(header.unit_type().dw_ut(), DW_UT_split_type);
+        assert_eq!(header.type_offset(), Some(UnitOffset(25)));
+
+        let buf = section(DW_UT_skeleton, &dwo_extra);
+        let header = parse(&buf).unwrap();
+        assert_eq!(
+            header.unit_type(),
+            UnitType::Skeleton(DwoId(0x0807_0605_0403_0201))
+        );
+        assert_eq!(header.header_size(), 20);
+        assert_eq!(header.dwo_id(), Some(DwoId(0x0807_0605_0403_0201)));
+        assert_eq!(header.type_signature(), None);
+
+        let buf = section(DW_UT_split_compile, &dwo_extra);
+        let header = parse(&buf).unwrap();
+        assert_eq!(
+            header.unit_type(),
+            UnitType::SplitCompilation(DwoId(0x0807_0605_0403_0201))
+        );
+        assert_eq!(header.dwo_id(), Some(DwoId(0x0807_0605_0403_0201)));
+
+        assert_eq!(
+            parse(&section(DwUt(0x80), &[])),
+            Err(Error::UnsupportedUnitType)
+        );
+    }
+
     #[test]
     fn test_unit_version_incomplete() {
         let buf = [0x01, 0x00, 0x00, 0x00, 0x04];
@@ -3842,6 +4790,7 @@ mod tests {
             let (value, expect_udata, expect_sdata) = *test;
             let attribute = Attribute {
                 name: DW_AT_data_member_location,
+                form: DW_FORM_udata,
                 value,
             };
             assert_eq!(attribute.udata_value(), expect_udata);
@@ -3849,6 +4798,112 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_attribute_flag_value() {
+        let tests: &[(AttributeValue<EndianSlice<LittleEndian>>, Option<bool>)] = &[
+            (AttributeValue::Flag(true), Some(true)),
+            (AttributeValue::Flag(false), Some(false)),
+            (AttributeValue::Data1(1), None),
+            (AttributeValue::Udata(0), None),
+        ];
+        for test in tests.iter() {
+            let (value, expect) = *test;
+            let attribute = Attribute {
+                name: constants::DW_AT_external,
+                form: constants::DW_FORM_flag,
+                value,
+            };
+            assert_eq!(attribute.flag_value(), expect);
+        }
+
+        // Both flag forms are parsed as `AttributeValue::Flag`.
+        let unit = test_parse_attribute_unit_default();
+        let spec = AttributeSpecification::new(
+            constants::DW_AT_external,
+            constants::DW_FORM_flag_present,
+            None,
+        );
+        let input = &mut EndianSlice::new(&[], LittleEndian);
+        let attribute = parse_attribute(input, &unit, &[spec]).unwrap().0;
+        assert_eq!(attribute.flag_value(), Some(true));
+
+        let spec =
+            AttributeSpecification::new(constants::DW_AT_external, constants::DW_FORM_flag, None);
+        for &(byte, expect) in &[(0, false), (1, true), (0x80, true)] {
+            let buf = [byte];
+            let input = &mut EndianSlice::new(&buf, LittleEndian);
+            let attribute = parse_attribute(input, &unit, &[spec]).unwrap().0;
+            assert_eq!(attribute.flag_value(), Some(expect));
+        }
+    }
+
+    #[test]
+    fn test_attribute_u8_offset_value() {
+        let tests: &[(
+            AttributeValue<EndianSlice<LittleEndian>>,
+            Option<u8>,
+            Option<usize>,
+        )] = &[
+            (AttributeValue::Data1(0xff), Some(0xff), None),
+            (AttributeValue::Data2(0xff), Some(0xff), None),
+            (AttributeValue::Data2(0x100), None, None),
+            (AttributeValue::Udata(0xff), Some(0xff), None),
+            (AttributeValue::Sdata(-1), None, None),
+            (AttributeValue::SecOffset(0x10), None, Some(0x10)),
+            (AttributeValue::Data4(0x10), Some(0x10), None),
+        ];
+        for test in tests.iter() {
+            let (value, expect_u8, expect_offset) = *test;
+            let attribute = Attribute {
+                name: DW_AT_data_member_location,
+                form: DW_FORM_udata,
+                value,
+            };
+            assert_eq!(attribute.u8_value(), expect_u8);
+            assert_eq!(attribute.offset_value(), expect_offset);
+        }
+    }
+
+    #[test]
+    fn test_attribute_exprloc_value() {
+        let buf = [constants::DW_OP_reg1.0];
+        let data = EndianSlice::new(&buf, LittleEndian);
+        let tests: &[(
+            AttributeValue<EndianSlice<LittleEndian>>,
+            Option<Expression<EndianSlice<LittleEndian>>>,
+        )] = &[
+            (
+                AttributeValue::Exprloc(Expression(data)),
+                Some(Expression(data)),
+            ),
+            (AttributeValue::Block(data), Some(Expression(data))),
+            (AttributeValue::Data1(1), None),
+            (AttributeValue::SecOffset(0x10), None),
+        ];
+        for test in tests.iter() {
+            let (value, expect) = *test;
+            let attribute = Attribute {
+                name: constants::DW_AT_location,
+                form: constants::DW_FORM_exprloc,
+                value,
+            };
+            assert_eq!(attribute.exprloc_value(), expect);
+        }
+
+        // All block forms can be used for expressions.
+        let unit = test_parse_attribute_unit_default();
+        for &(form, ref buf) in &[
+            (constants::DW_FORM_exprloc, [1, constants::DW_OP_reg1.0]),
+            (constants::DW_FORM_block, [1, constants::DW_OP_reg1.0]),
+            (constants::DW_FORM_block1, [1, constants::DW_OP_reg1.0]),
+        ] {
+            let spec = AttributeSpecification::new(constants::DW_AT_location, form, None);
+            let input = &mut EndianSlice::new(buf, LittleEndian);
+            let attribute = parse_attribute(input, &unit, &[spec]).unwrap().0;
+            assert_eq!(attribute.exprloc_value(), Some(Expression(data)));
+        }
+    }
+
     fn test_parse_attribute_unit<Endian>(
         address_size: u8,
         format: Format,
@@ -3891,6 +4946,7 @@ mod tests {
 
         let expect = Attribute {
             name: constants::DW_AT_low_pc,
+            form,
             value,
         };
 
@@ -3905,6 +4961,10 @@ mod tests {
                 assert!(false);
             }
         };
+
+        let rest = &mut EndianSlice::new(buf, Endian::default());
+        skip_attributes(rest, unit.encoding(), &spec[..]).unwrap();
+        assert_eq!(*rest, EndianSlice::new(&buf[len..], Endian::default()));
     }
 
     #[test]
@@ -4001,6 +5061,18 @@ mod tests {
         test_parse_attribute(&buf, 8, &unit, form, value);
     }
 
+    #[test]
+    fn test_parse_attribute_data16() {
+        let buf = [
+            0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+            0x0f, 0x10, 0x99, 0x99,
+        ];
+        let unit = test_parse_attribute_unit_default();
+        let form = constants::DW_FORM_data16;
+        let value = AttributeValue::Block(EndianSlice::new(&buf[..16], LittleEndian));
+        test_parse_attribute(&buf, 16, &unit, form, value);
+    }
+
     #[test]
     fn test_parse_attribute_udata() {
         let mut buf = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
@@ -4069,6 +5141,58 @@ mod tests {
         test_parse_attribute(&buf, 0, &unit, form, value);
     }
 
+    #[test]
+    fn test_parse_attribute_implicit_const() {
+        let encoding = Encoding {
+            format: Format::Dwarf32,
+            version: 5,
+            address_size: 4,
+        };
+        let unit = UnitHeader::new(
+            encoding,
+            7,
+            DebugAbbrevOffset(0),
+            EndianSlice::new(&[], LittleEndian),
+        );
+        let abbrev = Abbreviation::new(
+            1,
+            constants::DW_TAG_variable,
+            constants::DW_CHILDREN_no,
+            vec![
+                AttributeSpecification::new(constants::DW_AT_name, constants::DW_FORM_string, None),
+                AttributeSpecification::new(
+                    constants::DW_AT_decl_file,
+                    constants::DW_FORM_implicit_const,
+                    Some(-42),
+                ),
+                AttributeSpecification::new(constants::DW_AT_low_pc, constants::DW_FORM_addr, None),
+            ],
+        );
+        // "foo", 0x1234
+        let buf = [0x66, 0x6f, 0x6f, 0x00, 0x34, 0x12, 0x00, 0x00];
+        let entry = DebuggingInformationEntry {
+            offset: UnitOffset(0),
+            attrs_slice: EndianSlice::new(&buf, LittleEndian),
+            attrs_len: Cell::new(None),
+            abbrev: &abbrev,
+            unit: &unit,
+        };
+
+        // DW_FORM_implicit_const does not consume any bytes of the input stream.
+        assert_eq!(
+            entry.attrs_map(),
+            Ok(vec![
+                (
+                    constants::DW_AT_name,
+                    AttributeValue::String(EndianSlice::new(b"foo", LittleEndian))
+                ),
+                (constants::DW_AT_decl_file, AttributeValue::Sdata(-42)),
+                (constants::DW_AT_low_pc, AttributeValue::Addr(0x1234)),
+            ])
+        );
+        assert_eq!(entry.attrs_len.get(), Some(buf.len()));
+    }
+
     #[test]
     fn test_parse_attribute_sec_offset_32() {
         let buf = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x10];
@@ -4227,6 +5351,15 @@ mod tests {
         test_parse_attribute(&buf, 8, &unit, form, value);
     }
 
+    #[test]
+    fn test_parse_attribute_refsig8_big_endian() {
+        let buf = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x99, 0x99];
+        let unit = test_parse_attribute_unit(4, Format::Dwarf32, BigEndian);
+        let form = constants::DW_FORM_ref_sig8;
+        let value = AttributeValue::DebugTypesRef(DebugTypeSignature(0x0102_0304_0506_0708));
+        test_parse_attribute(&buf, 8, &unit, form, value);
+    }
+
     #[test]
     fn test_parse_attribute_string() {
         let buf = [0x01, 0x02, 0x03, 0x04, 0x05, 0x0, 0x99, 0x99];
@@ -4437,9 +5570,20 @@ mod tests {
         };
 
         let unit = test_parse_attribute_unit_default();
-        let form = constants::DW_FORM_indirect;
-        let value = AttributeValue::Udata(9_999_999);
-        test_parse_attribute(&buf, bytes_written, &unit, form, value);
+        let spec = [AttributeSpecification::new(
+            constants::DW_AT_low_pc,
+            constants::DW_FORM_indirect,
+            None,
+        )];
+        let rest = &mut EndianSlice::new(&buf, LittleEndian);
+        let (attr, _) = parse_attribute(rest, &unit, &spec[..]).unwrap();
+        assert_eq!(attr.form(), constants::DW_FORM_udata);
+        assert_eq!(attr.raw_value(), AttributeValue::Udata(9_999_999));
+        assert_eq!(*rest, EndianSlice::new(&buf[bytes_written..], LittleEndian));
+
+        let rest = &mut EndianSlice::new(&buf, LittleEndian);
+        skip_attributes(rest, unit.encoding(), &spec[..]).unwrap();
+        assert_eq!(*rest, EndianSlice::new(&buf[bytes_written..], LittleEndian));
     }
 
     #[test]
@@ -4497,6 +5641,7 @@ mod tests {
                     attr,
                     Attribute {
                         name: constants::DW_AT_name,
+                        form: constants::DW_FORM_string,
                         value: AttributeValue::String(EndianSlice::new(b"foo", LittleEndian)),
                     }
                 );
@@ -4515,6 +5660,7 @@ mod tests {
                     attr,
                     Attribute {
                         name: constants::DW_AT_low_pc,
+                        form: constants::DW_FORM_addr,
                         value: AttributeValue::Addr(0x2a),
                     }
                 );
@@ -4533,6 +5679,7 @@ mod tests {
                     attr,
                     Attribute {
                         name: constants::DW_AT_high_pc,
+                        form: constants::DW_FORM_addr,
                         value: AttributeValue::Addr(0x539),
                     }
                 );
@@ -4550,7 +5697,19 @@ mod tests {
         assert_eq!(
             entry.attrs_len.get().expect("should have entry.attrs_len"),
             buf.len() - 4
-        )
+        );
+
+        assert_eq!(
+            entry.attrs_map(),
+            Ok(vec![
+                (
+                    constants::DW_AT_name,
+                    AttributeValue::String(EndianSlice::new(b"foo", LittleEndian))
+                ),
+                (constants::DW_AT_low_pc, AttributeValue::Addr(0x2a)),
+                (constants::DW_AT_high_pc, AttributeValue::Addr(0x539)),
+            ])
+        );
     }
 
     #[test]
@@ -4605,6 +5764,7 @@ mod tests {
                     attr,
                     Attribute {
                         name: constants::DW_AT_name,
+                        form: constants::DW_FORM_string,
                         value: AttributeValue::String(EndianSlice::new(b"foo", LittleEndian)),
                     }
                 );
@@ -4629,6 +5789,102 @@ mod tests {
         assert!(entry.attrs_len.get().is_none());
     }
 
+    #[test]
+    fn test_entry_pc_range() {
+        let encoding = Encoding {
+            format: Format::Dwarf32,
+            version: 5,
+            address_size: 4,
+        };
+        let unit = UnitHeader::new(
+            encoding,
+            7,
+            DebugAbbrevOffset(0),
+            EndianSlice::new(&[], LittleEndian),
+        );
+        let debug_addr_buf = [
+            0xaa, 0xaa, 0xaa, 0xaa, 0x00, 0x10, 0x00, 0x00, 0x00, 0x20, 0x00, 0x00,
+        ];
+        let debug_addr = DebugAddr::from(EndianSlice::new(&debug_addr_buf, LittleEndian));
+        let addr_base = DebugAddrBase(4);
+
+        let pc_range = |low_pc, high_pc, buf: &[u8]| {
+            let abbrev = Abbreviation::new(
+                1,
+                constants::DW_TAG_subprogram,
+                constants::DW_CHILDREN_no,
+                vec![
+                    AttributeSpecification::new(constants::DW_AT_low_pc, low_pc, None),
+                    AttributeSpecification::new(constants::DW_AT_high_pc, high_pc, None),
+                ],
+            );
+            let entry = DebuggingInformationEntry {
+                offset: UnitOffset(0),
+                attrs_slice: EndianSlice::new(buf, LittleEndian),
+                attrs_len: Cell::new(None),
+                abbrev: &abbrev,
+                unit: &unit,
+            };
+            entry.pc_range(&debug_addr, addr_base)
+        };
+
+        // Absolute `DW_AT_high_pc`.
+        assert_eq!(
+            pc_range(
+                constants::DW_FORM_addr,
+                constants::DW_FORM_addr,
+                &[0x00, 0x10, 0x00, 0x00, 0x00, 0x11, 0x00, 0x00],
+            ),
+            Ok(Some(0x1000..0x1100))
+        );
+        assert_eq!(
+            pc_range(
+                constants::DW_FORM_addrx1,
+                constants::DW_FORM_addrx1,
+                &[0, 1]
+            ),
+            Ok(Some(0x1000..0x2000))
+        );
+
+        // `DW_AT_high_pc` relative to `DW_AT_low_pc`.
+        assert_eq!(
+            pc_range(
+                constants::DW_FORM_addrx1,
+                constants::DW_FORM_data2,
+                &[1, 0x10, 0x00]
+            ),
+            Ok(Some(0x2000..0x2010))
+        );
+        assert_eq!(
+            pc_range(
+                constants::DW_FORM_addr,
+                constants::DW_FORM_udata,
+                &[0x00, 0x10, 0x00, 0x00, 0x20]
+            ),
+            Ok(Some(0x1000..0x1020))
+        );
+
+        // `DW_AT_low_pc` forms that aren't addresses are ignored.
+        assert_eq!(
+            pc_range(
+                constants::DW_FORM_udata,
+                constants::DW_FORM_udata,
+                &[0x01, 0x20]
+            ),
+            Ok(None)
+        );
+
+        // Non-constant `DW_AT_high_pc`.
+        assert_eq!(
+            pc_range(
+                constants::DW_FORM_addr,
+                constants::DW_FORM_string,
+                &[0x00, 0x10, 0x00, 0x00, 0x00]
+            ),
+            Err(Error::UnsupportedAttributeForm)
+        );
+    }
+
     fn assert_entry_name<Endian>(entry: &DebuggingInformationEntry<EndianSlice<Endian>>, name: &str)
     where
         Endian: Endianity,
@@ -4773,6 +6029,7 @@ mod tests {
                 entries_buf: EndianSlice::new(&entries_buf, LittleEndian),
             },
             offset: DebugInfoOffset(0),
+            unit_type: UnitType::Compilation,
         };
         let section = Section::with_endian(Endian::Little).comp_unit(&mut unit);
         section.get_contents().unwrap()
@@ -4800,6 +6057,7 @@ mod tests {
                 entries_buf: EndianSlice::new(&entries_buf, LittleEndian),
             },
             offset: DebugInfoOffset(0),
+            unit_type: UnitType::Compilation,
         };
         let section = Section::with_endian(Endian::Little).comp_unit(&mut unit);
         let info_buf = &section.get_contents().unwrap();
@@ -4837,6 +6095,173 @@ mod tests {
         assert!(cursor.next_entry().is_err());
     }
 
+    #[test]
+    fn test_cursor_lenient() {
+        #[rustfmt::skip]
+        let abbrevs_buf = Section::with_endian(Endian::Little)
+            .abbrev(1, DW_TAG_subprogram, DW_CHILDREN_no)
+                .abbrev_attr(DW_AT_byte_size, DW_FORM_udata)
+                .abbrev_attr(DW_AT_name, DW_FORM_string)
+                .abbrev_attr_null()
+            .abbrev(2, DW_TAG_subprogram, DW_CHILDREN_no)
+                .abbrev_attr(DW_AT_name, DW_FORM_strx1)
+                .abbrev_attr(DW_AT_byte_size, DW_FORM_indirect)
+                .abbrev_attr_null()
+            .abbrev_null()
+            .get_contents()
+            .unwrap();
+
+        #[rustfmt::skip]
+        let entries_buf = Section::with_endian(Endian::Little)
+            // An overflowing LEB128 value.
+            .die(1, |s| s.append_repeated(0xff, 9).D8(0x7f).attr_string("001"))
+            .die(1, |s| s.uleb(4).attr_string("002"))
+            // An unknown form.
+            .die(2, |s| s.D8(0).uleb(0x7f).D8(0))
+            .die(1, |s| s.uleb(4).attr_string("003"))
+            .get_contents()
+            .unwrap();
+
+        let encoding = Encoding {
+            format: Format::Dwarf32,
+            version: 4,
+            address_size: 4,
+        };
+        let mut unit = CompilationUnitHeader {
+            header: UnitHeader {
+                encoding,
+                unit_length: 0,
+                debug_abbrev_offset: DebugAbbrevOffset(0),
+                entries_buf: EndianSlice::new(&entries_buf, LittleEndian),
+            },
+            offset: DebugInfoOffset(0),
+            unit_type: UnitType::Compilation,
+        };
+        let section = Section::with_endian(Endian::Little).comp_unit(&mut unit);
+        let info_buf = &section.get_contents().unwrap();
+        let debug_info = DebugInfo::new(info_buf, LittleEndian);
+        let unit = debug_info.units().next().unwrap().unwrap();
+        let debug_abbrev = DebugAbbrev::new(&abbrevs_buf, LittleEndian);
+        let abbrevs = unit.abbreviations(&debug_abbrev).unwrap();
+
+        // The default behavior stops at the invalid value.
+        let mut cursor = unit.entries(&abbrevs);
+        cursor.next_dfs().unwrap().unwrap();
+        assert_eq!(cursor.next_dfs().err(), Some(Error::BadUnsignedLeb128));
+
+        let mut cursor = unit.entries(&abbrevs);
+        cursor.set_lenient(true);
+        {
+            let (_, entry) = cursor.next_dfs().unwrap().unwrap();
+            let mut attrs = entry.attrs();
+            assert_eq!(attrs.next().err(), Some(Error::BadUnsignedLeb128));
+        }
+        {
+            let (_, entry) = cursor.next_dfs().unwrap().unwrap();
+            let name = EndianSlice::new(b"002", LittleEndian);
+            assert_eq!(
+                entry.attr_value(DW_AT_name).unwrap(),
+                Some(AttributeValue::String(name))
+            );
+        }
+        {
+            let (_, entry) = cursor.next_dfs().unwrap().unwrap();
+            assert_eq!(entry.attrs().count().err(), Some(Error::UnknownForm));
+        }
+        // The size of the unknown form can't be determined.
+        assert_eq!(cursor.next_dfs().err(), Some(Error::UnknownForm));
+    }
+
+    #[test]
+    fn test_entries_raw() {
+        let info_buf = &entries_cursor_tests_debug_info_buf();
+        let debug_info = DebugInfo::new(info_buf, LittleEndian);
+        let unit = debug_info.units().next().unwrap().unwrap();
+        let abbrevs_buf = &entries_cursor_tests_abbrev_buf();
+        let debug_abbrev = DebugAbbrev::new(abbrevs_buf, LittleEndian);
+        let abbrevs = unit.abbreviations(&debug_abbrev).unwrap();
+
+        let mut entries = unit.entries_raw(&abbrevs, None).unwrap();
+        let mut names = Vec::new();
+        let mut skipped = 0;
+        while !entries.is_empty() {
+            let depth = entries.next_depth();
+            let offset = entries.next_offset();
+            let abbrev = match entries.read_abbreviation().unwrap() {
+                Some(abbrev) => abbrev,
+                None => continue,
+            };
+            assert_eq!(
+                unit.header.entry(&abbrevs, offset).unwrap().offset(),
+                offset
+            );
+            if depth == 1 {
+                let attr = entries.read_attribute(abbrev.attributes()[0]).unwrap();
+                assert_eq!(attr.name(), DW_AT_name);
+                match attr.value() {
+                    AttributeValue::String(name) => names.push(name.slice()),
+                    otherwise => panic!("Unexpected value {:?}", otherwise),
+                }
+            } else {
+                entries.skip_attributes(abbrev.attributes()).unwrap();
+                skipped += 1;
+            }
+        }
+        assert_eq!(names, [&b"002"[..], b"004", b"007", b"010"]);
+        assert_eq!(skipped, 6);
+        assert_eq!(entries.next_depth(), 0);
+    }
+
+    #[test]
+    fn test_entry_is() {
+        let info_buf = &entries_cursor_tests_debug_info_buf();
+        let debug_info = DebugInfo::new(info_buf, LittleEndian);
+        let unit = debug_info.units().next().unwrap().unwrap();
+        let abbrevs_buf = &entries_cursor_tests_abbrev_buf();
+        let debug_abbrev = DebugAbbrev::new(abbrevs_buf, LittleEndian);
+        let abbrevs = unit.abbreviations(&debug_abbrev).unwrap();
+
+        let mut cursor = unit.entries(&abbrevs);
+        let (_, entry) = cursor.next_dfs().unwrap().unwrap();
+        assert!(entry.is(DW_TAG_subprogram));
+        assert!(!entry.is(DW_TAG_compile_unit));
+        assert!(entry.has_children());
+    }
+
+    #[test]
+    fn test_max_depth() {
+        let info_buf = &entries_cursor_tests_debug_info_buf();
+        let debug_info = DebugInfo::new(info_buf, LittleEndian);
+        let unit = debug_info.units().next().unwrap().unwrap();
+        let abbrevs_buf = &entries_cursor_tests_abbrev_buf();
+        let debug_abbrev = DebugAbbrev::new(abbrevs_buf, LittleEndian);
+        let abbrevs = unit.abbreviations(&debug_abbrev).unwrap();
+
+        // The default maximum depth allows the whole tree.
+        let mut cursor = unit.entries(&abbrevs);
+        let mut count = 0;
+        while cursor.next_dfs().unwrap().is_some() {
+            count += 1;
+        }
+        assert_eq!(count, 10);
+
+        let mut cursor = unit.entries(&abbrevs);
+        cursor.set_max_depth(1);
+        assert_next_dfs(&mut cursor, "001", 0);
+        assert_next_dfs(&mut cursor, "002", 1);
+        assert_eq!(cursor.next_dfs().err(), Some(Error::NestingTooDeep));
+        assert!(cursor.next_dfs().unwrap().is_none());
+
+        let mut tree = unit.entries_tree(&abbrevs, None).unwrap();
+        tree.set_max_depth(1);
+        let root = tree.root().unwrap();
+        let mut children = root.children();
+        let child = children.next().unwrap().unwrap();
+        assert_entry_name(child.entry(), "002");
+        let mut grandchildren = child.children();
+        assert_eq!(grandchildren.next().err(), Some(Error::NestingTooDeep));
+    }
+
     #[test]
     fn test_cursor_next_entry() {
         let info_buf = &entries_cursor_tests_debug_info_buf();
@@ -5131,6 +6556,7 @@ mod tests {
                 entries_buf: EndianSlice::new(&entries_buf, LittleEndian),
             },
             offset: DebugInfoOffset(0),
+            unit_type: UnitType::Compilation,
         };
         let section = Section::with_endian(Endian::Little).comp_unit(&mut unit);
         let info_buf = section.get_contents().unwrap();
@@ -5329,6 +6755,7 @@ mod tests {
                 entries_buf: EndianSlice::new(&entries_buf, LittleEndian),
             },
             offset: DebugInfoOffset(0),
+            unit_type: UnitType::Compilation,
         };
         let info_buf = Section::with_endian(Endian::Little)
             .comp_unit(&mut unit)
@@ -5425,6 +6852,7 @@ mod tests {
                 entries_buf: EndianSlice::new(entries, LittleEndian),
             },
             offset: DebugInfoOffset(0),
+            unit_type: UnitType::Compilation,
         };
         Section::with_endian(Endian::Little)
             .append_bytes(padding)
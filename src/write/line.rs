@@ -87,9 +87,7 @@ impl LineProgram {
     ///
     /// # Panics
     ///
-    /// Panics if `line_encoding.line_base` > 0.
-    ///
-    /// Panics if `line_encoding.line_base` + `line_encoding.line_range` <= 0.
+    /// Panics if `line_encoding.line_range` is 0.
     ///
     /// Panics if `comp_dir` is empty or contains a null byte.
     ///
@@ -103,10 +101,7 @@ impl LineProgram {
         comp_file: LineString,
         comp_file_info: Option<FileInfo>,
     ) -> LineProgram {
-        // We require a special opcode for a line advance of 0.
-        // See the debug_asserts in generate_row().
-        assert!(line_encoding.line_base <= 0);
-        assert!(line_encoding.line_base + line_encoding.line_range as i8 > 0);
+        assert!(line_encoding.line_range > 0);
         let mut program = LineProgram {
             none: false,
             encoding,
@@ -411,55 +406,71 @@ impl LineProgram {
         let line_advance = self.row.line as i64 - self.prev_row.line as i64;
         let op_advance = self.op_advance();
 
-        // Default to special advances of 0.
+        // Special opcodes can only encode line advances in the range
+        // `line_base..line_base + line_range`, which may not include 0.
         let special_base = u64::from(OPCODE_BASE);
-        // TODO: handle lack of special opcodes for 0 line advance
-        debug_assert!(self.line_encoding.line_base <= 0);
-        debug_assert!(self.line_encoding.line_base + self.line_encoding.line_range as i8 >= 0);
-        let special_default = special_base.wrapping_sub(line_base);
+        let line_special = |line_advance: i64| {
+            let special_line = (line_advance as u64).wrapping_sub(line_base);
+            if special_line < line_range {
+                Some(special_base + special_line)
+            } else {
+                None
+            }
+        };
+        let special_default = line_special(0);
         let mut special = special_default;
         let mut use_special = false;
 
         if line_advance != 0 {
-            let special_line = (line_advance as u64).wrapping_sub(line_base);
-            if special_line < line_range {
-                special = special_base + special_line;
+            special = line_special(line_advance);
+            if special.is_some() {
                 use_special = true;
             } else {
                 self.instructions
                     .push(LineInstruction::AdvanceLine(line_advance));
+                special = special_default;
             }
         }
 
         if op_advance != 0 {
-            // Using ConstAddPc can save a byte.
-            let (special_op_advance, const_add_pc) = if special + op_advance * line_range <= 255 {
-                (op_advance, false)
-            } else {
-                let op_range = (255 - special_base) / line_range;
-                (op_advance - op_range, true)
-            };
+            match special {
+                Some(line_special) => {
+                    // Using ConstAddPc can save a byte.
+                    let (special_op_advance, const_add_pc) =
+                        if line_special + op_advance * line_range <= 255 {
+                            (op_advance, false)
+                        } else {
+                            let op_range = (255 - special_base) / line_range;
+                            (op_advance - op_range, true)
+                        };
 
-            let special_op = special_op_advance * line_range;
-            if special + special_op <= 255 {
-                special += special_op;
-                use_special = true;
-                if const_add_pc {
-                    self.instructions.push(LineInstruction::ConstAddPc);
+                    let special_op = special_op_advance * line_range;
+                    if line_special + special_op <= 255 {
+                        special = Some(line_special + special_op);
+                        use_special = true;
+                        if const_add_pc {
+                            self.instructions.push(LineInstruction::ConstAddPc);
+                        }
+                    } else {
+                        self.instructions
+                            .push(LineInstruction::AdvancePc(op_advance));
+                    }
+                }
+                None => {
+                    self.instructions
+                        .push(LineInstruction::AdvancePc(op_advance));
                 }
-            } else {
-                self.instructions
-                    .push(LineInstruction::AdvancePc(op_advance));
             }
         }
 
-        if use_special && special != special_default {
-            debug_assert!(special >= special_base);
-            debug_assert!(special <= 255);
-            self.instructions
-                .push(LineInstruction::Special(special as u8));
-        } else {
-            self.instructions.push(LineInstruction::Copy);
+        match special {
+            Some(special) if use_special && Some(special) != special_default => {
+                debug_assert!(special >= special_base);
+                debug_assert!(special <= 255);
+                self.instructions
+                    .push(LineInstruction::Special(special as u8));
+            }
+            _ => self.instructions.push(LineInstruction::Copy),
         }
 
         self.prev_row = self.row;
@@ -1003,9 +1014,6 @@ mod convert {
                     None => (LineString::new(&[][..], encoding, line_strings), None),
                 };
 
-                if from_header.line_base() > 0 {
-                    return Err(ConvertError::InvalidLineBase);
-                }
                 let mut program = LineProgram::new(
                     encoding,
                     from_header.line_encoding(),
@@ -1734,7 +1742,7 @@ mod tests {
 
         for minimum_instruction_length in vec![1, 4] {
             for maximum_operations_per_instruction in vec![1, 3] {
-                for line_base in vec![-5, 0] {
+                for line_base in vec![-5, 0, 1, -25] {
                     for line_range in vec![10, 20] {
                         let line_encoding = LineEncoding {
                             minimum_instruction_length,
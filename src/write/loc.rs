@@ -465,11 +465,17 @@ mod tests {
                         abbreviations: read::Abbreviations::default(),
                         name: None,
                         comp_dir: None,
+                        language: None,
                         low_pc: 0,
+                        high_pc: None,
                         str_offsets_base: DebugStrOffsetsBase(0),
+                        str_offsets_count: None,
                         addr_base: DebugAddrBase(0),
                         loclists_base: DebugLocListsBase(0),
                         rnglists_base: DebugRngListsBase(0),
+                        dwo_name: None,
+                        producer: None,
+                        dwo_id: None,
                         line_program: None,
                     };
                     let context = ConvertUnitContext {
@@ -189,6 +189,8 @@ pub enum Error {
     InvalidFrameDataOffset(i32),
     /// Unsupported eh_frame pointer encoding.
     UnsupportedPointerEncoding(constants::DwEhPe),
+    /// Unsupported reference to a symbol.
+    InvalidReference,
 }
 
 impl fmt::Display for Error {
@@ -235,6 +237,7 @@ impl fmt::Display for Error {
             Error::UnsupportedPointerEncoding(eh_pe) => {
                 write!(f, "Unsupported eh_frame pointer encoding ({}).", eh_pe)
             }
+            Error::InvalidReference => write!(f, "Unsupported reference to a symbol."),
         }
     }
 }
@@ -263,6 +266,20 @@ pub enum Address {
     },
 }
 
+/// A reference to a `.debug_info` entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Reference {
+    /// An external symbol.
+    ///
+    /// The meaning of this value is decided by the writer, but
+    /// will typically be an index into a symbol table.
+    Symbol(usize),
+    /// An entry in the same section.
+    ///
+    /// This only supports references in units that are emitted together.
+    Entry(UnitId, UnitEntryId),
+}
+
 // This type is only used in debug assertions.
 #[cfg(not(debug_assertions))]
 type BaseId = ();
@@ -308,8 +325,6 @@ mod convert {
         InvalidFileIndex,
         /// A `.debug_line` directory index is invalid.
         InvalidDirectoryIndex,
-        /// A `.debug_line` line base is invalid.
-        InvalidLineBase,
         /// A `.debug_line` reference is invalid.
         InvalidLineRef,
         /// Invalid relative address in a range list.
@@ -347,7 +362,6 @@ mod convert {
                 ),
                 InvalidFileIndex => write!(f, "A `.debug_line` file index is invalid."),
                 InvalidDirectoryIndex => write!(f, "A `.debug_line` directory index is invalid."),
-                InvalidLineBase => write!(f, "A `.debug_line` line base is invalid."),
                 InvalidLineRef => write!(f, "A `.debug_line` reference is invalid."),
                 InvalidRangeRelativeAddress => {
                     write!(f, "Invalid relative address in a range list.")
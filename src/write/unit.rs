@@ -11,7 +11,7 @@ use crate::write::{
     Abbreviation, AbbreviationTable, Address, AttributeSpecification, BaseId, DebugLineStrOffsets,
     DebugStrOffsets, Error, FileId, LineProgram, LineStringId, LocationList, LocationListId,
     LocationListOffsets, LocationListTable, RangeList, RangeListId, RangeListOffsets,
-    RangeListTable, Result, Section, Sections, StringId, Writer,
+    RangeListTable, Reference, Result, Section, Sections, StringId, Writer,
 };
 
 define_id!(UnitId, "An identifier for a unit in a `UnitTable`.");
@@ -722,7 +722,10 @@ pub enum AttributeValue {
     ThisUnitEntryRef(UnitEntryId),
 
     /// A reference to a `DebuggingInformationEntry` in a potentially different unit.
-    AnyUnitEntryRef((UnitId, UnitEntryId)),
+    ///
+    /// This is written as a `.debug_info` section offset, so it can also be a
+    /// reference to a symbol that the writer resolves using a relocation.
+    DebugInfoRef(Reference),
 
     /// A reference to the current `.debug_info` section, but possibly a different
     /// unit from the current one.
@@ -851,7 +854,7 @@ impl AttributeValue {
                     Format::Dwarf64 => constants::DW_FORM_ref8,
                 }
             }
-            AttributeValue::AnyUnitEntryRef(_) => constants::DW_FORM_ref_addr,
+            AttributeValue::DebugInfoRef(_) => constants::DW_FORM_ref_addr,
             AttributeValue::DebugInfoRefSup(_) => {
                 // TODO: should this depend on the size of supplementary section?
                 match encoding.format {
@@ -972,15 +975,20 @@ impl AttributeValue {
                 unit_refs.push((w.offset(), id));
                 w.write_udata(0, unit.format().word_size())?;
             }
-            AttributeValue::AnyUnitEntryRef(id) => {
+            AttributeValue::DebugInfoRef(reference) => {
                 debug_assert_form!(constants::DW_FORM_ref_addr);
                 let size = if unit.version() == 2 {
                     unit.address_size()
                 } else {
                     unit.format().word_size()
                 };
-                debug_info_refs.push((w.offset(), id, size));
-                w.write_udata(0, size)?;
+                match reference {
+                    Reference::Symbol(symbol) => w.write_reference(symbol, size)?,
+                    Reference::Entry(unit, entry) => {
+                        debug_info_refs.push((w.offset(), (unit, entry), size));
+                        w.write_udata(0, size)?;
+                    }
+                }
             }
             AttributeValue::DebugInfoRefSup(val) => {
                 match unit.format() {
@@ -1239,7 +1247,8 @@ pub(crate) mod convert {
                             if id.0 == unit_id {
                                 attr.value = AttributeValue::ThisUnitEntryRef(id.1)
                             } else {
-                                attr.value = AttributeValue::AnyUnitEntryRef(id)
+                                attr.value =
+                                    AttributeValue::DebugInfoRef(Reference::Entry(id.0, id.1))
                             }
                         }
                     }
@@ -2161,11 +2170,17 @@ mod tests {
                             abbreviations: read::Abbreviations::default(),
                             name: None,
                             comp_dir: None,
+                            language: None,
                             low_pc: 0,
+                            high_pc: None,
                             str_offsets_base: DebugStrOffsetsBase(0),
+                            str_offsets_count: None,
                             addr_base: DebugAddrBase(0),
                             loclists_base: DebugLocListsBase(0),
                             rnglists_base: DebugRngListsBase(0),
+                            dwo_name: None,
+                            producer: None,
+                            dwo_id: None,
                             line_program: None,
                         };
 
@@ -2235,7 +2250,7 @@ mod tests {
                 let child2 = unit1.get_mut(child_id2);
                 child2.set(
                     constants::DW_AT_type,
-                    AttributeValue::AnyUnitEntryRef((unit_id2, unit2_child1)),
+                    AttributeValue::DebugInfoRef(Reference::Entry(unit_id2, unit2_child1)),
                 );
             }
         }
@@ -2257,7 +2272,7 @@ mod tests {
                 let child2 = unit2.get_mut(child_id2);
                 child2.set(
                     constants::DW_AT_type,
-                    AttributeValue::AnyUnitEntryRef((unit_id1, unit1_child1)),
+                    AttributeValue::DebugInfoRef(Reference::Entry(unit_id1, unit1_child1)),
                 );
             }
         }
@@ -2371,11 +2386,11 @@ mod tests {
                 assert_eq!(convert_attr.name, attr.name);
                 match (convert_attr.value.clone(), attr.value.clone()) {
                     (
-                        AttributeValue::AnyUnitEntryRef(convert_id),
-                        AttributeValue::AnyUnitEntryRef(id),
+                        AttributeValue::DebugInfoRef(Reference::Entry(convert_unit_id, convert_id)),
+                        AttributeValue::DebugInfoRef(Reference::Entry(unit_id, id)),
                     ) => {
-                        assert_eq!((convert_id.0).index, (id.0).index);
-                        assert_eq!((convert_id.1).index, (id.1).index);
+                        assert_eq!(convert_unit_id.index, unit_id.index);
+                        assert_eq!(convert_id.index, id.index);
                     }
                     (
                         AttributeValue::ThisUnitEntryRef(convert_id),
@@ -2394,11 +2409,11 @@ mod tests {
                 assert_eq!(convert_attr.name, attr.name);
                 match (convert_attr.value.clone(), attr.value.clone()) {
                     (
-                        AttributeValue::AnyUnitEntryRef(convert_id),
-                        AttributeValue::AnyUnitEntryRef(id),
+                        AttributeValue::DebugInfoRef(Reference::Entry(convert_unit_id, convert_id)),
+                        AttributeValue::DebugInfoRef(Reference::Entry(unit_id, id)),
                     ) => {
-                        assert_eq!((convert_id.0).index, (id.0).index);
-                        assert_eq!((convert_id.1).index, (id.1).index);
+                        assert_eq!(convert_unit_id.index, unit_id.index);
+                        assert_eq!(convert_id.index, id.index);
                     }
                     (
                         AttributeValue::ThisUnitEntryRef(convert_id),
@@ -2500,6 +2515,148 @@ mod tests {
         check_sibling(&read_units.next().unwrap().unwrap(), &read_debug_abbrev);
     }
 
+    #[test]
+    fn test_abbreviation_sharing() {
+        let encoding = Encoding {
+            format: Format::Dwarf32,
+            version: 4,
+            address_size: 8,
+        };
+        let mut units = UnitTable::default();
+        let unit_id = units.add(Unit::new(encoding, LineProgram::none()));
+        let unit = units.get_mut(unit_id);
+        let root = unit.root();
+        for &(tag, name, ref line) in &[
+            (constants::DW_TAG_subprogram, "a", None),
+            (constants::DW_TAG_subprogram, "b", None),
+            (constants::DW_TAG_variable, "c", None),
+            (
+                constants::DW_TAG_subprogram,
+                "d",
+                Some(AttributeValue::Data1(1)),
+            ),
+            (
+                constants::DW_TAG_subprogram,
+                "e",
+                Some(AttributeValue::Udata(1)),
+            ),
+            (
+                constants::DW_TAG_subprogram,
+                "f",
+                Some(AttributeValue::Data1(2)),
+            ),
+        ] {
+            let id = unit.add(root, tag);
+            let entry = unit.get_mut(id);
+            entry.set(constants::DW_AT_name, AttributeValue::String(name.into()));
+            if let Some(line) = line {
+                entry.set(constants::DW_AT_decl_line, line.clone());
+            }
+        }
+
+        let debug_line_str_offsets = DebugLineStrOffsets::none();
+        let debug_str_offsets = DebugStrOffsets::none();
+        let mut sections = Sections::new(EndianVec::new(LittleEndian));
+        units
+            .write(&mut sections, &debug_line_str_offsets, &debug_str_offsets)
+            .unwrap();
+
+        let read_debug_info = read::DebugInfo::new(sections.debug_info.slice(), LittleEndian);
+        let read_debug_abbrev = read::DebugAbbrev::new(sections.debug_abbrev.slice(), LittleEndian);
+        let read_unit = read_debug_info.units().next().unwrap().unwrap();
+        let abbrevs = read_unit.abbreviations(&read_debug_abbrev).unwrap();
+        let mut entries = read_unit.entries(&abbrevs);
+        let mut codes = Vec::new();
+        while let Some((_, entry)) = entries.next_dfs().unwrap() {
+            codes.push(entry.code());
+        }
+        // Entries with the same tag, attributes and forms share an abbreviation,
+        // and the form of each attribute is chosen by its value.
+        assert_eq!(codes, vec![1, 2, 2, 3, 4, 5, 4]);
+        assert!(abbrevs.get(5).is_some());
+        assert!(abbrevs.get(6).is_none());
+    }
+
+    #[test]
+    fn test_debug_info_ref_symbol() {
+        // A writer that records the references to symbols that it is given.
+        #[derive(Clone)]
+        struct RelocateWriter {
+            writer: EndianVec<LittleEndian>,
+            relocs: Vec<(usize, usize, u8)>,
+        }
+
+        impl Writer for RelocateWriter {
+            type Endian = LittleEndian;
+
+            fn endian(&self) -> Self::Endian {
+                self.writer.endian()
+            }
+
+            fn len(&self) -> usize {
+                self.writer.len()
+            }
+
+            fn write(&mut self, bytes: &[u8]) -> Result<()> {
+                self.writer.write(bytes)
+            }
+
+            fn write_at(&mut self, offset: usize, bytes: &[u8]) -> Result<()> {
+                self.writer.write_at(offset, bytes)
+            }
+
+            fn write_reference(&mut self, symbol: usize, size: u8) -> Result<()> {
+                self.relocs.push((self.len(), symbol, size));
+                self.write_udata(0, size)
+            }
+        }
+
+        let encoding = Encoding {
+            format: Format::Dwarf32,
+            version: 4,
+            address_size: 8,
+        };
+        let mut units = UnitTable::default();
+        let unit_id = units.add(Unit::new(encoding, LineProgram::none()));
+        let unit = units.get_mut(unit_id);
+        let root = unit.root();
+        let id = unit.add(root, constants::DW_TAG_variable);
+        unit.get_mut(id).set(
+            constants::DW_AT_type,
+            AttributeValue::DebugInfoRef(Reference::Symbol(7)),
+        );
+
+        let debug_line_str_offsets = DebugLineStrOffsets::none();
+        let debug_str_offsets = DebugStrOffsets::none();
+        let mut sections = Sections::new(RelocateWriter {
+            writer: EndianVec::new(LittleEndian),
+            relocs: Vec::new(),
+        });
+        units
+            .write(&mut sections, &debug_line_str_offsets, &debug_str_offsets)
+            .unwrap();
+
+        let read_debug_info =
+            read::DebugInfo::new(sections.debug_info.writer.slice(), LittleEndian);
+        let read_debug_abbrev =
+            read::DebugAbbrev::new(sections.debug_abbrev.writer.slice(), LittleEndian);
+        let read_unit = read_debug_info.units().next().unwrap().unwrap();
+        let abbrevs = read_unit.abbreviations(&read_debug_abbrev).unwrap();
+        let mut entries = read_unit.entries(&abbrevs);
+        entries.next_dfs().unwrap().unwrap();
+        let (_, entry) = entries.next_dfs().unwrap().unwrap();
+        let attr = entry.attr(constants::DW_AT_type).unwrap().unwrap();
+        assert_eq!(
+            attr.value(),
+            read::AttributeValue::DebugInfoRef(DebugInfoOffset(0))
+        );
+
+        // The reference follows the abbreviation code.
+        let offset = read_unit.offset().0 + entry.offset().0 + 1;
+        assert_eq!(sections.debug_info.relocs, vec![(offset, 7, 4)]);
+        assert!(sections.debug_abbrev.relocs.is_empty());
+    }
+
     #[test]
     fn test_line_ref() {
         for &version in &[2, 3, 4, 5] {
@@ -2635,11 +2792,17 @@ mod tests {
                             abbreviations: read::Abbreviations::default(),
                             name: None,
                             comp_dir: None,
+                            language: None,
                             low_pc: 0,
+                            high_pc: None,
                             str_offsets_base: DebugStrOffsetsBase(0),
+                            str_offsets_count: None,
                             addr_base: DebugAddrBase(0),
                             loclists_base: DebugLocListsBase(0),
                             rnglists_base: DebugRngListsBase(0),
+                            dwo_name: None,
+                            producer: None,
+                            dwo_id: None,
                             line_program: None,
                         };
 
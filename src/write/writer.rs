@@ -120,6 +120,14 @@ pub trait Writer {
         self.write_udata_at(offset, val as u64, size)
     }
 
+    /// Write a reference to a symbol.
+    ///
+    /// If the writer supports symbols, then it must provide its own implementation
+    /// of this method.
+    fn write_reference(&mut self, _symbol: usize, _size: u8) -> Result<()> {
+        Err(Error::InvalidReference)
+    }
+
     /// Write a u8.
     fn write_u8(&mut self, val: u8) -> Result<()> {
         let bytes = [val];
@@ -340,6 +348,7 @@ mod tests {
             ),
             Err(Error::InvalidAddress)
         );
+        assert_eq!(w.write_reference(0, 4), Err(Error::InvalidReference));
 
         let mut w = write::EndianVec::new(LittleEndian);
         w.write_offset(0x1122_3344, SectionId::DebugInfo, 4)
@@ -0,0 +1,42 @@
+//! Check that the parts of the read API that only borrow from the input can
+//! be used from a `no_std` crate.
+//!
+//! The library itself is built without `std` and `alloc` by the `no_std` CI job.
+#![no_std]
+
+use gimli::{DebugInfo, DebugStr, DebugStrOffset, EndianSlice, LittleEndian, Reader};
+
+#[test]
+fn test_no_std_str() {
+    let debug_str = DebugStr::new(b"foo\0bar\0", LittleEndian);
+    let string = debug_str.get_str(DebugStrOffset(4)).unwrap();
+    assert_eq!(string, EndianSlice::new(b"bar", LittleEndian));
+    assert_eq!(string.to_string(), Ok("bar"));
+}
+
+#[test]
+fn test_no_std_unit_headers() {
+    #[rustfmt::skip]
+    let buf = [
+        // 32-bit unit length.
+        0x08, 0x00, 0x00, 0x00,
+        // Version 4.
+        0x04, 0x00,
+        // Abbreviations offset.
+        0x00, 0x00, 0x00, 0x00,
+        // Address size.
+        0x08,
+        // A null entry.
+        0x00,
+    ];
+    let debug_info = DebugInfo::new(&buf, LittleEndian);
+    let mut units = debug_info.units();
+    let header = units.next().unwrap().unwrap();
+    assert_eq!(header.version(), 4);
+    assert_eq!(header.address_size(), 8);
+    assert_eq!(header.header_size(), 11);
+    assert!(units.next().unwrap().is_none());
+
+    let mut input = EndianSlice::new(&buf[..4], LittleEndian);
+    assert_eq!(input.read_u32(), Ok(8));
+}
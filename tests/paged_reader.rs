@@ -0,0 +1,234 @@
+//! Test that parsing works with a `Reader` that does not store its data
+//! contiguously, and so cannot rely on slice pointer arithmetic.
+//!
+//! This also uses the default implementation of `Reader::to_slice`.
+
+use gimli::{
+    AttributeValue, Dwarf, EndianSlice, Error, LittleEndian, Reader, ReaderOffset, ReaderOffsetId,
+    SectionId,
+};
+use std::borrow::Cow;
+use std::env;
+use std::fs::File;
+use std::io::Read;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+fn read_section(section: &str) -> Vec<u8> {
+    let mut path = PathBuf::new();
+    if let Ok(dir) = env::var("CARGO_MANIFEST_DIR") {
+        path.push(dir);
+    }
+    path.push("fixtures/self");
+    path.push(section);
+
+    let mut buf = Vec::new();
+    if let Ok(mut file) = File::open(path) {
+        file.read_to_end(&mut buf).unwrap();
+    }
+    buf
+}
+
+fn load_section(id: SectionId) -> Vec<u8> {
+    match id {
+        SectionId::DebugAbbrev
+        | SectionId::DebugInfo
+        | SectionId::DebugLine
+        | SectionId::DebugLoc
+        | SectionId::DebugRanges
+        | SectionId::DebugStr => read_section(&id.name()[1..]),
+        _ => Vec::new(),
+    }
+}
+
+const PAGE_SIZE: usize = 64;
+
+/// A `Reader` over data that is stored in separate fixed size pages.
+#[derive(Debug, Clone)]
+struct PagedReader {
+    pages: Rc<Vec<Vec<u8>>>,
+    start: usize,
+    end: usize,
+}
+
+impl PagedReader {
+    fn new(data: &[u8]) -> Self {
+        PagedReader {
+            pages: Rc::new(data.chunks(PAGE_SIZE).map(|page| page.to_vec()).collect()),
+            start: 0,
+            end: data.len(),
+        }
+    }
+
+    fn byte(&self, offset: usize) -> u8 {
+        self.pages[offset / PAGE_SIZE][offset % PAGE_SIZE]
+    }
+
+    fn check_len(&self, len: usize) -> gimli::Result<()> {
+        if len > self.end - self.start {
+            Err(Error::UnexpectedEof(self.offset_id()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Reader for PagedReader {
+    type Endian = LittleEndian;
+    type Offset = usize;
+
+    fn endian(&self) -> LittleEndian {
+        LittleEndian
+    }
+
+    fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    fn empty(&mut self) {
+        self.end = self.start;
+    }
+
+    fn truncate(&mut self, len: usize) -> gimli::Result<()> {
+        self.check_len(len)?;
+        self.end = self.start + len;
+        Ok(())
+    }
+
+    fn offset_from(&self, base: &Self) -> usize {
+        assert!(Rc::ptr_eq(&self.pages, &base.pages));
+        self.start - base.start
+    }
+
+    fn offset_id(&self) -> ReaderOffsetId {
+        ReaderOffsetId(self.start as u64)
+    }
+
+    fn lookup_offset_id(&self, _id: ReaderOffsetId) -> Option<usize> {
+        None
+    }
+
+    fn find(&self, byte: u8) -> gimli::Result<usize> {
+        (self.start..self.end)
+            .position(|offset| self.byte(offset) == byte)
+            .ok_or_else(|| Error::UnexpectedEof(self.offset_id()))
+    }
+
+    fn skip(&mut self, len: usize) -> gimli::Result<()> {
+        self.check_len(len)?;
+        self.start += len;
+        Ok(())
+    }
+
+    fn split(&mut self, len: usize) -> gimli::Result<Self> {
+        self.check_len(len)?;
+        let mut head = self.clone();
+        head.end = self.start + len;
+        self.start += len;
+        Ok(head)
+    }
+
+    fn to_string(&self) -> gimli::Result<Cow<'_, str>> {
+        match String::from_utf8(self.to_slice()?.into_owned()) {
+            Ok(s) => Ok(s.into()),
+            Err(_) => Err(Error::BadUtf8),
+        }
+    }
+
+    fn to_string_lossy(&self) -> gimli::Result<Cow<'_, str>> {
+        Ok(String::from_utf8_lossy(&self.to_slice()?)
+            .into_owned()
+            .into())
+    }
+
+    fn read_slice(&mut self, buf: &mut [u8]) -> gimli::Result<()> {
+        self.check_len(buf.len())?;
+        let mut done = 0;
+        while done < buf.len() {
+            let page = &self.pages[self.start / PAGE_SIZE];
+            let page_offset = self.start % PAGE_SIZE;
+            let len = (page.len() - page_offset).min(buf.len() - done);
+            buf[done..done + len].copy_from_slice(&page[page_offset..page_offset + len]);
+            done += len;
+            self.start += len;
+        }
+        Ok(())
+    }
+}
+
+/// The information that is compared between readers.
+#[derive(Debug, PartialEq, Eq)]
+enum Item {
+    Entry(u64, gimli::DwTag, usize, Option<Vec<u8>>),
+    Row(u64, u64, Option<u64>),
+    Range(u64, u64),
+}
+
+fn collect<R: Reader>(dwarf: &Dwarf<R>) -> Vec<Item> {
+    let mut items = Vec::new();
+    let mut units = dwarf.units();
+    while let Some(header) = units.next().expect("Should parse unit header") {
+        let unit = dwarf.unit(header).expect("Should parse unit");
+
+        let mut entries = unit.entries();
+        while let Some((_, entry)) = entries.next_dfs().expect("Should parse entry") {
+            let mut count = 0;
+            let mut name = None;
+            let mut attrs = entry.attrs();
+            while let Some(attr) = attrs.next().expect("Should parse attribute") {
+                count += 1;
+                match attr.value() {
+                    AttributeValue::DebugStrRef(_) | AttributeValue::String(_) => {
+                        if attr.name() == gimli::DW_AT_name {
+                            let value = dwarf
+                                .attr_string(&unit, attr.value())
+                                .expect("Should read string");
+                            name = Some(value.to_slice().unwrap().into_owned());
+                        }
+                    }
+                    AttributeValue::RangeListsRef(offset) => {
+                        let mut ranges = dwarf.ranges(&unit, offset).expect("Should read ranges");
+                        while let Some(range) = ranges.next().expect("Should parse range") {
+                            items.push(Item::Range(range.begin, range.end));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            items.push(Item::Entry(
+                entry.offset().0.into_u64(),
+                entry.tag(),
+                count,
+                name,
+            ));
+        }
+
+        if let Some(program) = unit.line_program.clone() {
+            let mut rows = program.rows();
+            while let Some((_, row)) = rows.next_row().expect("Should parse row") {
+                items.push(Item::Row(row.address(), row.file_index(), row.line()));
+            }
+        }
+    }
+    items
+}
+
+#[test]
+fn test_paged_reader() {
+    let sections: Dwarf<Vec<u8>> =
+        Dwarf::load(|id| Ok::<_, ()>(load_section(id)), |_| Ok(Vec::new())).unwrap();
+
+    let slices = sections.borrow(|section| EndianSlice::new(section, LittleEndian));
+    let paged = sections.borrow(|section| PagedReader::new(section));
+
+    let expect = collect(&slices);
+    assert!(expect.iter().any(|item| match *item {
+        Item::Entry(_, _, _, Some(_)) => true,
+        _ => false,
+    }));
+    assert!(expect.iter().any(|item| match *item {
+        Item::Row(..) => true,
+        _ => false,
+    }));
+    assert_eq!(collect(&paged), expect);
+}
@@ -333,6 +333,30 @@ fn test_parse_self_debug_pubnames() {
     }
 }
 
+#[test]
+fn test_parse_self_debug_pubnames_find() {
+    let debug_pubnames = read_section("debug_pubnames");
+    let debug_pubnames = DebugPubNames::new(&debug_pubnames, LittleEndian);
+
+    // Find the last entry to search the entire section.
+    let mut last = None;
+    let mut pubnames = debug_pubnames.items();
+    while let Some(entry) = pubnames.next().expect("Should parse pubname OK") {
+        last = Some(entry);
+    }
+    let entry = last.expect("Should have a pubname");
+    let found = debug_pubnames
+        .find(entry.name().slice())
+        .expect("Should parse pubname OK")
+        .expect("Should find pubname");
+    assert_eq!(found.name(), entry.name());
+    assert_eq!(found.die_offset(), entry.die_offset());
+    assert!(debug_pubnames
+        .find(b"not a pubname")
+        .expect("Should parse pubname OK")
+        .is_none());
+}
+
 #[test]
 fn test_parse_self_debug_pubtypes() {
     let debug_info = read_section("debug_info");
@@ -367,6 +391,30 @@ fn test_parse_self_debug_pubtypes() {
     }
 }
 
+#[test]
+fn test_parse_self_debug_pubtypes_find() {
+    let debug_pubtypes = read_section("debug_pubtypes");
+    let debug_pubtypes = DebugPubTypes::new(&debug_pubtypes, LittleEndian);
+
+    // Find the last entry to search the entire section.
+    let mut last = None;
+    let mut pubtypes = debug_pubtypes.items();
+    while let Some(entry) = pubtypes.next().expect("Should parse pubtype OK") {
+        last = Some(entry);
+    }
+    let entry = last.expect("Should have a pubtype");
+    let found = debug_pubtypes
+        .find(entry.name().slice())
+        .expect("Should parse pubtype OK")
+        .expect("Should find pubtype");
+    assert_eq!(found.name(), entry.name());
+    assert_eq!(found.die_offset(), entry.die_offset());
+    assert!(debug_pubtypes
+        .find(b"not a pubtype")
+        .expect("Should parse pubtype OK")
+        .is_none());
+}
+
 #[test]
 fn test_parse_self_eh_frame() {
     use gimli::{BaseAddresses, CieOrFde, EhFrame, UnwindSection};